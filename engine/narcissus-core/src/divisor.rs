@@ -0,0 +1,465 @@
+//! Division by runtime-invariant integers using multiplication and shifts.
+//!
+//! Based on the "round-up" method from libdivide <https://libdivide.com>.
+
+use std::ops::{Div, Rem};
+
+const SHIFT_MASK_32: u8 = 0x1f;
+const SHIFT_MASK_64: u8 = 0x3f;
+const ADD_MARKER: u8 = 0x40;
+const NEGATIVE_DIVISOR: u8 = 0x80;
+
+/// A divisor with precomputed magic numbers, allowing repeated division by the
+/// same value without a hardware divide.
+///
+/// # Example
+///
+/// ```
+/// use narcissus_core::Divisor;
+/// const TILE_SIZE: Divisor<u32> = Divisor::<u32>::new(24);
+/// assert_eq!(TILE_SIZE.div_rem(100), (4, 4));
+/// assert_eq!(100 / TILE_SIZE, 4);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Divisor<T> {
+    divisor: T,
+    magic: T,
+    more: u8,
+}
+
+macro_rules! impl_unsigned {
+    ($t:ty, $wide:ty, $shift_mask:expr) => {
+        impl Divisor<$t> {
+            /// Creates a new divisor.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `divisor` is zero.
+            pub const fn new(divisor: $t) -> Self {
+                assert!(divisor != 0, "division by zero");
+
+                let floor_log_2_d = (<$t>::BITS - 1 - divisor.leading_zeros()) as u8;
+
+                if divisor.is_power_of_two() {
+                    // Shift only.
+                    return Self {
+                        divisor,
+                        magic: 0,
+                        more: floor_log_2_d,
+                    };
+                }
+
+                let numerator = (1 as $wide) << (floor_log_2_d as u32 + <$t>::BITS);
+                let mut proposed_m = (numerator / divisor as $wide) as $t;
+                let rem = (numerator % divisor as $wide) as $t;
+                let e = divisor - rem;
+
+                let more = if e < (1 as $t) << floor_log_2_d {
+                    // This power works.
+                    floor_log_2_d
+                } else {
+                    // We have to use the general 33-bit algorithm. We need to compute
+                    // (2**power) / d. However, we already have (2**(power-1))/d and
+                    // its remainder. By doubling both, and then correcting the
+                    // remainder, we can compute the larger division.
+                    proposed_m = proposed_m.wrapping_add(proposed_m);
+                    let twice_rem = rem.wrapping_add(rem);
+                    if twice_rem >= divisor || twice_rem < rem {
+                        proposed_m += 1;
+                    }
+                    floor_log_2_d | ADD_MARKER
+                };
+
+                Self {
+                    divisor,
+                    magic: proposed_m.wrapping_add(1),
+                    more,
+                }
+            }
+
+            #[inline(always)]
+            pub const fn divisor(self) -> $t {
+                self.divisor
+            }
+
+            /// Returns `numerator / self`.
+            #[inline(always)]
+            #[must_use]
+            pub const fn div(self, numerator: $t) -> $t {
+                let shift = (self.more & $shift_mask) as u32;
+                if self.magic == 0 {
+                    numerator >> shift
+                } else {
+                    let q = ((self.magic as $wide * numerator as $wide) >> <$t>::BITS) as $t;
+                    if self.more & ADD_MARKER != 0 {
+                        let t = ((numerator - q) >> 1) + q;
+                        t >> shift
+                    } else {
+                        q >> shift
+                    }
+                }
+            }
+
+            /// Returns `numerator % self`.
+            #[inline(always)]
+            #[must_use]
+            pub const fn rem(self, numerator: $t) -> $t {
+                numerator - self.div(numerator) * self.divisor
+            }
+
+            /// Returns `(numerator / self, numerator % self)`.
+            #[inline(always)]
+            #[must_use]
+            pub const fn div_rem(self, numerator: $t) -> ($t, $t) {
+                let q = self.div(numerator);
+                (q, numerator - q * self.divisor)
+            }
+        }
+
+        impl Div<Divisor<$t>> for $t {
+            type Output = $t;
+
+            #[inline(always)]
+            fn div(self, rhs: Divisor<$t>) -> Self::Output {
+                rhs.div(self)
+            }
+        }
+
+        impl Rem<Divisor<$t>> for $t {
+            type Output = $t;
+
+            #[inline(always)]
+            fn rem(self, rhs: Divisor<$t>) -> Self::Output {
+                rhs.rem(self)
+            }
+        }
+    };
+}
+
+macro_rules! impl_signed {
+    ($t:ty, $ut:ty, $wide:ty, $uwide:ty, $shift_mask:expr) => {
+        impl Divisor<$t> {
+            /// Creates a new divisor.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `divisor` is zero.
+            pub const fn new(divisor: $t) -> Self {
+                assert!(divisor != 0, "division by zero");
+
+                let abs_d = divisor.unsigned_abs();
+                let floor_log_2_d = (<$t>::BITS - 1 - abs_d.leading_zeros()) as u8;
+                let negative = if divisor < 0 { NEGATIVE_DIVISOR } else { 0 };
+
+                if abs_d.is_power_of_two() {
+                    // Shift only, with the sign applied after.
+                    return Self {
+                        divisor,
+                        magic: 0,
+                        more: floor_log_2_d | negative,
+                    };
+                }
+
+                // This is the (power - 1) variant, the largest power where the
+                // magic number still fits in the signed type.
+                let numerator = (1 as $uwide) << (floor_log_2_d as u32 - 1 + <$t>::BITS);
+                let mut proposed_m = (numerator / abs_d as $uwide) as $ut;
+                let rem = (numerator % abs_d as $uwide) as $ut;
+                let e = abs_d - rem;
+
+                let more = if e < (1 as $ut) << floor_log_2_d {
+                    floor_log_2_d - 1
+                } else {
+                    // Double and correct as in the unsigned case, the add marker
+                    // compensates for the overflow of the magic number.
+                    proposed_m = proposed_m.wrapping_add(proposed_m);
+                    let twice_rem = rem.wrapping_add(rem);
+                    if twice_rem >= abs_d || twice_rem < rem {
+                        proposed_m += 1;
+                    }
+                    floor_log_2_d | ADD_MARKER
+                };
+
+                let magic = proposed_m.wrapping_add(1) as $t;
+
+                Self {
+                    divisor,
+                    magic: if divisor < 0 {
+                        magic.wrapping_neg()
+                    } else {
+                        magic
+                    },
+                    more: more | negative,
+                }
+            }
+
+            #[inline(always)]
+            pub const fn divisor(self) -> $t {
+                self.divisor
+            }
+
+            /// Returns `numerator / self`, rounding towards zero.
+            ///
+            /// Wraps on overflow, in the same manner as `wrapping_div`.
+            #[inline(always)]
+            #[must_use]
+            pub const fn div(self, numerator: $t) -> $t {
+                let shift = (self.more & $shift_mask) as u32;
+                // All ones if the divisor is negative, otherwise zero.
+                let sign = ((self.more as i8) >> 7) as $t;
+                if self.magic == 0 {
+                    // Round towards zero by adding `2^shift - 1` to negative
+                    // numerators before shifting.
+                    let mask = ((1 as $ut) << shift).wrapping_sub(1);
+                    let uq = (numerator as $ut)
+                        .wrapping_add(((numerator >> (<$t>::BITS - 1)) as $ut) & mask);
+                    let q = (uq as $t) >> shift;
+                    (q ^ sign).wrapping_sub(sign)
+                } else {
+                    let mut uq = ((self.magic as $wide * numerator as $wide) >> <$t>::BITS) as $t;
+                    if self.more & ADD_MARKER != 0 {
+                        uq = uq.wrapping_add((numerator ^ sign).wrapping_sub(sign));
+                    }
+                    let q = uq >> shift;
+                    // Round towards zero.
+                    q.wrapping_add((q < 0) as $t)
+                }
+            }
+
+            /// Returns `numerator % self`, with the sign of the numerator.
+            ///
+            /// Wraps on overflow, in the same manner as `wrapping_rem`.
+            #[inline(always)]
+            #[must_use]
+            pub const fn rem(self, numerator: $t) -> $t {
+                numerator.wrapping_sub(self.div(numerator).wrapping_mul(self.divisor))
+            }
+
+            /// Returns `(numerator / self, numerator % self)`.
+            #[inline(always)]
+            #[must_use]
+            pub const fn div_rem(self, numerator: $t) -> ($t, $t) {
+                let q = self.div(numerator);
+                (q, numerator.wrapping_sub(q.wrapping_mul(self.divisor)))
+            }
+        }
+
+        impl Div<Divisor<$t>> for $t {
+            type Output = $t;
+
+            #[inline(always)]
+            fn div(self, rhs: Divisor<$t>) -> Self::Output {
+                rhs.div(self)
+            }
+        }
+
+        impl Rem<Divisor<$t>> for $t {
+            type Output = $t;
+
+            #[inline(always)]
+            fn rem(self, rhs: Divisor<$t>) -> Self::Output {
+                rhs.rem(self)
+            }
+        }
+    };
+}
+
+impl_unsigned!(u32, u64, SHIFT_MASK_32);
+impl_unsigned!(u64, u128, SHIFT_MASK_64);
+impl_signed!(i32, u32, i64, u64, SHIFT_MASK_32);
+impl_signed!(i64, u64, i128, u128, SHIFT_MASK_64);
+
+#[cfg(test)]
+mod tests {
+    use crate::random::Pcg64;
+
+    use super::Divisor;
+
+    fn check_u32(d: u32, n: u32) {
+        let divisor = Divisor::<u32>::new(d);
+        assert_eq!(divisor.div_rem(n), (n / d, n % d), "{n} / {d}");
+    }
+
+    fn check_u64(d: u64, n: u64) {
+        let divisor = Divisor::<u64>::new(d);
+        assert_eq!(divisor.div_rem(n), (n / d, n % d), "{n} / {d}");
+    }
+
+    fn check_i32(d: i32, n: i32) {
+        let divisor = Divisor::<i32>::new(d);
+        let expected = (n.wrapping_div(d), n.wrapping_rem(d));
+        assert_eq!(divisor.div_rem(n), expected, "{n} / {d}");
+    }
+
+    fn check_i64(d: i64, n: i64) {
+        let divisor = Divisor::<i64>::new(d);
+        let expected = (n.wrapping_div(d), n.wrapping_rem(d));
+        assert_eq!(divisor.div_rem(n), expected, "{n} / {d}");
+    }
+
+    // Test is exhaustive and quite slow in debug mode. So ignore by default.
+    #[test]
+    #[ignore]
+    fn divisor_u16_exhaustive() {
+        for d in 1..=u16::MAX {
+            let divisor = Divisor::<u32>::new(d as u32);
+            for n in 0..=u16::MAX {
+                assert_eq!(
+                    divisor.div_rem(n as u32),
+                    (n as u32 / d as u32, n as u32 % d as u32)
+                );
+            }
+        }
+    }
+
+    // Test is exhaustive and quite slow in debug mode. So ignore by default.
+    #[test]
+    #[ignore]
+    fn divisor_i16_exhaustive() {
+        for d in i16::MIN..=i16::MAX {
+            if d == 0 {
+                continue;
+            }
+            let divisor = Divisor::<i32>::new(d as i32);
+            for n in i16::MIN..=i16::MAX {
+                assert_eq!(
+                    divisor.div_rem(n as i32),
+                    (n as i32 / d as i32, n as i32 % d as i32)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn divisor_u16() {
+        for d in 1..=u16::MAX as u32 {
+            for n in (0..=u16::MAX as u32)
+                .step_by(251)
+                .chain([d - 1, d, d + 1, 65535])
+            {
+                check_u32(d, n);
+            }
+        }
+    }
+
+    #[test]
+    fn divisor_edge_cases() {
+        let edges_u32 = [0, 1, 2, 3, 7, 24, 641, u32::MAX / 2, u32::MAX - 1, u32::MAX];
+        let edges_u64 = [
+            0,
+            1,
+            2,
+            3,
+            7,
+            24,
+            641,
+            6700417,
+            u64::MAX / 2,
+            u64::MAX - 1,
+            u64::MAX,
+        ];
+        let edges_i32 = [
+            i32::MIN,
+            i32::MIN + 1,
+            -24,
+            -7,
+            -3,
+            -2,
+            -1,
+            0,
+            1,
+            2,
+            3,
+            7,
+            24,
+            i32::MAX,
+        ];
+        let edges_i64 = [
+            i64::MIN,
+            i64::MIN + 1,
+            -24,
+            -7,
+            -2,
+            -1,
+            0,
+            1,
+            2,
+            7,
+            24,
+            i64::MAX,
+        ];
+
+        for d in edges_u32.into_iter().filter(|&d| d != 0) {
+            for n in edges_u32 {
+                check_u32(d, n);
+            }
+        }
+        for d in edges_u64.into_iter().filter(|&d| d != 0) {
+            for n in edges_u64 {
+                check_u64(d, n);
+            }
+        }
+        for d in edges_i32.into_iter().filter(|&d| d != 0) {
+            for n in edges_i32 {
+                check_i32(d, n);
+            }
+        }
+        for d in edges_i64.into_iter().filter(|&d| d != 0) {
+            for n in edges_i64 {
+                check_i64(d, n);
+            }
+        }
+
+        for shift in 0..32 {
+            check_u32(1 << shift, u32::MAX);
+            check_i32(1 << shift, i32::MIN);
+            check_i32((1_i32 << shift).wrapping_neg(), i32::MAX);
+        }
+        for shift in 0..64 {
+            check_u64(1 << shift, u64::MAX);
+            check_i64(1 << shift, i64::MIN);
+            check_i64((1_i64 << shift).wrapping_neg(), i64::MAX);
+        }
+    }
+
+    #[test]
+    fn divisor_random() {
+        let mut rng = Pcg64::new();
+        for _ in 0..10_000 {
+            // Vary the magnitude of the divisor, since uniformly random divisors
+            // are almost always huge.
+            let bits = rng.next_bound_u64(64) as u32;
+            let d = rng.next_u64() >> bits;
+            let divisor_u32 = Divisor::<u32>::new((d as u32).max(1));
+            let divisor_u64 = Divisor::<u64>::new(d.max(1));
+            let d = if bits & 1 == 0 { d } else { d.wrapping_neg() };
+            let divisor_i32 = Divisor::<i32>::new(if d as i32 == 0 { 1 } else { d as i32 });
+            let divisor_i64 = Divisor::<i64>::new(if d as i64 == 0 { 1 } else { d as i64 });
+            for _ in 0..100 {
+                let n = rng.next_u64();
+                assert_eq!(n as u32 / divisor_u32, n as u32 / divisor_u32.divisor());
+                assert_eq!(n as u32 % divisor_u32, n as u32 % divisor_u32.divisor());
+                assert_eq!(n / divisor_u64, n / divisor_u64.divisor());
+                assert_eq!(n % divisor_u64, n % divisor_u64.divisor());
+                let n_i32 = n as i32;
+                assert_eq!(
+                    n_i32 / divisor_i32,
+                    n_i32.wrapping_div(divisor_i32.divisor())
+                );
+                assert_eq!(
+                    n_i32 % divisor_i32,
+                    n_i32.wrapping_rem(divisor_i32.divisor())
+                );
+                let n_i64 = n as i64;
+                assert_eq!(
+                    n_i64 / divisor_i64,
+                    n_i64.wrapping_div(divisor_i64.divisor())
+                );
+                assert_eq!(
+                    n_i64 % divisor_i64,
+                    n_i64.wrapping_rem(divisor_i64.divisor())
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,350 @@
+/// Converts the absolute value of an `f32`, given as bits, to an unsigned float
+/// with a 5 bit exponent and `mantissa_bits` bits of mantissa, rounding to
+/// nearest even.
+///
+/// Shared by [`F16`] and the packed small float formats.
+#[inline(always)]
+pub(crate) const fn f32_to_small_float(abs: u32, mantissa_bits: u32) -> u32 {
+    debug_assert!(abs & 0x8000_0000 == 0);
+    let shift = 23 - mantissa_bits;
+    let infinity = 0x1f << mantissa_bits;
+
+    // Inf or NaN, NaNs are quieted but keep the top bits of their payload.
+    if abs >= 0x7f80_0000 {
+        return if abs > 0x7f80_0000 {
+            infinity | (1 << (mantissa_bits - 1)) | ((abs & 0x7f_ffff) >> shift)
+        } else {
+            infinity
+        };
+    }
+
+    // Halfway between the largest finite value and infinity rounds up, since the
+    // largest finite value has an odd mantissa.
+    let overflow =
+        (((142 << mantissa_bits) | ((1 << mantissa_bits) - 1)) << shift) | (1 << (shift - 1));
+    if abs >= overflow {
+        return infinity;
+    }
+
+    // Normal, rebias the exponent and round the mantissa. Rounding may carry
+    // into the exponent, which is exactly what we want.
+    if abs >= 113 << 23 {
+        let odd = (abs >> shift) & 1;
+        return (abs - (112 << 23) + (1 << (shift - 1)) - 1 + odd) >> shift;
+    }
+
+    // Subnormal, or rounds to zero.
+    let exponent = abs >> 23;
+    if exponent + mantissa_bits < 112 {
+        return 0;
+    }
+    let mantissa = (abs & 0x7f_ffff) | 0x80_0000;
+    let shift = 136 - mantissa_bits - exponent;
+    let half = 1 << (shift - 1);
+    let rem = mantissa & ((1 << shift) - 1);
+    let result = mantissa >> shift;
+    if rem > half || (rem == half && result & 1 == 1) {
+        result + 1
+    } else {
+        result
+    }
+}
+
+/// Converts an unsigned float with a 5 bit exponent and `mantissa_bits` bits of
+/// mantissa to the bits of an `f32`. The conversion is exact.
+#[inline(always)]
+pub(crate) const fn small_float_to_f32(bits: u32, mantissa_bits: u32) -> u32 {
+    let shift = 23 - mantissa_bits;
+    let mask = (1 << mantissa_bits) - 1;
+    let exponent = bits >> mantissa_bits;
+    let mantissa = bits & mask;
+
+    if exponent == 0x1f {
+        let quiet = if mantissa != 0 { 0x40_0000 } else { 0 };
+        return 0x7f80_0000 | quiet | (mantissa << shift);
+    }
+
+    if exponent == 0 {
+        if mantissa == 0 {
+            return 0;
+        }
+        // Normalize so the leading bit becomes the implicit one.
+        let s = mantissa.leading_zeros() - (31 - mantissa_bits);
+        let mantissa = (mantissa << s) & mask;
+        let exponent = 113 - s;
+        return (exponent << 23) | (mantissa << shift);
+    }
+
+    ((exponent + 112) << 23) | (mantissa << shift)
+}
+
+/// An IEEE 754 binary16 half-precision floating point value.
+///
+/// Conversions from `f32` round to nearest, ties to even. Conversions to `f32`
+/// are exact. NaNs are quieted in both directions, and keep as much of their
+/// payload as fits.
+#[repr(transparent)]
+#[derive(Clone, Copy, Default)]
+pub struct F16(u16);
+
+impl F16 {
+    pub const ZERO: F16 = F16(0x0000);
+    pub const NEG_ZERO: F16 = F16(0x8000);
+    pub const ONE: F16 = F16(0x3c00);
+    pub const NEG_ONE: F16 = F16(0xbc00);
+    pub const INFINITY: F16 = F16(0x7c00);
+    pub const NEG_INFINITY: F16 = F16(0xfc00);
+    pub const NAN: F16 = F16(0x7e00);
+    /// Largest finite value, 65504.
+    pub const MAX: F16 = F16(0x7bff);
+    /// Smallest finite value, -65504.
+    pub const MIN: F16 = F16(0xfbff);
+    /// Smallest positive normal value, 2^-14.
+    pub const MIN_POSITIVE: F16 = F16(0x0400);
+    /// Difference between 1.0 and the next larger value, 2^-10.
+    pub const EPSILON: F16 = F16(0x1400);
+
+    #[inline(always)]
+    pub const fn from_bits(bits: u16) -> F16 {
+        F16(bits)
+    }
+
+    #[inline(always)]
+    pub const fn to_bits(self) -> u16 {
+        self.0
+    }
+
+    #[inline(always)]
+    pub const fn from_f32(x: f32) -> F16 {
+        let x = x.to_bits();
+        let sign = (x >> 16) & 0x8000;
+        let abs = x & 0x7fff_ffff;
+        F16((sign | f32_to_small_float(abs, 10)) as u16)
+    }
+
+    #[inline(always)]
+    pub const fn to_f32(self) -> f32 {
+        let sign = ((self.0 & 0x8000) as u32) << 16;
+        let abs = (self.0 & 0x7fff) as u32;
+        f32::from_bits(sign | small_float_to_f32(abs, 10))
+    }
+
+    #[inline(always)]
+    pub const fn is_nan(self) -> bool {
+        self.0 & 0x7fff > 0x7c00
+    }
+
+    #[inline(always)]
+    pub const fn is_infinite(self) -> bool {
+        self.0 & 0x7fff == 0x7c00
+    }
+
+    #[inline(always)]
+    pub const fn is_finite(self) -> bool {
+        self.0 & 0x7fff < 0x7c00
+    }
+
+    #[inline(always)]
+    pub const fn is_sign_negative(self) -> bool {
+        self.0 & 0x8000 != 0
+    }
+}
+
+impl From<F16> for f32 {
+    #[inline(always)]
+    fn from(x: F16) -> Self {
+        x.to_f32()
+    }
+}
+
+impl PartialEq for F16 {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.to_f32() == other.to_f32()
+    }
+}
+
+impl PartialOrd for F16 {
+    #[inline(always)]
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        self.to_f32().partial_cmp(&other.to_f32())
+    }
+}
+
+impl std::fmt::Debug for F16 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.to_f32().fmt(f)
+    }
+}
+
+impl std::fmt::Display for F16 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.to_f32().fmt(f)
+    }
+}
+
+/// Converts a slice of `f32` values to [`F16`], with the same results as
+/// [`F16::from_f32`].
+///
+/// # Panics
+///
+/// Panics if `src` and `dst` have different lengths.
+pub fn f32_to_f16_slice(src: &[f32], dst: &mut [F16]) {
+    assert_eq!(src.len(), dst.len());
+
+    #[cfg(all(target_arch = "x86_64", target_feature = "f16c"))]
+    let (src, dst) = {
+        use std::arch::x86_64::{
+            _MM_FROUND_TO_NEAREST_INT, _mm_storeu_si128, _mm256_cvtps_ph, _mm256_loadu_ps,
+        };
+
+        let len = src.len() & !7;
+        let (src, src_rem) = src.split_at(len);
+        let (dst, dst_rem) = dst.split_at_mut(len);
+        for (src, dst) in src.chunks_exact(8).zip(dst.chunks_exact_mut(8)) {
+            // SAFETY: Both chunks contain exactly 8 elements, and loads and stores
+            // are unaligned.
+            unsafe {
+                let x = _mm256_loadu_ps(src.as_ptr());
+                let h = _mm256_cvtps_ph::<_MM_FROUND_TO_NEAREST_INT>(x);
+                _mm_storeu_si128(dst.as_mut_ptr().cast(), h);
+            }
+        }
+        (src_rem, dst_rem)
+    };
+
+    for (src, dst) in src.iter().zip(dst.iter_mut()) {
+        *dst = F16::from_f32(*src);
+    }
+}
+
+/// Converts a slice of [`F16`] values to `f32`, with the same results as
+/// [`F16::to_f32`].
+///
+/// # Panics
+///
+/// Panics if `src` and `dst` have different lengths.
+pub fn f16_to_f32_slice(src: &[F16], dst: &mut [f32]) {
+    assert_eq!(src.len(), dst.len());
+
+    #[cfg(all(target_arch = "x86_64", target_feature = "f16c"))]
+    let (src, dst) = {
+        use std::arch::x86_64::{_mm_loadu_si128, _mm256_cvtph_ps, _mm256_storeu_ps};
+
+        let len = src.len() & !7;
+        let (src, src_rem) = src.split_at(len);
+        let (dst, dst_rem) = dst.split_at_mut(len);
+        for (src, dst) in src.chunks_exact(8).zip(dst.chunks_exact_mut(8)) {
+            // SAFETY: Both chunks contain exactly 8 elements, and loads and stores
+            // are unaligned.
+            unsafe {
+                let h = _mm_loadu_si128(src.as_ptr().cast());
+                _mm256_storeu_ps(dst.as_mut_ptr(), _mm256_cvtph_ps(h));
+            }
+        }
+        (src_rem, dst_rem)
+    };
+
+    for (src, dst) in src.iter().zip(dst.iter_mut()) {
+        *dst = src.to_f32();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{F16, f16_to_f32_slice, f32_to_f16_slice};
+
+    #[test]
+    fn constants() {
+        assert_eq!(F16::ONE.to_f32(), 1.0);
+        assert_eq!(F16::NEG_ONE.to_f32(), -1.0);
+        assert_eq!(F16::MAX.to_f32(), 65504.0);
+        assert_eq!(F16::MIN.to_f32(), -65504.0);
+        assert_eq!(F16::MIN_POSITIVE.to_f32(), 2.0_f32.powi(-14));
+        assert_eq!(F16::EPSILON.to_f32(), 2.0_f32.powi(-10));
+        assert_eq!(F16::from_bits(1).to_f32(), 2.0_f32.powi(-24));
+        assert_eq!(F16::INFINITY.to_f32(), f32::INFINITY);
+        assert_eq!(F16::NEG_INFINITY.to_f32(), f32::NEG_INFINITY);
+        assert!(F16::NAN.is_nan());
+        assert!(F16::NAN.to_f32().is_nan());
+        assert_eq!(F16::ZERO, F16::NEG_ZERO);
+        assert!(F16::NEG_ZERO.is_sign_negative());
+    }
+
+    #[test]
+    fn special() {
+        assert_eq!(F16::from_f32(f32::INFINITY).to_bits(), 0x7c00);
+        assert_eq!(F16::from_f32(f32::NEG_INFINITY).to_bits(), 0xfc00);
+        assert_eq!(F16::from_f32(f32::MAX).to_bits(), 0x7c00);
+        assert_eq!(F16::from_f32(65519.996).to_bits(), 0x7bff);
+        assert_eq!(F16::from_f32(65520.0).to_bits(), 0x7c00);
+        assert_eq!(F16::from_f32(-0.0).to_bits(), 0x8000);
+        assert_eq!(F16::from_f32(f32::MIN_POSITIVE).to_bits(), 0);
+        assert_eq!(F16::from_f32(2.0_f32.powi(-25)).to_bits(), 0);
+        assert_eq!(F16::from_f32(2.0_f32.powi(-25) * 1.5).to_bits(), 1);
+        assert!(F16::from_f32(f32::NAN).is_nan());
+        // Signalling NaNs are quieted, payload is kept.
+        assert_eq!(F16::from_f32(f32::from_bits(0x7f80_2000)).to_bits(), 0x7e01);
+        assert_eq!(F16::from_bits(0x7c01).to_f32().to_bits(), 0x7fc0_2000);
+    }
+
+    #[test]
+    fn exhaustive_roundtrip() {
+        for bits in 0..=u16::MAX {
+            let x = F16::from_bits(bits);
+            let y = F16::from_f32(x.to_f32());
+            if x.is_nan() {
+                assert!(y.is_nan());
+                assert_eq!(y.to_bits(), bits | 0x0200);
+            } else {
+                assert_eq!(y.to_bits(), bits);
+            }
+        }
+    }
+
+    #[test]
+    fn exhaustive_round_to_nearest_even() {
+        // For each pair of adjacent finite values, check values at and either side
+        // of the midpoint. Midpoints are exactly representable as f32.
+        for bits in 0..0x7bff_u16 {
+            for sign in [0, 0x8000] {
+                let lo = F16::from_bits(sign | bits);
+                let hi = F16::from_bits(sign | (bits + 1));
+                let mid = (lo.to_f32() + hi.to_f32()) * 0.5;
+                let even = if bits & 1 == 0 { lo } else { hi };
+                // Adjacent f32 values in magnitude, regardless of sign.
+                let toward_lo = f32::from_bits(mid.to_bits() - 1);
+                let toward_hi = f32::from_bits(mid.to_bits() + 1);
+                assert_eq!(F16::from_f32(mid).to_bits(), even.to_bits());
+                if bits != 0 {
+                    assert_eq!(F16::from_f32(toward_lo).to_bits(), lo.to_bits());
+                }
+                assert_eq!(F16::from_f32(toward_hi).to_bits(), hi.to_bits());
+            }
+        }
+    }
+
+    #[test]
+    fn exhaustive_slice() {
+        let halves = (0..=u16::MAX).map(F16::from_bits).collect::<Vec<_>>();
+        let mut floats = vec![0.0; halves.len()];
+        f16_to_f32_slice(&halves, &mut floats);
+        for (h, f) in halves.iter().zip(&floats) {
+            assert_eq!(h.to_f32().to_bits(), f.to_bits());
+        }
+
+        // Include odd lengths to exercise the remainder, and the midpoints to
+        // exercise rounding.
+        let mut floats = floats
+            .iter()
+            .zip(&floats[1..])
+            .flat_map(|(&a, &b)| [a, (a + b) * 0.5])
+            .collect::<Vec<_>>();
+        floats.push(1.0);
+        let mut halves = vec![F16::ZERO; floats.len()];
+        f32_to_f16_slice(&floats, &mut halves);
+        for (f, h) in floats.iter().zip(&halves) {
+            assert_eq!(F16::from_f32(*f).to_bits(), h.to_bits());
+        }
+    }
+}
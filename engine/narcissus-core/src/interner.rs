@@ -0,0 +1,200 @@
+use std::{
+    collections::HashMap,
+    sync::{OnceLock, RwLock},
+};
+
+use crate::VirtualVec;
+
+/// An interned string.
+///
+/// Symbols are cheap to copy, compare and hash. A symbol is only meaningful
+/// for the table that created it, either the global table via
+/// [`Symbol::intern`], or a local [`Interner`].
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[repr(transparent)]
+pub struct Symbol(u32);
+
+impl Symbol {
+    pub const fn from_raw(value: u32) -> Self {
+        Self(value)
+    }
+
+    pub const fn as_raw(self) -> u32 {
+        self.0
+    }
+
+    /// Intern `str` in the global table.
+    pub fn intern(str: &str) -> Symbol {
+        let interner = global();
+
+        if let Some(symbol) = interner.read().unwrap().get(str) {
+            return symbol;
+        }
+
+        interner.write().unwrap().intern(str)
+    }
+
+    /// Resolve a symbol previously returned by [`Symbol::intern`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the symbol did not come from the global table.
+    pub fn as_str(self) -> &'static str {
+        let interner = global().read().unwrap();
+        let str = interner.resolve(self);
+        // SAFETY: The global table is never dropped, and string storage is never
+        // moved or modified once written.
+        unsafe { std::mem::transmute::<&str, &'static str>(str) }
+    }
+}
+
+const DEFAULT_MAX_BYTES: usize = 1 << 30;
+const DEFAULT_MAX_SYMBOLS: usize = 1 << 24;
+
+fn global() -> &'static RwLock<Interner> {
+    static GLOBAL: OnceLock<RwLock<Interner>> = OnceLock::new();
+    GLOBAL.get_or_init(|| RwLock::new(Interner::new()))
+}
+
+/// A local string table.
+///
+/// String data is stored in virtual memory so it's never moved as the table
+/// grows, only the address space for `max_bytes` is reserved up front.
+pub struct Interner {
+    bytes: VirtualVec<u8>,
+    spans: VirtualVec<(u32, u32)>,
+    lookup: HashMap<&'static str, Symbol>,
+}
+
+// SAFETY: The `&'static str` keys in `lookup` point into `bytes`, which is
+// owned by the interner.
+unsafe impl Send for Interner {}
+unsafe impl Sync for Interner {}
+
+impl Interner {
+    pub fn new() -> Self {
+        Self::with_max_capacity(DEFAULT_MAX_BYTES, DEFAULT_MAX_SYMBOLS)
+    }
+
+    /// Creates a table that can hold at most `max_symbols` unique strings,
+    /// totaling at most `max_bytes`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_bytes` doesn't fit within a `u32`.
+    pub fn with_max_capacity(max_bytes: usize, max_symbols: usize) -> Self {
+        assert!(max_bytes <= u32::MAX as usize + 1);
+        Self {
+            bytes: VirtualVec::new(max_bytes),
+            spans: VirtualVec::new(max_symbols),
+            lookup: HashMap::new(),
+        }
+    }
+
+    /// Returns the symbol for `str`, adding it to the table if required.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the table's maximum capacity is exceeded.
+    pub fn intern(&mut self, str: &str) -> Symbol {
+        if let Some(symbol) = self.get(str) {
+            return symbol;
+        }
+
+        let offset = self.bytes.len();
+        let len = str.len();
+        self.bytes.extend_from_slice(str.as_bytes());
+
+        let symbol = Symbol(self.spans.len() as u32);
+        self.spans.push((offset as u32, len as u32));
+
+        // SAFETY: The string was just copied into `bytes` which never moves its
+        // contents, and entries are never removed or modified.
+        let str = unsafe {
+            let bytes = std::slice::from_raw_parts(self.bytes.as_ptr().add(offset), len);
+            std::str::from_utf8_unchecked(bytes)
+        };
+        self.lookup.insert(str, symbol);
+
+        symbol
+    }
+
+    /// Returns the symbol for `str` if it has already been interned.
+    pub fn get(&self, str: &str) -> Option<Symbol> {
+        self.lookup.get(str).copied()
+    }
+
+    /// Returns the string for the given symbol.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the symbol did not come from this table.
+    pub fn resolve(&self, symbol: Symbol) -> &str {
+        let (offset, len) = self.spans[symbol.0 as usize];
+        let bytes = &self.bytes[offset as usize..offset as usize + len as usize];
+        // SAFETY: Only ever written from valid `str` slices.
+        unsafe { std::str::from_utf8_unchecked(bytes) }
+    }
+
+    /// Returns the number of unique strings in the table.
+    pub fn len(&self) -> usize {
+        self.spans.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+}
+
+impl Default for Interner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Interner, Symbol};
+
+    #[test]
+    fn local() {
+        let mut interner = Interner::with_max_capacity(4096, 64);
+        let a = interner.intern("glyph atlas");
+        let b = interner.intern("");
+        let c = interner.intern("glyph");
+        assert_eq!(interner.intern("glyph atlas"), a);
+        assert_eq!(interner.intern(""), b);
+        assert_ne!(a, c);
+        assert_eq!(interner.len(), 3);
+        assert_eq!(interner.resolve(a), "glyph atlas");
+        assert_eq!(interner.resolve(b), "");
+        assert_eq!(interner.resolve(c), "glyph");
+        assert_eq!(interner.get("atlas"), None);
+    }
+
+    #[test]
+    fn global() {
+        let threads = (0..4)
+            .map(|_| {
+                std::thread::spawn(|| {
+                    (0..256)
+                        .map(|i| Symbol::intern(&format!("interner_test_{i}")))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let symbols = threads
+            .into_iter()
+            .map(|thread| thread.join().unwrap())
+            .collect::<Vec<_>>();
+
+        for s in &symbols[1..] {
+            assert_eq!(s, &symbols[0]);
+        }
+
+        for (i, symbol) in symbols[0].iter().enumerate() {
+            assert_eq!(symbol.as_str(), format!("interner_test_{i}"));
+        }
+    }
+}
@@ -3,19 +3,28 @@ mod bitset;
 pub mod crypto_random;
 pub mod dds;
 mod directory;
+mod divisor;
 pub mod errno;
 mod finite;
 mod fixed_vec;
 mod fourcc;
+mod half;
+mod interner;
 mod libc;
 pub mod linear_log_binning;
+pub mod log;
 pub mod manual_arc;
+pub mod memory_tracking;
+mod mirrored_ring_buffer;
 mod mutex;
 pub mod obj;
+pub mod packed;
 mod pool;
 pub mod random;
 pub mod raw_window;
 mod ref_count;
+pub mod riff;
+pub mod serialize;
 pub mod slice;
 pub mod svg;
 mod uuid;
@@ -27,14 +36,20 @@ mod widen;
 pub use arena::{Arena, HybridArena};
 pub use bitset::BitIter;
 pub use directory::{cache_dir, config_dir, data_dir, runtime_dir};
+pub use divisor::Divisor;
 pub use finite::{FiniteF32, FiniteF64, NotFiniteError};
 pub use fixed_vec::FixedVec;
 pub use fourcc::FourCC;
+pub use half::{F16, f16_to_f32_slice, f32_to_f16_slice};
+pub use interner::{Interner, Symbol};
+pub use mirrored_ring_buffer::MirroredRingBuffer;
 pub use mutex::Mutex;
 pub use pool::{Handle, Pool};
 pub use ref_count::{Arc, Rc};
 pub use uuid::Uuid;
-pub use virtual_mem::{virtual_commit, virtual_free, virtual_reserve};
+pub use virtual_mem::{
+    virtual_advise_huge_pages, virtual_commit, virtual_decommit, virtual_free, virtual_reserve,
+};
 pub use virtual_vec::{VirtualDeque, VirtualVec};
 pub use widen::Widen;
 
@@ -77,6 +77,11 @@ pub const PROT_READ: c_int = 1;
 pub const PROT_WRITE: c_int = 2;
 pub const PROT_EXEC: c_int = 4;
 
+pub const MADV_DONTNEED: c_int = 4;
+pub const MADV_HUGEPAGE: c_int = 14;
+
+pub const MFD_CLOEXEC: c_uint = 0x0001;
+
 pub const SYS_read: c_long = 0;
 pub const SYS_write: c_long = 1;
 pub const SYS_open: c_long = 2;
@@ -443,6 +448,10 @@ unsafe extern "C" {
     ) -> *mut c_void;
     pub fn munmap(addr: *mut c_void, len: size_t) -> c_int;
     pub fn mprotect(addr: *mut c_void, len: size_t, prot: c_int) -> c_int;
+    pub fn madvise(addr: *mut c_void, len: size_t, advice: c_int) -> c_int;
+
+    pub fn ftruncate(fd: c_int, length: off_t) -> c_int;
+    pub fn close(fd: c_int) -> c_int;
 
     #[cfg_attr(target_os = "linux", link_name = "__errno_location")]
     pub fn errno_location() -> *mut c_int;
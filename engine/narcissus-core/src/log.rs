@@ -0,0 +1,597 @@
+//! Structured logging with severity levels, categories and pluggable sinks.
+//!
+//! Records are emitted with the `log_error!`, `log_warn!`, `log_info!`,
+//! `log_debug!` and `log_trace!` macros. Each record carries a category, which
+//! defaults to the calling module's path, but can be given explicitly.
+//!
+//! ```
+//! use narcissus_core::{log_info, log_warn};
+//!
+//! log_info!("loaded {} glyphs", 128);
+//! log_warn!(category: "sqlite", "database is locked");
+//! ```
+//!
+//! Levels above [`STATIC_MAX_LEVEL`] are compiled out entirely. The limit is
+//! selected with the `log_max_level_*` cargo features. Remaining records are
+//! filtered at runtime against the global level set by [`set_max_level`], and
+//! any per-category overrides set by [`set_category_level`], before being
+//! passed to every registered [`Sink`]. Until a sink is registered, records
+//! are written to stderr so that early diagnostics aren't lost.
+
+use std::{
+    collections::VecDeque,
+    fmt,
+    fs::File,
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::{
+        Arc, OnceLock, RwLock,
+        atomic::{AtomicBool, AtomicU8, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use crate::{Mutex, data_dir};
+
+/// Severity of a log record.
+///
+/// Levels are ordered by verbosity, so `Level::Error < Level::Trace`.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[repr(u8)]
+pub enum Level {
+    Error = 1,
+    Warn = 2,
+    Info = 3,
+    Debug = 4,
+    Trace = 5,
+}
+
+impl Level {
+    #[inline(always)]
+    const fn from_raw(value: u8) -> Option<Level> {
+        match value {
+            1 => Some(Level::Error),
+            2 => Some(Level::Warn),
+            3 => Some(Level::Info),
+            4 => Some(Level::Debug),
+            5 => Some(Level::Trace),
+            _ => None,
+        }
+    }
+
+    /// Returns the upper-case name of the level.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Level::Error => "ERROR",
+            Level::Warn => "WARN",
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+            Level::Trace => "TRACE",
+        }
+    }
+
+    /// Returns the ANSI escape sequence used to colour the level when writing
+    /// to a terminal.
+    const fn ansi_color(self) -> &'static str {
+        match self {
+            Level::Error => "\x1b[1;31m",
+            Level::Warn => "\x1b[1;33m",
+            Level::Info => "\x1b[32m",
+            Level::Debug => "\x1b[36m",
+            Level::Trace => "\x1b[90m",
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+/// The most verbose level which will be compiled into the binary.
+///
+/// Controlled by the `log_max_level_off`, `log_max_level_error`,
+/// `log_max_level_warn`, `log_max_level_info` and `log_max_level_debug`
+/// features. When several are enabled, the most restrictive wins.
+pub const STATIC_MAX_LEVEL: Option<Level> = if cfg!(feature = "log_max_level_off") {
+    None
+} else if cfg!(feature = "log_max_level_error") {
+    Some(Level::Error)
+} else if cfg!(feature = "log_max_level_warn") {
+    Some(Level::Warn)
+} else if cfg!(feature = "log_max_level_info") {
+    Some(Level::Info)
+} else if cfg!(feature = "log_max_level_debug") {
+    Some(Level::Debug)
+} else {
+    Some(Level::Trace)
+};
+
+/// Returns `true` if records of the given `level` survive compile-time
+/// filtering.
+#[inline(always)]
+pub const fn static_enabled(level: Level) -> bool {
+    match STATIC_MAX_LEVEL {
+        Some(max_level) => level as u8 <= max_level as u8,
+        None => false,
+    }
+}
+
+const DEFAULT_MAX_LEVEL: Level = if cfg!(debug_assertions) {
+    Level::Debug
+} else {
+    Level::Info
+};
+
+static MAX_LEVEL: AtomicU8 = AtomicU8::new(DEFAULT_MAX_LEVEL as u8);
+
+/// Per-category overrides of the global maximum level.
+static CATEGORY_LEVELS: RwLock<Vec<(&'static str, Option<Level>)>> = RwLock::new(Vec::new());
+
+/// Summary of `CATEGORY_LEVELS`, so the common case of no overrides never
+/// takes the lock.
+static HAS_CATEGORY_LEVELS: AtomicBool = AtomicBool::new(false);
+static CATEGORY_MAX_LEVEL: AtomicU8 = AtomicU8::new(0);
+
+static SINKS: RwLock<Vec<Arc<dyn Sink>>> = RwLock::new(Vec::new());
+
+/// Returns the global runtime maximum level, or `None` if logging is disabled.
+pub fn max_level() -> Option<Level> {
+    Level::from_raw(MAX_LEVEL.load(Ordering::Relaxed))
+}
+
+/// Sets the global runtime maximum level. `None` disables all logging.
+///
+/// Has no effect on levels removed by [`STATIC_MAX_LEVEL`].
+pub fn set_max_level(level: Option<Level>) {
+    MAX_LEVEL.store(level.map_or(0, |level| level as u8), Ordering::Relaxed)
+}
+
+/// Overrides the maximum level for a single category. `None` disables all
+/// logging for that category.
+///
+/// Categories with an override ignore the global maximum level.
+pub fn set_category_level(category: &'static str, level: Option<Level>) {
+    let mut category_levels = CATEGORY_LEVELS.write().unwrap();
+    if let Some(entry) = category_levels.iter_mut().find(|(c, _)| *c == category) {
+        entry.1 = level;
+    } else {
+        category_levels.push((category, level));
+    }
+    update_category_summary(&category_levels);
+}
+
+/// Removes any override previously set with [`set_category_level`].
+pub fn clear_category_level(category: &str) {
+    let mut category_levels = CATEGORY_LEVELS.write().unwrap();
+    category_levels.retain(|(c, _)| *c != category);
+    update_category_summary(&category_levels);
+}
+
+/// Must be called with the `CATEGORY_LEVELS` write lock held.
+fn update_category_summary(category_levels: &[(&'static str, Option<Level>)]) {
+    let max_level = category_levels
+        .iter()
+        .filter_map(|&(_, level)| level)
+        .max()
+        .map_or(0, |level| level as u8);
+    CATEGORY_MAX_LEVEL.store(max_level, Ordering::Relaxed);
+    HAS_CATEGORY_LEVELS.store(!category_levels.is_empty(), Ordering::Relaxed);
+}
+
+/// Returns `true` if a record with the given `level` and `category` would be
+/// passed to the sinks.
+pub fn enabled(level: Level, category: &str) -> bool {
+    if !static_enabled(level) {
+        return false;
+    }
+
+    let global_enabled = level as u8 <= MAX_LEVEL.load(Ordering::Relaxed);
+    if !HAS_CATEGORY_LEVELS.load(Ordering::Relaxed) {
+        return global_enabled;
+    }
+
+    // Neither the global level nor any override admits this record.
+    if !global_enabled && level as u8 > CATEGORY_MAX_LEVEL.load(Ordering::Relaxed) {
+        return false;
+    }
+
+    if let Some(&(_, max_level)) = CATEGORY_LEVELS
+        .read()
+        .unwrap()
+        .iter()
+        .find(|(c, _)| *c == category)
+    {
+        return max_level.is_some_and(|max_level| level <= max_level);
+    }
+
+    global_enabled
+}
+
+/// Registers a sink which will receive all subsequent records.
+///
+/// Until the first sink is registered, records are written to stderr.
+pub fn add_sink(sink: Arc<dyn Sink>) {
+    SINKS.write().unwrap().push(sink)
+}
+
+/// Removes all registered sinks, flushing each one.
+pub fn clear_sinks() {
+    let sinks = std::mem::take(&mut *SINKS.write().unwrap());
+    for sink in sinks {
+        sink.flush();
+    }
+}
+
+/// Flushes all registered sinks.
+pub fn flush() {
+    for sink in SINKS.read().unwrap().iter() {
+        sink.flush()
+    }
+}
+
+/// Returns the time elapsed since the logger was first used.
+fn elapsed() -> Duration {
+    static START: OnceLock<Instant> = OnceLock::new();
+    START.get_or_init(Instant::now).elapsed()
+}
+
+/// A single log message, as passed to each [`Sink`].
+pub struct Record<'a> {
+    pub level: Level,
+    pub category: &'a str,
+    pub file: &'static str,
+    pub line: u32,
+    /// Time since the logger was first used.
+    pub time: Duration,
+    pub args: fmt::Arguments<'a>,
+}
+
+/// Destination for log records.
+pub trait Sink: Send + Sync {
+    fn write(&self, record: &Record);
+
+    fn flush(&self) {}
+}
+
+#[doc(hidden)]
+#[cold]
+#[inline(never)]
+pub fn log_impl(level: Level, category: &str, file: &'static str, line: u32, args: fmt::Arguments) {
+    if !enabled(level, category) {
+        return;
+    }
+
+    let record = Record {
+        level,
+        category,
+        file,
+        line,
+        time: elapsed(),
+        args,
+    };
+
+    let sinks = SINKS.read().unwrap();
+    if sinks.is_empty() {
+        let mut stderr = std::io::stderr().lock();
+        let _ = write_record(&mut stderr, &record, false);
+        return;
+    }
+
+    for sink in sinks.iter() {
+        sink.write(&record);
+    }
+}
+
+/// Writes a record with an explicit level.
+#[macro_export]
+macro_rules! log {
+    ($level:expr, category: $category:expr, $($arg:tt)+) => {{
+        let level: $crate::log::Level = $level;
+        if $crate::log::static_enabled(level) {
+            $crate::log::log_impl(level, $category, file!(), line!(), format_args!($($arg)+));
+        }
+    }};
+    ($level:expr, $($arg:tt)+) => {
+        $crate::log!($level, category: module_path!(), $($arg)+)
+    };
+}
+
+#[macro_export]
+macro_rules! log_error {
+    ($($arg:tt)+) => {
+        $crate::log!($crate::log::Level::Error, $($arg)+)
+    };
+}
+
+#[macro_export]
+macro_rules! log_warn {
+    ($($arg:tt)+) => {
+        $crate::log!($crate::log::Level::Warn, $($arg)+)
+    };
+}
+
+#[macro_export]
+macro_rules! log_info {
+    ($($arg:tt)+) => {
+        $crate::log!($crate::log::Level::Info, $($arg)+)
+    };
+}
+
+#[macro_export]
+macro_rules! log_debug {
+    ($($arg:tt)+) => {
+        $crate::log!($crate::log::Level::Debug, $($arg)+)
+    };
+}
+
+#[macro_export]
+macro_rules! log_trace {
+    ($($arg:tt)+) => {
+        $crate::log!($crate::log::Level::Trace, $($arg)+)
+    };
+}
+
+fn write_record(w: &mut impl Write, record: &Record, color: bool) -> std::io::Result<()> {
+    let secs = record.time.as_secs();
+    let millis = record.time.subsec_millis();
+    if color {
+        writeln!(
+            w,
+            "\x1b[90m[{secs:>6}.{millis:03}]\x1b[0m {}{:<5}\x1b[0m \x1b[90m{}:\x1b[0m {}",
+            record.level.ansi_color(),
+            record.level,
+            record.category,
+            record.args
+        )
+    } else {
+        writeln!(
+            w,
+            "[{secs:>6}.{millis:03}] {:<5} {}: {}",
+            record.level, record.category, record.args
+        )
+    }
+}
+
+/// Sink writing records to stderr, with optional ANSI colours.
+pub struct StderrSink {
+    color: bool,
+}
+
+impl StderrSink {
+    pub fn new(color: bool) -> Self {
+        Self { color }
+    }
+}
+
+impl Default for StderrSink {
+    fn default() -> Self {
+        use std::io::IsTerminal;
+        Self::new(std::io::stderr().is_terminal())
+    }
+}
+
+impl Sink for StderrSink {
+    fn write(&self, record: &Record) {
+        let mut stderr = std::io::stderr().lock();
+        let _ = write_record(&mut stderr, record, self.color);
+    }
+}
+
+/// Sink writing records to a file.
+///
+/// Output is buffered, but flushed whenever an error is written.
+pub struct FileSink {
+    path: PathBuf,
+    writer: Mutex<BufWriter<File>>,
+}
+
+impl FileSink {
+    /// Creates, or truncates, the file at `path`.
+    pub fn new(path: &Path) -> std::io::Result<Self> {
+        let file = File::create(path)?;
+        Ok(Self {
+            path: path.to_owned(),
+            writer: Mutex::new(BufWriter::new(file)),
+        })
+    }
+
+    /// Creates, or truncates, the file `file_name` inside the `app_name`
+    /// sub-directory of the user's data directory.
+    ///
+    /// See [`data_dir`].
+    pub fn in_data_dir(app_name: &str, file_name: &str) -> std::io::Result<Self> {
+        let mut path = data_dir()
+            .ok_or_else(|| std::io::Error::other("could not determine data directory"))?
+            .to_path_buf();
+        path.push(app_name);
+        std::fs::create_dir_all(&path)?;
+        path.push(file_name);
+        Self::new(&path)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Sink for FileSink {
+    fn write(&self, record: &Record) {
+        let mut writer = self.writer.lock();
+        let _ = write_record(&mut *writer, record, false);
+        if record.level == Level::Error {
+            let _ = writer.flush();
+        }
+    }
+
+    fn flush(&self) {
+        let _ = self.writer.lock().flush();
+    }
+}
+
+/// A log record retained by a [`RingBufferSink`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Entry {
+    pub level: Level,
+    pub category: String,
+    pub time: Duration,
+    pub message: String,
+}
+
+/// Sink retaining the most recent records in memory, e.g. for display in an
+/// in-game console.
+pub struct RingBufferSink {
+    capacity: usize,
+    entries: Mutex<VecDeque<Entry>>,
+}
+
+impl RingBufferSink {
+    /// Creates a new sink which retains at most `capacity` records.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity != 0);
+        Self {
+            capacity,
+            entries: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of records currently retained.
+    pub fn len(&self) -> usize {
+        self.entries.lock().len()
+    }
+
+    /// Calls `f` for each retained record, from oldest to newest.
+    pub fn for_each<F>(&self, mut f: F)
+    where
+        F: FnMut(&Entry),
+    {
+        for entry in self.entries.lock().iter() {
+            f(entry)
+        }
+    }
+
+    /// Returns a copy of the retained records, from oldest to newest.
+    pub fn entries(&self) -> Vec<Entry> {
+        self.entries.lock().iter().cloned().collect()
+    }
+
+    /// Removes all retained records.
+    pub fn clear(&self) {
+        self.entries.lock().clear()
+    }
+}
+
+impl Sink for RingBufferSink {
+    fn write(&self, record: &Record) {
+        let mut entries = self.entries.lock();
+        // Re-use the evicted entry's allocations where possible.
+        let mut entry = if entries.len() == self.capacity {
+            entries.pop_front().unwrap()
+        } else {
+            Entry {
+                level: record.level,
+                category: String::new(),
+                time: record.time,
+                message: String::new(),
+            }
+        };
+        entry.level = record.level;
+        entry.time = record.time;
+        entry.category.clear();
+        entry.category.push_str(record.category);
+        entry.message.clear();
+        let _ = fmt::write(&mut entry.message, record.args);
+        entries.push_back(entry);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{
+        Level, Record, RingBufferSink, Sink, clear_category_level, enabled, max_level,
+        set_category_level,
+    };
+
+    #[test]
+    fn level_ordering() {
+        assert!(Level::Error < Level::Warn);
+        assert!(Level::Warn < Level::Info);
+        assert!(Level::Info < Level::Debug);
+        assert!(Level::Debug < Level::Trace);
+        assert_eq!(format!("{:<5}|", Level::Warn), "WARN |");
+    }
+
+    #[test]
+    fn category_filter() {
+        set_category_level("log_test_category_filter", Some(Level::Warn));
+        assert!(enabled(Level::Error, "log_test_category_filter"));
+        assert!(enabled(Level::Warn, "log_test_category_filter"));
+        assert!(!enabled(Level::Info, "log_test_category_filter"));
+        set_category_level("log_test_category_filter", None);
+        assert!(!enabled(Level::Error, "log_test_category_filter"));
+    }
+
+    #[test]
+    fn category_overrides_global() {
+        const CATEGORY: &str = "log_test_category_overrides_global";
+        let global = max_level().unwrap();
+        assert!(global < Level::Trace);
+        assert!(!enabled(Level::Trace, CATEGORY));
+        set_category_level(CATEGORY, Some(Level::Trace));
+        assert!(enabled(Level::Trace, CATEGORY));
+        // Other categories still use the global level.
+        assert!(!enabled(
+            Level::Trace,
+            "log_test_category_overrides_global_other"
+        ));
+        clear_category_level(CATEGORY);
+        assert!(!enabled(Level::Trace, CATEGORY));
+    }
+
+    #[test]
+    fn ring_buffer() {
+        // Write to the sink directly, so no global state is modified.
+        let ring = RingBufferSink::new(4);
+        let write = |level, message: &str| {
+            ring.write(&Record {
+                level,
+                category: "ring",
+                file: file!(),
+                line: line!(),
+                time: Duration::ZERO,
+                args: format_args!("{message}"),
+            })
+        };
+
+        for i in 0..10 {
+            write(Level::Info, &format!("message {i}"));
+        }
+        write(Level::Trace, "trace");
+
+        let entries = ring.entries();
+        assert_eq!(ring.len(), 4);
+        assert_eq!(
+            entries
+                .iter()
+                .map(|entry| entry.message.as_str())
+                .collect::<Vec<_>>(),
+            ["message 7", "message 8", "message 9", "trace"]
+        );
+        assert_eq!(entries.last().unwrap().level, Level::Trace);
+        assert_eq!(entries[0].category, "ring");
+
+        ring.clear();
+        assert_eq!(ring.len(), 0);
+    }
+}
@@ -0,0 +1,489 @@
+//! Tagged CPU memory accounting.
+//!
+//! [`TrackingAllocator`] wraps another global allocator and attributes every
+//! allocation to the innermost [`MemoryTag`] entered on the allocating thread.
+//! It's opt-in, and must be installed by the application.
+//!
+//! ```ignore
+//! use narcissus_core::memory_tracking::{MemoryTag, TrackingAllocator};
+//!
+//! #[global_allocator]
+//! static GLOBAL: TrackingAllocator = TrackingAllocator::new(std::alloc::System);
+//!
+//! let fonts = MemoryTag::new("fonts");
+//! {
+//!     let _scope = fonts.enter();
+//!     // ...
+//! }
+//! ```
+//!
+//! Frees are credited to the tag that made the allocation, regardless of the
+//! tag active at the time of the free.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    fmt,
+    sync::atomic::{AtomicU64, AtomicUsize, Ordering},
+};
+
+use crate::{PhantomUnsend, svg};
+
+/// Maximum number of distinct tags, including [`MemoryTag::UNTAGGED`].
+pub const MAX_TAGS: usize = 64;
+
+/// Maximum nesting depth of tag scopes on a single thread.
+pub const MAX_DEPTH: usize = 32;
+
+/// Space reserved in front of each allocation to store the tag. Must be at
+/// least as large as a `u32`, and a power of two.
+const HEADER_SIZE: usize = 16;
+
+struct TagCounters {
+    live_bytes: AtomicUsize,
+    peak_bytes: AtomicUsize,
+    alloc_count: AtomicU64,
+    free_count: AtomicU64,
+}
+
+impl TagCounters {
+    const fn new() -> Self {
+        Self {
+            live_bytes: AtomicUsize::new(0),
+            peak_bytes: AtomicUsize::new(0),
+            alloc_count: AtomicU64::new(0),
+            free_count: AtomicU64::new(0),
+        }
+    }
+}
+
+static COUNTERS: [TagCounters; MAX_TAGS] = [const { TagCounters::new() }; MAX_TAGS];
+
+struct TagNames {
+    names: [&'static str; MAX_TAGS],
+    len: usize,
+}
+
+static TAG_NAMES: std::sync::Mutex<TagNames> = std::sync::Mutex::new(TagNames {
+    names: {
+        let mut names = [""; MAX_TAGS];
+        names[0] = "untagged";
+        names
+    },
+    len: 1,
+});
+
+thread_local! {
+    static TAG_STACK: Cell<[u8; MAX_DEPTH]> = const { Cell::new([0; MAX_DEPTH]) };
+    static TAG_DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Identifies a category of memory usage.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct MemoryTag(u8);
+
+impl MemoryTag {
+    /// Tag for allocations made outside any tag scope.
+    pub const UNTAGGED: MemoryTag = MemoryTag(0);
+
+    /// Returns the tag with the given name, registering it if required.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than [`MAX_TAGS`] distinct tags are registered.
+    pub fn new(name: &'static str) -> MemoryTag {
+        let mut tag_names = TAG_NAMES.lock().unwrap();
+        let len = tag_names.len;
+        if let Some(index) = tag_names.names[..len].iter().position(|&n| n == name) {
+            return MemoryTag(index as u8);
+        }
+        assert!(len < MAX_TAGS, "too many memory tags");
+        tag_names.names[len] = name;
+        tag_names.len += 1;
+        MemoryTag(len as u8)
+    }
+
+    pub fn name(self) -> &'static str {
+        TAG_NAMES.lock().unwrap().names[self.0 as usize]
+    }
+
+    /// Returns the tag which will be assigned to allocations made on the
+    /// current thread.
+    pub fn current() -> MemoryTag {
+        MemoryTag(current_tag())
+    }
+
+    /// Attributes allocations on the current thread to this tag, until the
+    /// returned scope is dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than [`MAX_DEPTH`] scopes are nested.
+    pub fn enter(self) -> TagScope {
+        TAG_DEPTH.with(|depth| {
+            let d = depth.get();
+            assert!(d < MAX_DEPTH, "memory tag scopes nested too deeply");
+            TAG_STACK.with(|stack| {
+                let mut s = stack.get();
+                s[d] = self.0;
+                stack.set(s);
+            });
+            depth.set(d + 1);
+        });
+        TagScope {
+            _phantom: PhantomUnsend {},
+        }
+    }
+
+    /// Returns the current statistics for this tag.
+    pub fn stats(self) -> TagStats {
+        let counters = &COUNTERS[self.0 as usize];
+        TagStats {
+            name: self.name(),
+            live_bytes: counters.live_bytes.load(Ordering::Relaxed),
+            peak_bytes: counters.peak_bytes.load(Ordering::Relaxed),
+            alloc_count: counters.alloc_count.load(Ordering::Relaxed),
+            free_count: counters.free_count.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Guard returned by [`MemoryTag::enter`], pops the tag when dropped.
+#[must_use]
+pub struct TagScope {
+    _phantom: PhantomUnsend,
+}
+
+impl Drop for TagScope {
+    fn drop(&mut self) {
+        TAG_DEPTH.with(|depth| depth.set(depth.get() - 1));
+    }
+}
+
+/// Must not allocate, as it's called from within the allocator.
+#[inline(always)]
+fn current_tag() -> u8 {
+    TAG_DEPTH
+        .try_with(|depth| match depth.get() {
+            0 => 0,
+            d => TAG_STACK.with(|stack| stack.get()[d - 1]),
+        })
+        .unwrap_or(0)
+}
+
+#[inline(always)]
+fn record_alloc(tag: u8, size: usize) {
+    let counters = &COUNTERS[tag as usize];
+    counters.alloc_count.fetch_add(1, Ordering::Relaxed);
+    let live = counters.live_bytes.fetch_add(size, Ordering::Relaxed) + size;
+    counters.peak_bytes.fetch_max(live, Ordering::Relaxed);
+}
+
+#[inline(always)]
+fn record_free(tag: u8, size: usize) {
+    let counters = &COUNTERS[tag as usize];
+    counters.free_count.fetch_add(1, Ordering::Relaxed);
+    counters.live_bytes.fetch_sub(size, Ordering::Relaxed);
+}
+
+/// Returns the layout for the underlying allocation, and the offset of the
+/// user's pointer within it.
+#[inline(always)]
+fn inner_layout(size: usize, align: usize) -> Option<(Layout, usize)> {
+    let offset = align.max(HEADER_SIZE);
+    let size = size.checked_add(offset)?;
+    let layout = Layout::from_size_align(size, offset).ok()?;
+    Some((layout, offset))
+}
+
+#[inline(always)]
+unsafe fn tag_ptr(ptr: *mut u8) -> *mut u32 {
+    unsafe { ptr.sub(size_of::<u32>()) as *mut u32 }
+}
+
+/// A global allocator wrapper that tracks memory usage per [`MemoryTag`].
+pub struct TrackingAllocator<A = System> {
+    inner: A,
+}
+
+impl<A> TrackingAllocator<A> {
+    pub const fn new(inner: A) -> Self {
+        Self { inner }
+    }
+}
+
+unsafe impl<A: GlobalAlloc> GlobalAlloc for TrackingAllocator<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let Some((inner_layout, offset)) = inner_layout(layout.size(), layout.align()) else {
+            return std::ptr::null_mut();
+        };
+        unsafe {
+            let base = self.inner.alloc(inner_layout);
+            if base.is_null() {
+                return base;
+            }
+            let tag = current_tag();
+            let ptr = base.add(offset);
+            tag_ptr(ptr).write(tag as u32);
+            record_alloc(tag, layout.size());
+            ptr
+        }
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let Some((inner_layout, offset)) = inner_layout(layout.size(), layout.align()) else {
+            return std::ptr::null_mut();
+        };
+        unsafe {
+            let base = self.inner.alloc_zeroed(inner_layout);
+            if base.is_null() {
+                return base;
+            }
+            let tag = current_tag();
+            let ptr = base.add(offset);
+            tag_ptr(ptr).write(tag as u32);
+            record_alloc(tag, layout.size());
+            ptr
+        }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe {
+            let (inner_layout, offset) =
+                inner_layout(layout.size(), layout.align()).unwrap_unchecked();
+            let tag = tag_ptr(ptr).read() as u8;
+            record_free(tag, layout.size());
+            self.inner.dealloc(ptr.sub(offset), inner_layout)
+        }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        unsafe {
+            let (inner_layout, offset) =
+                inner_layout(layout.size(), layout.align()).unwrap_unchecked();
+            let Some(new_inner_size) = new_size.checked_add(offset) else {
+                return std::ptr::null_mut();
+            };
+            let tag = tag_ptr(ptr).read() as u8;
+            let base = self
+                .inner
+                .realloc(ptr.sub(offset), inner_layout, new_inner_size);
+            if base.is_null() {
+                return base;
+            }
+
+            // The header is moved along with the allocation, so the original
+            // tag retains ownership.
+            let counters = &COUNTERS[tag as usize];
+            if new_size >= layout.size() {
+                let grow = new_size - layout.size();
+                let live = counters.live_bytes.fetch_add(grow, Ordering::Relaxed) + grow;
+                counters.peak_bytes.fetch_max(live, Ordering::Relaxed);
+            } else {
+                let shrink = layout.size() - new_size;
+                counters.live_bytes.fetch_sub(shrink, Ordering::Relaxed);
+            }
+
+            base.add(offset)
+        }
+    }
+}
+
+/// A snapshot of the counters for a single tag.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TagStats {
+    pub name: &'static str,
+    pub live_bytes: usize,
+    pub peak_bytes: usize,
+    pub alloc_count: u64,
+    pub free_count: u64,
+}
+
+/// Returns a snapshot of the counters for every registered tag.
+pub fn stats() -> Vec<TagStats> {
+    let len = TAG_NAMES.lock().unwrap().len;
+    (0..len).map(|i| MemoryTag(i as u8).stats()).collect()
+}
+
+struct Bytes(usize);
+
+impl fmt::Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bytes = self.0;
+        if bytes < 1024 {
+            write!(f, "{bytes}b")
+        } else if bytes < 1024 * 1024 {
+            write!(f, "{:.2}KiB", bytes as f64 / 1024.0)
+        } else if bytes < 1024 * 1024 * 1024 {
+            write!(f, "{:.2}MiB", bytes as f64 / (1024.0 * 1024.0))
+        } else {
+            write!(f, "{:.2}GiB", bytes as f64 / (1024.0 * 1024.0 * 1024.0))
+        }
+    }
+}
+
+/// Writes a plain text table of `stats`.
+pub fn write_report(w: &mut dyn std::io::Write, stats: &[TagStats]) -> std::io::Result<()> {
+    writeln!(
+        w,
+        "{:<24} {:>12} {:>12} {:>12} {:>12}",
+        "tag", "live", "peak", "allocs", "frees"
+    )?;
+    for stat in stats {
+        writeln!(
+            w,
+            "{:<24} {:>12} {:>12} {:>12} {:>12}",
+            stat.name,
+            Bytes(stat.live_bytes).to_string(),
+            Bytes(stat.peak_bytes).to_string(),
+            stat.alloc_count,
+            stat.free_count
+        )?;
+    }
+    Ok(())
+}
+
+/// Writes an SVG treemap of live bytes per tag in `stats`.
+pub fn write_svg_treemap(
+    w: &mut dyn std::io::Write,
+    stats: &[TagStats],
+    width: f32,
+    height: f32,
+) -> std::io::Result<()> {
+    let mut items = stats
+        .iter()
+        .enumerate()
+        .filter(|(_, stat)| stat.live_bytes != 0)
+        .map(|(i, stat)| (i, stat.live_bytes))
+        .collect::<Vec<_>>();
+    items.sort_unstable_by_key(|&(_, weight)| std::cmp::Reverse(weight));
+
+    let mut rects = Vec::with_capacity(items.len());
+    treemap(&items, [0.0, 0.0, width, height], &mut rects);
+
+    let stroke = svg::stroke(svg::black(), 1.0, 1.0);
+    let label = svg::style(svg::fill(svg::black(), 1.0), svg::Stroke::None);
+
+    write!(w, "{}", svg::svg_begin(width, height))?;
+    for (i, [x, y, rw, rh]) in rects {
+        let stat = &stats[i];
+        let hue = (i as u32).wrapping_mul(137) % 360;
+        let style = svg::style(svg::fill(svg::hsl(hue, 60, 70), 1.0), stroke);
+        let title = format!(
+            "{}: {} live, {} peak",
+            stat.name,
+            Bytes(stat.live_bytes),
+            Bytes(stat.peak_bytes)
+        );
+        write!(w, "{}", svg::rect(x, y, rw, rh).style(style).title(&title))?;
+        if rw > 48.0 && rh > 16.0 {
+            let text = format!("{} {}", stat.name, Bytes(stat.live_bytes));
+            write!(w, "{}", svg::text(x + 4.0, y + 14.0, 12.0, label, &text))?;
+        }
+    }
+    write!(w, "{}", svg::svg_end())?;
+
+    Ok(())
+}
+
+/// Lays out `items` (index, weight), sorted by descending weight, within
+/// `rect` by recursively splitting the list into halves of similar weight
+/// along the rectangle's longest side.
+fn treemap(items: &[(usize, usize)], rect: [f32; 4], out: &mut Vec<(usize, [f32; 4])>) {
+    match items {
+        [] => {}
+        [(i, _)] => out.push((*i, rect)),
+        _ => {
+            let total = items.iter().map(|(_, weight)| weight).sum::<usize>();
+
+            let mut split = 1;
+            let mut left = items[0].1;
+            while split < items.len() - 1 && (left + items[split].1) * 2 <= total {
+                left += items[split].1;
+                split += 1;
+            }
+
+            let t = left as f32 / total as f32;
+            let [x, y, w, h] = rect;
+            let (a, b) = if w >= h {
+                let wa = w * t;
+                ([x, y, wa, h], [x + wa, y, w - wa, h])
+            } else {
+                let ha = h * t;
+                ([x, y, w, ha], [x, y + ha, w, h - ha])
+            };
+
+            treemap(&items[..split], a, out);
+            treemap(&items[split..], b, out);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::alloc::{GlobalAlloc, Layout, System};
+
+    use super::{MemoryTag, TrackingAllocator, treemap};
+
+    #[test]
+    fn tracking() {
+        let allocator = TrackingAllocator::new(System);
+        let tag = MemoryTag::new("memory_tracking_test");
+        let nested = MemoryTag::new("memory_tracking_test_nested");
+        assert_eq!(MemoryTag::new("memory_tracking_test"), tag);
+        assert_eq!(tag.name(), "memory_tracking_test");
+
+        unsafe {
+            let scope = tag.enter();
+            assert_eq!(MemoryTag::current(), tag);
+
+            let layout = Layout::from_size_align(100, 64).unwrap();
+            let a = allocator.alloc(layout);
+            assert_eq!(a as usize % 64, 0);
+
+            let b = {
+                let _scope = nested.enter();
+                allocator.alloc_zeroed(Layout::new::<[u8; 10]>())
+            };
+            assert_eq!(MemoryTag::current(), tag);
+            drop(scope);
+            assert_eq!(MemoryTag::current(), MemoryTag::UNTAGGED);
+
+            let a = allocator.realloc(a, layout, 200);
+            let stats = tag.stats();
+            assert_eq!(stats.live_bytes, 200);
+            assert_eq!(stats.peak_bytes, 200);
+            assert_eq!(stats.alloc_count, 1);
+
+            allocator.dealloc(a, Layout::from_size_align(200, 64).unwrap());
+            allocator.dealloc(b, Layout::new::<[u8; 10]>());
+
+            let stats = tag.stats();
+            assert_eq!(stats.live_bytes, 0);
+            assert_eq!(stats.peak_bytes, 200);
+            assert_eq!(stats.free_count, 1);
+
+            let stats = nested.stats();
+            assert_eq!(stats.live_bytes, 0);
+            assert_eq!(stats.peak_bytes, 10);
+            assert_eq!(stats.alloc_count, 1);
+            assert_eq!(stats.free_count, 1);
+        }
+    }
+
+    #[test]
+    fn treemap_layout() {
+        let items = [(0, 50), (1, 25), (2, 15), (3, 10)];
+        let mut rects = Vec::new();
+        treemap(&items, [0.0, 0.0, 200.0, 100.0], &mut rects);
+        assert_eq!(rects.len(), items.len());
+
+        let total_area = 200.0 * 100.0;
+        for ((i, weight), (j, [x, y, w, h])) in items.iter().zip(rects.iter()) {
+            assert_eq!(i, j);
+            assert!(*x >= 0.0 && *y >= 0.0 && x + w <= 200.0 && y + h <= 100.0);
+            let expected = total_area * *weight as f32 / 100.0;
+            assert!((w * h - expected).abs() < 1e-2);
+        }
+    }
+}
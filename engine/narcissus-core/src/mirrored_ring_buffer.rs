@@ -0,0 +1,291 @@
+use std::ffi::c_void;
+
+use crate::{libc, page_size, virtual_free, virtual_mem::MapError, virtual_reserve};
+
+/// A byte ring buffer whose pages are mapped twice, back to back.
+///
+/// Because the second mapping aliases the first, any window of up to
+/// `capacity` bytes starting anywhere in the buffer is contiguous in memory.
+/// So unlike [`crate::VirtualDeque`] the readable and writable regions are
+/// always available as single slices, regardless of wraparound.
+///
+/// The producer appends at the write cursor and the consumer removes from the
+/// read cursor. Both cursors increase monotonically, the difference between
+/// them being the number of readable bytes.
+pub struct MirroredRingBuffer {
+    ptr: *mut u8,
+    capacity: usize,
+    read: u64,
+    write: u64,
+}
+
+impl MirroredRingBuffer {
+    /// Creates a new ring buffer.
+    ///
+    /// Capacity will be rounded up to align with the system's page size.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn new(capacity: usize) -> Result<Self, MapError> {
+        assert!(capacity != 0);
+
+        let page_size = page_size();
+        let capacity = capacity
+            .checked_next_multiple_of(page_size)
+            .ok_or(MapError::MapFailed)?;
+        let reserve_size = capacity.checked_mul(2).ok_or(MapError::MapFailed)?;
+
+        unsafe {
+            let fd = libc::syscall(
+                libc::SYS_memfd_create,
+                c"narcissus-mirrored-ring-buffer".as_ptr(),
+                libc::MFD_CLOEXEC,
+            ) as i32;
+            if fd < 0 {
+                return Err(MapError::MapFailed);
+            }
+
+            if libc::ftruncate(fd, capacity as libc::off_t) != 0 {
+                libc::close(fd);
+                return Err(MapError::MapFailed);
+            }
+
+            // Reserve the full range first so the two mappings are guaranteed to
+            // be adjacent, then replace each half with a shared mapping of the
+            // same file.
+            let ptr = match virtual_reserve(reserve_size) {
+                Ok(ptr) => ptr,
+                Err(err) => {
+                    libc::close(fd);
+                    return Err(err);
+                }
+            };
+
+            let mut result = Ok(());
+            for half in [ptr, ptr.byte_add(capacity)] {
+                let mapped = libc::mmap(
+                    half,
+                    capacity,
+                    libc::PROT_READ | libc::PROT_WRITE,
+                    libc::MAP_SHARED | libc::MAP_FIXED,
+                    fd,
+                    0,
+                );
+                if mapped != half {
+                    result = Err(MapError::MapFailed);
+                    break;
+                }
+            }
+
+            // The mappings keep the file alive.
+            libc::close(fd);
+
+            if let Err(err) = result {
+                let _ = virtual_free(ptr, reserve_size);
+                return Err(err);
+            }
+
+            Ok(Self {
+                ptr: ptr as *mut u8,
+                capacity,
+                read: 0,
+                write: 0,
+            })
+        }
+    }
+
+    #[inline(always)]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of bytes available to read.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        (self.write - self.read) as usize
+    }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.read == self.write
+    }
+
+    #[inline(always)]
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity
+    }
+
+    /// Returns the number of bytes available to write.
+    #[inline(always)]
+    pub fn available(&self) -> usize {
+        self.capacity - self.len()
+    }
+
+    /// Returns the total number of bytes ever consumed.
+    #[inline(always)]
+    pub fn read_cursor(&self) -> u64 {
+        self.read
+    }
+
+    /// Returns the total number of bytes ever produced.
+    #[inline(always)]
+    pub fn write_cursor(&self) -> u64 {
+        self.write
+    }
+
+    #[inline(always)]
+    fn offset(&self, cursor: u64) -> usize {
+        (cursor % self.capacity as u64) as usize
+    }
+
+    /// Returns all readable bytes as a single slice.
+    #[inline(always)]
+    pub fn read_slice(&self) -> &[u8] {
+        // SAFETY: The offset is less than capacity, and the length at most
+        // capacity, so the slice is within the double mapping.
+        unsafe { std::slice::from_raw_parts(self.ptr.add(self.offset(self.read)), self.len()) }
+    }
+
+    /// Returns all writable bytes as a single slice. Call [`Self::produce`] to
+    /// make bytes written to the slice readable.
+    #[inline(always)]
+    pub fn write_slice(&mut self) -> &mut [u8] {
+        // SAFETY: The offset is less than capacity, and the length at most
+        // capacity, so the slice is within the double mapping. The writable
+        // region never overlaps the readable region.
+        unsafe {
+            std::slice::from_raw_parts_mut(self.ptr.add(self.offset(self.write)), self.available())
+        }
+    }
+
+    /// Advances the write cursor, making `count` bytes written through
+    /// [`Self::write_slice`] readable.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `count` exceeds the number of writable bytes.
+    #[inline(always)]
+    pub fn produce(&mut self, count: usize) {
+        assert!(count <= self.available());
+        self.write += count as u64;
+    }
+
+    /// Advances the read cursor, discarding `count` readable bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `count` exceeds the number of readable bytes.
+    #[inline(always)]
+    pub fn consume(&mut self, count: usize) {
+        assert!(count <= self.len());
+        self.read += count as u64;
+    }
+
+    /// Copies as much of `bytes` as will fit into the buffer, returning the
+    /// number of bytes written.
+    pub fn push(&mut self, bytes: &[u8]) -> usize {
+        let count = bytes.len().min(self.available());
+        self.write_slice()[..count].copy_from_slice(&bytes[..count]);
+        self.produce(count);
+        count
+    }
+
+    /// Copies as many bytes as will fit into `bytes` out of the buffer, returning
+    /// the number of bytes read.
+    pub fn pop(&mut self, bytes: &mut [u8]) -> usize {
+        let count = bytes.len().min(self.len());
+        bytes[..count].copy_from_slice(&self.read_slice()[..count]);
+        self.consume(count);
+        count
+    }
+
+    /// Discards all readable bytes.
+    pub fn clear(&mut self) {
+        self.read = self.write;
+    }
+}
+
+impl Drop for MirroredRingBuffer {
+    fn drop(&mut self) {
+        unsafe {
+            virtual_free(self.ptr as *mut c_void, self.capacity * 2).expect("failed to unmap")
+        }
+    }
+}
+
+// SAFETY: The buffer uniquely owns its mappings.
+unsafe impl Send for MirroredRingBuffer {}
+unsafe impl Sync for MirroredRingBuffer {}
+
+#[cfg(test)]
+mod tests {
+    use crate::page_size;
+
+    use super::MirroredRingBuffer;
+
+    #[test]
+    fn mirrored() {
+        let ring = MirroredRingBuffer::new(1).unwrap();
+        let capacity = ring.capacity();
+        assert_eq!(capacity, page_size());
+
+        // Writes to the first mapping are visible through the second.
+        unsafe {
+            for i in 0..capacity {
+                ring.ptr.add(i).write(i as u8);
+            }
+            for i in 0..capacity {
+                assert_eq!(ring.ptr.add(capacity + i).read(), i as u8);
+            }
+        }
+    }
+
+    #[test]
+    fn wraparound() {
+        let mut ring = MirroredRingBuffer::new(4096).unwrap();
+        let capacity = ring.capacity();
+        assert!(ring.is_empty());
+        assert_eq!(ring.available(), capacity);
+
+        let mut next_write = 0_u64;
+        let mut next_read = 0_u64;
+        let mut chunk = vec![0; capacity];
+
+        // Odd sizes walk the cursors through every alignment across the wrap.
+        for step in 0..1000 {
+            let write_len = (step * 97) % capacity + 1;
+            for byte in chunk[..write_len].iter_mut() {
+                *byte = (next_write % 251) as u8;
+                next_write += 1;
+            }
+            let written = ring.push(&chunk[..write_len]);
+            next_write -= (write_len - written) as u64;
+            assert_eq!(ring.write_cursor(), next_write);
+
+            // The full readable region is always one contiguous slice.
+            let readable = ring.read_slice();
+            assert_eq!(readable.len() as u64, next_write - next_read);
+            for (i, &byte) in readable.iter().enumerate() {
+                assert_eq!(byte, ((next_read + i as u64) % 251) as u8);
+            }
+
+            let read_len = ((step * 31) % capacity).min(ring.len());
+            assert_eq!(ring.pop(&mut chunk[..read_len]), read_len);
+            for &byte in &chunk[..read_len] {
+                assert_eq!(byte, (next_read % 251) as u8);
+                next_read += 1;
+            }
+            assert_eq!(ring.read_cursor(), next_read);
+        }
+
+        ring.clear();
+        assert!(ring.is_empty());
+        assert_eq!(ring.write_slice().len(), capacity);
+        ring.write_slice().fill(1);
+        ring.produce(capacity);
+        assert!(ring.is_full());
+        assert_eq!(ring.push(&[2]), 0);
+        assert!(ring.read_slice().iter().all(|&b| b == 1));
+    }
+}
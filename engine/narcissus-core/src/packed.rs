@@ -0,0 +1,257 @@
+//! Packing and unpacking for GPU packed formats.
+//!
+//! Bit layouts match the corresponding Vulkan `_PACK32` formats, with the first
+//! component in the least significant bits.
+
+use crate::half::{f32_to_small_float, small_float_to_f32};
+
+#[inline(always)]
+fn f32_to_ufloat(x: f32, mantissa_bits: u32) -> u32 {
+    let bits = x.to_bits();
+    // Negative values, including negative infinity, clamp to zero. NaNs are kept
+    // regardless of sign.
+    if bits & 0x8000_0000 != 0 && !x.is_nan() {
+        return 0;
+    }
+    f32_to_small_float(bits & 0x7fff_ffff, mantissa_bits)
+}
+
+#[inline(always)]
+fn ufloat_to_f32(bits: u32, mantissa_bits: u32) -> f32 {
+    f32::from_bits(small_float_to_f32(bits, mantissa_bits))
+}
+
+/// Packs an RGB value into the `B10G11R11_UFLOAT` format.
+///
+/// Red and green are stored as 11 bit floats, and blue as a 10 bit float, each
+/// with a 5 bit exponent and no sign bit. Values are rounded to nearest even,
+/// and negative values are clamped to zero.
+#[inline]
+pub fn pack_r11g11b10_ufloat(rgb: [f32; 3]) -> u32 {
+    let [r, g, b] = rgb;
+    f32_to_ufloat(r, 6) | (f32_to_ufloat(g, 6) << 11) | (f32_to_ufloat(b, 5) << 22)
+}
+
+/// Unpacks an RGB value from the `B10G11R11_UFLOAT` format.
+#[inline]
+pub fn unpack_r11g11b10_ufloat(packed: u32) -> [f32; 3] {
+    [
+        ufloat_to_f32(packed & 0x7ff, 6),
+        ufloat_to_f32((packed >> 11) & 0x7ff, 6),
+        ufloat_to_f32(packed >> 22, 5),
+    ]
+}
+
+const E5B9G9R9_MANTISSA_BITS: i32 = 9;
+const E5B9G9R9_EXPONENT_BIAS: i32 = 15;
+const E5B9G9R9_MAX: f32 = 65408.0;
+
+/// Returns 2^`exponent` for exponents in the normal range.
+#[inline(always)]
+fn exp2i(exponent: i32) -> f32 {
+    debug_assert!((-126..=127).contains(&exponent));
+    f32::from_bits(((exponent + 127) as u32) << 23)
+}
+
+/// Packs an RGB value into the `E5B9G9R9_UFLOAT` shared exponent format.
+///
+/// Each component is stored as a 9 bit mantissa sharing a 5 bit exponent, as
+/// described by the Vulkan specification. Values are rounded to nearest, and
+/// clamped to the representable range. NaNs are treated as zero.
+pub fn pack_e5b9g9r9_ufloat(rgb: [f32; 3]) -> u32 {
+    // NaNs fail the comparison, so are flushed to zero.
+    let [r, g, b] = rgb.map(|x| if x >= 0.0 { x.min(E5B9G9R9_MAX) } else { 0.0 });
+    let max = r.max(g).max(b);
+
+    // For `max` zero or denormal, the exponent clamps to its minimum anyway.
+    let floor_log2 = ((max.to_bits() >> 23) as i32 - 127).max(-E5B9G9R9_EXPONENT_BIAS - 1);
+    let mut exponent = floor_log2 + 1 + E5B9G9R9_EXPONENT_BIAS;
+
+    // Rounding may overflow the mantissa, in which case bump the exponent.
+    let scale = exp2i(E5B9G9R9_EXPONENT_BIAS + E5B9G9R9_MANTISSA_BITS - exponent);
+    if (max * scale).round() as u32 == 1 << E5B9G9R9_MANTISSA_BITS {
+        exponent += 1;
+    }
+
+    let scale = exp2i(E5B9G9R9_EXPONENT_BIAS + E5B9G9R9_MANTISSA_BITS - exponent);
+    let r = (r * scale).round() as u32;
+    let g = (g * scale).round() as u32;
+    let b = (b * scale).round() as u32;
+    r | (g << 9) | (b << 18) | ((exponent as u32) << 27)
+}
+
+/// Unpacks an RGB value from the `E5B9G9R9_UFLOAT` shared exponent format.
+pub fn unpack_e5b9g9r9_ufloat(packed: u32) -> [f32; 3] {
+    let exponent = (packed >> 27) as i32;
+    let scale = exp2i(exponent - E5B9G9R9_EXPONENT_BIAS - E5B9G9R9_MANTISSA_BITS);
+    [
+        (packed & 0x1ff) as f32 * scale,
+        ((packed >> 9) & 0x1ff) as f32 * scale,
+        ((packed >> 18) & 0x1ff) as f32 * scale,
+    ]
+}
+
+/// Packs an RGBA value into the `A2B10G10R10_UNORM` format.
+///
+/// Values are clamped to `[0.0, 1.0]` and rounded to nearest. NaNs are treated
+/// as zero.
+#[inline]
+pub fn pack_a2b10g10r10_unorm(rgba: [f32; 4]) -> u32 {
+    let [r, g, b, a] = rgba;
+    let quantize = |x: f32, max: f32| (x.clamp(0.0, 1.0) * max).round() as u32;
+    quantize(r, 1023.0)
+        | (quantize(g, 1023.0) << 10)
+        | (quantize(b, 1023.0) << 20)
+        | (quantize(a, 3.0) << 30)
+}
+
+/// Unpacks an RGBA value from the `A2B10G10R10_UNORM` format.
+#[inline]
+pub fn unpack_a2b10g10r10_unorm(packed: u32) -> [f32; 4] {
+    [
+        (packed & 0x3ff) as f32 / 1023.0,
+        ((packed >> 10) & 0x3ff) as f32 / 1023.0,
+        ((packed >> 20) & 0x3ff) as f32 / 1023.0,
+        (packed >> 30) as f32 / 3.0,
+    ]
+}
+
+/// Packs a value into a 16 bit signed normalized integer.
+///
+/// Values are clamped to `[-1.0, 1.0]` and rounded to nearest. NaNs are treated
+/// as zero.
+#[inline]
+pub fn pack_snorm16(x: f32) -> i16 {
+    (x.clamp(-1.0, 1.0) * 32767.0).round() as i16
+}
+
+/// Unpacks a value from a 16 bit signed normalized integer.
+///
+/// Both `-32767` and `-32768` unpack to `-1.0`.
+#[inline]
+pub fn unpack_snorm16(x: i16) -> f32 {
+    (x as f32 / 32767.0).max(-1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::F16;
+
+    use super::*;
+
+    #[test]
+    fn r11g11b10_ufloat() {
+        assert_eq!(pack_r11g11b10_ufloat([0.0, 0.0, 0.0]), 0);
+        assert_eq!(
+            unpack_r11g11b10_ufloat(pack_r11g11b10_ufloat([1.0, 2.0, 0.5])),
+            [1.0, 2.0, 0.5]
+        );
+        assert_eq!(pack_r11g11b10_ufloat([-1.0, f32::NEG_INFINITY, -0.0]), 0);
+        assert_eq!(
+            unpack_r11g11b10_ufloat(pack_r11g11b10_ufloat([f32::INFINITY, 1e9, 64800.0])),
+            [f32::INFINITY, f32::INFINITY, 64512.0]
+        );
+        assert!(
+            unpack_r11g11b10_ufloat(pack_r11g11b10_ufloat([f32::NAN; 3]))
+                .iter()
+                .all(|x| x.is_nan())
+        );
+    }
+
+    #[test]
+    fn exhaustive_r11g11b10_ufloat() {
+        for bits in 0..1 << 11 {
+            let x = ufloat_to_f32(bits, 6);
+            // The 11 and 10 bit formats are truncated halves.
+            let half = F16::from_bits((bits << 4) as u16);
+            assert_eq!(x.to_bits(), half.to_f32().to_bits());
+            if x.is_nan() {
+                continue;
+            }
+            let packed = pack_r11g11b10_ufloat([x, x, 0.0]);
+            assert_eq!(packed, bits | (bits << 11));
+            if bits < 1 << 10 {
+                let x = ufloat_to_f32(bits, 5);
+                if x.is_nan() {
+                    continue;
+                }
+                assert_eq!(pack_r11g11b10_ufloat([0.0, 0.0, x]), bits << 22);
+            }
+        }
+
+        // Midpoints round to even.
+        for bits in 0..0x7bf {
+            let lo = ufloat_to_f32(bits, 6);
+            let hi = ufloat_to_f32(bits + 1, 6);
+            let even = if bits & 1 == 0 { bits } else { bits + 1 };
+            assert_eq!(pack_r11g11b10_ufloat([(lo + hi) * 0.5, 0.0, 0.0]), even);
+        }
+    }
+
+    #[test]
+    fn e5b9g9r9_ufloat() {
+        assert_eq!(pack_e5b9g9r9_ufloat([0.0, 0.0, 0.0]), 0);
+        assert_eq!(
+            unpack_e5b9g9r9_ufloat(pack_e5b9g9r9_ufloat([1.0, 0.5, 0.25])),
+            [1.0, 0.5, 0.25]
+        );
+        assert_eq!(
+            unpack_e5b9g9r9_ufloat(pack_e5b9g9r9_ufloat([f32::INFINITY, -1.0, f32::NAN])),
+            [E5B9G9R9_MAX, 0.0, 0.0]
+        );
+        // Mantissa overflow during rounding bumps the exponent.
+        assert_eq!(
+            unpack_e5b9g9r9_ufloat(pack_e5b9g9r9_ufloat([1.999, 0.0, 0.0])),
+            [2.0, 0.0, 0.0]
+        );
+    }
+
+    #[test]
+    fn exhaustive_e5b9g9r9_ufloat() {
+        // Every packed value where the largest component uses the full mantissa
+        // (or the exponent is at its minimum) is canonical and must roundtrip.
+        for exponent in 0..32 {
+            for mantissa in 0..1 << 9 {
+                if exponent != 0 && mantissa < 1 << 8 {
+                    continue;
+                }
+                for (r, g, b) in [
+                    (mantissa, 0, 0),
+                    (0, mantissa, mantissa / 2),
+                    (mantissa / 3, mantissa / 5, mantissa),
+                ] {
+                    let packed = r | (g << 9) | (b << 18) | (exponent << 27);
+                    let rgb = unpack_e5b9g9r9_ufloat(packed);
+                    assert_eq!(pack_e5b9g9r9_ufloat(rgb), packed);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn exhaustive_a2b10g10r10_unorm() {
+        for x in 0..1 << 10 {
+            let a = x & 3;
+            let packed = x | (x << 10) | ((1023 - x) << 20) | (a << 30);
+            let rgba = unpack_a2b10g10r10_unorm(packed);
+            assert_eq!(pack_a2b10g10r10_unorm(rgba), packed);
+        }
+        assert_eq!(pack_a2b10g10r10_unorm([1.0, 0.0, 1.0, 1.0]), 0xfff0_03ff);
+        assert_eq!(
+            pack_a2b10g10r10_unorm([2.0, -1.0, f32::NAN, 0.5]),
+            0x8000_03ff
+        );
+    }
+
+    #[test]
+    fn exhaustive_snorm16() {
+        for x in i16::MIN..=i16::MAX {
+            let expected = x.max(-i16::MAX);
+            assert_eq!(pack_snorm16(unpack_snorm16(x)), expected);
+        }
+        assert_eq!(pack_snorm16(1.0), i16::MAX);
+        assert_eq!(pack_snorm16(-2.0), -i16::MAX);
+        assert_eq!(pack_snorm16(f32::NAN), 0);
+        assert_eq!(unpack_snorm16(i16::MIN), -1.0);
+    }
+}
@@ -1,8 +1,9 @@
 use std::{marker::PhantomData, mem::size_of, ptr::NonNull};
 
 use crate::{
-    Widen, align_offset, mod_inverse_u32, static_assert, virtual_commit, virtual_free,
-    virtual_reserve,
+    Widen, align_offset, mod_inverse_u32,
+    serialize::{Deserialize, DeserializeError, Reader, Serialize, Writer},
+    static_assert, virtual_commit, virtual_free, virtual_reserve,
 };
 
 /// Each handle uses `GEN_BITS` bits of per-slot generation counter. Looking up
@@ -40,6 +41,7 @@ const GEN_SHIFT: u32 = IDX_SHIFT + IDX_BITS;
 /// provide type safety preventing the handles from separate pools from becoming
 /// confused.
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(transparent)]
 pub struct Handle(u32);
 
 impl Default for Handle {
@@ -322,6 +324,15 @@ impl Slots {
             return None;
         }
 
+        self.grow_to(new_len);
+        Some((len as u32, new_len as u32))
+    }
+
+    /// Grows the slots array to exactly `new_len` empty slots.
+    fn grow_to(&mut self, new_len: usize) {
+        let len = self.len;
+        debug_assert!(len <= new_len && new_len <= MAX_CAP);
+
         unsafe {
             virtual_commit(
                 self.ptr.as_ptr().add(len) as _,
@@ -333,7 +344,6 @@ impl Slots {
         }
 
         self.len = new_len;
-        Some((len as u32, new_len as u32))
     }
 }
 
@@ -491,6 +501,9 @@ impl<T> Values<T> {
 /// lookup using a handle from another pool will return a valid object. The pool
 /// will not have memory unsafety in this case however, as it will only return
 /// valid objects from the pool.
+///
+/// Pools implement [`Serialize`] and [`Deserialize`], and handles remain valid
+/// across a save and restore.
 pub struct Pool<T> {
     encode_multiplier: u32,
     decode_multiplier: u32,
@@ -683,11 +696,160 @@ impl<T> Default for Pool<T> {
     }
 }
 
+/// Writes a snapshot of the pool, including its handle mixing constant, slot
+/// generations and free list, so that a restored pool resolves previously
+/// issued handles to the same values and issues the same handles in future.
+impl<T: Serialize> Serialize for Pool<T> {
+    fn serialize(&self, w: &mut Writer) {
+        w.write(&self.encode_multiplier);
+
+        w.write_len(self.slots.len);
+        for i in 0..self.slots.len {
+            let slot = self.slots.get(SlotIndex(i as u32)).unwrap();
+            w.write(&slot.value_index_and_gen);
+        }
+
+        let free_slots = &self.free_slots;
+        w.write_len(free_slots.cap);
+        w.write_len(if free_slots.cap == 0 {
+            0
+        } else {
+            free_slots.tail()
+        });
+        w.write_len(free_slots.len());
+        for i in 0..free_slots.len() {
+            let index = free_slots.tail.wrapping_add(i) & (free_slots.cap - 1);
+            let slot_index = unsafe { std::ptr::read(free_slots.ptr.as_ptr().add(index)) };
+            w.write(&slot_index.0);
+        }
+
+        w.write_len(self.values.len);
+        for (i, value) in self.values.as_slice().iter().enumerate() {
+            let slot_index = unsafe { std::ptr::read(self.values.slots_ptr.as_ptr().add(i)) };
+            w.write(&slot_index.0);
+            w.write(value);
+        }
+    }
+}
+
+/// Restores a pool written by [`Serialize`].
+///
+/// Returns [`DeserializeError::InvalidValue`] if the snapshot is inconsistent.
+impl<'a, T: Deserialize<'a>> Deserialize<'a> for Pool<T> {
+    fn deserialize(r: &mut Reader<'a>) -> Result<Self, DeserializeError> {
+        let mut pool = Pool::new();
+
+        let encode_multiplier: u32 = r.read()?;
+        if encode_multiplier & 1 == 0 {
+            return Err(DeserializeError::InvalidValue);
+        }
+        pool.encode_multiplier = encode_multiplier;
+        pool.decode_multiplier = mod_inverse_u32(encode_multiplier);
+
+        let slots_len = r.read_len()?;
+        // Slots are only ever grown a page at a time.
+        if slots_len > MAX_CAP
+            || !(slots_len.is_multiple_of(SLOT_GROWTH_AMOUNT) || slots_len == MAX_CAP)
+            || slots_len > r.remaining() / size_of::<u32>()
+        {
+            return Err(DeserializeError::InvalidValue);
+        }
+        if slots_len != 0 {
+            pool.slots.grow_to(slots_len);
+        }
+        for i in 0..slots_len {
+            let slot = pool.slots.get_mut(SlotIndex(i as u32)).unwrap();
+            slot.value_index_and_gen = r.read()?;
+        }
+
+        let cap = r.read_len()?;
+        let tail = r.read_len()?;
+        let len = r.read_len()?;
+        let valid = if cap == 0 {
+            tail == 0 && len == 0
+        } else {
+            cap.is_power_of_two() && (1024..=MAX_IDX).contains(&cap) && tail < cap && len <= cap
+        };
+        if !valid {
+            return Err(DeserializeError::InvalidValue);
+        }
+        // Match the original capacity and position so the free list wraps and
+        // grows at the same points.
+        while pool.free_slots.cap < cap {
+            pool.free_slots.grow();
+        }
+        pool.free_slots.tail = tail;
+        pool.free_slots.head = tail;
+        // Each free slot may only appear in the free list once, otherwise it would
+        // be handed out twice.
+        let mut listed = vec![0u64; slots_len.div_ceil(64)];
+        for _ in 0..len {
+            let slot_index = SlotIndex(r.read()?);
+            match pool.slots.get(slot_index) {
+                Some(slot) if slot.generation() & 1 == 0 => {
+                    let i = slot_index.0.widen();
+                    let bit = 1 << (i % 64);
+                    if listed[i / 64] & bit != 0 {
+                        return Err(DeserializeError::InvalidValue);
+                    }
+                    listed[i / 64] |= bit;
+                    pool.free_slots.push(slot_index)
+                }
+                _ => return Err(DeserializeError::InvalidValue),
+            }
+        }
+
+        let values_len = r.read_len()?;
+        if values_len > slots_len {
+            return Err(DeserializeError::InvalidValue);
+        }
+        for _ in 0..values_len {
+            let slot_index = SlotIndex(r.read()?);
+            let value = r.read()?;
+            let value_index = pool.values.push(value);
+            pool.values.set_slot(value_index, slot_index);
+        }
+
+        // Every occupied slot must refer to a value which refers back to it, every
+        // value must be referenced by an occupied slot, and every free slot must be
+        // in the free list.
+        let mut occupied = 0;
+        for i in 0..slots_len {
+            let slot_index = SlotIndex(i as u32);
+            let slot = pool.slots.get(slot_index).unwrap();
+            if slot.generation() & 1 == 1 {
+                let value_index = slot.value_index();
+                if value_index.0.widen() >= values_len
+                    || pool.values.get_slot(value_index) != slot_index
+                {
+                    return Err(DeserializeError::InvalidValue);
+                }
+                occupied += 1;
+            }
+        }
+        if occupied != values_len || slots_len - occupied != len {
+            return Err(DeserializeError::InvalidValue);
+        }
+
+        Ok(pool)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::atomic::{AtomicU32, Ordering};
 
-    use super::{Handle, MAX_CAP, Pool};
+    use crate::{
+        Uuid,
+        serialize::{DeserializeError, Reader, Schema, Writer},
+    };
+
+    use super::{Handle, MAX_CAP, Pool, SLOT_GROWTH_AMOUNT, Slot};
+
+    const SCHEMA: Schema = Schema::new(
+        Uuid::parse_str_unwrap("0f6c2e0a-5d7b-4b8e-a2f1-3c9d4e8b7a61"),
+        1,
+    );
 
     #[test]
     fn lookup_null() {
@@ -799,4 +961,135 @@ mod tests {
         drop(pool);
         assert_eq!(DROP_COUNT.load(Ordering::Relaxed), 4);
     }
+
+    #[test]
+    fn serialize_roundtrip() {
+        let mut pool = Pool::new();
+        let handles = (0..3000).map(|i| pool.insert(i)).collect::<Vec<_>>();
+        for &handle in handles.iter().step_by(3) {
+            pool.remove(handle);
+        }
+
+        let mut w = Writer::new(SCHEMA);
+        w.write(&pool);
+        w.write(&handles);
+        let bytes = w.finish();
+
+        let mut r = Reader::new(&bytes, SCHEMA.id).unwrap();
+        let mut restored: Pool<u32> = r.read().unwrap();
+        let restored_handles: Vec<Handle> = r.read().unwrap();
+        assert!(r.is_empty());
+
+        assert_eq!(restored_handles, handles);
+        assert_eq!(restored.values(), pool.values());
+        for (i, &handle) in handles.iter().enumerate() {
+            let expected = if i % 3 == 0 { None } else { Some(&(i as u32)) };
+            assert_eq!(restored.get(handle), expected);
+        }
+
+        // The restored pool must issue the same handles as the original.
+        for i in 0..5000 {
+            let handle = pool.insert(i);
+            assert_eq!(restored.insert(i), handle);
+            if i % 2 == 0 {
+                assert_eq!(pool.remove(handle), Some(i));
+                assert_eq!(restored.remove(handle), Some(i));
+            }
+        }
+        assert_eq!(restored.values(), pool.values());
+    }
+
+    #[test]
+    fn serialize_empty() {
+        let pool = Pool::<u32>::new();
+        let mut w = Writer::new(SCHEMA);
+        w.write(&pool);
+        let bytes = w.finish();
+        let mut r = Reader::new(&bytes, SCHEMA.id).unwrap();
+        let mut restored: Pool<u32> = r.read().unwrap();
+        assert!(restored.is_empty());
+        let handle = restored.insert(1);
+        assert_eq!(restored.get(handle), Some(&1));
+    }
+
+    #[test]
+    fn serialize_invalid() {
+        let mut pool = Pool::new();
+        let _ = pool.insert(1_u32);
+        let mut w = Writer::new(SCHEMA);
+        w.write(&pool);
+        let bytes = w.finish();
+
+        let read = |bytes: &[u8]| -> Result<Pool<u32>, DeserializeError> {
+            Reader::new(bytes, SCHEMA.id).unwrap().read()
+        };
+        assert!(read(&bytes).is_ok());
+        assert_eq!(
+            read(&bytes[..bytes.len() - 1]).err(),
+            Some(DeserializeError::UnexpectedEof)
+        );
+
+        // The value's back-reference to its slot is the second to last word.
+        let mut corrupt = bytes.clone();
+        let len = corrupt.len();
+        corrupt[len - 8] ^= 1;
+        assert_eq!(read(&corrupt).err(), Some(DeserializeError::InvalidValue));
+
+        // Even multipliers can't be inverted.
+        let mut corrupt = bytes.clone();
+        corrupt[24] &= !1;
+        assert_eq!(read(&corrupt).err(), Some(DeserializeError::InvalidValue));
+    }
+
+    #[test]
+    fn serialize_invalid_free_list() {
+        let free = Slot::new().value_index_and_gen;
+        let snapshot = |free_list: &[u32]| {
+            let mut w = Writer::new(SCHEMA);
+            w.write(&1_u32);
+            w.write_len(SLOT_GROWTH_AMOUNT);
+            for _ in 0..SLOT_GROWTH_AMOUNT {
+                w.write(&free);
+            }
+            w.write_len(1024);
+            w.write_len(0);
+            w.write_len(free_list.len());
+            for slot_index in free_list {
+                w.write(slot_index);
+            }
+            w.write_len(0);
+            w.finish()
+        };
+        let read = |bytes: &[u8]| -> Result<Pool<u32>, DeserializeError> {
+            Reader::new(bytes, SCHEMA.id).unwrap().read()
+        };
+
+        let mut free_list = (0..SLOT_GROWTH_AMOUNT as u32).collect::<Vec<_>>();
+        let mut pool = read(&snapshot(&free_list)).unwrap();
+        let handles = (0..SLOT_GROWTH_AMOUNT * 2)
+            .map(|i| pool.insert(i as u32))
+            .collect::<Vec<_>>();
+        for (i, &handle) in handles.iter().enumerate() {
+            assert_eq!(pool.get(handle), Some(&(i as u32)));
+        }
+
+        // Duplicated free slot.
+        free_list.push(7);
+        assert_eq!(
+            read(&snapshot(&free_list)).err(),
+            Some(DeserializeError::InvalidValue)
+        );
+        free_list[0] = 7;
+        free_list.pop();
+        assert_eq!(
+            read(&snapshot(&free_list)).err(),
+            Some(DeserializeError::InvalidValue)
+        );
+        // Missing free slot.
+        free_list.remove(0);
+        assert_eq!(
+            read(&snapshot(&free_list)).err(),
+            Some(DeserializeError::InvalidValue)
+        );
+    }
 }
@@ -0,0 +1,462 @@
+//! RIFF-style chunked containers.
+//!
+//! Each chunk is a [`FourCC`] id, followed by a little-endian `u32` size, then
+//! `size` bytes of data, then padding up to the container's alignment. The
+//! padding is not included in the size. Container chunks ([`RIFF`] and
+//! [`LIST`]) begin with a form type [`FourCC`], followed by nested chunks.
+//!
+//! Alignment is measured from the start of the file. Chunk headers are placed
+//! so that the data of every chunk begins at a multiple of the alignment, with
+//! any padding needed before a header counted as part of the parent's data.
+//!
+//! Standard RIFF files, such as WAV, use an alignment of 2, which never needs
+//! padding before a header.
+
+use std::{error::Error, fmt::Display};
+
+use crate::{FourCC, align_offset, fourcc};
+
+pub const RIFF: FourCC = fourcc!("RIFF");
+pub const LIST: FourCC = fourcc!("LIST");
+
+/// Alignment used by standard RIFF files.
+pub const RIFF_ALIGN: usize = 2;
+
+const CHUNK_HEADER_SIZE: usize = 8;
+
+/// Returns the padding required before a chunk header at `offset`, such that
+/// the chunk's data is aligned.
+#[inline(always)]
+fn header_padding(offset: usize, align: usize) -> usize {
+    align_offset(offset + CHUNK_HEADER_SIZE, align) - CHUNK_HEADER_SIZE - offset
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RiffError {
+    /// The buffer is too small to contain the chunk header or data.
+    TooSmall,
+    /// The file doesn't begin with a [`RIFF`] chunk.
+    BadMagic,
+    /// A container chunk is too small to contain its form type.
+    BadHeader,
+}
+
+impl Display for RiffError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+impl Error for RiffError {}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Chunk<'a> {
+    pub id: FourCC,
+    pub data: &'a [u8],
+    /// Offset of `data` from the start of the file.
+    offset: usize,
+    align: usize,
+}
+
+impl<'a> Chunk<'a> {
+    /// Returns true if the chunk is a container of other chunks.
+    pub fn is_container(&self) -> bool {
+        self.id == RIFF || self.id == LIST
+    }
+
+    /// Returns the form type of a container chunk.
+    pub fn form_type(&self) -> Result<FourCC, RiffError> {
+        let form_type: [u8; 4] = self
+            .data
+            .get(..4)
+            .ok_or(RiffError::BadHeader)?
+            .try_into()
+            .unwrap();
+        Ok(form_type.into())
+    }
+
+    /// Returns an iterator over the chunks nested within a container chunk.
+    pub fn children(&self) -> Result<Chunks<'a>, RiffError> {
+        if self.data.len() < 4 {
+            return Err(RiffError::BadHeader);
+        }
+        Ok(Chunks {
+            bytes: &self.data[4..],
+            offset: self.offset + 4,
+            align: self.align,
+        })
+    }
+}
+
+/// Iterator over a sequence of chunks, created by [`chunks`].
+///
+/// Iteration stops after the first error.
+#[derive(Clone, Debug)]
+pub struct Chunks<'a> {
+    bytes: &'a [u8],
+    /// Offset of `bytes` from the start of the file.
+    offset: usize,
+    align: usize,
+}
+
+impl<'a> Chunks<'a> {
+    /// Returns the first chunk with the given id, skipping any others.
+    pub fn find_chunk(self, id: FourCC) -> Result<Option<Chunk<'a>>, RiffError> {
+        for chunk in self {
+            let chunk = chunk?;
+            if chunk.id == id {
+                return Ok(Some(chunk));
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl<'a> Iterator for Chunks<'a> {
+    type Item = Result<Chunk<'a>, RiffError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Anything shorter than the padding before the next header is trailing
+        // padding.
+        let padding = header_padding(self.offset, self.align);
+        if self.bytes.len() <= padding {
+            self.bytes = &[];
+            return None;
+        }
+
+        let header = &self.bytes[padding..];
+        if header.len() < CHUNK_HEADER_SIZE {
+            self.bytes = &[];
+            return Some(Err(RiffError::TooSmall));
+        }
+
+        let id: [u8; 4] = header[..4].try_into().unwrap();
+        let size = u32::from_le_bytes(header[4..8].try_into().unwrap()) as usize;
+        let bytes = &header[CHUNK_HEADER_SIZE..];
+        let offset = self.offset + padding + CHUNK_HEADER_SIZE;
+
+        if size > bytes.len() {
+            self.bytes = &[];
+            return Some(Err(RiffError::TooSmall));
+        }
+
+        let data = &bytes[..size];
+        // The final chunk's padding may be missing.
+        let padded_size = (align_offset(offset + size, self.align) - offset).min(bytes.len());
+        self.bytes = &bytes[padded_size..];
+        self.offset = offset + padded_size;
+
+        Some(Ok(Chunk {
+            id: id.into(),
+            data,
+            offset,
+            align: self.align,
+        }))
+    }
+}
+
+/// Returns an iterator over the sequence of chunks in `bytes`, which must be
+/// the start of the file.
+///
+/// # Panics
+///
+/// Panics if `align` is not a power of two.
+pub fn chunks(bytes: &[u8], align: usize) -> Chunks<'_> {
+    assert!(align.is_power_of_two());
+    Chunks {
+        bytes,
+        offset: 0,
+        align,
+    }
+}
+
+/// Reads the [`RIFF`] chunk at the start of `bytes` using the standard RIFF
+/// alignment, returning its form type and nested chunks.
+pub fn read_riff(bytes: &[u8]) -> Result<(FourCC, Chunks<'_>), RiffError> {
+    let chunk = chunks(bytes, RIFF_ALIGN)
+        .next()
+        .ok_or(RiffError::TooSmall)??;
+    if chunk.id != RIFF {
+        return Err(RiffError::BadMagic);
+    }
+    Ok((chunk.form_type()?, chunk.children()?))
+}
+
+/// Writes a chunked container, patching chunk sizes as each chunk is ended.
+pub struct RiffWriter {
+    bytes: Vec<u8>,
+    align: usize,
+    open_chunks: Vec<usize>,
+}
+
+impl RiffWriter {
+    /// Creates a writer using the standard RIFF alignment.
+    pub fn new() -> Self {
+        Self::with_alignment(RIFF_ALIGN)
+    }
+
+    /// Creates a writer which aligns the data of every chunk to `align` bytes
+    /// from the start of the output.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `align` is not a power of two.
+    pub fn with_alignment(align: usize) -> Self {
+        assert!(align.is_power_of_two());
+        Self {
+            bytes: Vec::new(),
+            align,
+            open_chunks: Vec::new(),
+        }
+    }
+
+    /// Starts a new chunk, which must be closed with [`RiffWriter::end_chunk`].
+    pub fn begin_chunk(&mut self, id: FourCC) {
+        let len = self.bytes.len() + header_padding(self.bytes.len(), self.align);
+        self.bytes.resize(len, 0);
+        self.open_chunks.push(len);
+        self.bytes.extend_from_slice(&id.as_raw().to_le_bytes());
+        // Size is patched by `end_chunk`.
+        self.bytes.extend_from_slice(&0_u32.to_le_bytes());
+    }
+
+    /// Starts a new [`LIST`] container chunk with the given form type.
+    pub fn begin_list(&mut self, form_type: FourCC) {
+        self.begin_chunk(LIST);
+        self.write_bytes(&form_type.as_raw().to_le_bytes());
+    }
+
+    /// Starts a new [`RIFF`] container chunk with the given form type.
+    pub fn begin_riff(&mut self, form_type: FourCC) {
+        self.begin_chunk(RIFF);
+        self.write_bytes(&form_type.as_raw().to_le_bytes());
+    }
+
+    /// Appends bytes to the innermost open chunk.
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        debug_assert!(!self.open_chunks.is_empty());
+        self.bytes.extend_from_slice(bytes);
+    }
+
+    /// Ends the innermost open chunk, patching its size and padding to the
+    /// writer's alignment.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there's no open chunk, or if the chunk's size doesn't fit in a
+    /// `u32`.
+    pub fn end_chunk(&mut self) {
+        let start = self.open_chunks.pop().expect("no open chunk");
+        let size = self.bytes.len() - start - CHUNK_HEADER_SIZE;
+        let size: u32 = size.try_into().expect("chunk too large");
+        self.bytes[start + 4..start + 8].copy_from_slice(&size.to_le_bytes());
+        let len = align_offset(self.bytes.len(), self.align);
+        self.bytes.resize(len, 0);
+    }
+
+    /// Writes a complete chunk containing `data`.
+    pub fn chunk(&mut self, id: FourCC, data: &[u8]) {
+        self.begin_chunk(id);
+        self.write_bytes(data);
+        self.end_chunk();
+    }
+
+    /// Returns the finished container.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any chunks are still open.
+    pub fn finish(self) -> Vec<u8> {
+        assert!(self.open_chunks.is_empty(), "unclosed chunks");
+        self.bytes
+    }
+}
+
+impl Default for RiffWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{FourCC, fourcc};
+
+    use super::{Chunks, LIST, RiffError, RiffWriter, chunks, read_riff};
+
+    const WAVE: FourCC = fourcc!("WAVE");
+    const FMT: FourCC = fourcc!("fmt ");
+    const DATA: FourCC = fourcc!("data");
+
+    /// A mono 8kHz 16 bit WAV file with three samples, as written by other tools.
+    const WAV: &[u8] = &[
+        b'R', b'I', b'F', b'F', 42, 0, 0, 0, b'W', b'A', b'V', b'E', //
+        b'f', b'm', b't', b' ', 16, 0, 0, 0, //
+        1, 0, 1, 0, 0x40, 0x1f, 0, 0, 0x80, 0x3e, 0, 0, 2, 0, 16, 0, //
+        b'd', b'a', b't', b'a', 6, 0, 0, 0, //
+        0, 0, 0xff, 0x7f, 0x00, 0x80,
+    ];
+
+    #[test]
+    fn read_wav() {
+        let (form_type, chunks) = read_riff(WAV).unwrap();
+        assert_eq!(form_type, WAVE);
+
+        let fmt = chunks.clone().find_chunk(FMT).unwrap().unwrap();
+        let channels = u16::from_le_bytes(fmt.data[2..4].try_into().unwrap());
+        let sample_rate = u32::from_le_bytes(fmt.data[4..8].try_into().unwrap());
+        assert_eq!(channels, 1);
+        assert_eq!(sample_rate, 8000);
+
+        let data = chunks.find_chunk(DATA).unwrap().unwrap();
+        assert_eq!(data.data, &[0, 0, 0xff, 0x7f, 0x00, 0x80]);
+    }
+
+    #[test]
+    fn write_wav() {
+        let mut writer = RiffWriter::new();
+        writer.begin_riff(WAVE);
+        writer.chunk(FMT, &WAV[20..36]);
+        writer.chunk(DATA, &WAV[44..]);
+        writer.end_chunk();
+        assert_eq!(writer.finish(), WAV);
+    }
+
+    #[test]
+    fn nested_and_unknown() {
+        let mut writer = RiffWriter::new();
+        writer.begin_riff(fourcc!("TEST"));
+        // Odd sized chunks are padded.
+        writer.chunk(fourcc!("odd "), &[1, 2, 3]);
+        writer.begin_list(fourcc!("INFO"));
+        writer.chunk(fourcc!("INAM"), b"name\0");
+        writer.chunk(fourcc!("ISFT"), b"narcissus\0");
+        writer.end_chunk();
+        writer.chunk(fourcc!("last"), &[4]);
+        writer.end_chunk();
+        let bytes = writer.finish();
+        assert_eq!(bytes.len() % 2, 0);
+
+        let (form_type, chunks) = read_riff(&bytes).unwrap();
+        assert_eq!(form_type, fourcc!("TEST"));
+
+        let ids = chunks
+            .clone()
+            .map(|chunk| chunk.unwrap().id)
+            .collect::<Vec<_>>();
+        assert_eq!(ids, [fourcc!("odd "), LIST, fourcc!("last")]);
+
+        // Skips unknown chunks, including the list.
+        let last = chunks.clone().find_chunk(fourcc!("last")).unwrap().unwrap();
+        assert_eq!(last.data, &[4]);
+
+        let list = chunks.clone().find_chunk(LIST).unwrap().unwrap();
+        assert!(list.is_container());
+        assert_eq!(list.form_type().unwrap(), fourcc!("INFO"));
+        let software = list
+            .children()
+            .unwrap()
+            .find_chunk(fourcc!("ISFT"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(software.data, b"narcissus\0");
+    }
+
+    #[test]
+    fn alignment() {
+        let mut writer = RiffWriter::with_alignment(8);
+        writer.chunk(fourcc!("aaaa"), &[1]);
+        writer.chunk(fourcc!("bbbb"), &[2, 3]);
+        let bytes = writer.finish();
+        assert_eq!(bytes.len(), 32);
+
+        let data = chunks(&bytes, 8)
+            .map(|chunk| chunk.unwrap().data)
+            .collect::<Vec<_>>();
+        assert_eq!(data, [&[1][..], &[2, 3][..]]);
+    }
+
+    /// Checks the data of every chunk, including nested chunks, is aligned
+    /// relative to the start of `bytes`.
+    fn assert_aligned(bytes: &[u8], chunks: Chunks, align: usize) {
+        for chunk in chunks {
+            let chunk = chunk.unwrap();
+            let offset = chunk.data.as_ptr() as usize - bytes.as_ptr() as usize;
+            assert!(offset.is_multiple_of(align));
+            if chunk.is_container() {
+                assert_aligned(bytes, chunk.children().unwrap(), align);
+            }
+        }
+    }
+
+    #[test]
+    fn nested_alignment() {
+        let mut writer = RiffWriter::with_alignment(8);
+        writer.begin_riff(fourcc!("TEST"));
+        writer.begin_list(fourcc!("LST0"));
+        writer.chunk(fourcc!("aaaa"), &[1]);
+        writer.chunk(fourcc!("bbbb"), &[2, 3, 4, 5, 6, 7, 8, 9, 10]);
+        writer.end_chunk();
+        writer.chunk(fourcc!("cccc"), &[11, 12]);
+        writer.end_chunk();
+        let bytes = writer.finish();
+
+        assert_aligned(&bytes, chunks(&bytes, 8), 8);
+
+        let riff = chunks(&bytes, 8).next().unwrap().unwrap();
+        assert_eq!(riff.form_type().unwrap(), fourcc!("TEST"));
+
+        let children = riff
+            .children()
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(children.len(), 2);
+        assert_eq!(children[0].form_type().unwrap(), fourcc!("LST0"));
+        assert_eq!(children[1].data, &[11, 12]);
+
+        let data = children[0]
+            .children()
+            .unwrap()
+            .map(|chunk| chunk.unwrap().data)
+            .collect::<Vec<_>>();
+        assert_eq!(data, [&[1][..], &[2, 3, 4, 5, 6, 7, 8, 9, 10][..]]);
+
+        // Larger alignments than the chunk header also round-trip.
+        let mut writer = RiffWriter::with_alignment(16);
+        writer.begin_list(fourcc!("LST1"));
+        writer.chunk(fourcc!("aaaa"), &[1]);
+        writer.chunk(fourcc!("bbbb"), &[2]);
+        writer.end_chunk();
+        writer.chunk(fourcc!("cccc"), &[3]);
+        let bytes = writer.finish();
+
+        assert_aligned(&bytes, chunks(&bytes, 16), 16);
+
+        let top = chunks(&bytes, 16).collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(top.len(), 2);
+        assert_eq!(top[1].data, &[3]);
+        let data = top[0]
+            .children()
+            .unwrap()
+            .map(|chunk| chunk.unwrap().data)
+            .collect::<Vec<_>>();
+        assert_eq!(data, [&[1][..], &[2][..]]);
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(read_riff(&[]).err(), Some(RiffError::TooSmall));
+        assert_eq!(read_riff(&WAV[..7]).err(), Some(RiffError::TooSmall));
+        assert_eq!(read_riff(&WAV[..20]).err(), Some(RiffError::TooSmall));
+        assert_eq!(read_riff(&WAV[12..]).err(), Some(RiffError::BadMagic));
+
+        // Truncated nested chunk.
+        let mut wav = WAV[..WAV.len() - 2].to_vec();
+        wav[4] -= 2;
+        let (_, mut chunks) = read_riff(&wav).unwrap();
+        assert!(chunks.next().unwrap().is_ok());
+        assert_eq!(chunks.next().unwrap().err(), Some(RiffError::TooSmall));
+        assert!(chunks.next().is_none());
+    }
+}
@@ -0,0 +1,784 @@
+//! Versioned little-endian binary serialization for mostly-POD data.
+//!
+//! A stream begins with a header identifying its schema by [`Uuid`] and
+//! version, followed by any number of chunks. Each chunk is tagged with a
+//! [`FourCC`] and its own version, and is length prefixed so readers can skip
+//! chunks they don't understand.
+//!
+//! Compatibility between versions is handled by convention:
+//!
+//! * New fields are only ever appended to the end of a chunk. Older readers
+//!   stop reading before them, newer readers check [`Reader::version`] or
+//!   [`Reader::is_empty`] before reading them.
+//! * New data that older readers can't interpret goes into new chunks.
+//!
+//! Arrays of [`Pod`] types written with [`Writer::write_slice`] are aligned
+//! within the stream, so they can be borrowed directly from the buffer by
+//! [`Reader::read_slice`] provided the buffer itself is suitably aligned.
+
+use std::{error::Error, fmt::Display};
+
+use crate::{F16, FixedVec, FourCC, Handle, Uuid, align_offset, fourcc};
+
+#[cfg(not(target_endian = "little"))]
+compile_error!("serialization assumes a little-endian target");
+
+/// Magic number at the start of every stream.
+pub const MAGIC: FourCC = fourcc!("NSER");
+
+const HEADER_SIZE: usize = 24;
+const CHUNK_HEADER_SIZE: usize = 16;
+const CHUNK_ALIGN: usize = 8;
+
+/// Maximum alignment of [`Pod`] types which can be written with
+/// [`Writer::write_slice`].
+pub const MAX_ALIGN: usize = 16;
+
+/// Marker for types which can be serialized by copying their bytes.
+///
+/// # Safety
+///
+/// Implementors must be `#[repr(C)]` or `#[repr(transparent)]`, contain no
+/// padding, pointers or references, and every bit pattern must be a valid
+/// value.
+pub unsafe trait Pod: Copy + 'static {}
+
+macro_rules! impl_pod {
+    ($($t:ty),*) => {
+        $(unsafe impl Pod for $t {})*
+    };
+}
+
+impl_pod!(
+    u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64, F16, FourCC, Handle
+);
+
+unsafe impl<T: Pod, const N: usize> Pod for [T; N] {}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DeserializeError {
+    /// The stream ended before the value was complete.
+    UnexpectedEof,
+    /// The stream header has an incorrect magic number.
+    BadMagic,
+    /// The stream was written with a different schema.
+    SchemaMismatch,
+    /// The buffer isn't suitably aligned to borrow an array from.
+    Misaligned,
+    /// A string contained invalid UTF-8.
+    InvalidUtf8,
+    /// A value was out of range for its type.
+    InvalidValue,
+}
+
+impl Display for DeserializeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeserializeError::UnexpectedEof => "unexpected end of stream".fmt(f),
+            DeserializeError::BadMagic => "bad magic number".fmt(f),
+            DeserializeError::SchemaMismatch => "schema mismatch".fmt(f),
+            DeserializeError::Misaligned => "misaligned array".fmt(f),
+            DeserializeError::InvalidUtf8 => "invalid utf-8".fmt(f),
+            DeserializeError::InvalidValue => "invalid value".fmt(f),
+        }
+    }
+}
+
+impl Error for DeserializeError {}
+
+/// Identifies the layout of a stream.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Schema {
+    pub id: Uuid,
+    pub version: u32,
+}
+
+impl Schema {
+    pub const fn new(id: Uuid, version: u32) -> Self {
+        Self { id, version }
+    }
+}
+
+pub trait Serialize {
+    fn serialize(&self, w: &mut Writer);
+}
+
+pub trait Deserialize<'a>: Sized {
+    fn deserialize(r: &mut Reader<'a>) -> Result<Self, DeserializeError>;
+}
+
+/// Opaque marker for an open chunk, returned by [`Writer::begin_chunk`].
+#[must_use]
+pub struct ChunkMark {
+    header: usize,
+}
+
+pub struct Writer {
+    bytes: Vec<u8>,
+}
+
+impl Writer {
+    /// Creates a new stream, and writes the header for `schema`.
+    pub fn new(schema: Schema) -> Self {
+        let mut writer = Writer {
+            bytes: Vec::with_capacity(4096),
+        };
+        writer.write_pod(&MAGIC);
+        writer.write_pod(&schema.version);
+        writer.write_bytes(&schema.id.to_bytes_be());
+        debug_assert_eq!(writer.bytes.len(), HEADER_SIZE);
+        writer
+    }
+
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn finish(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    #[inline(always)]
+    pub fn write<T: Serialize + ?Sized>(&mut self, value: &T) {
+        value.serialize(self)
+    }
+
+    #[inline(always)]
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        self.bytes.extend_from_slice(bytes)
+    }
+
+    #[inline(always)]
+    pub fn write_pod<T: Pod>(&mut self, value: &T) {
+        self.write_bytes(pod_bytes(std::slice::from_ref(value)))
+    }
+
+    /// Writes a length prefix, then pads the stream so the array data is
+    /// aligned to `T`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the alignment of `T` exceeds [`MAX_ALIGN`], or the length
+    /// doesn't fit within a `u32`.
+    pub fn write_slice<T: Pod>(&mut self, values: &[T]) {
+        assert!(align_of::<T>() <= MAX_ALIGN);
+        self.write_len(values.len());
+        self.align(align_of::<T>());
+        self.write_bytes(pod_bytes(values))
+    }
+
+    /// Pads the stream with zeros until its length is a multiple of `align`.
+    pub fn align(&mut self, align: usize) {
+        let len = align_offset(self.bytes.len(), align);
+        self.bytes.resize(len, 0)
+    }
+
+    /// Starts a chunk tagged with `id` and `version`. Must be paired with a call
+    /// to [`Writer::end_chunk`], chunks may be nested.
+    pub fn begin_chunk(&mut self, id: FourCC, version: u32) -> ChunkMark {
+        self.align(CHUNK_ALIGN);
+        let header = self.bytes.len();
+        self.write_pod(&id);
+        self.write_pod(&version);
+        // Size is patched by `end_chunk`.
+        self.write_pod(&0_u32);
+        // Reserved.
+        self.write_pod(&0_u32);
+        ChunkMark { header }
+    }
+
+    /// Finishes the chunk, patching the size into its header.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the chunk's size doesn't fit within a `u32`.
+    pub fn end_chunk(&mut self, mark: ChunkMark) {
+        self.align(CHUNK_ALIGN);
+        let size = self.bytes.len() - mark.header - CHUNK_HEADER_SIZE;
+        let size: u32 = size.try_into().expect("chunk too large");
+        self.bytes[mark.header + 8..mark.header + 12].copy_from_slice(&size.to_le_bytes());
+    }
+
+    /// Writes a chunk whose contents are written by `f`.
+    pub fn chunk<F: FnOnce(&mut Writer)>(&mut self, id: FourCC, version: u32, f: F) {
+        let mark = self.begin_chunk(id, version);
+        f(self);
+        self.end_chunk(mark);
+    }
+
+    pub(crate) fn write_len(&mut self, len: usize) {
+        let len: u32 = len.try_into().expect("length too large");
+        self.write_pod(&len)
+    }
+}
+
+/// A chunk read by [`Reader::next_chunk`].
+pub struct Chunk<'a> {
+    pub id: FourCC,
+    /// Reader over the chunk's contents, reports the chunk's version.
+    pub reader: Reader<'a>,
+}
+
+#[derive(Clone)]
+pub struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    end: usize,
+    version: u32,
+}
+
+impl<'a> Reader<'a> {
+    /// Reads the stream header, checking it was written with the schema `id`.
+    ///
+    /// Any version of the schema is accepted, it's up to the caller to check
+    /// [`Reader::version`].
+    pub fn new(bytes: &'a [u8], id: Uuid) -> Result<Self, DeserializeError> {
+        let mut reader = Reader {
+            bytes,
+            pos: 0,
+            end: bytes.len(),
+            version: 0,
+        };
+        if reader.read_pod::<FourCC>()? != MAGIC {
+            return Err(DeserializeError::BadMagic);
+        }
+        reader.version = reader.read_pod()?;
+        let uuid = Uuid::from_bytes_be(reader.read_pod()?);
+        if uuid != id {
+            return Err(DeserializeError::SchemaMismatch);
+        }
+        Ok(reader)
+    }
+
+    /// Returns the schema version for the top level reader, or the chunk
+    /// version for readers returned by [`Reader::next_chunk`].
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.end - self.pos
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pos == self.end
+    }
+
+    #[inline(always)]
+    pub fn read<T: Deserialize<'a>>(&mut self) -> Result<T, DeserializeError> {
+        T::deserialize(self)
+    }
+
+    #[inline(always)]
+    pub fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], DeserializeError> {
+        if len > self.remaining() {
+            return Err(DeserializeError::UnexpectedEof);
+        }
+        let bytes = &self.bytes[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    #[inline(always)]
+    pub fn read_pod<T: Pod>(&mut self) -> Result<T, DeserializeError> {
+        let bytes = self.read_bytes(size_of::<T>())?;
+        // SAFETY: `Pod` guarantees every bit pattern is valid.
+        Ok(unsafe { std::ptr::read_unaligned(bytes.as_ptr() as *const T) })
+    }
+
+    /// Borrows an array written by [`Writer::write_slice`] directly from the
+    /// buffer.
+    ///
+    /// Returns [`DeserializeError::Misaligned`] if the buffer isn't aligned to
+    /// at least the alignment of `T`, use [`Reader::read_vec`] to copy instead.
+    pub fn read_slice<T: Pod>(&mut self) -> Result<&'a [T], DeserializeError> {
+        let len = self.read_slice_len::<T>()?;
+        let ptr = self.bytes[self.pos..].as_ptr();
+        if !(ptr as usize).is_multiple_of(align_of::<T>()) {
+            return Err(DeserializeError::Misaligned);
+        }
+        let bytes = self.read_bytes(len * size_of::<T>())?;
+        // SAFETY: Length and alignment are checked above, and `Pod` guarantees
+        // every bit pattern is valid.
+        Ok(unsafe { std::slice::from_raw_parts(bytes.as_ptr() as *const T, len) })
+    }
+
+    /// Copies an array written by [`Writer::write_slice`] out of the buffer.
+    pub fn read_vec<T: Pod>(&mut self) -> Result<Vec<T>, DeserializeError> {
+        let len = self.read_slice_len::<T>()?;
+        let bytes = self.read_bytes(len * size_of::<T>())?;
+        let mut values = Vec::<T>::with_capacity(len);
+        // SAFETY: Length is checked above, and `Pod` guarantees every bit
+        // pattern is valid.
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                bytes.as_ptr(),
+                values.as_mut_ptr() as *mut u8,
+                bytes.len(),
+            );
+            values.set_len(len);
+        }
+        Ok(values)
+    }
+
+    /// Returns the next chunk, or `None` if there are no more chunks.
+    ///
+    /// The chunk's contents are skipped in this reader regardless of how much
+    /// is read from the returned chunk.
+    pub fn next_chunk(&mut self) -> Result<Option<Chunk<'a>>, DeserializeError> {
+        self.skip_to_align(CHUNK_ALIGN)?;
+        if self.is_empty() {
+            return Ok(None);
+        }
+        let id = self.read_pod()?;
+        let version = self.read_pod()?;
+        let size = self.read_pod::<u32>()? as usize;
+        let _reserved = self.read_pod::<u32>()?;
+        let start = self.pos;
+        self.read_bytes(size)?;
+        Ok(Some(Chunk {
+            id,
+            reader: Reader {
+                bytes: self.bytes,
+                pos: start,
+                end: start + size,
+                version,
+            },
+        }))
+    }
+
+    pub(crate) fn read_len(&mut self) -> Result<usize, DeserializeError> {
+        Ok(self.read_pod::<u32>()? as usize)
+    }
+
+    fn read_slice_len<T: Pod>(&mut self) -> Result<usize, DeserializeError> {
+        let len = self.read_len()?;
+        self.skip_to_align(align_of::<T>().min(MAX_ALIGN))?;
+        if len > self.remaining() / size_of::<T>().max(1) {
+            return Err(DeserializeError::UnexpectedEof);
+        }
+        Ok(len)
+    }
+
+    fn skip_to_align(&mut self, align: usize) -> Result<(), DeserializeError> {
+        let pos = align_offset(self.pos, align);
+        self.read_bytes(pos - self.pos)?;
+        Ok(())
+    }
+}
+
+fn pod_bytes<T: Pod>(values: &[T]) -> &[u8] {
+    // SAFETY: `Pod` guarantees there's no padding, so all bytes are initialized.
+    unsafe { std::slice::from_raw_parts(values.as_ptr() as *const u8, size_of_val(values)) }
+}
+
+impl<T: Pod> Serialize for T {
+    #[inline(always)]
+    fn serialize(&self, w: &mut Writer) {
+        w.write_pod(self)
+    }
+}
+
+impl<T: Pod> Deserialize<'_> for T {
+    #[inline(always)]
+    fn deserialize(r: &mut Reader<'_>) -> Result<Self, DeserializeError> {
+        r.read_pod()
+    }
+}
+
+impl Serialize for bool {
+    fn serialize(&self, w: &mut Writer) {
+        w.write_pod(&(*self as u8))
+    }
+}
+
+impl Deserialize<'_> for bool {
+    fn deserialize(r: &mut Reader<'_>) -> Result<Self, DeserializeError> {
+        match r.read_pod::<u8>()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(DeserializeError::InvalidValue),
+        }
+    }
+}
+
+impl Serialize for str {
+    fn serialize(&self, w: &mut Writer) {
+        w.write_len(self.len());
+        w.write_bytes(self.as_bytes())
+    }
+}
+
+impl Serialize for String {
+    fn serialize(&self, w: &mut Writer) {
+        self.as_str().serialize(w)
+    }
+}
+
+impl<'a> Deserialize<'a> for &'a str {
+    fn deserialize(r: &mut Reader<'a>) -> Result<Self, DeserializeError> {
+        let len = r.read_len()?;
+        let bytes = r.read_bytes(len)?;
+        std::str::from_utf8(bytes).map_err(|_| DeserializeError::InvalidUtf8)
+    }
+}
+
+impl Deserialize<'_> for String {
+    fn deserialize(r: &mut Reader<'_>) -> Result<Self, DeserializeError> {
+        r.read::<&str>().map(|str| str.to_owned())
+    }
+}
+
+impl Serialize for Uuid {
+    fn serialize(&self, w: &mut Writer) {
+        w.write_bytes(&self.to_bytes_be())
+    }
+}
+
+impl Deserialize<'_> for Uuid {
+    fn deserialize(r: &mut Reader<'_>) -> Result<Self, DeserializeError> {
+        Ok(Uuid::from_bytes_be(r.read_pod()?))
+    }
+}
+
+impl<T: Serialize> Serialize for Option<T> {
+    fn serialize(&self, w: &mut Writer) {
+        match self {
+            None => w.write_pod(&0_u8),
+            Some(value) => {
+                w.write_pod(&1_u8);
+                value.serialize(w)
+            }
+        }
+    }
+}
+
+impl<'a, T: Deserialize<'a>> Deserialize<'a> for Option<T> {
+    fn deserialize(r: &mut Reader<'a>) -> Result<Self, DeserializeError> {
+        match r.read_pod::<u8>()? {
+            0 => Ok(None),
+            1 => Ok(Some(r.read()?)),
+            _ => Err(DeserializeError::InvalidValue),
+        }
+    }
+}
+
+impl<T: Serialize> Serialize for Vec<T> {
+    fn serialize(&self, w: &mut Writer) {
+        w.write_len(self.len());
+        for value in self {
+            value.serialize(w)
+        }
+    }
+}
+
+impl<'a, T: Deserialize<'a>> Deserialize<'a> for Vec<T> {
+    fn deserialize(r: &mut Reader<'a>) -> Result<Self, DeserializeError> {
+        let len = r.read_len()?;
+        // Don't trust the length for the allocation, every value takes at least
+        // one byte unless it's zero-sized.
+        let mut values = Vec::with_capacity(len.min(r.remaining()));
+        for _ in 0..len {
+            values.push(r.read()?)
+        }
+        Ok(values)
+    }
+}
+
+impl<T: Serialize, const CAP: usize> Serialize for FixedVec<T, CAP> {
+    fn serialize(&self, w: &mut Writer) {
+        w.write_len(self.len());
+        for value in self.iter() {
+            value.serialize(w)
+        }
+    }
+}
+
+impl<'a, T: Deserialize<'a>, const CAP: usize> Deserialize<'a> for FixedVec<T, CAP> {
+    fn deserialize(r: &mut Reader<'a>) -> Result<Self, DeserializeError> {
+        let len = r.read_len()?;
+        if len > CAP {
+            return Err(DeserializeError::InvalidValue);
+        }
+        let mut values = FixedVec::new();
+        for _ in 0..len {
+            values.push(r.read()?)
+        }
+        Ok(values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{FixedVec, FourCC, Uuid, fourcc};
+
+    use super::{DeserializeError, Reader, Schema, Writer};
+
+    const SCHEMA_ID: Uuid = Uuid::parse_str_unwrap("6b2b1e5c-0b8f-4a8e-9d44-2f3c1c7e5a10");
+    const SCHEMA_V1: Schema = Schema::new(SCHEMA_ID, 1);
+    const SCHEMA_V2: Schema = Schema::new(SCHEMA_ID, 2);
+
+    const PLAYER: FourCC = fourcc!("PLYR");
+    const INVENTORY: FourCC = fourcc!("INVT");
+
+    #[repr(align(16))]
+    struct Aligned<const N: usize>([u8; N]);
+
+    #[derive(PartialEq, Debug)]
+    struct PlayerV1 {
+        name: String,
+        health: u32,
+    }
+
+    #[derive(Clone, PartialEq, Debug)]
+    struct PlayerV2 {
+        name: String,
+        health: u32,
+        position: [f32; 3],
+    }
+
+    fn write_v1(player: &PlayerV1) -> Vec<u8> {
+        let mut w = Writer::new(SCHEMA_V1);
+        w.chunk(PLAYER, 1, |w| {
+            w.write(&player.name);
+            w.write(&player.health);
+        });
+        w.finish()
+    }
+
+    fn write_v2(player: &PlayerV2, inventory: &[u32]) -> Vec<u8> {
+        let mut w = Writer::new(SCHEMA_V2);
+        w.chunk(PLAYER, 2, |w| {
+            w.write(&player.name);
+            w.write(&player.health);
+            w.write(&player.position);
+        });
+        w.chunk(INVENTORY, 1, |w| w.write_slice(inventory));
+        w.finish()
+    }
+
+    fn read_v1(bytes: &[u8]) -> Result<PlayerV1, DeserializeError> {
+        let mut r = Reader::new(bytes, SCHEMA_ID)?;
+        let mut player = None;
+        while let Some(mut chunk) = r.next_chunk()? {
+            if chunk.id == PLAYER {
+                player = Some(PlayerV1 {
+                    name: chunk.reader.read()?,
+                    health: chunk.reader.read()?,
+                });
+            }
+        }
+        player.ok_or(DeserializeError::InvalidValue)
+    }
+
+    fn read_v2(bytes: &[u8]) -> Result<(PlayerV2, Vec<u32>), DeserializeError> {
+        let mut r = Reader::new(bytes, SCHEMA_ID)?;
+        let mut player = None;
+        let mut inventory = Vec::new();
+        while let Some(mut chunk) = r.next_chunk()? {
+            match chunk.id {
+                PLAYER => {
+                    let r = &mut chunk.reader;
+                    player = Some(PlayerV2 {
+                        name: r.read()?,
+                        health: r.read()?,
+                        position: if r.version() >= 2 {
+                            r.read()?
+                        } else {
+                            [0.0; 3]
+                        },
+                    })
+                }
+                INVENTORY => inventory = chunk.reader.read_vec()?,
+                _ => {}
+            }
+        }
+        Ok((player.ok_or(DeserializeError::InvalidValue)?, inventory))
+    }
+
+    #[test]
+    fn roundtrip() {
+        let mut fixed = FixedVec::<u16, 4>::new();
+        fixed.push(1);
+        fixed.push(2);
+
+        let mut w = Writer::new(SCHEMA_V1);
+        w.write(&true);
+        w.write(&-5_i64);
+        w.write(&1.5_f64);
+        w.write("hello");
+        w.write(&Some(7_u8));
+        w.write(&None::<u8>);
+        w.write(&vec![String::from("a"), String::from("bc")]);
+        w.write(&fixed);
+        w.write(&SCHEMA_ID);
+        w.write(&PLAYER);
+        let bytes = w.finish();
+
+        let mut r = Reader::new(&bytes, SCHEMA_ID).unwrap();
+        assert_eq!(r.version(), 1);
+        assert!(r.read::<bool>().unwrap());
+        assert_eq!(r.read::<i64>().unwrap(), -5);
+        assert_eq!(r.read::<f64>().unwrap(), 1.5);
+        assert_eq!(r.read::<&str>().unwrap(), "hello");
+        assert_eq!(r.read::<Option<u8>>().unwrap(), Some(7));
+        assert_eq!(r.read::<Option<u8>>().unwrap(), None);
+        assert_eq!(r.read::<Vec<String>>().unwrap(), ["a", "bc"]);
+        assert_eq!(r.read::<FixedVec<u16, 4>>().unwrap().as_slice(), &[1, 2]);
+        assert_eq!(r.read::<Uuid>().unwrap(), SCHEMA_ID);
+        assert_eq!(r.read::<FourCC>().unwrap(), PLAYER);
+        assert!(r.is_empty());
+        assert_eq!(r.read::<u8>(), Err(DeserializeError::UnexpectedEof));
+
+        // Too many elements for the destination.
+        let mut r = Reader::new(&bytes, SCHEMA_ID).unwrap();
+        r.read_bytes(1 + 8 + 8 + 4 + 5 + 2 + 1 + 4 + 5 + 6).unwrap();
+        assert_eq!(
+            r.read::<FixedVec<u16, 1>>().err(),
+            Some(DeserializeError::InvalidValue)
+        );
+    }
+
+    #[test]
+    fn header() {
+        let bytes = Writer::new(SCHEMA_V1).finish();
+        assert!(Reader::new(&bytes, SCHEMA_ID).is_ok());
+        assert_eq!(
+            Reader::new(&bytes, Uuid::nil()).err(),
+            Some(DeserializeError::SchemaMismatch)
+        );
+        assert_eq!(
+            Reader::new(&bytes[1..], SCHEMA_ID).err(),
+            Some(DeserializeError::BadMagic)
+        );
+        assert_eq!(
+            Reader::new(&bytes[..8], SCHEMA_ID).err(),
+            Some(DeserializeError::UnexpectedEof)
+        );
+    }
+
+    #[test]
+    fn zero_copy() {
+        let values = [1.0_f32, 2.0, 3.0, 4.0, 5.0];
+        let mut w = Writer::new(SCHEMA_V1);
+        w.write(&1_u8);
+        w.write_slice(&values);
+        w.chunk(INVENTORY, 1, |w| w.write_slice(&[[7_u64; 2]; 3]));
+        let bytes = w.finish();
+
+        let mut buffer = Aligned([0; 256]);
+        buffer.0[..bytes.len()].copy_from_slice(&bytes);
+        let buffer = &buffer.0[..bytes.len()];
+
+        let mut r = Reader::new(buffer, SCHEMA_ID).unwrap();
+        assert_eq!(r.read::<u8>().unwrap(), 1);
+        let slice = r.read_slice::<f32>().unwrap();
+        assert_eq!(slice, values);
+        assert!(
+            buffer
+                .as_ptr_range()
+                .contains(&(slice.as_ptr() as *const u8))
+        );
+        let mut chunk = r.next_chunk().unwrap().unwrap();
+        assert_eq!(chunk.reader.read_slice::<[u64; 2]>().unwrap(), [[7; 2]; 3]);
+        assert!(r.next_chunk().unwrap().is_none());
+
+        // Misaligned buffers can still be copied from.
+        let mut buffer = Aligned([0; 256]);
+        buffer.0[1..bytes.len() + 1].copy_from_slice(&bytes);
+        let buffer = &buffer.0[1..bytes.len() + 1];
+
+        let mut r = Reader::new(buffer, SCHEMA_ID).unwrap();
+        r.read::<u8>().unwrap();
+        let mut copy = r.clone();
+        assert_eq!(r.read_slice::<f32>(), Err(DeserializeError::Misaligned));
+        assert_eq!(copy.read_vec::<f32>().unwrap(), values);
+    }
+
+    #[test]
+    fn nested_chunks() {
+        let mut w = Writer::new(SCHEMA_V1);
+        let outer = w.begin_chunk(PLAYER, 3);
+        w.write(&1_u8);
+        w.chunk(INVENTORY, 4, |w| w.write(&2_u32));
+        w.end_chunk(outer);
+        w.chunk(INVENTORY, 5, |w| w.write(&3_u16));
+        let bytes = w.finish();
+
+        let mut r = Reader::new(&bytes, SCHEMA_ID).unwrap();
+        let mut outer = r.next_chunk().unwrap().unwrap();
+        assert_eq!(outer.id, PLAYER);
+        assert_eq!(outer.reader.version(), 3);
+        assert_eq!(outer.reader.read::<u8>().unwrap(), 1);
+        let mut inner = outer.reader.next_chunk().unwrap().unwrap();
+        assert_eq!(inner.id, INVENTORY);
+        assert_eq!(inner.reader.version(), 4);
+        assert_eq!(inner.reader.read::<u32>().unwrap(), 2);
+        assert!(outer.reader.next_chunk().unwrap().is_none());
+        let mut next = r.next_chunk().unwrap().unwrap();
+        assert_eq!(next.reader.version(), 5);
+        assert_eq!(next.reader.read::<u16>().unwrap(), 3);
+        assert!(r.next_chunk().unwrap().is_none());
+    }
+
+    #[test]
+    fn backward_compatibility() {
+        let player = PlayerV1 {
+            name: "shark".into(),
+            health: 100,
+        };
+        let bytes = write_v1(&player);
+        let (player, inventory) = read_v2(&bytes).unwrap();
+        assert_eq!(
+            player,
+            PlayerV2 {
+                name: "shark".into(),
+                health: 100,
+                position: [0.0; 3],
+            }
+        );
+        assert!(inventory.is_empty());
+    }
+
+    #[test]
+    fn forward_compatibility() {
+        let player = PlayerV2 {
+            name: "shark".into(),
+            health: 50,
+            position: [1.0, 2.0, 3.0],
+        };
+        let bytes = write_v2(&player, &[1, 2, 3]);
+        assert_eq!(
+            read_v1(&bytes).unwrap(),
+            PlayerV1 {
+                name: "shark".into(),
+                health: 50
+            }
+        );
+        assert_eq!(read_v2(&bytes).unwrap(), (player, vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn truncated() {
+        let player = PlayerV2 {
+            name: "shark".into(),
+            health: 50,
+            position: [1.0, 2.0, 3.0],
+        };
+        let bytes = write_v2(&player, &[1, 2, 3]);
+        for len in 0..bytes.len() {
+            // Truncating exactly at a chunk boundary leaves a valid stream.
+            if let Ok(result) = read_v2(&bytes[..len]) {
+                assert_eq!(result, (player.clone(), vec![]));
+            }
+        }
+    }
+}
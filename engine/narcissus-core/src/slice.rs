@@ -1,6 +1,7 @@
 use std::{
     iter::FusedIterator,
     marker::PhantomData,
+    mem::MaybeUninit,
     slice::{Iter, IterMut},
 };
 
@@ -518,3 +519,391 @@ pub fn rsplit_array_mut<T, const N: usize>(slice: &mut [T]) -> (&mut [T], &mut [
     // SAFETY: b points to [T; N]? Yes it's [T] of length N (checked by split_at_mut)
     unsafe { (a, &mut *(b.as_mut_ptr() as *mut [T; N])) }
 }
+
+/// Number of bits sorted per pass by the radix sort functions, matching the GPU
+/// radix sort.
+pub const RADIX_BITS: usize = 8;
+
+const RADIX: usize = 1 << RADIX_BITS;
+
+/// Unsigned integer keys for the radix sort.
+trait RadixKey: Copy {
+    const DIGITS: usize;
+    fn digit(self, digit: usize) -> usize;
+}
+
+impl RadixKey for u32 {
+    const DIGITS: usize = 4;
+
+    #[inline(always)]
+    fn digit(self, digit: usize) -> usize {
+        ((self >> (digit * RADIX_BITS)) as usize) & (RADIX - 1)
+    }
+}
+
+impl RadixKey for u64 {
+    const DIGITS: usize = 8;
+
+    #[inline(always)]
+    fn digit(self, digit: usize) -> usize {
+        ((self >> (digit * RADIX_BITS)) as usize) & (RADIX - 1)
+    }
+}
+
+/// Number of digits in the widest supported key.
+const MAX_DIGITS: usize = 8;
+
+/// Sorts `keys` and `values` together by `keys` with a least significant digit
+/// radix sort, using the first `keys.len()` elements of `scratch_keys` and
+/// `scratch_values` as scratch space.
+///
+/// Passes where every key has the same digit are skipped.
+fn radix_sort_impl<K: RadixKey, V: Copy>(
+    keys: &mut [K],
+    values: &mut [V],
+    scratch_keys: &mut [MaybeUninit<K>],
+    scratch_values: &mut [MaybeUninit<V>],
+) {
+    let len = keys.len();
+    assert_eq!(values.len(), len);
+    assert!(scratch_keys.len() >= len);
+    assert!(scratch_values.len() >= len);
+    debug_assert!(K::DIGITS <= MAX_DIGITS);
+
+    if len < 2 {
+        return;
+    }
+
+    // Build histograms for every digit in a single pass over the keys.
+    let mut histograms = [[0_usize; RADIX]; MAX_DIGITS];
+    let histograms = &mut histograms[..K::DIGITS];
+    for &key in keys.iter() {
+        for (digit, histogram) in histograms.iter_mut().enumerate() {
+            histogram[key.digit(digit)] += 1;
+        }
+    }
+
+    // SAFETY: `MaybeUninit<T>` has the same layout as `T`, and only
+    // initialized values are ever written through these views.
+    let keys = unsafe { &mut *(keys as *mut [K] as *mut [MaybeUninit<K>]) };
+    let values = unsafe { &mut *(values as *mut [V] as *mut [MaybeUninit<V>]) };
+
+    let mut src_keys = keys;
+    let mut src_values = values;
+    let mut dst_keys = &mut scratch_keys[..len];
+    let mut dst_values = &mut scratch_values[..len];
+    let mut swapped = false;
+
+    for (digit, histogram) in histograms.iter_mut().enumerate() {
+        // SAFETY: The source is either the caller's initialized slice, or a
+        // scratch slice fully written by the previous pass, as the bucket
+        // offsets form a permutation of `0..len`.
+        if histogram[unsafe { src_keys[0].assume_init_read() }.digit(digit)] == len {
+            continue;
+        }
+
+        // Exclusive prefix sum to find the starting offset of each bucket.
+        let mut sum = 0;
+        for count in histogram.iter_mut() {
+            let c = *count;
+            *count = sum;
+            sum += c;
+        }
+
+        for (key, value) in src_keys.iter().zip(src_values.iter()) {
+            // SAFETY: As above.
+            let key = unsafe { key.assume_init_read() };
+            let offset = &mut histogram[key.digit(digit)];
+            dst_keys[*offset] = MaybeUninit::new(key);
+            dst_values[*offset] = *value;
+            *offset += 1;
+        }
+
+        std::mem::swap(&mut src_keys, &mut dst_keys);
+        std::mem::swap(&mut src_values, &mut dst_values);
+        swapped = !swapped;
+    }
+
+    // After an odd number of passes the result lives in the scratch buffers.
+    if swapped {
+        dst_keys.copy_from_slice(src_keys);
+        dst_values.copy_from_slice(src_values);
+    }
+}
+
+/// Sorts `keys` in ascending order with an 8-bit digit LSD radix sort.
+///
+/// Allocates scratch space for each call, see [`radix_sort_u32_with_scratch`]
+/// to re-use a buffer instead.
+pub fn radix_sort_u32(keys: &mut [u32]) {
+    let mut scratch = Vec::with_capacity(keys.len());
+    radix_sort_u32_with_scratch(keys, scratch.spare_capacity_mut())
+}
+
+/// Sorts `keys` in ascending order with an 8-bit digit LSD radix sort.
+///
+/// Allocates scratch space for each call, see [`radix_sort_u64_with_scratch`]
+/// to re-use a buffer instead.
+pub fn radix_sort_u64(keys: &mut [u64]) {
+    let mut scratch = Vec::with_capacity(keys.len());
+    radix_sort_u64_with_scratch(keys, scratch.spare_capacity_mut())
+}
+
+/// Sorts `keys` in ascending order with an 8-bit digit LSD radix sort, using
+/// `scratch` instead of allocating.
+///
+/// # Panics
+///
+/// Panics if `scratch` is shorter than `keys`.
+pub fn radix_sort_u32_with_scratch(keys: &mut [u32], scratch: &mut [MaybeUninit<u32>]) {
+    // Zero-sized values never allocate.
+    let mut values = vec![(); keys.len()];
+    let mut scratch_values = Vec::<()>::new();
+    radix_sort_impl(
+        keys,
+        &mut values,
+        scratch,
+        scratch_values.spare_capacity_mut(),
+    )
+}
+
+/// Sorts `keys` in ascending order with an 8-bit digit LSD radix sort, using
+/// `scratch` instead of allocating.
+///
+/// # Panics
+///
+/// Panics if `scratch` is shorter than `keys`.
+pub fn radix_sort_u64_with_scratch(keys: &mut [u64], scratch: &mut [MaybeUninit<u64>]) {
+    // Zero-sized values never allocate.
+    let mut values = vec![(); keys.len()];
+    let mut scratch_values = Vec::<()>::new();
+    radix_sort_impl(
+        keys,
+        &mut values,
+        scratch,
+        scratch_values.spare_capacity_mut(),
+    )
+}
+
+/// Sorts `keys` in ascending order with an 8-bit digit LSD radix sort, applying
+/// the same permutation to `values`.
+///
+/// The sort is stable, values with equal keys retain their relative order.
+///
+/// Allocates scratch space for each call, see
+/// [`radix_sort_u32_by_key_with_scratch`] to re-use buffers instead.
+///
+/// # Panics
+///
+/// Panics if `keys` and `values` have different lengths.
+pub fn radix_sort_u32_by_key<V: Copy>(keys: &mut [u32], values: &mut [V]) {
+    let mut scratch_keys = Vec::with_capacity(keys.len());
+    let mut scratch_values = Vec::with_capacity(keys.len());
+    radix_sort_u32_by_key_with_scratch(
+        keys,
+        values,
+        scratch_keys.spare_capacity_mut(),
+        scratch_values.spare_capacity_mut(),
+    )
+}
+
+/// Sorts `keys` in ascending order with an 8-bit digit LSD radix sort, applying
+/// the same permutation to `values`.
+///
+/// The sort is stable, values with equal keys retain their relative order.
+///
+/// Allocates scratch space for each call, see
+/// [`radix_sort_u64_by_key_with_scratch`] to re-use buffers instead.
+///
+/// # Panics
+///
+/// Panics if `keys` and `values` have different lengths.
+pub fn radix_sort_u64_by_key<V: Copy>(keys: &mut [u64], values: &mut [V]) {
+    let mut scratch_keys = Vec::with_capacity(keys.len());
+    let mut scratch_values = Vec::with_capacity(keys.len());
+    radix_sort_u64_by_key_with_scratch(
+        keys,
+        values,
+        scratch_keys.spare_capacity_mut(),
+        scratch_values.spare_capacity_mut(),
+    )
+}
+
+/// Sorts `keys` in ascending order with an 8-bit digit LSD radix sort, applying
+/// the same permutation to `values`, using `scratch_keys` and `scratch_values`
+/// instead of allocating.
+///
+/// The sort is stable, values with equal keys retain their relative order.
+///
+/// # Panics
+///
+/// Panics if `keys` and `values` have different lengths, or if either scratch
+/// slice is shorter than `keys`.
+pub fn radix_sort_u32_by_key_with_scratch<V: Copy>(
+    keys: &mut [u32],
+    values: &mut [V],
+    scratch_keys: &mut [MaybeUninit<u32>],
+    scratch_values: &mut [MaybeUninit<V>],
+) {
+    radix_sort_impl(keys, values, scratch_keys, scratch_values)
+}
+
+/// Sorts `keys` in ascending order with an 8-bit digit LSD radix sort, applying
+/// the same permutation to `values`, using `scratch_keys` and `scratch_values`
+/// instead of allocating.
+///
+/// The sort is stable, values with equal keys retain their relative order.
+///
+/// # Panics
+///
+/// Panics if `keys` and `values` have different lengths, or if either scratch
+/// slice is shorter than `keys`.
+pub fn radix_sort_u64_by_key_with_scratch<V: Copy>(
+    keys: &mut [u64],
+    values: &mut [V],
+    scratch_keys: &mut [MaybeUninit<u64>],
+    scratch_values: &mut [MaybeUninit<V>],
+) {
+    radix_sort_impl(keys, values, scratch_keys, scratch_values)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::random::Pcg64;
+
+    use std::{mem::MaybeUninit, num::NonZeroU32};
+
+    use super::{
+        radix_sort_u32, radix_sort_u32_by_key, radix_sort_u32_by_key_with_scratch,
+        radix_sort_u32_with_scratch, radix_sort_u64, radix_sort_u64_by_key,
+        radix_sort_u64_with_scratch,
+    };
+
+    #[test]
+    fn radix_sort() {
+        let mut rng = Pcg64::new();
+        for len in [0, 1, 2, 3, 255, 256, 1000, 65537] {
+            let mut keys = (0..len).map(|_| rng.next_u64() as u32).collect::<Vec<_>>();
+            let mut expected = keys.clone();
+            expected.sort_unstable();
+            radix_sort_u32(&mut keys);
+            assert_eq!(keys, expected);
+
+            let mut keys = (0..len).map(|_| rng.next_u64()).collect::<Vec<_>>();
+            let mut expected = keys.clone();
+            expected.sort_unstable();
+            radix_sort_u64(&mut keys);
+            assert_eq!(keys, expected);
+        }
+
+        // Keys which only differ in some digits.
+        let mut keys = (0..1000)
+            .map(|_| (rng.next_u64() as u32) & 0x00ff_0f00)
+            .collect::<Vec<_>>();
+        let mut expected = keys.clone();
+        expected.sort_unstable();
+        radix_sort_u32(&mut keys);
+        assert_eq!(keys, expected);
+    }
+
+    #[test]
+    fn radix_sort_stable() {
+        let mut rng = Pcg64::new();
+        for len in [0, 1, 2, 100, 4096] {
+            let mut keys = (0..len)
+                .map(|_| rng.next_bound_u64(64) as u32 * 0x0101_0101)
+                .collect::<Vec<_>>();
+            let mut values = (0..len).collect::<Vec<usize>>();
+            let mut expected = keys
+                .iter()
+                .copied()
+                .zip(values.iter().copied())
+                .collect::<Vec<_>>();
+            // `sort_by_key` is stable.
+            expected.sort_by_key(|&(key, _)| key);
+            radix_sort_u32_by_key(&mut keys, &mut values);
+            assert!(
+                keys.iter()
+                    .copied()
+                    .zip(values.iter().copied())
+                    .eq(expected)
+            );
+
+            let mut keys = (0..len)
+                .map(|_| rng.next_bound_u64(64) << 40)
+                .collect::<Vec<_>>();
+            let mut values = (0..len).collect::<Vec<usize>>();
+            let mut expected = keys
+                .iter()
+                .copied()
+                .zip(values.iter().copied())
+                .collect::<Vec<_>>();
+            expected.sort_by_key(|&(key, _)| key);
+            radix_sort_u64_by_key(&mut keys, &mut values);
+            assert!(
+                keys.iter()
+                    .copied()
+                    .zip(values.iter().copied())
+                    .eq(expected)
+            );
+        }
+    }
+
+    #[test]
+    fn radix_sort_with_scratch() {
+        let mut rng = Pcg64::new();
+
+        // Scratch buffers are re-used across calls, and may be longer than the
+        // keys.
+        let mut scratch_keys = vec![MaybeUninit::uninit(); 1000];
+        let mut scratch_keys_u64 = vec![MaybeUninit::uninit(); 1000];
+        let mut scratch_values = vec![MaybeUninit::uninit(); 1000];
+        for len in [0, 1, 500, 1000] {
+            let mut keys = (0..len).map(|_| rng.next_u64() as u32).collect::<Vec<_>>();
+            let mut expected = keys.clone();
+            expected.sort_unstable();
+            radix_sort_u32_with_scratch(&mut keys, &mut scratch_keys);
+            assert_eq!(keys, expected);
+
+            let mut keys = (0..len).map(|_| rng.next_u64()).collect::<Vec<_>>();
+            let mut expected = keys.clone();
+            expected.sort_unstable();
+            radix_sort_u64_with_scratch(&mut keys, &mut scratch_keys_u64);
+            assert_eq!(keys, expected);
+
+            // Values need not implement `Default`.
+            let mut keys = (0..len)
+                .map(|_| rng.next_bound_u64(16) as u32)
+                .collect::<Vec<_>>();
+            let mut values = (1..=len as u32)
+                .map(|i| NonZeroU32::new(i).unwrap())
+                .collect::<Vec<_>>();
+            let mut expected = keys
+                .iter()
+                .copied()
+                .zip(values.iter().copied())
+                .collect::<Vec<_>>();
+            expected.sort_by_key(|&(key, _)| key);
+            radix_sort_u32_by_key_with_scratch(
+                &mut keys,
+                &mut values,
+                &mut scratch_keys,
+                &mut scratch_values,
+            );
+            assert!(
+                keys.iter()
+                    .copied()
+                    .zip(values.iter().copied())
+                    .eq(expected)
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn radix_sort_short_scratch() {
+        let mut keys = [3, 2, 1];
+        let mut scratch = [MaybeUninit::uninit(); 2];
+        radix_sort_u32_with_scratch(&mut keys, &mut scratch);
+    }
+}
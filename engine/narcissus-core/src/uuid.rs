@@ -24,6 +24,10 @@ impl Uuid {
         Self(bytes)
     }
 
+    pub fn to_bytes_be(self) -> [u8; 16] {
+        self.0
+    }
+
     pub const fn parse_str_unwrap(uuid: &str) -> Self {
         match Uuid::parse_str(uuid) {
             Ok(uuid) => uuid,
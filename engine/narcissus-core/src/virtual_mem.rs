@@ -62,6 +62,52 @@ pub unsafe fn virtual_commit(ptr: *mut std::ffi::c_void, size: usize) {
     }
 }
 
+/// Decommit (part of) a previously committed memory range.
+///
+/// The physical pages backing the range are returned to the system, and the range
+/// is marked inaccessible until it's committed again. Contents are not preserved.
+///
+/// Size will be rounded up to align with the system's page size.
+///
+/// # Safety
+///
+/// - Must point to an existing assignment created by [`virtual_reserve`].
+/// - `ptr` must be aligned to the system's page size.
+/// - size must be within range of that reservation.
+///
+/// # Panics
+///
+/// Panics if releasing the pages, or changing page permissions for the range fails.
+#[cold]
+#[inline(never)]
+pub unsafe fn virtual_decommit(ptr: *mut std::ffi::c_void, size: usize) {
+    unsafe {
+        let result = libc::madvise(ptr, size, libc::MADV_DONTNEED);
+        assert!(result == 0);
+        let result = libc::mprotect(ptr, size, libc::PROT_NONE);
+        assert!(result == 0);
+    }
+}
+
+/// Hint that a reserved memory range should be backed by transparent huge pages.
+///
+/// Only the huge page aligned portion of the range is affected, and the hint may
+/// be ignored depending on system configuration.
+///
+/// # Safety
+///
+/// - Must point to an existing assignment created by [`virtual_reserve`].
+/// - `ptr` must be aligned to the system's page size.
+/// - size must be within range of that reservation.
+#[cold]
+#[inline(never)]
+pub unsafe fn virtual_advise_huge_pages(ptr: *mut std::ffi::c_void, size: usize) {
+    unsafe {
+        // Failure just means the kernel doesn't support THP, which is fine for a hint.
+        let _ = libc::madvise(ptr, size, libc::MADV_HUGEPAGE);
+    }
+}
+
 /// Release a reserved or comitted virtual memory range.
 ///
 /// # Safety
@@ -234,6 +234,76 @@ impl<T> VirtualDeque<T> {
         }
     }
 
+    /// Shrinks the capacity of the deque as much as possible, returning pages
+    /// which are no longer required to the system.
+    pub fn shrink_to_fit(&mut self) {
+        self.shrink_to(0);
+    }
+
+    /// Shrinks the capacity of the deque with a lower bound, returning pages
+    /// which are no longer required to the system.
+    ///
+    /// The capacity will remain at least as large as both the length and the
+    /// supplied value. If the current capacity is less than the lower limit,
+    /// this is a no-op.
+    pub fn shrink_to(&mut self, min_capacity: usize) {
+        let old_cap = self.cap();
+        let new_cap = std::cmp::max(self.len(), min_capacity)
+            .max(MINIMUM_CAPACITY)
+            .checked_add(1)
+            .and_then(|cap| cap.checked_next_power_of_two())
+            .expect("capacity overflow");
+
+        if new_cap >= old_cap {
+            return;
+        }
+
+        // Move elements so they all lie within the new capacity.
+        //    T             H
+        // A [. . o o o o o . . . . . . . . . ]
+        //    T   H
+        //   [o o o o o . . . ]
+        //    H                 T
+        // B [o o . . . . . . . . . . . . o o ]
+        //        H         T
+        //   [o o . . . . . o ]
+        unsafe {
+            if self.tail <= self.head {
+                // A
+                if self.head >= new_cap {
+                    let len = self.head - self.tail;
+                    self.copy(0, self.tail, len);
+                    self.tail = 0;
+                    self.head = len;
+                }
+            } else {
+                // B
+                let tail_len = old_cap - self.tail;
+                let new_tail = new_cap - tail_len;
+                self.copy(new_tail, self.tail, tail_len);
+                self.tail = new_tail;
+            }
+
+            self.buf.shrink_to(new_cap);
+        }
+
+        debug_assert!(self.head < self.cap());
+        debug_assert!(self.tail < self.cap());
+        debug_assert!(self.cap().count_ones() == 1);
+    }
+
+    /// Hint that the deque's memory should be backed by transparent huge pages.
+    pub fn advise_huge_pages(&self) {
+        self.buf.advise_huge_pages()
+    }
+
+    /// Returns the number of bytes currently committed to back the deque's
+    /// capacity.
+    #[inline]
+    pub fn committed_bytes(&self) -> usize {
+        self.buf.committed_bytes()
+    }
+
     pub fn get(&self, index: usize) -> Option<&T> {
         if index < self.len() {
             let idx = self.wrap_add(self.tail, index);
@@ -61,4 +61,79 @@ mod tests {
             v.push_back(i);
         }
     }
+
+    #[test]
+    fn virtual_vec_shrink() {
+        let page_size = crate::page_size();
+        let mut v = VirtualVec::new(1 << 20);
+        for i in 0..100_000_u32 {
+            v.push(i);
+        }
+        let committed = v.committed_bytes();
+        assert!(committed >= 100_000 * 4);
+        assert_eq!(committed % page_size, 0);
+
+        v.truncate(1000);
+        v.shrink_to(2000);
+        assert_eq!(v.capacity(), 2000);
+        assert_eq!(v.committed_bytes(), 2 * page_size);
+
+        v.shrink_to_fit();
+        assert_eq!(v.capacity(), 1000);
+        assert_eq!(v.committed_bytes(), page_size);
+        assert!(v.iter().copied().eq(0..1000));
+
+        // Shrinking below the length or growing are both no-ops.
+        v.shrink_to(0);
+        v.shrink_to(5000);
+        assert_eq!(v.capacity(), 1000);
+
+        // Growing again recommits the pages.
+        for i in 1000..100_000_u32 {
+            v.push(i);
+        }
+        assert!(v.iter().copied().eq(0..100_000));
+
+        v.clear();
+        v.shrink_to_fit();
+        assert_eq!(v.committed_bytes(), 0);
+        v.push(1);
+        assert_eq!(v[0], 1);
+    }
+
+    #[test]
+    fn virtual_deque_shrink() {
+        // Contiguous, but beyond the new capacity.
+        let mut queue = VirtualDeque::new(1 << 16);
+        for i in 0..10_000 {
+            queue.push_back(i);
+        }
+        for _ in 0..9_990 {
+            queue.pop_front();
+        }
+        queue.shrink_to_fit();
+        assert_eq!(queue.committed_bytes(), crate::page_size());
+        assert!(queue.iter().copied().eq(9_990..10_000));
+
+        // Wrapped around the end of the buffer.
+        let mut queue = VirtualDeque::new(1 << 16);
+        for i in 0..10_000 {
+            queue.push_back(i);
+        }
+        for _ in 0..5000 {
+            queue.pop_front();
+        }
+        for i in 10_000..15_000 {
+            queue.push_back(i);
+        }
+        for _ in 0..9_900 {
+            queue.pop_front();
+        }
+        queue.shrink_to(200);
+        assert!(queue.iter().copied().eq(14_900..15_000));
+        for i in 15_000..20_000 {
+            queue.push_back(i);
+        }
+        assert!(queue.iter().copied().eq(14_900..20_000));
+    }
 }
@@ -4,7 +4,10 @@ use std::{
     ptr::NonNull,
 };
 
-use crate::{page_size, virtual_commit, virtual_free, virtual_reserve};
+use crate::{
+    align_offset, page_size, virtual_advise_huge_pages, virtual_commit, virtual_decommit,
+    virtual_free, virtual_reserve,
+};
 
 pub struct VirtualRawVec<T> {
     ptr: NonNull<T>,
@@ -106,6 +109,51 @@ impl<T> VirtualRawVec<T> {
         }
     }
 
+    /// Reduces the capacity to `new_capacity`, returning any pages no longer
+    /// required to hold the new capacity to the system.
+    ///
+    /// # Safety
+    ///
+    /// No live elements may be stored beyond `new_capacity`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `new_capacity` is greater than the current capacity.
+    #[cold]
+    #[inline(never)]
+    pub unsafe fn shrink_to(&mut self, new_capacity: usize) {
+        assert!(new_capacity <= self.cap);
+        let old_committed = self.committed_bytes();
+        self.cap = new_capacity;
+        let new_committed = self.committed_bytes();
+        if new_committed < old_committed {
+            unsafe {
+                virtual_decommit(
+                    self.ptr.as_ptr().byte_add(new_committed) as *mut std::ffi::c_void,
+                    old_committed - new_committed,
+                );
+            }
+        }
+    }
+
+    /// Hint that the entire reservation should be backed by transparent huge
+    /// pages.
+    pub fn advise_huge_pages(&self) {
+        unsafe {
+            virtual_advise_huge_pages(
+                self.ptr.as_ptr() as *mut std::ffi::c_void,
+                self.max_cap * size_of::<T>(),
+            )
+        }
+    }
+
+    /// Returns the number of bytes of the reservation which are currently
+    /// committed.
+    #[inline(always)]
+    pub fn committed_bytes(&self) -> usize {
+        align_offset(self.cap * size_of::<T>(), page_size())
+    }
+
     #[inline(always)]
     pub fn capacity(&self) -> usize {
         self.cap
@@ -233,6 +233,40 @@ impl<T> VirtualVec<T> {
         self.buf.reserve(self.len, additional);
     }
 
+    /// Shrinks the capacity of the vector as much as possible, returning pages
+    /// which are no longer required to the system.
+    pub fn shrink_to_fit(&mut self) {
+        self.shrink_to(0)
+    }
+
+    /// Shrinks the capacity of the vector with a lower bound, returning pages
+    /// which are no longer required to the system.
+    ///
+    /// The capacity will remain at least as large as both the length and the
+    /// supplied value. If the current capacity is less than the lower limit,
+    /// this is a no-op.
+    pub fn shrink_to(&mut self, min_capacity: usize) {
+        let new_capacity = std::cmp::max(self.len, min_capacity);
+        if new_capacity < self.capacity() {
+            // SAFETY: All elements are below `len`.
+            unsafe { self.buf.shrink_to(new_capacity) }
+        }
+    }
+
+    /// Hint that the vector's memory should be backed by transparent huge pages.
+    ///
+    /// Useful for very large vectors which are accessed randomly.
+    pub fn advise_huge_pages(&self) {
+        self.buf.advise_huge_pages()
+    }
+
+    /// Returns the number of bytes currently committed to back the vector's
+    /// capacity.
+    #[inline]
+    pub fn committed_bytes(&self) -> usize {
+        self.buf.committed_bytes()
+    }
+
     #[inline]
     pub fn is_empty(&self) -> bool {
         self.len() == 0
@@ -296,6 +330,21 @@ impl<T: Clone> VirtualVec<T> {
             self.truncate(new_len);
         }
     }
+
+    /// Clones and appends all elements in a slice to the `Vec`.
+    pub fn extend_from_slice(&mut self, other: &[T]) {
+        self.reserve(other.len());
+
+        unsafe {
+            let mut ptr = self.as_mut_ptr().add(self.len());
+            let mut local_len = SetLenOnDrop::new(&mut self.len);
+            for value in other {
+                ptr::write(ptr, value.clone());
+                ptr = ptr.add(1);
+                local_len.increment_len(1);
+            }
+        }
+    }
 }
 
 impl<T> VirtualVec<T> {
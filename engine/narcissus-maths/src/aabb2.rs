@@ -0,0 +1,160 @@
+use crate::{Point2, Vec2};
+
+/// Axis aligned bounding box in 2d space.
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[repr(C)]
+pub struct Aabb2 {
+    pub min: Point2,
+    pub max: Point2,
+}
+
+impl Aabb2 {
+    /// Empty box, with `min` greater than `max`, which contains nothing and is
+    /// the identity for [`Aabb2::union`].
+    pub const EMPTY: Aabb2 = Aabb2 {
+        min: Point2::splat(f32::INFINITY),
+        max: Point2::splat(f32::NEG_INFINITY),
+    };
+
+    /// Constructs a new box with the given `min` and `max` corners.
+    #[inline(always)]
+    pub const fn new(min: Point2, max: Point2) -> Self {
+        Self { min, max }
+    }
+
+    /// Constructs a new box with the given `center` and `half_extents`.
+    #[inline(always)]
+    pub fn from_center_half_extents(center: Point2, half_extents: Vec2) -> Self {
+        Self {
+            min: center - half_extents,
+            max: center + half_extents,
+        }
+    }
+
+    /// Returns the smallest box containing all `points`, or [`Aabb2::EMPTY`] if
+    /// `points` is empty.
+    pub fn from_points(points: &[Point2]) -> Self {
+        points
+            .iter()
+            .fold(Aabb2::EMPTY, |aabb, &point| aabb.include_point(point))
+    }
+
+    /// Returns `true` if the box contains no points.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        !(self.min.x <= self.max.x && self.min.y <= self.max.y)
+    }
+
+    #[inline(always)]
+    pub fn center(&self) -> Point2 {
+        Point2::lerp(0.5, self.min, self.max)
+    }
+
+    #[inline(always)]
+    pub fn extents(&self) -> Vec2 {
+        self.max - self.min
+    }
+
+    #[inline(always)]
+    pub fn half_extents(&self) -> Vec2 {
+        (self.max - self.min) * 0.5
+    }
+
+    #[inline(always)]
+    pub fn area(&self) -> f32 {
+        let extents = self.extents();
+        extents.x * extents.y
+    }
+
+    /// Returns the smallest box containing both `self` and `point`.
+    #[must_use]
+    #[inline(always)]
+    pub fn include_point(&self, point: Point2) -> Aabb2 {
+        Self {
+            min: Point2::min(self.min, point),
+            max: Point2::max(self.max, point),
+        }
+    }
+
+    /// Returns the smallest box containing both `a` and `b`.
+    #[must_use]
+    #[inline(always)]
+    pub fn union(a: Aabb2, b: Aabb2) -> Aabb2 {
+        Self {
+            min: Point2::min(a.min, b.min),
+            max: Point2::max(a.max, b.max),
+        }
+    }
+
+    /// Returns the overlapping region of `a` and `b`, which is empty if they don't
+    /// overlap.
+    #[must_use]
+    #[inline(always)]
+    pub fn intersection(a: Aabb2, b: Aabb2) -> Aabb2 {
+        Self {
+            min: Point2::max(a.min, b.min),
+            max: Point2::min(a.max, b.max),
+        }
+    }
+
+    /// Returns `true` if `point` is inside or on the boundary of `self`.
+    #[inline(always)]
+    pub fn contains_point(&self, point: Point2) -> bool {
+        point.x >= self.min.x
+            && point.x <= self.max.x
+            && point.y >= self.min.y
+            && point.y <= self.max.y
+    }
+
+    /// Returns `true` if `a` and `b` overlap, including touching boundaries.
+    #[inline(always)]
+    pub fn overlaps(a: &Aabb2, b: &Aabb2) -> bool {
+        a.min.x <= b.max.x && a.max.x >= b.min.x && a.min.y <= b.max.y && a.max.y >= b.min.y
+    }
+
+    /// Returns the point inside `self` closest to `point`.
+    #[inline(always)]
+    pub fn closest_point(&self, point: Point2) -> Point2 {
+        Point2::clamp(point, self.min, self.max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Aabb2, Point2, Vec2, point2};
+
+    #[test]
+    fn basic() {
+        assert!(Aabb2::EMPTY.is_empty());
+        assert!(!Aabb2::EMPTY.contains_point(Point2::ZERO));
+
+        let a = Aabb2::from_points(&[point2(1.0, -1.0), point2(-1.0, 2.0), point2(0.0, 0.0)]);
+        assert_eq!(a, Aabb2::new(point2(-1.0, -1.0), point2(1.0, 2.0)));
+        assert_eq!(a.center(), point2(0.0, 0.5));
+        assert_eq!(a.extents(), Vec2::new(2.0, 3.0));
+        assert_eq!(a.area(), 6.0);
+        assert_eq!(
+            Aabb2::from_center_half_extents(a.center(), a.half_extents()),
+            a
+        );
+        assert_eq!(Aabb2::union(Aabb2::EMPTY, a), a);
+        assert!(a.contains_point(point2(1.0, 2.0)));
+        assert!(!a.contains_point(point2(1.5, 0.0)));
+        assert_eq!(a.closest_point(point2(5.0, 0.5)), point2(1.0, 0.5));
+    }
+
+    #[test]
+    fn overlap() {
+        let a = Aabb2::new(point2(0.0, 0.0), point2(1.0, 1.0));
+        let b = Aabb2::new(point2(1.0, 0.5), point2(2.0, 2.0));
+        let c = Aabb2::new(point2(1.5, 1.5), point2(2.0, 2.0));
+        assert!(Aabb2::overlaps(&a, &b));
+        assert!(!Aabb2::overlaps(&a, &c));
+        assert!(!Aabb2::overlaps(&a, &Aabb2::EMPTY));
+        assert_eq!(
+            Aabb2::intersection(a, b),
+            Aabb2::new(point2(1.0, 0.5), point2(1.0, 1.0))
+        );
+        assert!(Aabb2::intersection(a, c).is_empty());
+    }
+}
@@ -0,0 +1,223 @@
+use crate::{Affine3, Point3, Vec3};
+
+/// Axis aligned bounding box in 3d space.
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[repr(C)]
+pub struct Aabb3 {
+    pub min: Point3,
+    pub max: Point3,
+}
+
+impl Aabb3 {
+    /// Empty box, with `min` greater than `max`, which contains nothing and is
+    /// the identity for [`Aabb3::union`].
+    pub const EMPTY: Aabb3 = Aabb3 {
+        min: Point3::splat(f32::INFINITY),
+        max: Point3::splat(f32::NEG_INFINITY),
+    };
+
+    /// Constructs a new box with the given `min` and `max` corners.
+    #[inline(always)]
+    pub const fn new(min: Point3, max: Point3) -> Self {
+        Self { min, max }
+    }
+
+    /// Constructs a new box with the given `center` and `half_extents`.
+    #[inline(always)]
+    pub fn from_center_half_extents(center: Point3, half_extents: Vec3) -> Self {
+        Self {
+            min: center - half_extents,
+            max: center + half_extents,
+        }
+    }
+
+    /// Returns the smallest box containing all `points`, or [`Aabb3::EMPTY`] if
+    /// `points` is empty.
+    pub fn from_points(points: &[Point3]) -> Self {
+        points
+            .iter()
+            .fold(Aabb3::EMPTY, |aabb, &point| aabb.include_point(point))
+    }
+
+    /// Returns `true` if the box contains no points.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        !(self.min.x <= self.max.x && self.min.y <= self.max.y && self.min.z <= self.max.z)
+    }
+
+    #[inline(always)]
+    pub fn center(&self) -> Point3 {
+        Point3::lerp(0.5, self.min, self.max)
+    }
+
+    #[inline(always)]
+    pub fn extents(&self) -> Vec3 {
+        self.max - self.min
+    }
+
+    #[inline(always)]
+    pub fn half_extents(&self) -> Vec3 {
+        (self.max - self.min) * 0.5
+    }
+
+    #[inline(always)]
+    pub fn volume(&self) -> f32 {
+        let extents = self.extents();
+        extents.x * extents.y * extents.z
+    }
+
+    #[inline(always)]
+    pub fn surface_area(&self) -> f32 {
+        let extents = self.extents();
+        2.0 * (extents.x * extents.y + extents.y * extents.z + extents.z * extents.x)
+    }
+
+    /// Returns the smallest box containing both `self` and `point`.
+    #[must_use]
+    #[inline(always)]
+    pub fn include_point(&self, point: Point3) -> Aabb3 {
+        Self {
+            min: Point3::min(self.min, point),
+            max: Point3::max(self.max, point),
+        }
+    }
+
+    /// Returns the smallest box containing both `a` and `b`.
+    #[must_use]
+    #[inline(always)]
+    pub fn union(a: Aabb3, b: Aabb3) -> Aabb3 {
+        Self {
+            min: Point3::min(a.min, b.min),
+            max: Point3::max(a.max, b.max),
+        }
+    }
+
+    /// Returns the overlapping region of `a` and `b`, which is empty if they don't
+    /// overlap.
+    #[must_use]
+    #[inline(always)]
+    pub fn intersection(a: Aabb3, b: Aabb3) -> Aabb3 {
+        Self {
+            min: Point3::max(a.min, b.min),
+            max: Point3::min(a.max, b.max),
+        }
+    }
+
+    /// Returns the smallest box containing `self` after transformation by
+    /// `transform`.
+    ///
+    /// Empty boxes remain empty.
+    #[must_use]
+    pub fn transform(&self, transform: &Affine3) -> Aabb3 {
+        if self.is_empty() {
+            return Aabb3::EMPTY;
+        }
+        let center = transform.transform_point3(self.center());
+        let half_extents = self.half_extents();
+        let rows = transform.matrix.as_rows();
+        let half_extents = Vec3::new(
+            Vec3::dot(Vec3::from(rows[0]).abs(), half_extents),
+            Vec3::dot(Vec3::from(rows[1]).abs(), half_extents),
+            Vec3::dot(Vec3::from(rows[2]).abs(), half_extents),
+        );
+        Aabb3::from_center_half_extents(center, half_extents)
+    }
+
+    /// Returns `true` if `point` is inside or on the boundary of `self`.
+    #[inline(always)]
+    pub fn contains_point(&self, point: Point3) -> bool {
+        point.x >= self.min.x
+            && point.x <= self.max.x
+            && point.y >= self.min.y
+            && point.y <= self.max.y
+            && point.z >= self.min.z
+            && point.z <= self.max.z
+    }
+
+    /// Returns `true` if `a` and `b` overlap, including touching boundaries.
+    #[inline(always)]
+    pub fn overlaps(a: &Aabb3, b: &Aabb3) -> bool {
+        a.min.x <= b.max.x
+            && a.max.x >= b.min.x
+            && a.min.y <= b.max.y
+            && a.max.y >= b.min.y
+            && a.min.z <= b.max.z
+            && a.max.z >= b.min.z
+    }
+
+    /// Returns the point inside `self` closest to `point`.
+    #[inline(always)]
+    pub fn closest_point(&self, point: Point3) -> Point3 {
+        Point3::clamp(point, self.min, self.max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Aabb3, Affine3, HalfTurn, Mat3, Point3, Vec3, point3};
+
+    #[test]
+    fn basic() {
+        assert!(Aabb3::EMPTY.is_empty());
+        assert!(!Aabb3::EMPTY.contains_point(Point3::ZERO));
+
+        let a = Aabb3::from_points(&[
+            point3(1.0, -1.0, 0.0),
+            point3(-1.0, 2.0, 4.0),
+            point3(0.0, 0.0, 0.0),
+        ]);
+        assert_eq!(
+            a,
+            Aabb3::new(point3(-1.0, -1.0, 0.0), point3(1.0, 2.0, 4.0))
+        );
+        assert_eq!(a.center(), point3(0.0, 0.5, 2.0));
+        assert_eq!(a.extents(), Vec3::new(2.0, 3.0, 4.0));
+        assert_eq!(a.volume(), 24.0);
+        assert_eq!(a.surface_area(), 52.0);
+        assert_eq!(
+            Aabb3::from_center_half_extents(a.center(), a.half_extents()),
+            a
+        );
+        assert_eq!(Aabb3::union(Aabb3::EMPTY, a), a);
+        assert!(a.contains_point(point3(1.0, 2.0, 4.0)));
+        assert!(!a.contains_point(point3(0.0, 0.0, -0.5)));
+        assert_eq!(
+            a.closest_point(point3(5.0, 0.5, -3.0)),
+            point3(1.0, 0.5, 0.0)
+        );
+    }
+
+    #[test]
+    fn overlap() {
+        let a = Aabb3::new(Point3::ZERO, Point3::ONE);
+        let b = Aabb3::new(point3(1.0, 0.5, 0.5), Point3::splat(2.0));
+        let c = Aabb3::new(point3(0.5, 0.5, 1.5), Point3::splat(2.0));
+        assert!(Aabb3::overlaps(&a, &b));
+        assert!(!Aabb3::overlaps(&a, &c));
+        assert!(!Aabb3::overlaps(&a, &Aabb3::EMPTY));
+        assert!(!Aabb3::intersection(a, b).is_empty());
+        assert!(Aabb3::intersection(a, c).is_empty());
+    }
+
+    #[test]
+    fn transform() {
+        let a = Aabb3::new(Point3::ZERO, point3(2.0, 1.0, 1.0));
+        let translate = Affine3::new(Mat3::IDENTITY, Vec3::new(1.0, 2.0, 3.0));
+        assert_eq!(
+            a.transform(&translate),
+            Aabb3::new(point3(1.0, 2.0, 3.0), point3(3.0, 3.0, 4.0))
+        );
+
+        // Rotating a quarter turn around z swaps the x and y extents.
+        let rotate = Affine3::new(
+            Mat3::from_axis_rotation(Vec3::Z, HalfTurn::new(0.5)),
+            Vec3::ZERO,
+        );
+        let rotated = a.transform(&rotate);
+        assert_eq!(rotated.extents(), Vec3::new(1.0, 2.0, 1.0));
+        assert_eq!(rotated.center(), point3(-0.5, 1.0, 0.5));
+
+        assert_eq!(Aabb3::EMPTY.transform(&translate), Aabb3::EMPTY);
+        assert_eq!(Aabb3::EMPTY.transform(&rotate), Aabb3::EMPTY);
+    }
+}
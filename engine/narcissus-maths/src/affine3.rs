@@ -1,4 +1,4 @@
-use crate::{Mat3, Point3, Vec3};
+use crate::{Mat3, Point3, Quat, Vec3};
 
 /// Matrix and translation vector which together represent a 3d affine
 /// transformation.
@@ -27,10 +27,81 @@ impl Affine3 {
         }
     }
 
+    /// Constructs a transform which scales by `scale`, then rotates by
+    /// `rotation`, then translates by `translation`.
+    pub fn from_translation_rotation_scale(translation: Vec3, rotation: Quat, scale: Vec3) -> Self {
+        let [x, y, z] = Mat3::from_quat(rotation).to_columns();
+        Self {
+            matrix: Mat3::from_columns([x * scale.x, y * scale.y, z * scale.z]),
+            translation,
+        }
+    }
+
+    /// Decomposes `self` into translation, rotation and scale, such that
+    /// [`Affine3::from_translation_rotation_scale`] reconstructs `self`.
+    ///
+    /// If the transform contains a reflection, it's represented by a negative
+    /// `x` scale. The result is unspecified if the transform contains shear or
+    /// is singular.
+    pub fn to_translation_rotation_scale(&self) -> (Vec3, Quat, Vec3) {
+        let [x, y, z] = self.matrix.to_columns();
+        let sign = if self.matrix.determinant() < 0.0 {
+            -1.0
+        } else {
+            1.0
+        };
+        let scale = Vec3::new(x.length() * sign, y.length(), z.length());
+        let rotation = Mat3::from_columns([x / scale.x, y / scale.y, z / scale.z]);
+        (self.translation, Quat::from_mat3(rotation), scale)
+    }
+
+    /// Returns the determinant of the linear part of `self`.
+    pub fn determinant(&self) -> f32 {
+        self.matrix.determinant()
+    }
+
+    /// Returns the inverse of `self`.
+    ///
+    /// If `self` is singular the result will contain non-finite values. See
+    /// [`Affine3::checked_inverse`].
+    #[must_use]
+    pub fn inverse(&self) -> Affine3 {
+        let matrix = self.matrix.inverse();
+        Self {
+            matrix,
+            translation: -(matrix * self.translation),
+        }
+    }
+
+    /// Returns the inverse of `self`, or `None` if `self` is singular or the
+    /// inverse is not finite.
+    #[must_use]
+    pub fn checked_inverse(&self) -> Option<Affine3> {
+        let matrix = self.matrix.checked_inverse()?;
+        let translation = -(matrix * self.translation);
+        let is_finite =
+            translation.x.is_finite() && translation.y.is_finite() && translation.z.is_finite();
+        is_finite.then_some(Self {
+            matrix,
+            translation,
+        })
+    }
+
+    /// Returns the inverse of `self`, assuming the linear part of `self` is
+    /// orthonormal, that is, a rotation and translation only.
+    #[must_use]
+    pub fn orthonormal_inverse(&self) -> Affine3 {
+        let matrix = self.matrix.transpose();
+        Self {
+            matrix,
+            translation: -(matrix * self.translation),
+        }
+    }
+
     pub fn mul_affine3(&self, rhs: Affine3) -> Affine3 {
         Self {
             matrix: self.matrix * rhs.matrix,
-            translation: self.translation + rhs.translation,
+            translation: self.matrix * rhs.translation + self.translation,
         }
     }
 
@@ -76,3 +147,146 @@ impl std::ops::Mul<Point3> for Affine3 {
         self.transform_point3(rhs)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use narcissus_core::random::Pcg64;
+
+    use crate::{
+        Affine3, HalfTurn, Mat3, Point3, Quat, Vec3,
+        reference::{assert_close, determinant_inverse},
+    };
+
+    fn to_rows(affine: Affine3) -> [[f32; 4]; 4] {
+        let [r0, r1, r2] = *affine.matrix.as_rows();
+        let t = affine.translation;
+        [
+            [r0[0], r0[1], r0[2], t.x],
+            [r1[0], r1[1], r1[2], t.y],
+            [r2[0], r2[1], r2[2], t.z],
+            [0.0, 0.0, 0.0, 1.0],
+        ]
+    }
+
+    fn random_trs(rng: &mut Pcg64) -> (Vec3, Quat, Vec3) {
+        let mut random_vec3 = || Vec3::new(rng.next_f32_s(), rng.next_f32_s(), rng.next_f32_s());
+        let translation = random_vec3() * 100.0;
+        let axis = loop {
+            let axis = random_vec3();
+            if axis.length_sq() > 0.01 {
+                break axis.normalized();
+            }
+        };
+        let scale = random_vec3().abs() * 4.0 + 0.25;
+        let rotation = Quat::from_axis_rotation(axis, HalfTurn::new(rng.next_f32_s()));
+        (translation, rotation, scale)
+    }
+
+    #[test]
+    fn mul() {
+        let a = Affine3::new(Mat3::from_scale(Vec3::splat(2.0)), Vec3::new(1.0, 2.0, 3.0));
+        let b = Affine3::new(Mat3::IDENTITY, Vec3::new(1.0, 1.0, 1.0));
+        let p = Point3::new(1.0, 0.0, 0.0);
+        assert_eq!((a * b) * p, a * (b * p));
+        assert_eq!((a * b) * p, Point3::new(5.0, 4.0, 5.0));
+    }
+
+    // Composition must apply `rhs` first, so its translation is transformed by
+    // the linear part of `self`, matching the product of the 4x4 matrices.
+    #[test]
+    fn mul_matches_matrix_product() {
+        let mut rng = Pcg64::new();
+        for _ in 0..1000 {
+            let (translation, rotation, scale) = random_trs(&mut rng);
+            let a = Affine3::from_translation_rotation_scale(translation, rotation, scale);
+            let (translation, rotation, scale) = random_trs(&mut rng);
+            let b = Affine3::from_translation_rotation_scale(translation, rotation, scale);
+            let (a_rows, b_rows) = (to_rows(a), to_rows(b));
+            let expected: [[f64; 4]; 4] = std::array::from_fn(|i| {
+                std::array::from_fn(|j| {
+                    (0..4)
+                        .map(|k| a_rows[i][k] as f64 * b_rows[k][j] as f64)
+                        .sum()
+                })
+            });
+            assert_close(
+                to_rows(a.mul_affine3(b)).as_flattened(),
+                expected.as_flattened(),
+                1e-5,
+            );
+        }
+    }
+
+    #[test]
+    fn inverse() {
+        assert_eq!(Affine3::IDENTITY.inverse(), Affine3::IDENTITY);
+        assert_eq!(Affine3::ZERO.checked_inverse(), None);
+
+        let mut rng = Pcg64::new();
+        for _ in 0..1000 {
+            let (translation, rotation, scale) = random_trs(&mut rng);
+            let affine = Affine3::from_translation_rotation_scale(translation, rotation, scale);
+            let (det, expected) = determinant_inverse(to_rows(affine));
+            let expected = expected.unwrap();
+            assert_close(&[affine.determinant()], &[det], 1e-5);
+            assert_close(
+                to_rows(affine.checked_inverse().unwrap()).as_flattened(),
+                expected.as_flattened(),
+                1e-5,
+            );
+
+            let rigid = Affine3::from_translation_rotation_scale(translation, rotation, Vec3::ONE);
+            let (_, expected) = determinant_inverse(to_rows(rigid));
+            assert_close(
+                to_rows(rigid.orthonormal_inverse()).as_flattened(),
+                expected.unwrap().as_flattened(),
+                1e-5,
+            );
+        }
+    }
+
+    #[test]
+    fn translation_rotation_scale() {
+        let mut rng = Pcg64::new();
+        for _ in 0..1000 {
+            let (translation, rotation, scale) = random_trs(&mut rng);
+            let affine = Affine3::from_translation_rotation_scale(translation, rotation, scale);
+            let (t, r, s) = affine.to_translation_rotation_scale();
+            assert_eq!(t, translation);
+            assert_close(
+                &<[f32; 3]>::from(s),
+                &<[f32; 3]>::from(scale).map(|x| x as f64),
+                1e-5,
+            );
+            // `q` and `-q` represent the same rotation.
+            let dot = r.a * rotation.a + r.b * rotation.b + r.c * rotation.c + r.d * rotation.d;
+            let sign = if dot < 0.0 { -1.0 } else { 1.0 };
+            assert_close(
+                &[r.a, r.b, r.c, r.d].map(|x| x * sign),
+                &[rotation.a, rotation.b, rotation.c, rotation.d].map(|x| x as f64),
+                1e-5,
+            );
+
+            let reconstructed = Affine3::from_translation_rotation_scale(t, r, s);
+            assert_close(
+                to_rows(reconstructed).as_flattened(),
+                to_rows(affine)
+                    .map(|row| row.map(|x| x as f64))
+                    .as_flattened(),
+                1e-5,
+            );
+        }
+
+        // Reflections are represented by a negative x scale.
+        let mirror = Affine3::new(Mat3::from_scale(Vec3::new(1.0, -2.0, 3.0)), Vec3::ZERO);
+        let (_, r, s) = mirror.to_translation_rotation_scale();
+        let reconstructed = Affine3::from_translation_rotation_scale(Vec3::ZERO, r, s);
+        assert_close(
+            to_rows(reconstructed).as_flattened(),
+            to_rows(mirror)
+                .map(|row| row.map(|x| x as f64))
+                .as_flattened(),
+            1e-6,
+        );
+    }
+}
@@ -0,0 +1,119 @@
+// Evaluates a minimax polynomial for asin(x) / pi on [0,sqrt(1/2)], and
+// otherwise uses asin(x) = pi/2 - 2 asin(sqrt((1 - x) / 2)) to reduce the
+// argument. Reducing from sqrt(1/2) rather than the usual 1/2 avoids the
+// cancellation in the subtraction.
+
+// constants for asin(x) / pi = x / pi + x^3 p(x^2) for x on [0,sqrt(1/2)]
+const F32_ASIN_PI_K: [f32; 8] = unsafe {
+    std::mem::transmute::<[u32; 8], _>([
+        0x3d594c8a, // 0x1.b29914p-5
+        0x3cc3a3ba, // 0x1.874774p-6
+        0x3c65e51a, // 0x1.cbca34p-7
+        0x3c3bb336, // 0x1.77666cp-7
+        0xbb2812ee, // -0x1.5025dcp-9
+        0x3d0fd86f, // 0x1.1fb0dep-5
+        0xbd367cad, // -0x1.6cf95ap-5
+        0x3d246d31, // 0x1.48da62p-5
+    ])
+};
+
+// 1 / pi split into high and low parts.
+const INV_PI_HI: f32 = 0.318_309_87; // 0x1.45f306p-2
+const INV_PI_LO: f32 = 1.284_127_6e-8; // 0x1.b9391p-27
+
+/// Computes asin(x) / pi for x on [-sqrt(1/2),sqrt(1/2)].
+#[inline(always)]
+fn asin_pi_f32_base(x: f32) -> f32 {
+    const T: [f32; 8] = F32_ASIN_PI_K;
+
+    let x2 = x * x;
+    let p = T[7];
+    let p = p.mul_add(x2, T[6]);
+    let p = p.mul_add(x2, T[5]);
+    let p = p.mul_add(x2, T[4]);
+    let p = p.mul_add(x2, T[3]);
+    let p = p.mul_add(x2, T[2]);
+    let p = p.mul_add(x2, T[1]);
+    let p = p.mul_add(x2, T[0]);
+
+    let t = x2 * x;
+    let t = p.mul_add(t, INV_PI_LO * x);
+    x.mul_add(INV_PI_HI, t)
+}
+
+/// Computes the arcsine of `a`, returning the angle in multiples of *pi*
+/// radians, or half-turns.
+///
+/// Returns `asin(a) / pi` in the range \[-0.5,0.5\], or `NaN` if `a` is outside
+/// the range \[-1,1\].
+///
+/// Error <= 1.11999 ulp.
+///
+/// # Examples
+///
+/// ```
+/// use narcissus_maths::asin_pi_f32;
+/// assert_eq!(asin_pi_f32(1.0), 0.5);
+/// assert_eq!(asin_pi_f32(-0.5), -1.0 / 6.0);
+/// ```
+pub fn asin_pi_f32(a: f32) -> f32 {
+    let x = a.abs();
+    let r = if x > std::f32::consts::FRAC_1_SQRT_2 {
+        let s = ((1.0 - x) * 0.5).sqrt();
+        (-2.0_f32).mul_add(asin_pi_f32_base(s), 0.5)
+    } else {
+        asin_pi_f32_base(x)
+    };
+    r.copysign(a)
+}
+
+/// Computes the arccosine of `a`, returning the angle in multiples of *pi*
+/// radians, or half-turns.
+///
+/// Returns `acos(a) / pi` in the range \[0,1\], or `NaN` if `a` is outside the
+/// range \[-1,1\].
+///
+/// Error <= 1.25020 ulp.
+///
+/// # Examples
+///
+/// ```
+/// use narcissus_maths::acos_pi_f32;
+/// assert_eq!(acos_pi_f32(1.0), 0.0);
+/// assert_eq!(acos_pi_f32(0.0), 0.5);
+/// assert_eq!(acos_pi_f32(-1.0), 1.0);
+/// ```
+pub fn acos_pi_f32(a: f32) -> f32 {
+    let x = a.abs();
+    if x > std::f32::consts::FRAC_1_SQRT_2 {
+        let s = ((1.0 - x) * 0.5).sqrt();
+        let r = 2.0 * asin_pi_f32_base(s);
+        if a < 0.0 { 1.0 - r } else { r }
+    } else {
+        0.5 - asin_pi_f32_base(a)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{acos_pi_f32, asin_pi_f32};
+
+    #[test]
+    fn basics() {
+        assert_eq!(asin_pi_f32(0.0), 0.0);
+        assert!(asin_pi_f32(-0.0).is_sign_negative());
+        assert_eq!(asin_pi_f32(0.5), 1.0 / 6.0);
+        assert_eq!(asin_pi_f32(1.0), 0.5);
+        assert_eq!(asin_pi_f32(-1.0), -0.5);
+        assert!(asin_pi_f32(1.000_001).is_nan());
+        assert!(asin_pi_f32(f32::NEG_INFINITY).is_nan());
+        assert!(asin_pi_f32(f32::NAN).is_nan());
+
+        assert_eq!(acos_pi_f32(1.0), 0.0);
+        assert_eq!(acos_pi_f32(0.0), 0.5);
+        assert_eq!(acos_pi_f32(-1.0), 1.0);
+        assert!(acos_pi_f32(-1.000_001).is_nan());
+        assert!(acos_pi_f32(f32::INFINITY).is_nan());
+        assert!(acos_pi_f32(f32::NAN).is_nan());
+    }
+}
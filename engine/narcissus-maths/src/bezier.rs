@@ -0,0 +1,408 @@
+use crate::{Curve, CurveVector};
+
+/// Maximum depth of recursive subdivision when flattening curves.
+const MAX_FLATTEN_DEPTH: u32 = 16;
+
+/// Returns the roots of `a t^2 + b t + c` which lie strictly inside (0,1).
+#[inline(always)]
+fn quadratic_roots_unit(a: f32, b: f32, c: f32) -> [Option<f32>; 2] {
+    let in_unit = |t: f32| (t > 0.0 && t < 1.0).then_some(t);
+    if a.abs() < 1e-12 {
+        if b == 0.0 {
+            return [None, None];
+        }
+        return [in_unit(-c / b), None];
+    }
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+        return [None, None];
+    }
+    // Avoid cancellation by computing the larger magnitude root first.
+    let q = -0.5 * (b + discriminant.sqrt().copysign(b));
+    let r0 = q / a;
+    let r1 = if q == 0.0 { r0 } else { c / q };
+    [in_unit(r0), in_unit(r1)]
+}
+
+/// Quadratic Bézier curve, with endpoints `p0` and `p2` and control point `p1`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct QuadraticBezier<T> {
+    pub p0: T,
+    pub p1: T,
+    pub p2: T,
+}
+
+impl<T: CurveVector> QuadraticBezier<T> {
+    /// Constructs a new curve from the given points.
+    #[inline(always)]
+    pub const fn new(p0: T, p1: T, p2: T) -> Self {
+        Self { p0, p1, p2 }
+    }
+
+    /// Returns the second derivative of the curve, which is constant.
+    #[inline(always)]
+    #[must_use]
+    pub fn second_derivative(&self) -> T {
+        (self.p0 - self.p1 * 2.0 + self.p2) * 2.0
+    }
+
+    /// Splits the curve at `t`, returning curves covering \[0,t\] and \[t,1\].
+    #[inline]
+    #[must_use]
+    pub fn split(&self, t: f32) -> (Self, Self) {
+        let p01 = T::mix(t, self.p0, self.p1);
+        let p12 = T::mix(t, self.p1, self.p2);
+        let p = T::mix(t, p01, p12);
+        (Self::new(self.p0, p01, p), Self::new(p, p12, self.p2))
+    }
+
+    /// Returns the exact degree elevated cubic curve.
+    #[inline(always)]
+    #[must_use]
+    pub fn to_cubic(&self) -> CubicBezier<T> {
+        CubicBezier::new(
+            self.p0,
+            T::mix(2.0 / 3.0, self.p0, self.p1),
+            T::mix(2.0 / 3.0, self.p2, self.p1),
+            self.p2,
+        )
+    }
+
+    /// Returns the smallest box containing the curve.
+    pub fn aabb(&self) -> T::Aabb {
+        let p0 = self.p0.to_array();
+        let p1 = self.p1.to_array();
+        let p2 = self.p2.to_array();
+        let mut lo = T::min(self.p0, self.p2).to_array();
+        let mut hi = T::max(self.p0, self.p2).to_array();
+        for i in 0..p0.as_ref().len() {
+            let (a, b, c) = (p0.as_ref()[i], p1.as_ref()[i], p2.as_ref()[i]);
+            // Root of the derivative.
+            let [t, _] = quadratic_roots_unit(0.0, a - 2.0 * b + c, b - a);
+            if let Some(t) = t {
+                let mt = 1.0 - t;
+                let x = a * mt * mt + 2.0 * b * mt * t + c * t * t;
+                lo.as_mut()[i] = lo.as_ref()[i].min(x);
+                hi.as_mut()[i] = hi.as_ref()[i].max(x);
+            }
+        }
+        T::aabb(T::from_array(lo), T::from_array(hi))
+    }
+
+    /// Approximates the curve with line segments, such that no point on the
+    /// curve is further than `tolerance` from the polyline.
+    ///
+    /// Appends the end point of each segment to `points`. The start point `p0`
+    /// is not included, so consecutive curves can be flattened into the same
+    /// polyline.
+    pub fn flatten(&self, tolerance: f32, points: &mut Vec<T>) {
+        // The largest distance between the curve and its chord is a quarter of
+        // the distance between the control point and the chord midpoint.
+        let deviation = (self.p0 - self.p1 * 2.0 + self.p2).length() * 0.25;
+        let segments = (deviation / tolerance).sqrt().ceil().clamp(1.0, 1024.0) as u32;
+        let step = 1.0 / segments as f32;
+        for i in 1..segments {
+            points.push(self.eval(i as f32 * step));
+        }
+        points.push(self.p2);
+    }
+}
+
+impl<T: CurveVector> Curve for QuadraticBezier<T> {
+    type Vector = T;
+
+    #[inline]
+    fn eval(&self, t: f32) -> T {
+        let mt = 1.0 - t;
+        self.p0 * (mt * mt) + self.p1 * (2.0 * mt * t) + self.p2 * (t * t)
+    }
+
+    #[inline]
+    fn derivative(&self, t: f32) -> T {
+        T::mix(t, self.p1 - self.p0, self.p2 - self.p1) * 2.0
+    }
+}
+
+/// Cubic Bézier curve, with endpoints `p0` and `p3` and control points `p1`
+/// and `p2`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct CubicBezier<T> {
+    pub p0: T,
+    pub p1: T,
+    pub p2: T,
+    pub p3: T,
+}
+
+impl<T: CurveVector> CubicBezier<T> {
+    /// Constructs a new curve from the given points.
+    #[inline(always)]
+    pub const fn new(p0: T, p1: T, p2: T, p3: T) -> Self {
+        Self { p0, p1, p2, p3 }
+    }
+
+    /// Returns the second derivative of the curve with respect to `t`.
+    #[inline]
+    #[must_use]
+    pub fn second_derivative(&self, t: f32) -> T {
+        let a = self.p0 - self.p1 * 2.0 + self.p2;
+        let b = self.p1 - self.p2 * 2.0 + self.p3;
+        T::mix(t, a, b) * 6.0
+    }
+
+    /// Splits the curve at `t`, returning curves covering \[0,t\] and \[t,1\].
+    #[inline]
+    #[must_use]
+    pub fn split(&self, t: f32) -> (Self, Self) {
+        let p01 = T::mix(t, self.p0, self.p1);
+        let p12 = T::mix(t, self.p1, self.p2);
+        let p23 = T::mix(t, self.p2, self.p3);
+        let p012 = T::mix(t, p01, p12);
+        let p123 = T::mix(t, p12, p23);
+        let p = T::mix(t, p012, p123);
+        (
+            Self::new(self.p0, p01, p012, p),
+            Self::new(p, p123, p23, self.p3),
+        )
+    }
+
+    /// Returns the smallest box containing the curve.
+    pub fn aabb(&self) -> T::Aabb {
+        let p0 = self.p0.to_array();
+        let p1 = self.p1.to_array();
+        let p2 = self.p2.to_array();
+        let p3 = self.p3.to_array();
+        let mut lo = T::min(self.p0, self.p3).to_array();
+        let mut hi = T::max(self.p0, self.p3).to_array();
+        for i in 0..p0.as_ref().len() {
+            let (a, b, c, d) = (
+                p0.as_ref()[i],
+                p1.as_ref()[i],
+                p2.as_ref()[i],
+                p3.as_ref()[i],
+            );
+            // Roots of the derivative, divided through by 3.
+            let qa = -a + 3.0 * (b - c) + d;
+            let qb = 2.0 * (a - 2.0 * b + c);
+            let qc = b - a;
+            for t in quadratic_roots_unit(qa, qb, qc).into_iter().flatten() {
+                let mt = 1.0 - t;
+                let x = a * mt * mt * mt + 3.0 * (b * mt * mt * t + c * mt * t * t) + d * t * t * t;
+                lo.as_mut()[i] = lo.as_ref()[i].min(x);
+                hi.as_mut()[i] = hi.as_ref()[i].max(x);
+            }
+        }
+        T::aabb(T::from_array(lo), T::from_array(hi))
+    }
+
+    /// Returns `true` if the control points are close enough to the line
+    /// between the endpoints that the curve is within `tolerance` of it.
+    #[inline(always)]
+    fn is_flat(&self, tolerance: f32) -> bool {
+        // Bound from Roger Willcocks, in a form that holds in any dimension.
+        let u = self.p1 * 3.0 - self.p0 * 2.0 - self.p3;
+        let v = self.p2 * 3.0 - self.p3 * 2.0 - self.p0;
+        u.length_sq() + v.length_sq() <= 16.0 * tolerance * tolerance
+    }
+
+    fn flatten_recursive(&self, tolerance: f32, depth: u32, points: &mut Vec<T>) {
+        if depth >= MAX_FLATTEN_DEPTH || self.is_flat(tolerance) {
+            points.push(self.p3);
+        } else {
+            let (a, b) = self.split(0.5);
+            a.flatten_recursive(tolerance, depth + 1, points);
+            b.flatten_recursive(tolerance, depth + 1, points);
+        }
+    }
+
+    /// Approximates the curve with line segments, such that no point on the
+    /// curve is further than `tolerance` from the polyline.
+    ///
+    /// Appends the end point of each segment to `points`. The start point `p0`
+    /// is not included, so consecutive curves can be flattened into the same
+    /// polyline.
+    pub fn flatten(&self, tolerance: f32, points: &mut Vec<T>) {
+        self.flatten_recursive(tolerance, 0, points);
+    }
+}
+
+impl<T: CurveVector> Curve for CubicBezier<T> {
+    type Vector = T;
+
+    #[inline]
+    fn eval(&self, t: f32) -> T {
+        let mt = 1.0 - t;
+        let mt2 = mt * mt;
+        let t2 = t * t;
+        self.p0 * (mt2 * mt)
+            + self.p1 * (3.0 * mt2 * t)
+            + self.p2 * (3.0 * mt * t2)
+            + self.p3 * (t2 * t)
+    }
+
+    #[inline]
+    fn derivative(&self, t: f32) -> T {
+        let mt = 1.0 - t;
+        let d0 = self.p1 - self.p0;
+        let d1 = self.p2 - self.p1;
+        let d2 = self.p3 - self.p2;
+        (d0 * (mt * mt) + d1 * (2.0 * mt * t) + d2 * (t * t)) * 3.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use narcissus_core::random::Pcg64;
+
+    use crate::{CubicBezier, Curve, QuadraticBezier, Vec2, point2, vec2, vec3};
+
+    fn random_vec2(rng: &mut Pcg64) -> Vec2 {
+        vec2(rng.next_f32_s() * 10.0, rng.next_f32_s() * 10.0)
+    }
+
+    #[test]
+    fn eval_endpoints() {
+        let quadratic = QuadraticBezier::new(vec2(0.0, 0.0), vec2(1.0, 2.0), vec2(2.0, 0.0));
+        assert_eq!(quadratic.eval(0.0), vec2(0.0, 0.0));
+        assert_eq!(quadratic.eval(0.5), vec2(1.0, 1.0));
+        assert_eq!(quadratic.eval(1.0), vec2(2.0, 0.0));
+        assert_eq!(quadratic.derivative(0.0), vec2(2.0, 4.0));
+        assert_eq!(quadratic.derivative(1.0), vec2(2.0, -4.0));
+        assert_eq!(quadratic.second_derivative(), vec2(0.0, -8.0));
+
+        let cubic = quadratic.to_cubic();
+        for i in 0..=8 {
+            let t = i as f32 / 8.0;
+            assert!((cubic.eval(t) - quadratic.eval(t)).length() < 1e-6);
+            assert!((cubic.derivative(t) - quadratic.derivative(t)).length() < 1e-5);
+            assert!((cubic.second_derivative(t) - quadratic.second_derivative()).length() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn derivatives_match_finite_differences() {
+        let mut rng = Pcg64::new();
+        let h = 1.0 / 1024.0;
+        for _ in 0..100 {
+            let cubic = CubicBezier::new(
+                random_vec2(&mut rng),
+                random_vec2(&mut rng),
+                random_vec2(&mut rng),
+                random_vec2(&mut rng),
+            );
+            let t = rng.next_f32() * 0.9 + 0.05;
+            let d = (cubic.eval(t + h) - cubic.eval(t - h)) * (0.5 / h);
+            assert!((d - cubic.derivative(t)).length() < 1e-2);
+            let d = (cubic.derivative(t + h) - cubic.derivative(t - h)) * (0.5 / h);
+            assert!((d - cubic.second_derivative(t)).length() < 1e-2);
+        }
+    }
+
+    #[test]
+    fn split() {
+        let mut rng = Pcg64::new();
+        for _ in 0..100 {
+            let cubic = CubicBezier::new(
+                random_vec2(&mut rng),
+                random_vec2(&mut rng),
+                random_vec2(&mut rng),
+                random_vec2(&mut rng),
+            );
+            let quadratic = QuadraticBezier::new(cubic.p0, cubic.p1, cubic.p2);
+            let s = rng.next_f32();
+            let (a, b) = cubic.split(s);
+            let (qa, qb) = quadratic.split(s);
+            for i in 0..=8 {
+                let t = i as f32 / 8.0;
+                assert!((a.eval(t) - cubic.eval(t * s)).length() < 1e-4);
+                assert!((b.eval(t) - cubic.eval(s + t * (1.0 - s))).length() < 1e-4);
+                assert!((qa.eval(t) - quadratic.eval(t * s)).length() < 1e-4);
+                assert!((qb.eval(t) - quadratic.eval(s + t * (1.0 - s))).length() < 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn aabb() {
+        let quadratic = QuadraticBezier::new(vec2(0.0, 0.0), vec2(1.0, 2.0), vec2(2.0, 0.0));
+        let aabb = quadratic.aabb();
+        assert_eq!(aabb.min, point2(0.0, 0.0));
+        assert_eq!(aabb.max, point2(2.0, 1.0));
+
+        let cubic = CubicBezier::new(
+            vec3(0.0, 0.0, 0.0),
+            vec3(0.0, 1.0, 0.0),
+            vec3(1.0, 1.0, 0.0),
+            vec3(1.0, 0.0, 0.0),
+        );
+        let aabb = cubic.aabb();
+        assert_eq!(aabb.max.y, 0.75);
+
+        // The box must contain every point on the curve and be tight.
+        let mut rng = Pcg64::new();
+        for _ in 0..100 {
+            let cubic = CubicBezier::new(
+                random_vec2(&mut rng),
+                random_vec2(&mut rng),
+                random_vec2(&mut rng),
+                random_vec2(&mut rng),
+            );
+            let aabb = cubic.aabb();
+            let mut min = Vec2::splat(f32::INFINITY);
+            let mut max = Vec2::splat(f32::NEG_INFINITY);
+            for i in 0..=4096 {
+                let p = cubic.eval(i as f32 / 4096.0);
+                assert!(aabb.min.x - 1e-5 <= p.x && p.x <= aabb.max.x + 1e-5);
+                assert!(aabb.min.y - 1e-5 <= p.y && p.y <= aabb.max.y + 1e-5);
+                min = Vec2::min(min, p);
+                max = Vec2::max(max, p);
+            }
+            assert!((min - aabb.min.as_vec2()).length() < 1e-3);
+            assert!((max - aabb.max.as_vec2()).length() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn flatten_within_tolerance() {
+        let mut rng = Pcg64::new();
+        for tolerance in [1.0, 0.1, 0.01] {
+            for _ in 0..20 {
+                let cubic = CubicBezier::new(
+                    random_vec2(&mut rng),
+                    random_vec2(&mut rng),
+                    random_vec2(&mut rng),
+                    random_vec2(&mut rng),
+                );
+                let quadratic = QuadraticBezier::new(cubic.p0, cubic.p1, cubic.p2);
+
+                let mut points = vec![cubic.p0];
+                cubic.flatten(tolerance, &mut points);
+                assert_eq!(*points.last().unwrap(), cubic.p3);
+                check_polyline(&cubic, &points, tolerance);
+
+                let mut points = vec![quadratic.p0];
+                quadratic.flatten(tolerance, &mut points);
+                assert_eq!(*points.last().unwrap(), quadratic.p2);
+                check_polyline(&quadratic, &points, tolerance);
+            }
+        }
+    }
+
+    /// Checks that every sampled point on `curve` is within `tolerance` of the
+    /// polyline.
+    fn check_polyline<C: Curve<Vector = Vec2>>(curve: &C, points: &[Vec2], tolerance: f32) {
+        for i in 0..=1024 {
+            let p = curve.eval(i as f32 / 1024.0);
+            let distance = points
+                .windows(2)
+                .map(|w| {
+                    let (a, b) = (w[0], w[1]);
+                    let ab = b - a;
+                    let t = (Vec2::dot(p - a, ab) / ab.length_sq()).clamp(0.0, 1.0);
+                    let t = if t.is_nan() { 0.0 } else { t };
+                    (a + ab * t - p).length()
+                })
+                .fold(f32::INFINITY, f32::min);
+            assert!(distance <= tolerance * 1.001, "{distance} > {tolerance}");
+        }
+    }
+}
@@ -0,0 +1,873 @@
+use narcissus_core::{FixedVec, Widen};
+
+use crate::{Aabb3, Point3, Ray3, Vec3};
+
+/// Number of buckets used when searching for the best split plane.
+const BIN_COUNT: usize = 16;
+/// Nodes with this many primitives or fewer may become leaves when the surface
+/// area heuristic finds splitting them isn't worth it.
+const MAX_LEAF_SIZE: u32 = 8;
+/// Nodes at this depth always become leaves, bounding the traversal stack.
+const MAX_DEPTH: usize = 48;
+/// Capacity of the traversal stack, which must exceed the tree height.
+const STACK_SIZE: usize = 64;
+
+/// Node of a [`Bvh`], laid out for upload to the GPU.
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[repr(C)]
+pub struct BvhNode {
+    pub aabb: Aabb3,
+    /// For internal nodes, the index of the first of two adjacent child nodes.
+    /// For leaves, the offset of the first primitive in [`Bvh::indices`].
+    pub first: u32,
+    /// Number of primitives in a leaf, or zero for internal nodes.
+    pub count: u32,
+}
+
+impl BvhNode {
+    #[inline(always)]
+    pub fn is_leaf(&self) -> bool {
+        self.count != 0
+    }
+}
+
+/// Bounding volume hierarchy over a static set of primitives, built with the
+/// binned surface area heuristic.
+///
+/// Nodes are stored in a flat array, with the root at index zero and each pair
+/// of children stored adjacently after their parent.
+///
+/// Primitives are identified by their index in the bounds passed to
+/// [`Bvh::build`]. Queries pass candidate primitives to a callback which
+/// performs the exact test.
+#[derive(Clone, Default, Debug)]
+pub struct Bvh {
+    nodes: Vec<BvhNode>,
+    indices: Vec<u32>,
+}
+
+impl Bvh {
+    /// Builds a hierarchy over primitives with the given `bounds`.
+    pub fn build(bounds: &[Aabb3]) -> Bvh {
+        let count = u32::try_from(bounds.len()).expect("too many primitives");
+        if count == 0 {
+            return Bvh::default();
+        }
+
+        let centroids = bounds.iter().map(|aabb| aabb.center()).collect::<Vec<_>>();
+        let mut indices = (0..count).collect::<Vec<_>>();
+        let mut nodes = Vec::with_capacity(bounds.len() * 2 - 1);
+        nodes.push(BvhNode {
+            aabb: Aabb3::EMPTY,
+            first: 0,
+            count,
+        });
+
+        let mut stack = vec![(0, 0)];
+        while let Some((node_index, depth)) = stack.pop() {
+            let node = &mut nodes[node_index];
+            let range = node.first.widen()..(node.first + node.count).widen();
+            node.aabb = indices[range.clone()]
+                .iter()
+                .fold(Aabb3::EMPTY, |aabb, &i| {
+                    Aabb3::union(aabb, bounds[i.widen()])
+                });
+
+            if node.count == 1 || depth >= MAX_DEPTH {
+                continue;
+            }
+
+            let Some(split) = find_split(&indices[range.clone()], bounds, &centroids) else {
+                continue;
+            };
+            let leaf_cost = node.count as f32 * node.aabb.surface_area();
+            if node.count <= MAX_LEAF_SIZE && split.cost >= leaf_cost {
+                continue;
+            }
+
+            let mut mid = range.start
+                + partition(&mut indices[range.clone()], |&i| {
+                    split.is_left(centroids[i.widen()])
+                });
+            // Non-finite bounds can put every primitive on one side, which would
+            // leave an empty child that looks like an internal node. Fall back to
+            // splitting at the median centroid.
+            if mid == range.start || mid == range.end {
+                let half = (range.end - range.start) / 2;
+                indices[range.clone()].select_nth_unstable_by(half, |&a, &b| {
+                    let a = <[f32; 3]>::from(centroids[a.widen()])[split.axis];
+                    let b = <[f32; 3]>::from(centroids[b.widen()])[split.axis];
+                    a.total_cmp(&b)
+                });
+                mid = range.start + half;
+            }
+
+            let first = node.first;
+            let left = nodes.len();
+            let node = &mut nodes[node_index];
+            node.first = left as u32;
+            node.count = 0;
+            nodes.push(BvhNode {
+                aabb: Aabb3::EMPTY,
+                first,
+                count: (mid - range.start) as u32,
+            });
+            nodes.push(BvhNode {
+                aabb: Aabb3::EMPTY,
+                first: mid as u32,
+                count: (range.end - mid) as u32,
+            });
+            stack.push((left, depth + 1));
+            stack.push((left + 1, depth + 1));
+        }
+
+        Bvh { nodes, indices }
+    }
+
+    /// Returns the nodes of the hierarchy, with the root at index zero.
+    #[inline(always)]
+    pub fn nodes(&self) -> &[BvhNode] {
+        &self.nodes
+    }
+
+    /// Returns the primitive indices referenced by leaf nodes.
+    #[inline(always)]
+    pub fn indices(&self) -> &[u32] {
+        &self.indices
+    }
+
+    /// Returns `true` if the hierarchy contains no primitives.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Updates node bounds after primitives have moved, without changing the
+    /// structure of the tree.
+    ///
+    /// This is much cheaper than a rebuild, but query performance degrades as
+    /// primitives move further from their positions at build time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bounds` has fewer elements than the hierarchy was built
+    /// with.
+    pub fn refit(&mut self, bounds: &[Aabb3]) {
+        // Children are always stored after their parent.
+        for i in (0..self.nodes.len()).rev() {
+            let node = self.nodes[i];
+            let first = node.first.widen();
+            self.nodes[i].aabb = if node.is_leaf() {
+                self.indices[first..first + node.count.widen()]
+                    .iter()
+                    .fold(Aabb3::EMPTY, |aabb, &i| {
+                        Aabb3::union(aabb, bounds[i.widen()])
+                    })
+            } else {
+                Aabb3::union(self.nodes[first].aabb, self.nodes[first + 1].aabb)
+            };
+        }
+    }
+
+    /// Returns the nearest primitive hit by `ray` with a ray parameter no
+    /// greater than `t_max`, along with the ray parameter of the hit.
+    ///
+    /// `intersect` is called with candidate primitives, and returns the ray
+    /// parameter at which the ray hits that primitive, if any.
+    pub fn raycast<F>(&self, ray: &Ray3, t_max: f32, intersect: F) -> Option<(u32, f32)>
+    where
+        F: FnMut(u32) -> Option<f32>,
+    {
+        raycast(self, ray, t_max, intersect)
+    }
+
+    /// Calls `f` with every primitive in a leaf overlapping `aabb`.
+    ///
+    /// The primitive bounds themselves are not tested, so `f` may be called
+    /// with primitives which don't overlap `aabb`.
+    pub fn query_aabb<F>(&self, aabb: &Aabb3, f: F)
+    where
+        F: FnMut(u32),
+    {
+        query_aabb(self, aabb, f)
+    }
+
+    /// Returns the primitive nearest to `point` within a squared distance of
+    /// `max_distance_sq`, along with its squared distance.
+    ///
+    /// `distance_sq` is called with candidate primitives, and returns the
+    /// squared distance from `point` to that primitive.
+    pub fn nearest<F>(
+        &self,
+        point: Point3,
+        max_distance_sq: f32,
+        distance_sq: F,
+    ) -> Option<(u32, f32)>
+    where
+        F: FnMut(u32) -> f32,
+    {
+        nearest(self, point, max_distance_sq, distance_sq)
+    }
+}
+
+impl BvhTree for Bvh {
+    #[inline(always)]
+    fn root(&self) -> Option<u32> {
+        (!self.nodes.is_empty()).then_some(0)
+    }
+
+    #[inline(always)]
+    fn aabb(&self, node: u32) -> &Aabb3 {
+        &self.nodes[node.widen()].aabb
+    }
+
+    #[inline(always)]
+    fn children(&self, node: u32) -> Option<[u32; 2]> {
+        let node = &self.nodes[node.widen()];
+        (!node.is_leaf()).then_some([node.first, node.first + 1])
+    }
+
+    #[inline(always)]
+    fn leaf_items(&self, node: u32) -> impl Iterator<Item = u32> {
+        let node = &self.nodes[node.widen()];
+        let first = node.first.widen();
+        self.indices[first..first + node.count.widen()]
+            .iter()
+            .copied()
+    }
+}
+
+/// Candidate split plane found by the surface area heuristic.
+struct Split {
+    axis: usize,
+    /// Primitives with centroids in bins below this index go left.
+    bin: usize,
+    min: f32,
+    scale: f32,
+    cost: f32,
+}
+
+impl Split {
+    #[inline(always)]
+    fn is_left(&self, centroid: Point3) -> bool {
+        bin_index(<[f32; 3]>::from(centroid)[self.axis], self.min, self.scale) < self.bin
+    }
+}
+
+#[inline(always)]
+fn bin_index(x: f32, min: f32, scale: f32) -> usize {
+    (((x - min) * scale) as usize).min(BIN_COUNT - 1)
+}
+
+/// Returns the split of `indices` with the lowest surface area heuristic cost,
+/// or `None` if the centroids are coincident.
+fn find_split(indices: &[u32], bounds: &[Aabb3], centroids: &[Point3]) -> Option<Split> {
+    let centroid_bounds = indices.iter().fold(Aabb3::EMPTY, |aabb, &i| {
+        aabb.include_point(centroids[i.widen()])
+    });
+    let centroid_min = <[f32; 3]>::from(centroid_bounds.min);
+    let centroid_extents = <[f32; 3]>::from(centroid_bounds.extents());
+
+    let mut best: Option<Split> = None;
+    for axis in 0..3 {
+        if centroid_extents[axis] <= 0.0 {
+            continue;
+        }
+        let min = centroid_min[axis];
+        let scale = BIN_COUNT as f32 / centroid_extents[axis];
+
+        let mut bin_bounds = [Aabb3::EMPTY; BIN_COUNT];
+        let mut bin_counts = [0_u32; BIN_COUNT];
+        for &i in indices {
+            let bin = bin_index(<[f32; 3]>::from(centroids[i.widen()])[axis], min, scale);
+            bin_bounds[bin] = Aabb3::union(bin_bounds[bin], bounds[i.widen()]);
+            bin_counts[bin] += 1;
+        }
+
+        // Sweep from the right to find the cost of everything right of each
+        // plane, then from the left to combine.
+        let mut right_costs = [0.0; BIN_COUNT];
+        let mut aabb = Aabb3::EMPTY;
+        let mut count = 0;
+        for bin in (1..BIN_COUNT).rev() {
+            aabb = Aabb3::union(aabb, bin_bounds[bin]);
+            count += bin_counts[bin];
+            right_costs[bin] = if count == 0 {
+                f32::INFINITY
+            } else {
+                count as f32 * aabb.surface_area()
+            };
+        }
+
+        let mut aabb = Aabb3::EMPTY;
+        let mut count = 0;
+        for bin in 1..BIN_COUNT {
+            aabb = Aabb3::union(aabb, bin_bounds[bin - 1]);
+            count += bin_counts[bin - 1];
+            if count == 0 {
+                continue;
+            }
+            let cost = count as f32 * aabb.surface_area() + right_costs[bin];
+            if best.as_ref().is_none_or(|best| cost < best.cost) {
+                best = Some(Split {
+                    axis,
+                    bin,
+                    min,
+                    scale,
+                    cost,
+                });
+            }
+        }
+    }
+
+    best
+}
+
+/// Reorders `slice` so that elements satisfying `pred` come first, and returns
+/// the number of such elements.
+fn partition<T, F: FnMut(&T) -> bool>(slice: &mut [T], mut pred: F) -> usize {
+    let mut left = 0;
+    for i in 0..slice.len() {
+        if pred(&slice[i]) {
+            slice.swap(left, i);
+            left += 1;
+        }
+    }
+    left
+}
+
+/// Bounding volume hierarchy over triangles, for raycasts and closest point
+/// queries against meshes.
+///
+/// # Examples
+///
+/// ```
+/// use narcissus_maths::{Ray3, TriangleBvh, point3, vec3};
+/// let positions = [
+///     point3(-1.0, -1.0, 0.0),
+///     point3(1.0, -1.0, 0.0),
+///     point3(1.0, 1.0, 0.0),
+///     point3(-1.0, 1.0, 0.0),
+/// ];
+/// let indices: [u16; 6] = [0, 1, 2, 0, 2, 3];
+/// let bvh = TriangleBvh::new(&positions, &indices);
+/// let ray = Ray3::new(point3(-0.5, 0.5, 2.0), vec3(0.0, 0.0, -1.0));
+/// assert_eq!(bvh.raycast(&ray, f32::INFINITY), Some((1, 2.0)));
+/// ```
+#[derive(Clone, Default, Debug)]
+pub struct TriangleBvh {
+    bvh: Bvh,
+    triangles: Vec<[Point3; 3]>,
+}
+
+impl TriangleBvh {
+    /// Builds a hierarchy over the triangle list given by `indices` into
+    /// `positions`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `indices` is not a multiple of three, or if any
+    /// index is out of bounds.
+    pub fn new<I>(positions: &[Point3], indices: &[I]) -> TriangleBvh
+    where
+        I: Copy + Widen<usize>,
+    {
+        assert!(indices.len().is_multiple_of(3));
+        let triangles = indices
+            .chunks_exact(3)
+            .map(|triangle| std::array::from_fn(|i| positions[triangle[i].widen()]))
+            .collect::<Vec<_>>();
+        let bounds = triangles
+            .iter()
+            .map(|triangle| Aabb3::from_points(triangle))
+            .collect::<Vec<_>>();
+        TriangleBvh {
+            bvh: Bvh::build(&bounds),
+            triangles,
+        }
+    }
+
+    /// Returns the underlying hierarchy, whose primitive indices are triangle
+    /// indices.
+    #[inline(always)]
+    pub fn bvh(&self) -> &Bvh {
+        &self.bvh
+    }
+
+    /// Returns the triangles, in the order they were given.
+    #[inline(always)]
+    pub fn triangles(&self) -> &[[Point3; 3]] {
+        &self.triangles
+    }
+
+    /// Returns the index of the nearest triangle hit by `ray` from either side,
+    /// along with the ray parameter of the hit.
+    pub fn raycast(&self, ray: &Ray3, t_max: f32) -> Option<(u32, f32)> {
+        self.bvh.raycast(ray, t_max, |i| {
+            let [a, b, c] = self.triangles[i.widen()];
+            ray.intersect_triangle(a, b, c)
+        })
+    }
+
+    /// Calls `f` with the index of every triangle whose bounds overlap
+    /// `aabb`.
+    pub fn query_aabb<F>(&self, aabb: &Aabb3, mut f: F)
+    where
+        F: FnMut(u32),
+    {
+        self.bvh.query_aabb(aabb, |i| {
+            if Aabb3::overlaps(aabb, &Aabb3::from_points(&self.triangles[i.widen()])) {
+                f(i)
+            }
+        })
+    }
+
+    /// Returns the index of the triangle nearest to `point`, along with the
+    /// closest point on that triangle.
+    pub fn closest_point(&self, point: Point3) -> Option<(u32, Point3)> {
+        let (i, _) = self.bvh.nearest(point, f32::INFINITY, |i| {
+            let [a, b, c] = self.triangles[i.widen()];
+            Point3::distance_sq(point, closest_point_triangle(point, a, b, c))
+        })?;
+        let [a, b, c] = self.triangles[i.widen()];
+        Some((i, closest_point_triangle(point, a, b, c)))
+    }
+}
+
+/// Returns the point on the triangle `a`, `b`, `c` closest to `p`.
+///
+/// Christer Ericson, "Real-Time Collision Detection", section 5.1.5.
+fn closest_point_triangle(p: Point3, a: Point3, b: Point3, c: Point3) -> Point3 {
+    let ab = b - a;
+    let ac = c - a;
+    let ap = p - a;
+    let d1 = Vec3::dot(ab, ap);
+    let d2 = Vec3::dot(ac, ap);
+    if d1 <= 0.0 && d2 <= 0.0 {
+        return a;
+    }
+
+    let bp = p - b;
+    let d3 = Vec3::dot(ab, bp);
+    let d4 = Vec3::dot(ac, bp);
+    if d3 >= 0.0 && d4 <= d3 {
+        return b;
+    }
+
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+        return a + ab * (d1 / (d1 - d3));
+    }
+
+    let cp = p - c;
+    let d5 = Vec3::dot(ab, cp);
+    let d6 = Vec3::dot(ac, cp);
+    if d6 >= 0.0 && d5 <= d6 {
+        return c;
+    }
+
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+        return a + ac * (d2 / (d2 - d6));
+    }
+
+    let va = d3 * d6 - d5 * d4;
+    if va <= 0.0 && (d4 - d3) >= 0.0 && (d5 - d6) >= 0.0 {
+        return b + (c - b) * ((d4 - d3) / ((d4 - d3) + (d5 - d6)));
+    }
+
+    let denom = 1.0 / (va + vb + vc);
+    a + ab * (vb * denom) + ac * (vc * denom)
+}
+
+/// Binary tree of bounding boxes, shared by the static and dynamic
+/// hierarchies so they can share traversal code.
+pub(crate) trait BvhTree {
+    /// Returns the root node, or `None` if the tree is empty.
+    fn root(&self) -> Option<u32>;
+    /// Returns the bounds of `node`.
+    fn aabb(&self, node: u32) -> &Aabb3;
+    /// Returns the children of `node`, or `None` if `node` is a leaf.
+    fn children(&self, node: u32) -> Option<[u32; 2]>;
+    /// Returns the items stored in the leaf `node`.
+    fn leaf_items(&self, node: u32) -> impl Iterator<Item = u32>;
+}
+
+/// Returns the ray parameter at which the ray enters `aabb`, if it does so
+/// before `t_max`.
+#[inline(always)]
+fn ray_aabb(origin: Point3, inv_dir: Vec3, aabb: &Aabb3, t_max: f32) -> Option<f32> {
+    let t0 = (aabb.min - origin) * inv_dir;
+    let t1 = (aabb.max - origin) * inv_dir;
+    let t_near = Vec3::min(t0, t1);
+    let t_far = Vec3::max(t0, t1);
+    let t_near = t_near.x.max(t_near.y).max(t_near.z).max(0.0);
+    let t_far = t_far.x.min(t_far.y).min(t_far.z).min(t_max);
+    (t_near <= t_far).then_some(t_near)
+}
+
+pub(crate) fn raycast<T, F>(
+    tree: &T,
+    ray: &Ray3,
+    t_max: f32,
+    mut intersect: F,
+) -> Option<(u32, f32)>
+where
+    T: BvhTree,
+    F: FnMut(u32) -> Option<f32>,
+{
+    let root = tree.root()?;
+    let inv_dir = ray.dir.map(|x| 1.0 / x);
+    ray_aabb(ray.origin, inv_dir, tree.aabb(root), t_max)?;
+
+    let mut best = None;
+    let mut t_max = t_max;
+    let mut stack = FixedVec::<(u32, f32), STACK_SIZE>::new();
+    stack.push((root, 0.0));
+    while let Some((node, t_enter)) = stack.pop() {
+        if t_enter > t_max {
+            continue;
+        }
+
+        let Some(children) = tree.children(node) else {
+            for item in tree.leaf_items(node) {
+                if let Some(t) = intersect(item).filter(|&t| t <= t_max) {
+                    t_max = t;
+                    best = Some((item, t));
+                }
+            }
+            continue;
+        };
+
+        // Visit the nearer child first, so later hits can prune the other.
+        let [a, b] = children.map(|child| {
+            (
+                child,
+                ray_aabb(ray.origin, inv_dir, tree.aabb(child), t_max),
+            )
+        });
+        match (a, b) {
+            ((a, Some(ta)), (b, Some(tb))) => {
+                let (near, far) = if ta <= tb {
+                    ((a, ta), (b, tb))
+                } else {
+                    ((b, tb), (a, ta))
+                };
+                stack.push(far);
+                stack.push(near);
+            }
+            ((node, Some(t)), _) | (_, (node, Some(t))) => stack.push((node, t)),
+            _ => {}
+        }
+    }
+
+    best
+}
+
+pub(crate) fn query_aabb<T, F>(tree: &T, aabb: &Aabb3, mut f: F)
+where
+    T: BvhTree,
+    F: FnMut(u32),
+{
+    let Some(root) = tree.root() else {
+        return;
+    };
+
+    let mut stack = FixedVec::<u32, STACK_SIZE>::new();
+    stack.push(root);
+    while let Some(node) = stack.pop() {
+        if !Aabb3::overlaps(tree.aabb(node), aabb) {
+            continue;
+        }
+        match tree.children(node) {
+            Some([a, b]) => {
+                stack.push(b);
+                stack.push(a);
+            }
+            None => tree.leaf_items(node).for_each(&mut f),
+        }
+    }
+}
+
+pub(crate) fn nearest<T, F>(
+    tree: &T,
+    point: Point3,
+    max_distance_sq: f32,
+    mut distance_sq: F,
+) -> Option<(u32, f32)>
+where
+    T: BvhTree,
+    F: FnMut(u32) -> f32,
+{
+    let root = tree.root()?;
+    let box_distance_sq = |node| Point3::distance_sq(point, tree.aabb(node).closest_point(point));
+
+    let mut best = None;
+    let mut best_distance_sq = max_distance_sq;
+    let mut stack = FixedVec::<(u32, f32), STACK_SIZE>::new();
+    stack.push((root, box_distance_sq(root)));
+    while let Some((node, node_distance_sq)) = stack.pop() {
+        if node_distance_sq > best_distance_sq {
+            continue;
+        }
+
+        let Some(children) = tree.children(node) else {
+            for item in tree.leaf_items(node) {
+                let d = distance_sq(item);
+                if d <= best_distance_sq {
+                    best_distance_sq = d;
+                    best = Some((item, d));
+                }
+            }
+            continue;
+        };
+
+        let [a, b] = children.map(|child| (child, box_distance_sq(child)));
+        let (near, far) = if a.1 <= b.1 { (a, b) } else { (b, a) };
+        if far.1 <= best_distance_sq {
+            stack.push(far);
+        }
+        if near.1 <= best_distance_sq {
+            stack.push(near);
+        }
+    }
+
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use narcissus_core::random::Pcg64;
+
+    use crate::{Aabb3, Bvh, Point3, Ray3, TriangleBvh, Vec3, point3, vec3};
+
+    use super::closest_point_triangle;
+
+    fn random_point(rng: &mut Pcg64, scale: f32) -> Point3 {
+        point3(
+            rng.next_f32_s() * scale,
+            rng.next_f32_s() * scale,
+            rng.next_f32_s() * scale,
+        )
+    }
+
+    fn random_boxes(rng: &mut Pcg64, count: usize) -> Vec<Aabb3> {
+        (0..count)
+            .map(|_| {
+                let center = random_point(rng, 10.0);
+                let half_extents = vec3(rng.next_f32(), rng.next_f32(), rng.next_f32()) * 0.5;
+                Aabb3::from_center_half_extents(center, half_extents)
+            })
+            .collect()
+    }
+
+    /// Checks every node bounds its children and every primitive is referenced
+    /// by exactly one leaf.
+    fn validate(bvh: &Bvh, bounds: &[Aabb3]) {
+        let mut seen = vec![false; bounds.len()];
+        for node in bvh.nodes() {
+            let first = node.first as usize;
+            if node.is_leaf() {
+                for &i in &bvh.indices()[first..first + node.count as usize] {
+                    assert!(!seen[i as usize]);
+                    seen[i as usize] = true;
+                    assert_eq!(Aabb3::union(node.aabb, bounds[i as usize]), node.aabb);
+                }
+            } else {
+                let children = Aabb3::union(bvh.nodes()[first].aabb, bvh.nodes()[first + 1].aabb);
+                assert_eq!(children, node.aabb);
+            }
+        }
+        assert!(seen.iter().all(|&x| x));
+    }
+
+    #[test]
+    fn build() {
+        assert!(Bvh::build(&[]).is_empty());
+        assert_eq!(
+            Bvh::build(&[]).raycast(&Ray3::new(Point3::ZERO, Vec3::X), f32::INFINITY, |_| Some(
+                0.0
+            )),
+            None
+        );
+
+        let mut rng = Pcg64::new();
+        for count in [1, 2, 7, 100, 1000] {
+            let bounds = random_boxes(&mut rng, count);
+            let bvh = Bvh::build(&bounds);
+            validate(&bvh, &bounds);
+        }
+
+        // Coincident primitives can't be split, and end up in a single leaf.
+        let bounds = vec![Aabb3::new(Point3::ZERO, Point3::ONE); 100];
+        let bvh = Bvh::build(&bounds);
+        validate(&bvh, &bounds);
+        assert_eq!(bvh.nodes().len(), 1);
+    }
+
+    #[test]
+    fn build_non_finite() {
+        let mut rng = Pcg64::new();
+        let mut bounds = random_boxes(&mut rng, 200);
+        for (i, aabb) in bounds.iter_mut().enumerate().step_by(3) {
+            *aabb = match i % 4 {
+                0 => Aabb3::new(Point3::splat(f32::NAN), Point3::splat(f32::NAN)),
+                1 => Aabb3::new(
+                    Point3::splat(f32::NEG_INFINITY),
+                    Point3::splat(f32::INFINITY),
+                ),
+                2 => Aabb3::new(Point3::ZERO, Point3::splat(f32::INFINITY)),
+                _ => Aabb3::new(Point3::splat(f32::NEG_INFINITY), Point3::ZERO),
+            };
+        }
+        let bvh = Bvh::build(&bounds);
+
+        // Every child must be a non-empty leaf or an internal node, and every
+        // primitive must be referenced by exactly one leaf.
+        let mut seen = vec![false; bounds.len()];
+        let mut stack = vec![0];
+        while let Some(index) = stack.pop() {
+            let node = bvh.nodes()[index];
+            let first = node.first as usize;
+            if node.is_leaf() {
+                for &i in &bvh.indices()[first..first + node.count as usize] {
+                    assert!(!seen[i as usize]);
+                    seen[i as usize] = true;
+                }
+            } else {
+                assert!(first > index && first + 1 < bvh.nodes().len());
+                stack.extend([first, first + 1]);
+            }
+        }
+        assert!(seen.iter().all(|&x| x));
+    }
+
+    #[test]
+    fn queries() {
+        let mut rng = Pcg64::new();
+        let mut bounds = random_boxes(&mut rng, 500);
+        let mut bvh = Bvh::build(&bounds);
+
+        for refit in [false, true] {
+            if refit {
+                for aabb in &mut bounds {
+                    let offset = random_point(&mut rng, 1.0).as_vec3();
+                    *aabb = Aabb3::new(aabb.min + offset, aabb.max + offset);
+                }
+                bvh.refit(&bounds);
+                validate(&bvh, &bounds);
+            }
+
+            for _ in 0..100 {
+                let ray = Ray3::new(
+                    random_point(&mut rng, 15.0),
+                    random_point(&mut rng, 1.0).as_vec3(),
+                );
+                let intersect = |i: u32| ray.intersect_aabb3(&bounds[i as usize]);
+                let expected = (0..bounds.len() as u32)
+                    .filter_map(|i| Some((i, intersect(i)?)))
+                    .min_by(|a, b| a.1.total_cmp(&b.1));
+                let hit = bvh.raycast(&ray, f32::INFINITY, intersect);
+                assert_eq!(hit.map(|hit| hit.1), expected.map(|hit| hit.1));
+
+                let query =
+                    Aabb3::from_center_half_extents(random_point(&mut rng, 10.0), Vec3::splat(2.0));
+                let mut found = vec![];
+                bvh.query_aabb(&query, |i| {
+                    if Aabb3::overlaps(&query, &bounds[i as usize]) {
+                        found.push(i)
+                    }
+                });
+                found.sort();
+                let expected = (0..bounds.len() as u32)
+                    .filter(|&i| Aabb3::overlaps(&query, &bounds[i as usize]))
+                    .collect::<Vec<_>>();
+                assert_eq!(found, expected);
+
+                let point = random_point(&mut rng, 15.0);
+                let distance_sq = |i: u32| {
+                    let aabb = &bounds[i as usize];
+                    Point3::distance_sq(point, aabb.closest_point(point))
+                };
+                let expected = (0..bounds.len() as u32)
+                    .map(distance_sq)
+                    .min_by(f32::total_cmp);
+                let nearest = bvh.nearest(point, f32::INFINITY, distance_sq);
+                assert_eq!(nearest.map(|x| x.1), expected);
+                let expected = expected.unwrap();
+                if expected > 0.0 {
+                    assert_eq!(bvh.nearest(point, expected * 0.5, distance_sq), None);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn triangles() {
+        // Closed unit cube made of twelve triangles.
+        let positions = (0..8)
+            .map(|i| point3((i & 1) as f32, ((i >> 1) & 1) as f32, ((i >> 2) & 1) as f32))
+            .collect::<Vec<_>>();
+        let indices: [u32; 36] = [
+            0, 2, 1, 1, 2, 3, 4, 5, 6, 5, 7, 6, 0, 1, 4, 1, 5, 4, 2, 6, 3, 3, 6, 7, 0, 4, 2, 2, 4,
+            6, 1, 3, 5, 3, 7, 5,
+        ];
+        let bvh = TriangleBvh::new(&positions, &indices);
+        assert_eq!(bvh.triangles().len(), 12);
+
+        let ray = Ray3::new(point3(0.25, 0.5, 5.0), vec3(0.0, 0.0, -2.0));
+        let (triangle, t) = bvh.raycast(&ray, f32::INFINITY).unwrap();
+        assert_eq!(t, 2.0);
+        assert!(
+            bvh.triangles()[triangle as usize]
+                .iter()
+                .all(|p| p.z == 1.0)
+        );
+        assert_eq!(bvh.raycast(&ray, 1.0), None);
+
+        // Inside the cube the nearest hit is the far face.
+        let ray = Ray3::new(point3(0.5, 0.5, 0.5), vec3(0.0, 1.0, 0.0));
+        assert_eq!(bvh.raycast(&ray, f32::INFINITY).unwrap().1, 0.5);
+
+        let (_, p) = bvh.closest_point(point3(0.5, 3.0, 0.25)).unwrap();
+        assert_eq!(p, point3(0.5, 1.0, 0.25));
+        let (_, p) = bvh.closest_point(point3(2.0, -1.0, 0.5)).unwrap();
+        assert_eq!(p, point3(1.0, 0.0, 0.5));
+
+        let mut count = 0;
+        bvh.query_aabb(
+            &Aabb3::new(point3(0.9, 0.9, 0.9), point3(2.0, 2.0, 2.0)),
+            |_| count += 1,
+        );
+        // Both triangles of the three faces touching the corner.
+        assert_eq!(count, 6);
+    }
+
+    #[test]
+    fn closest_point_on_triangle() {
+        let [a, b, c] = [
+            point3(0.0, 0.0, 0.0),
+            point3(2.0, 0.0, 0.0),
+            point3(0.0, 2.0, 0.0),
+        ];
+        assert_eq!(closest_point_triangle(point3(-1.0, -1.0, 1.0), a, b, c), a);
+        assert_eq!(closest_point_triangle(point3(3.0, -1.0, 0.0), a, b, c), b);
+        assert_eq!(closest_point_triangle(point3(-1.0, 3.0, 0.0), a, b, c), c);
+        assert_eq!(
+            closest_point_triangle(point3(1.0, -1.0, 0.0), a, b, c),
+            point3(1.0, 0.0, 0.0)
+        );
+        assert_eq!(
+            closest_point_triangle(point3(-1.0, 1.0, 0.0), a, b, c),
+            point3(0.0, 1.0, 0.0)
+        );
+        assert_eq!(
+            closest_point_triangle(point3(2.0, 2.0, 0.0), a, b, c),
+            point3(1.0, 1.0, 0.0)
+        );
+        assert_eq!(
+            closest_point_triangle(point3(0.5, 0.5, 3.0), a, b, c),
+            point3(0.5, 0.5, 0.0)
+        );
+    }
+}
@@ -0,0 +1,476 @@
+use crate::{Vec4, lerp, pow_f32};
+
+/// Converts an sRGB encoded value to linear light using the exact piecewise
+/// sRGB transfer function.
+///
+/// # Examples
+///
+/// ```
+/// use narcissus_maths::srgb_to_linear_f32;
+/// assert_eq!(srgb_to_linear_f32(0.0), 0.0);
+/// assert_eq!(srgb_to_linear_f32(1.0), 1.0);
+/// ```
+#[inline]
+pub fn srgb_to_linear_f32(x: f32) -> f32 {
+    if x <= 0.04045 {
+        x * (1.0 / 12.92)
+    } else {
+        // (x + 0.055) / 1.055, arranged so 1.0 maps to exactly 1.0.
+        pow_f32((1.0 - x).mul_add(0.055 / 1.055, x), 2.4)
+    }
+}
+
+/// Converts a linear light value to sRGB encoding using the exact piecewise
+/// sRGB transfer function.
+///
+/// # Examples
+///
+/// ```
+/// use narcissus_maths::linear_to_srgb_f32;
+/// assert_eq!(linear_to_srgb_f32(0.0), 0.0);
+/// assert_eq!(linear_to_srgb_f32(1.0), 1.0);
+/// ```
+#[inline]
+pub fn linear_to_srgb_f32(x: f32) -> f32 {
+    if x <= 0.0031308 {
+        x * 12.92
+    } else {
+        // 1.055 p - 0.055, arranged so 1.0 maps to exactly 1.0.
+        let p = pow_f32(x, 1.0 / 2.4);
+        0.055_f32.mul_add(p - 1.0, p)
+    }
+}
+
+/// Approximates [`srgb_to_linear_f32`] with a quintic polynomial for `x` on
+/// \[0,1\].
+///
+/// Relative error <= 0.00037.
+#[inline(always)]
+pub fn srgb_to_linear_fast_f32(x: f32) -> f32 {
+    if x <= 0.04045 {
+        x * (1.0 / 12.92)
+    } else {
+        let p = 0.116_591_57_f32; // 0x1.dd8f2p-4
+        let p = p.mul_add(x, -0.368_959_16); // -0x1.79d06ep-2
+        let p = p.mul_add(x, 0.710_378_35); // 0x1.6bb6b6p-1
+        let p = p.mul_add(x, 0.507_550_54); // 0x1.03ddaap-1
+        let p = p.mul_add(x, 0.033_922_795); // 0x1.15e542p-5
+        p.mul_add(x, 0.000_883_285) // 0x1.cf1882p-11
+    }
+}
+
+/// Approximates [`linear_to_srgb_f32`] with a weighted sum of the square,
+/// fourth and eighth roots for `x` on \[0,1\].
+///
+/// Absolute error <= 0.00157, less than half of an 8 bit step.
+#[inline(always)]
+pub fn linear_to_srgb_fast_f32(x: f32) -> f32 {
+    if x <= 0.0031308 {
+        x * 12.92
+    } else {
+        let s1 = x.sqrt();
+        let s2 = s1.sqrt();
+        let s3 = s2.sqrt();
+        0.585_122_4 * s1 + 0.783_140_35 * s2 - 0.368_262_74 * s3
+    }
+}
+
+/// Quantizes `x` on \[0,1\] to an unsigned normalized integer with `bits` bits,
+/// rounding to nearest.
+#[inline(always)]
+fn pack_unorm(x: f32, bits: u32) -> u32 {
+    let max = ((1 << bits) - 1) as f32;
+    // Float to int casts saturate, and convert NaN to zero.
+    (x.clamp(0.0, 1.0) * max + 0.5) as u32
+}
+
+/// Expands an 8 bit unsigned normalized integer to a float on \[0,1\].
+#[inline(always)]
+fn unpack_unorm8(x: u32) -> f32 {
+    (x & 0xff) as f32 / 255.0
+}
+
+macro_rules! impl_rgba {
+    ($name:ty) => {
+        impl $name {
+            /// Constructs a new color from the given components.
+            #[inline(always)]
+            #[must_use]
+            pub const fn new(r: f32, g: f32, b: f32, a: f32) -> Self {
+                Self { r, g, b, a }
+            }
+
+            /// Constructs a new opaque color from the given components.
+            #[inline(always)]
+            #[must_use]
+            pub const fn rgb(r: f32, g: f32, b: f32) -> Self {
+                Self { r, g, b, a: 1.0 }
+            }
+
+            /// Returns a copy of `self` with alpha replaced by `a`.
+            #[inline(always)]
+            #[must_use]
+            pub const fn with_alpha(self, a: f32) -> Self {
+                Self { a, ..self }
+            }
+
+            /// Packs the color into the memory layout of the `RGBA8` image
+            /// formats, with red in the lowest byte.
+            #[inline(always)]
+            #[must_use]
+            pub fn to_rgba8(self) -> u32 {
+                pack_unorm(self.r, 8)
+                    | pack_unorm(self.g, 8) << 8
+                    | pack_unorm(self.b, 8) << 16
+                    | pack_unorm(self.a, 8) << 24
+            }
+
+            /// Packs the color into the memory layout of the `BGRA8` image
+            /// formats, with blue in the lowest byte.
+            #[inline(always)]
+            #[must_use]
+            pub fn to_bgra8(self) -> u32 {
+                pack_unorm(self.b, 8)
+                    | pack_unorm(self.g, 8) << 8
+                    | pack_unorm(self.r, 8) << 16
+                    | pack_unorm(self.a, 8) << 24
+            }
+
+            /// Packs the color into the memory layout of the `A2R10G10B10`
+            /// image format, with blue in the lowest bits.
+            #[inline(always)]
+            #[must_use]
+            pub fn to_a2r10g10b10(self) -> u32 {
+                pack_unorm(self.b, 10)
+                    | pack_unorm(self.g, 10) << 10
+                    | pack_unorm(self.r, 10) << 20
+                    | pack_unorm(self.a, 2) << 30
+            }
+
+            /// Unpacks a color from the memory layout of the `RGBA8` image
+            /// formats.
+            #[inline(always)]
+            #[must_use]
+            pub fn from_rgba8(x: u32) -> Self {
+                Self {
+                    r: unpack_unorm8(x),
+                    g: unpack_unorm8(x >> 8),
+                    b: unpack_unorm8(x >> 16),
+                    a: unpack_unorm8(x >> 24),
+                }
+            }
+
+            /// Unpacks a color from the memory layout of the `BGRA8` image
+            /// formats.
+            #[inline(always)]
+            #[must_use]
+            pub fn from_bgra8(x: u32) -> Self {
+                Self {
+                    r: unpack_unorm8(x >> 16),
+                    g: unpack_unorm8(x >> 8),
+                    b: unpack_unorm8(x),
+                    a: unpack_unorm8(x >> 24),
+                }
+            }
+
+            /// Returns a color with alpha multiplied into the color components.
+            #[inline(always)]
+            #[must_use]
+            pub fn premultiply(self) -> Self {
+                Self {
+                    r: self.r * self.a,
+                    g: self.g * self.a,
+                    b: self.b * self.a,
+                    a: self.a,
+                }
+            }
+
+            /// Returns a color with premultiplied alpha divided out of the
+            /// color components.
+            ///
+            /// Fully transparent colors return transparent black.
+            #[inline(always)]
+            #[must_use]
+            pub fn unpremultiply(self) -> Self {
+                if self.a == 0.0 {
+                    return Self::new(0.0, 0.0, 0.0, 0.0);
+                }
+                let inv_a = 1.0 / self.a;
+                Self {
+                    r: self.r * inv_a,
+                    g: self.g * inv_a,
+                    b: self.b * inv_a,
+                    a: self.a,
+                }
+            }
+
+            /// Linearly interpolates each component between `a` and `b` using
+            /// `t` as a control value.
+            #[inline(always)]
+            #[must_use]
+            pub fn lerp(t: f32, a: Self, b: Self) -> Self {
+                Self {
+                    r: lerp(t, a.r, b.r),
+                    g: lerp(t, a.g, b.g),
+                    b: lerp(t, a.b, b.b),
+                    a: lerp(t, a.a, b.a),
+                }
+            }
+        }
+
+        impl From<Vec4> for $name {
+            #[inline(always)]
+            fn from(v: Vec4) -> Self {
+                Self::new(v.x, v.y, v.z, v.w)
+            }
+        }
+
+        impl From<$name> for Vec4 {
+            #[inline(always)]
+            fn from(c: $name) -> Self {
+                Vec4::new(c.r, c.g, c.b, c.a)
+            }
+        }
+
+        impl From<[f32; 4]> for $name {
+            #[inline(always)]
+            fn from([r, g, b, a]: [f32; 4]) -> Self {
+                Self::new(r, g, b, a)
+            }
+        }
+
+        impl From<$name> for [f32; 4] {
+            #[inline(always)]
+            fn from(c: $name) -> Self {
+                [c.r, c.g, c.b, c.a]
+            }
+        }
+    };
+}
+
+/// Color with sRGB encoded color components and linear alpha.
+///
+/// Packing stores the encoded components directly, as expected by the `_SRGB`
+/// image formats, or by shaders that work in sRGB space.
+#[derive(Clone, Copy, PartialEq, Default, Debug)]
+#[repr(C)]
+pub struct Srgba {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32,
+}
+
+impl_rgba!(Srgba);
+
+impl Srgba {
+    pub const TRANSPARENT: Srgba = Srgba::new(0.0, 0.0, 0.0, 0.0);
+    pub const BLACK: Srgba = Srgba::rgb(0.0, 0.0, 0.0);
+    pub const WHITE: Srgba = Srgba::rgb(1.0, 1.0, 1.0);
+
+    /// Constructs a color from a hex code in `0xRRGGBBAA` order, as colors are
+    /// usually written in CSS or design tools.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use narcissus_maths::Srgba;
+    /// let color = Srgba::from_hex(0xff8000ff);
+    /// assert_eq!(color.to_bgra8(), 0xffff8000);
+    /// assert_eq!(color.to_rgba8(), 0xff0080ff);
+    /// ```
+    #[inline(always)]
+    #[must_use]
+    pub const fn from_hex(x: u32) -> Srgba {
+        Srgba {
+            r: (x >> 24) as f32 / 255.0,
+            g: ((x >> 16) & 0xff) as f32 / 255.0,
+            b: ((x >> 8) & 0xff) as f32 / 255.0,
+            a: (x & 0xff) as f32 / 255.0,
+        }
+    }
+
+    /// Converts to linear color components using [`srgb_to_linear_f32`].
+    #[inline]
+    #[must_use]
+    pub fn to_linear(self) -> LinearRgba {
+        LinearRgba {
+            r: srgb_to_linear_f32(self.r),
+            g: srgb_to_linear_f32(self.g),
+            b: srgb_to_linear_f32(self.b),
+            a: self.a,
+        }
+    }
+
+    /// Converts to linear color components using [`srgb_to_linear_fast_f32`].
+    #[inline(always)]
+    #[must_use]
+    pub fn to_linear_fast(self) -> LinearRgba {
+        LinearRgba {
+            r: srgb_to_linear_fast_f32(self.r),
+            g: srgb_to_linear_fast_f32(self.g),
+            b: srgb_to_linear_fast_f32(self.b),
+            a: self.a,
+        }
+    }
+}
+
+/// Color with linear color components and linear alpha.
+///
+/// Blending, lighting and interpolation should happen in linear space. Packing
+/// stores the components directly, as expected by the `_UNORM` image formats.
+#[derive(Clone, Copy, PartialEq, Default, Debug)]
+#[repr(C)]
+pub struct LinearRgba {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32,
+}
+
+impl_rgba!(LinearRgba);
+
+impl LinearRgba {
+    pub const TRANSPARENT: LinearRgba = LinearRgba::new(0.0, 0.0, 0.0, 0.0);
+    pub const BLACK: LinearRgba = LinearRgba::rgb(0.0, 0.0, 0.0);
+    pub const WHITE: LinearRgba = LinearRgba::rgb(1.0, 1.0, 1.0);
+
+    /// Converts to sRGB encoded color components using [`linear_to_srgb_f32`].
+    #[inline]
+    #[must_use]
+    pub fn to_srgba(self) -> Srgba {
+        Srgba {
+            r: linear_to_srgb_f32(self.r),
+            g: linear_to_srgb_f32(self.g),
+            b: linear_to_srgb_f32(self.b),
+            a: self.a,
+        }
+    }
+
+    /// Converts to sRGB encoded color components using
+    /// [`linear_to_srgb_fast_f32`].
+    #[inline(always)]
+    #[must_use]
+    pub fn to_srgba_fast(self) -> Srgba {
+        Srgba {
+            r: linear_to_srgb_fast_f32(self.r),
+            g: linear_to_srgb_fast_f32(self.g),
+            b: linear_to_srgb_fast_f32(self.b),
+            a: self.a,
+        }
+    }
+
+    /// Returns the relative luminance of the color, as defined by Rec. 709.
+    #[inline(always)]
+    #[must_use]
+    pub fn luminance(self) -> f32 {
+        0.2126 * self.r + 0.7152 * self.g + 0.0722 * self.b
+    }
+}
+
+impl From<Srgba> for LinearRgba {
+    #[inline]
+    fn from(c: Srgba) -> Self {
+        c.to_linear()
+    }
+}
+
+impl From<LinearRgba> for Srgba {
+    #[inline]
+    fn from(c: LinearRgba) -> Self {
+        c.to_srgba()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        LinearRgba, Srgba, linear_to_srgb_f32, linear_to_srgb_fast_f32, srgb_to_linear_f32,
+        srgb_to_linear_fast_f32,
+    };
+
+    fn srgb_to_linear_f64(x: f64) -> f64 {
+        if x <= 0.04045 {
+            x / 12.92
+        } else {
+            ((x + 0.055) / 1.055).powf(2.4)
+        }
+    }
+
+    fn linear_to_srgb_f64(x: f64) -> f64 {
+        if x <= 0.0031308 {
+            x * 12.92
+        } else {
+            1.055 * x.powf(1.0 / 2.4) - 0.055
+        }
+    }
+
+    #[test]
+    fn transfer_matches_reference() {
+        for i in 0..=65536 {
+            let x = i as f32 / 65536.0;
+            let expected = srgb_to_linear_f64(x as f64);
+            assert!((srgb_to_linear_f32(x) as f64 - expected).abs() <= 4e-7 * expected);
+            assert!((srgb_to_linear_fast_f32(x) as f64 - expected).abs() <= 0.00037 * expected);
+
+            let expected = linear_to_srgb_f64(x as f64);
+            assert!((linear_to_srgb_f32(x) as f64 - expected).abs() <= 2.5e-7 * expected);
+            assert!((linear_to_srgb_fast_f32(x) as f64 - expected).abs() <= 0.00157);
+        }
+    }
+
+    #[test]
+    fn srgb8_round_trip() {
+        for i in 0..=255 {
+            let x = i as f32 / 255.0;
+            let exact = linear_to_srgb_f32(srgb_to_linear_f32(x));
+            assert_eq!((exact * 255.0).round() as u32, i);
+            let fast = linear_to_srgb_fast_f32(srgb_to_linear_fast_f32(x));
+            assert_eq!((fast * 255.0).round() as u32, i);
+        }
+    }
+
+    #[test]
+    fn packing() {
+        let c = Srgba::new(1.0, 0.5, 0.0, 0.25);
+        assert_eq!(c.to_rgba8(), 0x40_00_80_ff);
+        assert_eq!(c.to_bgra8(), 0x40_ff_80_00);
+        assert_eq!(c.to_a2r10g10b10(), 1 << 30 | 0x3ff << 20 | 512 << 10);
+        assert_eq!(Srgba::from_rgba8(0x40_00_80_ff).to_rgba8(), 0x40_00_80_ff);
+        assert_eq!(Srgba::from_bgra8(0x40_ff_80_00).to_rgba8(), 0x40_00_80_ff);
+        assert_eq!(
+            Srgba::from_hex(0xff_80_00_40),
+            Srgba::from_rgba8(0x40_00_80_ff)
+        );
+
+        // Out of range and NaN components clamp.
+        let c = LinearRgba::new(2.0, -1.0, f32::NAN, 1.0);
+        assert_eq!(c.to_rgba8(), 0xff_00_00_ff);
+
+        for i in 0..=255 {
+            let x = i * 0x01010101;
+            assert_eq!(LinearRgba::from_bgra8(x).to_bgra8(), x);
+        }
+    }
+
+    #[test]
+    fn premultiply() {
+        let c = LinearRgba::new(1.0, 0.5, 0.25, 0.5);
+        assert_eq!(c.premultiply(), LinearRgba::new(0.5, 0.25, 0.125, 0.5));
+        assert_eq!(c.premultiply().unpremultiply(), c);
+        assert_eq!(
+            LinearRgba::new(1.0, 1.0, 1.0, 0.0).unpremultiply(),
+            LinearRgba::TRANSPARENT
+        );
+    }
+
+    #[test]
+    fn srgb_linear_conversion() {
+        assert_eq!(Srgba::WHITE.to_linear(), LinearRgba::WHITE);
+        assert_eq!(LinearRgba::BLACK.to_srgba(), Srgba::BLACK);
+        let c = Srgba::from_hex(0x4292c6ff);
+        let round_trip = LinearRgba::from(c).to_srgba();
+        assert_eq!(round_trip.to_rgba8(), c.to_rgba8());
+        assert_eq!(round_trip.a, 1.0);
+    }
+}
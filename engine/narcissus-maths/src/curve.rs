@@ -0,0 +1,229 @@
+use std::ops::{Add, Mul, Sub};
+
+use crate::{Aabb2, Aabb3, Vec2, Vec3};
+
+/// Vector types that curves and splines can be built over.
+pub trait CurveVector:
+    Copy + Add<Output = Self> + Sub<Output = Self> + Mul<f32, Output = Self>
+{
+    /// Array with one element per component.
+    type Array: AsRef<[f32]> + AsMut<[f32]>;
+    /// Axis aligned bounding box with the same dimension as the vector.
+    type Aabb;
+
+    /// Returns the components of `self` as an array.
+    fn to_array(self) -> Self::Array;
+    /// Constructs a vector from an array of components.
+    fn from_array(array: Self::Array) -> Self;
+    /// Returns the dot product of `a` and `b`.
+    fn dot(a: Self, b: Self) -> f32;
+    /// Returns the component-wise minimum of `a` and `b`.
+    fn min(a: Self, b: Self) -> Self;
+    /// Returns the component-wise maximum of `a` and `b`.
+    fn max(a: Self, b: Self) -> Self;
+    /// Constructs a box with the given `min` and `max` corners.
+    fn aabb(min: Self, max: Self) -> Self::Aabb;
+
+    /// Linearly interpolates between `a` and `b` using `t` as a control value.
+    #[inline(always)]
+    fn mix(t: f32, a: Self, b: Self) -> Self {
+        a + (b - a) * t
+    }
+
+    /// Returns the squared length of `self`.
+    #[inline(always)]
+    fn length_sq(self) -> f32 {
+        Self::dot(self, self)
+    }
+
+    /// Returns the length of `self`.
+    #[inline(always)]
+    fn length(self) -> f32 {
+        self.length_sq().sqrt()
+    }
+}
+
+macro_rules! impl_curve_vector {
+    ($name:ty, $n:expr, $aabb:ty, $as_point:ident) => {
+        impl CurveVector for $name {
+            type Array = [f32; $n];
+            type Aabb = $aabb;
+
+            #[inline(always)]
+            fn to_array(self) -> Self::Array {
+                self.into()
+            }
+
+            #[inline(always)]
+            fn from_array(array: Self::Array) -> Self {
+                array.into()
+            }
+
+            #[inline(always)]
+            fn dot(a: Self, b: Self) -> f32 {
+                <$name>::dot(a, b)
+            }
+
+            #[inline(always)]
+            fn min(a: Self, b: Self) -> Self {
+                <$name>::min(a, b)
+            }
+
+            #[inline(always)]
+            fn max(a: Self, b: Self) -> Self {
+                <$name>::max(a, b)
+            }
+
+            #[inline(always)]
+            fn aabb(min: Self, max: Self) -> Self::Aabb {
+                <$aabb>::new(min.$as_point(), max.$as_point())
+            }
+        }
+    };
+}
+
+impl_curve_vector!(Vec2, 2, Aabb2, as_point2);
+impl_curve_vector!(Vec3, 3, Aabb3, as_point3);
+
+/// Parametric curve defined for `t` on \[0,1\].
+pub trait Curve {
+    type Vector: CurveVector;
+
+    /// Returns the position on the curve at `t`.
+    fn eval(&self, t: f32) -> Self::Vector;
+
+    /// Returns the first derivative of the curve with respect to `t`.
+    fn derivative(&self, t: f32) -> Self::Vector;
+}
+
+// Gauss-Legendre nodes on [-1,1] and weights.
+const GAUSS_LEGENDRE_5: [(f32, f32); 5] = [
+    (0.0, 0.568_888_9),
+    (-0.538_469_3, 0.478_628_67),
+    (0.538_469_3, 0.478_628_67),
+    (-0.906_179_85, 0.236_926_88),
+    (0.906_179_85, 0.236_926_88),
+];
+
+/// Table mapping distance along a curve to the curve parameter, for moving
+/// along a curve at a constant speed.
+///
+/// # Examples
+///
+/// ```
+/// use narcissus_maths::{ArcLength, CubicBezier, Curve, vec2};
+/// let curve = CubicBezier::new(
+///     vec2(0.0, 0.0),
+///     vec2(0.0, 1.0),
+///     vec2(1.0, 1.0),
+///     vec2(1.0, 0.0),
+/// );
+/// let arc_length = ArcLength::new(&curve, 32);
+/// let midpoint = curve.eval(arc_length.parameter(arc_length.length() * 0.5));
+/// assert!((midpoint.x - 0.5).abs() < 1e-5);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ArcLength {
+    /// Cumulative length at `t = i / (lengths.len() - 1)`.
+    lengths: Vec<f32>,
+}
+
+impl ArcLength {
+    /// Builds a table with `samples` uniformly spaced intervals, integrating
+    /// the speed of `curve` over each interval.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `samples` is zero.
+    pub fn new<C: Curve>(curve: &C, samples: usize) -> ArcLength {
+        assert!(samples > 0);
+
+        let step = 1.0 / samples as f32;
+        let mut lengths = Vec::with_capacity(samples + 1);
+        let mut length = 0.0;
+        lengths.push(length);
+        for i in 0..samples {
+            let center = (i as f32 + 0.5) * step;
+            let half = 0.5 * step;
+            let mut sum = 0.0;
+            for (x, w) in GAUSS_LEGENDRE_5 {
+                sum += w * curve.derivative(x.mul_add(half, center)).length();
+            }
+            length += sum * half;
+            lengths.push(length);
+        }
+
+        ArcLength { lengths }
+    }
+
+    /// Returns the total length of the curve.
+    #[inline(always)]
+    pub fn length(&self) -> f32 {
+        *self.lengths.last().unwrap()
+    }
+
+    /// Returns the curve parameter at the given `distance` along the curve.
+    ///
+    /// Distances outside of \[0,length\] are clamped. Within each interval the
+    /// parameter is interpolated linearly, so accuracy improves with the
+    /// number of samples.
+    pub fn parameter(&self, distance: f32) -> f32 {
+        let lengths = &self.lengths;
+        let samples = lengths.len() - 1;
+        if distance.is_nan() || distance <= 0.0 {
+            return 0.0;
+        }
+        if distance >= self.length() {
+            return 1.0;
+        }
+
+        // First index with a cumulative length greater than distance.
+        let i = lengths.partition_point(|&l| l <= distance);
+        let (l0, l1) = (lengths[i - 1], lengths[i]);
+        let f = (distance - l0) / (l1 - l0);
+        ((i - 1) as f32 + f) / samples as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ArcLength, CubicBezier, Curve, QuadraticBezier, vec2, vec3};
+
+    #[test]
+    fn straight_line() {
+        let curve = QuadraticBezier::new(
+            vec3(0.0, 0.0, 0.0),
+            vec3(1.0, 2.0, 2.0),
+            vec3(2.0, 4.0, 4.0),
+        );
+        let arc_length = ArcLength::new(&curve, 4);
+        assert!((arc_length.length() - 6.0).abs() < 1e-5);
+        assert_eq!(arc_length.parameter(-1.0), 0.0);
+        assert_eq!(arc_length.parameter(f32::NAN), 0.0);
+        assert_eq!(arc_length.parameter(7.0), 1.0);
+        for i in 0..=16 {
+            let t = i as f32 / 16.0;
+            assert!((arc_length.parameter(t * 6.0) - t).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn quarter_circle() {
+        // Cubic approximation of a unit quarter circle.
+        const K: f32 = 0.552_284_8;
+        let curve = CubicBezier::new(vec2(1.0, 0.0), vec2(1.0, K), vec2(K, 1.0), vec2(0.0, 1.0));
+        let arc_length = ArcLength::new(&curve, 16);
+        let expected = std::f32::consts::FRAC_PI_2;
+        assert!((arc_length.length() - expected).abs() < 1e-3);
+
+        // Constant speed samples should be evenly spaced along the arc.
+        let mut prev = vec2(1.0, 0.0);
+        for i in 1..=8 {
+            let t = arc_length.parameter(arc_length.length() * i as f32 / 8.0);
+            let p = curve.eval(t);
+            let chord = (p - prev).length();
+            assert!((chord - 2.0 * (expected / 16.0).sin()).abs() < 1e-3);
+            prev = p;
+        }
+    }
+}
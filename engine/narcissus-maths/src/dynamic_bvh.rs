@@ -0,0 +1,613 @@
+use narcissus_core::Widen;
+
+use crate::bvh::{BvhTree, nearest, query_aabb, raycast};
+use crate::{Aabb3, Point3, Ray3, Vec3};
+
+const NULL: u32 = u32::MAX;
+
+/// Handle to a box stored in a [`DynamicBvh`].
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct BvhProxy(u32);
+
+impl BvhProxy {
+    /// Returns the index of the leaf node holding this proxy.
+    #[inline(always)]
+    pub fn node_index(self) -> u32 {
+        self.0
+    }
+}
+
+/// Node of a [`DynamicBvh`], laid out for upload to the GPU.
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[repr(C)]
+pub struct DynamicBvhNode {
+    pub aabb: Aabb3,
+    /// Index of the parent node, or `u32::MAX` for the root.
+    ///
+    /// For nodes in the free list, the index of the next free node instead.
+    pub parent: u32,
+    /// Indices of the two child nodes, or `u32::MAX` for leaves.
+    pub children: [u32; 2],
+    /// Height of the subtree, where leaves are zero and free nodes are
+    /// `u32::MAX`.
+    pub height: u32,
+}
+
+impl DynamicBvhNode {
+    #[inline(always)]
+    pub fn is_leaf(&self) -> bool {
+        self.children[0] == NULL
+    }
+}
+
+/// Bounding volume hierarchy over boxes which can be inserted, moved and
+/// removed individually, for large numbers of moving objects.
+///
+/// Each leaf holds a single box, enlarged by a `margin` so that small
+/// movements don't require updating the tree. Insertion uses the surface area
+/// heuristic to choose a sibling, and tree rotations keep the tree balanced.
+///
+/// Erin Catto, "Dynamic Bounding Volume Hierarchies", GDC 2019.
+///
+/// # Examples
+///
+/// ```
+/// use narcissus_maths::{Aabb3, DynamicBvh, point3};
+/// let mut bvh = DynamicBvh::new(0.1);
+/// let a = bvh.insert(Aabb3::new(point3(0.0, 0.0, 0.0), point3(1.0, 1.0, 1.0)));
+/// let b = bvh.insert(Aabb3::new(point3(5.0, 0.0, 0.0), point3(6.0, 1.0, 1.0)));
+/// // Small movements stay within the enlarged box.
+/// assert!(!bvh.update(a, Aabb3::new(point3(0.05, 0.0, 0.0), point3(1.05, 1.0, 1.0))));
+///
+/// let mut hits = vec![];
+/// bvh.query_aabb(&Aabb3::new(point3(4.0, 0.0, 0.0), point3(5.5, 0.5, 0.5)), |proxy| {
+///     hits.push(proxy)
+/// });
+/// assert_eq!(hits, [b]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct DynamicBvh {
+    nodes: Vec<DynamicBvhNode>,
+    root: u32,
+    free: u32,
+    len: usize,
+    margin: f32,
+}
+
+impl Default for DynamicBvh {
+    fn default() -> Self {
+        Self::new(0.0)
+    }
+}
+
+impl DynamicBvh {
+    /// Constructs a new, empty hierarchy, which enlarges boxes by `margin` on
+    /// every side.
+    pub fn new(margin: f32) -> DynamicBvh {
+        DynamicBvh {
+            nodes: Vec::new(),
+            root: NULL,
+            free: NULL,
+            len: 0,
+            margin,
+        }
+    }
+
+    /// Returns the number of proxies in the hierarchy.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the hierarchy contains no proxies.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the nodes of the hierarchy, including any free nodes.
+    #[inline(always)]
+    pub fn nodes(&self) -> &[DynamicBvhNode] {
+        &self.nodes
+    }
+
+    /// Returns the index of the root node, or `None` if the hierarchy is
+    /// empty.
+    #[inline(always)]
+    pub fn root(&self) -> Option<u32> {
+        (self.root != NULL).then_some(self.root)
+    }
+
+    /// Returns the height of the tree, which is zero for a single leaf.
+    #[inline(always)]
+    pub fn height(&self) -> u32 {
+        self.root()
+            .map_or(0, |root| self.nodes[root.widen()].height)
+    }
+
+    /// Returns the enlarged box stored for `proxy`.
+    #[inline(always)]
+    pub fn aabb(&self, proxy: BvhProxy) -> &Aabb3 {
+        &self.nodes[proxy.0.widen()].aabb
+    }
+
+    /// Inserts `aabb` into the hierarchy, returning a proxy which identifies
+    /// it in queries.
+    pub fn insert(&mut self, aabb: Aabb3) -> BvhProxy {
+        let leaf = self.allocate_node();
+        self.nodes[leaf.widen()].aabb = self.fatten(aabb);
+        self.insert_leaf(leaf);
+        self.len += 1;
+        BvhProxy(leaf)
+    }
+
+    /// Removes `proxy` from the hierarchy.
+    ///
+    /// The proxy may be reused by later insertions.
+    pub fn remove(&mut self, proxy: BvhProxy) {
+        let leaf = proxy.0;
+        debug_assert!(self.nodes[leaf.widen()].is_leaf());
+        self.remove_leaf(leaf);
+        self.free_node(leaf);
+        self.len -= 1;
+    }
+
+    /// Moves `proxy` to `aabb`, reinserting it with a new enlarged box only if
+    /// `aabb` is no longer contained by the current one.
+    ///
+    /// Returns `true` if the proxy was reinserted.
+    pub fn update(&mut self, proxy: BvhProxy, aabb: Aabb3) -> bool {
+        let leaf = proxy.0;
+        let fat = &self.nodes[leaf.widen()].aabb;
+        if Aabb3::union(*fat, aabb) == *fat {
+            return false;
+        }
+        self.remove_leaf(leaf);
+        self.nodes[leaf.widen()].aabb = self.fatten(aabb);
+        self.insert_leaf(leaf);
+        true
+    }
+
+    /// Moves `proxy` to `aabb` and updates the bounds of its ancestors,
+    /// without changing the structure of the tree.
+    ///
+    /// This is cheaper than [`DynamicBvh::update`] when many proxies move
+    /// coherently, but the tree quality degrades as they move further from
+    /// where they were inserted.
+    pub fn refit(&mut self, proxy: BvhProxy, aabb: Aabb3) {
+        let leaf = proxy.0;
+        self.nodes[leaf.widen()].aabb = self.fatten(aabb);
+        let mut index = self.nodes[leaf.widen()].parent;
+        while index != NULL {
+            let [a, b] = self.nodes[index.widen()].children;
+            let aabb = Aabb3::union(self.nodes[a.widen()].aabb, self.nodes[b.widen()].aabb);
+            self.nodes[index.widen()].aabb = aabb;
+            index = self.nodes[index.widen()].parent;
+        }
+    }
+
+    /// Removes all proxies from the hierarchy.
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.root = NULL;
+        self.free = NULL;
+        self.len = 0;
+    }
+
+    /// Returns the nearest proxy hit by `ray` with a ray parameter no greater
+    /// than `t_max`, along with the ray parameter of the hit.
+    ///
+    /// `intersect` is called with proxies whose enlarged box is hit by the
+    /// ray, and returns the ray parameter at which the ray hits the object
+    /// itself, if any.
+    pub fn raycast<F>(&self, ray: &Ray3, t_max: f32, mut intersect: F) -> Option<(BvhProxy, f32)>
+    where
+        F: FnMut(BvhProxy) -> Option<f32>,
+    {
+        raycast(self, ray, t_max, |leaf| intersect(BvhProxy(leaf)))
+            .map(|(leaf, t)| (BvhProxy(leaf), t))
+    }
+
+    /// Calls `f` with every proxy whose enlarged box overlaps `aabb`.
+    pub fn query_aabb<F>(&self, aabb: &Aabb3, mut f: F)
+    where
+        F: FnMut(BvhProxy),
+    {
+        query_aabb(self, aabb, |leaf| f(BvhProxy(leaf)))
+    }
+
+    /// Returns the proxy nearest to `point` within a squared distance of
+    /// `max_distance_sq`, along with its squared distance.
+    ///
+    /// `distance_sq` is called with candidate proxies, and returns the squared
+    /// distance from `point` to the object itself.
+    pub fn nearest<F>(
+        &self,
+        point: Point3,
+        max_distance_sq: f32,
+        mut distance_sq: F,
+    ) -> Option<(BvhProxy, f32)>
+    where
+        F: FnMut(BvhProxy) -> f32,
+    {
+        nearest(self, point, max_distance_sq, |leaf| {
+            distance_sq(BvhProxy(leaf))
+        })
+        .map(|(leaf, d)| (BvhProxy(leaf), d))
+    }
+
+    #[inline(always)]
+    fn fatten(&self, aabb: Aabb3) -> Aabb3 {
+        let margin = Vec3::splat(self.margin);
+        Aabb3::new(aabb.min - margin, aabb.max + margin)
+    }
+
+    fn allocate_node(&mut self) -> u32 {
+        let node = DynamicBvhNode {
+            aabb: Aabb3::EMPTY,
+            parent: NULL,
+            children: [NULL; 2],
+            height: 0,
+        };
+        if self.free != NULL {
+            let index = self.free;
+            self.free = self.nodes[index.widen()].parent;
+            self.nodes[index.widen()] = node;
+            index
+        } else {
+            let index = u32::try_from(self.nodes.len()).expect("too many nodes");
+            assert!(index != NULL, "too many nodes");
+            self.nodes.push(node);
+            index
+        }
+    }
+
+    fn free_node(&mut self, index: u32) {
+        let node = &mut self.nodes[index.widen()];
+        node.parent = self.free;
+        node.height = u32::MAX;
+        self.free = index;
+    }
+
+    fn insert_leaf(&mut self, leaf: u32) {
+        if self.root == NULL {
+            self.root = leaf;
+            self.nodes[leaf.widen()].parent = NULL;
+            return;
+        }
+
+        // Descend towards the sibling with the lowest cost, where the cost of
+        // pairing with a node is the area of the new parent plus the increase
+        // in area of every ancestor.
+        let leaf_aabb = self.nodes[leaf.widen()].aabb;
+        let mut index = self.root;
+        while !self.nodes[index.widen()].is_leaf() {
+            let node = &self.nodes[index.widen()];
+            let area = node.aabb.surface_area();
+            let combined_area = Aabb3::union(node.aabb, leaf_aabb).surface_area();
+
+            let cost = 2.0 * combined_area;
+            let inheritance_cost = 2.0 * (combined_area - area);
+
+            let child_cost = |child: u32| {
+                let child = &self.nodes[child.widen()];
+                let area = Aabb3::union(child.aabb, leaf_aabb).surface_area();
+                if child.is_leaf() {
+                    area + inheritance_cost
+                } else {
+                    area - child.aabb.surface_area() + inheritance_cost
+                }
+            };
+            let [a, b] = node.children;
+            let (cost_a, cost_b) = (child_cost(a), child_cost(b));
+
+            if cost < cost_a && cost < cost_b {
+                break;
+            }
+            index = if cost_a < cost_b { a } else { b };
+        }
+
+        let sibling = index;
+        let old_parent = self.nodes[sibling.widen()].parent;
+        let new_parent = self.allocate_node();
+        self.nodes[new_parent.widen()] = DynamicBvhNode {
+            aabb: Aabb3::union(leaf_aabb, self.nodes[sibling.widen()].aabb),
+            parent: old_parent,
+            children: [sibling, leaf],
+            height: self.nodes[sibling.widen()].height + 1,
+        };
+        self.nodes[sibling.widen()].parent = new_parent;
+        self.nodes[leaf.widen()].parent = new_parent;
+
+        if old_parent == NULL {
+            self.root = new_parent;
+        } else {
+            self.replace_child(old_parent, sibling, new_parent);
+        }
+
+        self.fix_upwards(new_parent);
+    }
+
+    fn remove_leaf(&mut self, leaf: u32) {
+        if leaf == self.root {
+            self.root = NULL;
+            return;
+        }
+
+        let parent = self.nodes[leaf.widen()].parent;
+        let grandparent = self.nodes[parent.widen()].parent;
+        let [a, b] = self.nodes[parent.widen()].children;
+        let sibling = if a == leaf { b } else { a };
+
+        self.nodes[sibling.widen()].parent = grandparent;
+        self.free_node(parent);
+        if grandparent == NULL {
+            self.root = sibling;
+        } else {
+            self.replace_child(grandparent, parent, sibling);
+            self.fix_upwards(grandparent);
+        }
+    }
+
+    #[inline(always)]
+    fn replace_child(&mut self, parent: u32, old_child: u32, new_child: u32) {
+        let children = &mut self.nodes[parent.widen()].children;
+        if children[0] == old_child {
+            children[0] = new_child;
+        } else {
+            debug_assert!(children[1] == old_child);
+            children[1] = new_child;
+        }
+    }
+
+    /// Rebalances and recomputes the bounds of `index` and all its ancestors.
+    fn fix_upwards(&mut self, mut index: u32) {
+        while index != NULL {
+            index = self.balance(index);
+            let [a, b] = self.nodes[index.widen()].children;
+            let (a, b) = (&self.nodes[a.widen()], &self.nodes[b.widen()]);
+            let aabb = Aabb3::union(a.aabb, b.aabb);
+            let height = 1 + a.height.max(b.height);
+            let node = &mut self.nodes[index.widen()];
+            node.aabb = aabb;
+            node.height = height;
+            index = node.parent;
+        }
+    }
+
+    /// If the subtrees of `index` differ in height by more than one, rotates
+    /// the taller child up to replace it. Returns the index of the node now at
+    /// the root of the subtree.
+    fn balance(&mut self, a: u32) -> u32 {
+        let node_a = self.nodes[a.widen()];
+        if node_a.is_leaf() || node_a.height < 2 {
+            return a;
+        }
+
+        let [b, c] = node_a.children;
+        let height_b = self.nodes[b.widen()].height;
+        let height_c = self.nodes[c.widen()].height;
+
+        if height_c > height_b + 1 {
+            self.rotate_up(a, c, 1);
+            c
+        } else if height_b > height_c + 1 {
+            self.rotate_up(a, b, 0);
+            b
+        } else {
+            a
+        }
+    }
+
+    /// Rotates the child `x`, found at `slot` in `a`, up to take the place of
+    /// `a`, moving `a` down to become a child of `x` along with the taller of
+    /// the children of `x`. The shorter child of `x` takes the place of `x`
+    /// beneath `a`.
+    fn rotate_up(&mut self, a: u32, x: u32, slot: usize) {
+        let parent = self.nodes[a.widen()].parent;
+        let [f, g] = self.nodes[x.widen()].children;
+        let (tall, short) = if self.nodes[f.widen()].height > self.nodes[g.widen()].height {
+            (f, g)
+        } else {
+            (g, f)
+        };
+
+        // Swap x and a.
+        self.nodes[x.widen()].parent = parent;
+        self.nodes[a.widen()].parent = x;
+        if parent == NULL {
+            self.root = x;
+        } else {
+            self.replace_child(parent, a, x);
+        }
+
+        // x keeps its taller child and adopts a in place of the shorter one.
+        self.nodes[x.widen()].children = [a, tall];
+        self.nodes[a.widen()].children[slot] = short;
+        self.nodes[short.widen()].parent = a;
+
+        for node in [a, x] {
+            let [l, r] = self.nodes[node.widen()].children;
+            let (l, r) = (&self.nodes[l.widen()], &self.nodes[r.widen()]);
+            let aabb = Aabb3::union(l.aabb, r.aabb);
+            let height = 1 + l.height.max(r.height);
+            let node = &mut self.nodes[node.widen()];
+            node.aabb = aabb;
+            node.height = height;
+        }
+    }
+}
+
+impl BvhTree for DynamicBvh {
+    #[inline(always)]
+    fn root(&self) -> Option<u32> {
+        self.root()
+    }
+
+    #[inline(always)]
+    fn aabb(&self, node: u32) -> &Aabb3 {
+        &self.nodes[node.widen()].aabb
+    }
+
+    #[inline(always)]
+    fn children(&self, node: u32) -> Option<[u32; 2]> {
+        let node = &self.nodes[node.widen()];
+        (!node.is_leaf()).then_some(node.children)
+    }
+
+    #[inline(always)]
+    fn leaf_items(&self, node: u32) -> impl Iterator<Item = u32> {
+        std::iter::once(node)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use narcissus_core::random::Pcg64;
+
+    use crate::{Aabb3, BvhProxy, DynamicBvh, Point3, Ray3, Vec3, point3, vec3};
+
+    use super::NULL;
+
+    /// Checks parent links, heights and bounds of every reachable
+    /// node, returning the number of leaves.
+    fn validate(bvh: &DynamicBvh) -> usize {
+        let Some(root) = bvh.root() else {
+            return 0;
+        };
+        assert_eq!(bvh.nodes()[root as usize].parent, NULL);
+
+        let mut leaves = 0;
+        let mut stack = vec![root];
+        while let Some(index) = stack.pop() {
+            let node = &bvh.nodes()[index as usize];
+            if node.is_leaf() {
+                assert_eq!(node.height, 0);
+                leaves += 1;
+                continue;
+            }
+            let [a, b] = node.children.map(|child| &bvh.nodes()[child as usize]);
+            assert_eq!(a.parent, index);
+            assert_eq!(b.parent, index);
+            assert_eq!(node.height, 1 + a.height.max(b.height));
+            assert_eq!(node.aabb, Aabb3::union(a.aabb, b.aabb));
+            stack.extend(node.children);
+        }
+        assert_eq!(leaves, bvh.len());
+        leaves
+    }
+
+    fn random_aabb(rng: &mut Pcg64) -> Aabb3 {
+        let center = point3(
+            rng.next_f32_s() * 100.0,
+            rng.next_f32_s() * 10.0,
+            rng.next_f32_s() * 100.0,
+        );
+        Aabb3::from_center_half_extents(center, Vec3::splat(0.25 + rng.next_f32()))
+    }
+
+    #[test]
+    fn insert_remove() {
+        let mut rng = Pcg64::new();
+        let mut bvh = DynamicBvh::new(0.0);
+        assert_eq!(validate(&bvh), 0);
+
+        let mut proxies = vec![];
+        for i in 0..2000 {
+            proxies.push(bvh.insert(random_aabb(&mut rng)));
+            if i % 100 == 0 {
+                validate(&bvh);
+            }
+        }
+        assert_eq!(validate(&bvh), 2000);
+        // A balanced binary tree with 2000 leaves has height 11.
+        assert!(bvh.height() <= 16, "{}", bvh.height());
+
+        for proxy in proxies.drain(..1500) {
+            bvh.remove(proxy);
+        }
+        assert_eq!(validate(&bvh), 500);
+
+        // Freed nodes are reused.
+        let node_count = bvh.nodes().len();
+        for _ in 0..1000 {
+            proxies.push(bvh.insert(random_aabb(&mut rng)));
+        }
+        assert_eq!(bvh.nodes().len(), node_count);
+        assert_eq!(validate(&bvh), 1500);
+
+        for proxy in proxies.drain(..) {
+            bvh.remove(proxy);
+        }
+        assert!(bvh.is_empty());
+        assert_eq!(bvh.root(), None);
+    }
+
+    #[test]
+    fn moving_queries() {
+        let mut rng = Pcg64::new();
+        let mut bvh = DynamicBvh::new(0.5);
+        let mut objects = vec![];
+        for _ in 0..500 {
+            let aabb = random_aabb(&mut rng);
+            objects.push((bvh.insert(aabb), aabb));
+        }
+
+        for frame in 0..20 {
+            let mut reinserted = 0;
+            for (i, (proxy, aabb)) in objects.iter_mut().enumerate() {
+                let offset = vec3(rng.next_f32_s(), 0.0, rng.next_f32_s()) * 0.3;
+                *aabb = Aabb3::new(aabb.min + offset, aabb.max + offset);
+                if frame % 2 == 0 || i % 2 == 0 {
+                    reinserted += bvh.update(*proxy, *aabb) as usize;
+                } else {
+                    bvh.refit(*proxy, *aabb);
+                }
+                let fat = bvh.aabb(*proxy);
+                assert_eq!(Aabb3::union(*fat, *aabb), *fat);
+            }
+            assert!(reinserted < objects.len());
+            validate(&bvh);
+
+            let find = |proxy: BvhProxy| objects.iter().find(|x| x.0 == proxy).unwrap().1;
+
+            let query = random_aabb(&mut rng);
+            let mut found = vec![];
+            bvh.query_aabb(&query, |proxy| {
+                if Aabb3::overlaps(&query, &find(proxy)) {
+                    found.push(proxy)
+                }
+            });
+            found.sort();
+            let mut expected = objects
+                .iter()
+                .filter(|x| Aabb3::overlaps(&query, &x.1))
+                .map(|x| x.0)
+                .collect::<Vec<_>>();
+            expected.sort();
+            assert_eq!(found, expected);
+
+            let ray = Ray3::new(
+                point3(-120.0, rng.next_f32_s() * 5.0, rng.next_f32_s() * 100.0),
+                vec3(1.0, 0.0, rng.next_f32_s() * 0.2),
+            );
+            let hit = bvh.raycast(&ray, f32::INFINITY, |proxy| {
+                ray.intersect_aabb3(&find(proxy))
+            });
+            let expected = objects
+                .iter()
+                .filter_map(|x| ray.intersect_aabb3(&x.1))
+                .min_by(f32::total_cmp);
+            assert_eq!(hit.map(|x| x.1), expected);
+
+            let point = point3(rng.next_f32_s() * 100.0, 0.0, rng.next_f32_s() * 100.0);
+            let distance_sq = |aabb: Aabb3| Point3::distance_sq(point, aabb.closest_point(point));
+            let nearest = bvh.nearest(point, f32::INFINITY, |proxy| distance_sq(find(proxy)));
+            let expected = objects
+                .iter()
+                .map(|x| distance_sq(x.1))
+                .min_by(f32::total_cmp);
+            assert_eq!(nearest.map(|x| x.1), expected);
+        }
+    }
+}
@@ -0,0 +1,231 @@
+// Easing curves following the common set from Robert Penner's easing
+// equations. Each curve is defined by its ease-in form, with the ease-out and
+// ease-in-out forms derived by reflection.
+
+use crate::{exp_f32, sin_cos_pi_f32};
+
+/// Family of easing curves, mapping a linear control value on \[0,1\] to an
+/// eased control value which starts at `0.0` and ends at `1.0`.
+///
+/// # Examples
+///
+/// ```
+/// use narcissus_maths::{Easing, lerp};
+/// let t = Easing::Cubic.ease_in_out(0.25);
+/// assert_eq!(t, 0.0625);
+/// let x = lerp(t, 10.0, 20.0);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Easing {
+    Linear,
+    Quad,
+    Cubic,
+    Quart,
+    Quint,
+    Sine,
+    Expo,
+    Circ,
+    /// Overshoots slightly in the opposite direction before moving.
+    Back,
+    /// Oscillates with increasing amplitude before moving, like a plucked
+    /// spring.
+    Elastic,
+    /// Bounces with increasing height before moving, like a dropped ball in
+    /// reverse.
+    Bounce,
+}
+
+impl Easing {
+    /// Every easing curve, in declaration order.
+    pub const ALL: [Easing; 11] = [
+        Easing::Linear,
+        Easing::Quad,
+        Easing::Cubic,
+        Easing::Quart,
+        Easing::Quint,
+        Easing::Sine,
+        Easing::Expo,
+        Easing::Circ,
+        Easing::Back,
+        Easing::Elastic,
+        Easing::Bounce,
+    ];
+
+    /// Returns the curve eased at the start, beginning slowly and accelerating
+    /// towards the end.
+    ///
+    /// `t` is clamped to \[0,1\].
+    #[must_use]
+    pub fn ease_in(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::Quad => t * t,
+            Easing::Cubic => t * t * t,
+            Easing::Quart => (t * t) * (t * t),
+            Easing::Quint => (t * t) * (t * t) * t,
+            Easing::Sine => {
+                let (sin, _) = sin_cos_pi_f32((1.0 - t) * 0.5);
+                1.0 - sin
+            }
+            Easing::Expo => {
+                if t == 0.0 {
+                    0.0
+                } else {
+                    exp_f32((t - 1.0) * (10.0 * std::f32::consts::LN_2))
+                }
+            }
+            Easing::Circ => 1.0 - (1.0 - t * t).sqrt(),
+            Easing::Back => {
+                const C1: f32 = 1.701_58;
+                const C3: f32 = C1 + 1.0;
+                t * t * (C3 * t - C1)
+            }
+            Easing::Elastic => {
+                if t == 0.0 || t == 1.0 {
+                    t
+                } else {
+                    // Period of 0.3, or 2/3 of a half turn per 0.1.
+                    let (sin, _) = sin_cos_pi_f32((t * 10.0 - 10.75) * (2.0 / 3.0));
+                    -exp_f32((t - 1.0) * (10.0 * std::f32::consts::LN_2)) * sin
+                }
+            }
+            Easing::Bounce => 1.0 - bounce_out(1.0 - t),
+        }
+    }
+
+    /// Returns the curve eased at the end, beginning quickly and decelerating
+    /// towards the end.
+    ///
+    /// `t` is clamped to \[0,1\].
+    #[inline]
+    #[must_use]
+    pub fn ease_out(self, t: f32) -> f32 {
+        1.0 - self.ease_in(1.0 - t.clamp(0.0, 1.0))
+    }
+
+    /// Returns the curve eased at both the start and end, accelerating through
+    /// the first half and decelerating through the second.
+    ///
+    /// `t` is clamped to \[0,1\].
+    #[inline]
+    #[must_use]
+    pub fn ease_in_out(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        if t < 0.5 {
+            0.5 * self.ease_in(2.0 * t)
+        } else {
+            1.0 - 0.5 * self.ease_in(2.0 - 2.0 * t)
+        }
+    }
+}
+
+/// Bounce curve with an impact at `t = 1.0`, and three smaller bounces
+/// before it.
+#[inline(always)]
+fn bounce_out(t: f32) -> f32 {
+    const N1: f32 = 7.5625;
+    const D1: f32 = 2.75;
+    if t < 1.0 / D1 {
+        N1 * t * t
+    } else if t < 2.0 / D1 {
+        let t = t - 1.5 / D1;
+        N1 * t * t + 0.75
+    } else if t < 2.5 / D1 {
+        let t = t - 2.25 / D1;
+        N1 * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / D1;
+        N1 * t * t + 0.984375
+    }
+}
+
+/// Smooth Hermite interpolation of `x` between `edge0` and `edge1`, returning
+/// `0.0` at or below `edge0` and `1.0` at or above `edge1`, with zero slope at
+/// both edges.
+///
+/// # Examples
+///
+/// ```
+/// use narcissus_maths::smoothstep;
+/// assert_eq!(smoothstep(0.0, 2.0, 1.0), 0.5);
+/// assert_eq!(smoothstep(0.0, 2.0, 3.0), 1.0);
+/// ```
+#[inline(always)]
+#[must_use]
+pub fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32 {
+    let t = ((x - edge0) / (edge1 - edge0)).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+/// Ken Perlin's smoother variant of [`smoothstep`], which also has zero second
+/// derivative at both edges.
+#[inline(always)]
+#[must_use]
+pub fn smootherstep(edge0: f32, edge1: f32, x: f32) -> f32 {
+    let t = ((x - edge0) / (edge1 - edge0)).clamp(0.0, 1.0);
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Easing, smootherstep, smoothstep};
+
+    #[test]
+    fn endpoints() {
+        for easing in Easing::ALL {
+            for f in [Easing::ease_in, Easing::ease_out, Easing::ease_in_out] {
+                assert!(f(easing, 0.0).abs() < 1e-6, "{easing:?}");
+                assert!((f(easing, 1.0) - 1.0).abs() < 1e-6, "{easing:?}");
+                assert!(f(easing, -1.0).abs() < 1e-6, "{easing:?}");
+                assert!((f(easing, 2.0) - 1.0).abs() < 1e-6, "{easing:?}");
+            }
+            assert!((easing.ease_in_out(0.5) - 0.5).abs() < 1e-6, "{easing:?}");
+        }
+    }
+
+    #[test]
+    fn symmetry() {
+        for easing in Easing::ALL {
+            for i in 0..=64 {
+                let t = i as f32 / 64.0;
+                let a = easing.ease_out(t);
+                let b = 1.0 - easing.ease_in(1.0 - t);
+                assert!((a - b).abs() < 1e-6);
+                let a = easing.ease_in_out(t);
+                let b = 1.0 - easing.ease_in_out(1.0 - t);
+                assert!((a - b).abs() < 1e-6, "{easing:?} {t}");
+            }
+        }
+    }
+
+    #[test]
+    fn reference_values() {
+        assert_eq!(Easing::Linear.ease_in(0.25), 0.25);
+        assert_eq!(Easing::Quad.ease_in(0.5), 0.25);
+        assert_eq!(Easing::Quad.ease_out(0.5), 0.75);
+        assert_eq!(Easing::Cubic.ease_in(0.5), 0.125);
+        assert_eq!(Easing::Quart.ease_in(0.5), 0.0625);
+        assert_eq!(Easing::Quint.ease_in(0.5), 0.03125);
+        assert!((Easing::Sine.ease_in(0.5) - (1.0 - 0.5_f32.sqrt())).abs() < 1e-6);
+        assert!((Easing::Expo.ease_in(0.5) - 0.03125).abs() < 1e-6);
+        assert!((Easing::Circ.ease_in(0.6) - 0.2).abs() < 1e-6);
+        assert!(Easing::Back.ease_in(0.2) < 0.0);
+        assert!(Easing::Back.ease_out(0.8) > 1.0);
+        assert!(Easing::Elastic.ease_out(0.2) > 1.0);
+        assert!((Easing::Bounce.ease_out(1.0 / 2.75) - 1.0).abs() < 1e-6);
+        assert!((Easing::Bounce.ease_out(1.5 / 2.75) - 0.75).abs() < 1e-6);
+    }
+
+    #[test]
+    fn steps() {
+        assert_eq!(smoothstep(1.0, 2.0, 0.0), 0.0);
+        assert_eq!(smoothstep(1.0, 2.0, 1.5), 0.5);
+        assert_eq!(smoothstep(1.0, 2.0, 1.25), 0.15625);
+        assert_eq!(smootherstep(1.0, 2.0, 1.5), 0.5);
+        assert_eq!(smootherstep(1.0, 2.0, 1.0), 0.0);
+        assert_eq!(smootherstep(1.0, 2.0, 2.0), 1.0);
+        // Reversed edges invert the curve.
+        assert_eq!(smoothstep(2.0, 1.0, 1.25), 0.84375);
+    }
+}
@@ -0,0 +1,880 @@
+// Fixed-point arithmetic for deterministic simulation.
+//
+// All operations are implemented with integer arithmetic only, so results are
+// bit-identical across compilers, optimization levels and CPUs. Conversions to
+// and from floating point are provided for rendering and tooling, but should
+// never feed back into simulation state.
+
+use crate::{Vec2, Vec3};
+
+/// Number of fractional bits used by the trigonometric kernel.
+const Q62: u32 = 62;
+
+// Truncated Taylor series for sin(pi x) and cos(pi x) with coefficients in
+// Q62. For x on [-1/4, 1/4] the first omitted terms are below 2.1e-14 and
+// 1.1e-15 respectively (about 2^-45), well under the resolution of Q32.32.
+const SIN_PI_Q62: [i128; 7] = [
+    14488038916154245685,
+    -23831868775004974059,
+    11760555847398657682,
+    -2763619851208166269,
+    378831036755861614,
+    -33990113342135422,
+    2150442129711961,
+];
+
+const COS_PI_Q62: [i128; 8] = [
+    4611686018427387904,
+    -22757758311956604325,
+    18717505966217902901,
+    -6157812642053351409,
+    1085270977733811578,
+    -119013280202401979,
+    8898590864194755,
+    -482558085476649,
+];
+
+/// Shifts `x` right by `shift` bits, rounding to nearest with ties towards
+/// positive infinity.
+#[inline(always)]
+const fn round_shift(x: i128, shift: u32) -> i128 {
+    (x + (1 << (shift - 1))) >> shift
+}
+
+/// Computes `(sin(pi y), cos(pi y))` for `y` on [-1/4, 1/4], in Q62.
+fn sin_cos_pi_q62(y: i128) -> (i128, i128) {
+    let y2 = (y * y) >> Q62;
+
+    let mut sin = 0;
+    for k in SIN_PI_Q62.iter().rev() {
+        sin = k + ((sin * y2) >> Q62);
+    }
+    let sin = (sin * y) >> Q62;
+
+    let mut cos = 0;
+    for k in COS_PI_Q62.iter().rev() {
+        cos = k + ((cos * y2) >> Q62);
+    }
+
+    (sin, cos)
+}
+
+/// Computes `(sin(pi x), cos(pi x))` for `x` with `frac_bits` fractional bits,
+/// returning the results with the same number of fractional bits.
+fn sin_cos_pi_fixed(x: i128, frac_bits: u32) -> (i128, i128) {
+    // Reduce to a quarter of a half-turn either side of a multiple of 1/2.
+    let half = 1 << (frac_bits - 1);
+    let r = x.rem_euclid(4 * half);
+    let quadrant = (r + half / 2) / half;
+    let y = r - quadrant * half;
+
+    let (s, c) = sin_cos_pi_q62(y << (Q62 - frac_bits));
+    let (s, c) = match quadrant & 3 {
+        0 => (s, c),
+        1 => (c, -s),
+        2 => (-s, -c),
+        _ => (-c, s),
+    };
+
+    (
+        round_shift(s, Q62 - frac_bits),
+        round_shift(c, Q62 - frac_bits),
+    )
+}
+
+macro_rules! impl_fixed {
+    ($name:ident, $t:ty, $wide:ty, $unsigned_wide:ty, $frac_bits:expr) => {
+        impl $name {
+            /// Number of fractional bits.
+            pub const FRAC_BITS: u32 = $frac_bits;
+
+            pub const ZERO: Self = Self(0);
+            pub const ONE: Self = Self(1 << $frac_bits);
+            pub const HALF: Self = Self(1 << ($frac_bits - 1));
+            pub const MIN: Self = Self(<$t>::MIN);
+            pub const MAX: Self = Self(<$t>::MAX);
+
+            /// Smallest positive value.
+            pub const EPSILON: Self = Self(1);
+
+            /// Creates a value from its raw representation.
+            #[inline(always)]
+            #[must_use]
+            pub const fn from_bits(bits: $t) -> Self {
+                Self(bits)
+            }
+
+            /// Returns the raw representation, suitable for hashing state.
+            #[inline(always)]
+            #[must_use]
+            pub const fn to_bits(self) -> $t {
+                self.0
+            }
+
+            /// Converts an integer, saturating at the representable range.
+            #[inline(always)]
+            #[must_use]
+            pub const fn from_int(x: $t) -> Self {
+                Self(x.saturating_mul(1 << $frac_bits))
+            }
+
+            /// Returns the largest integer less than or equal to `self`.
+            #[inline(always)]
+            #[must_use]
+            pub const fn to_int(self) -> $t {
+                self.0 >> $frac_bits
+            }
+
+            /// Converts from `f32`, rounding to nearest and saturating at the
+            /// representable range. NaN converts to zero.
+            #[inline(always)]
+            #[must_use]
+            pub fn from_f32(x: f32) -> Self {
+                Self((x as f64 * (1u64 << $frac_bits) as f64).round() as $t)
+            }
+
+            /// Converts to the nearest `f32`.
+            #[inline(always)]
+            #[must_use]
+            pub fn to_f32(self) -> f32 {
+                self.to_f64() as f32
+            }
+
+            /// Converts from `f64`, rounding to nearest and saturating at the
+            /// representable range. NaN converts to zero.
+            #[inline(always)]
+            #[must_use]
+            pub fn from_f64(x: f64) -> Self {
+                Self((x * (1u64 << $frac_bits) as f64).round() as $t)
+            }
+
+            /// Converts to the nearest `f64`.
+            #[inline(always)]
+            #[must_use]
+            pub fn to_f64(self) -> f64 {
+                self.0 as f64 / (1u64 << $frac_bits) as f64
+            }
+
+            #[inline(always)]
+            #[must_use]
+            pub const fn floor(self) -> Self {
+                Self(self.0 & !(Self::ONE.0 - 1))
+            }
+
+            /// Rounds towards positive infinity, saturating at [`Self::MAX`].
+            #[inline(always)]
+            #[must_use]
+            pub const fn ceil(self) -> Self {
+                let floor = self.floor();
+                if floor.0 == self.0 {
+                    self
+                } else {
+                    Self(floor.0.saturating_add(Self::ONE.0))
+                }
+            }
+
+            /// Rounds to nearest with ties towards positive infinity,
+            /// saturating at [`Self::MAX`].
+            #[inline(always)]
+            #[must_use]
+            pub const fn round(self) -> Self {
+                let floor = self.floor();
+                if self.0 - floor.0 < Self::HALF.0 {
+                    floor
+                } else {
+                    Self(floor.0.saturating_add(Self::ONE.0))
+                }
+            }
+
+            /// Returns the fractional part, `self - self.floor()`.
+            #[inline(always)]
+            #[must_use]
+            pub const fn fract(self) -> Self {
+                Self(self.0 & (Self::ONE.0 - 1))
+            }
+
+            /// Returns the absolute value, saturating at [`Self::MAX`].
+            #[inline(always)]
+            #[must_use]
+            pub const fn abs(self) -> Self {
+                Self(self.0.saturating_abs())
+            }
+
+            /// Returns `-1`, `0` or `1` depending on the sign of `self`.
+            #[inline(always)]
+            #[must_use]
+            pub const fn signum(self) -> Self {
+                Self(self.0.signum() * Self::ONE.0)
+            }
+
+            #[inline(always)]
+            #[must_use]
+            pub const fn is_negative(self) -> bool {
+                self.0 < 0
+            }
+
+            #[inline(always)]
+            const fn mul_wide(self, rhs: Self) -> $wide {
+                (self.0 as $wide * rhs.0 as $wide + (1 << ($frac_bits - 1))) >> $frac_bits
+            }
+
+            #[inline(always)]
+            const fn div_wide(self, rhs: Self) -> $wide {
+                ((self.0 as $wide) << $frac_bits) / rhs.0 as $wide
+            }
+
+            #[inline(always)]
+            const fn saturate(x: $wide) -> Self {
+                if x > <$t>::MAX as $wide {
+                    Self::MAX
+                } else if x < <$t>::MIN as $wide {
+                    Self::MIN
+                } else {
+                    Self(x as $t)
+                }
+            }
+
+            #[inline(always)]
+            #[must_use]
+            pub const fn wrapping_add(self, rhs: Self) -> Self {
+                Self(self.0.wrapping_add(rhs.0))
+            }
+
+            #[inline(always)]
+            #[must_use]
+            pub const fn wrapping_sub(self, rhs: Self) -> Self {
+                Self(self.0.wrapping_sub(rhs.0))
+            }
+
+            /// Multiplies, rounding to nearest and wrapping on overflow.
+            #[inline(always)]
+            #[must_use]
+            pub const fn wrapping_mul(self, rhs: Self) -> Self {
+                Self(self.mul_wide(rhs) as $t)
+            }
+
+            /// Divides, rounding towards zero and wrapping on overflow.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `rhs` is zero.
+            #[inline(always)]
+            #[must_use]
+            pub const fn wrapping_div(self, rhs: Self) -> Self {
+                Self(self.div_wide(rhs) as $t)
+            }
+
+            #[inline(always)]
+            #[must_use]
+            pub const fn saturating_add(self, rhs: Self) -> Self {
+                Self(self.0.saturating_add(rhs.0))
+            }
+
+            #[inline(always)]
+            #[must_use]
+            pub const fn saturating_sub(self, rhs: Self) -> Self {
+                Self(self.0.saturating_sub(rhs.0))
+            }
+
+            /// Multiplies, rounding to nearest and saturating on overflow.
+            #[inline(always)]
+            #[must_use]
+            pub const fn saturating_mul(self, rhs: Self) -> Self {
+                Self::saturate(self.mul_wide(rhs))
+            }
+
+            /// Divides, rounding towards zero and saturating on overflow.
+            ///
+            /// Division by zero saturates towards the sign of `self`, and zero
+            /// divided by zero is zero.
+            #[inline(always)]
+            #[must_use]
+            pub const fn saturating_div(self, rhs: Self) -> Self {
+                if rhs.0 == 0 {
+                    if self.0 > 0 {
+                        Self::MAX
+                    } else if self.0 < 0 {
+                        Self::MIN
+                    } else {
+                        Self::ZERO
+                    }
+                } else {
+                    Self::saturate(self.div_wide(rhs))
+                }
+            }
+
+            /// Returns the square root rounded towards zero, or zero for
+            /// negative values.
+            #[inline]
+            #[must_use]
+            pub const fn sqrt(self) -> Self {
+                if self.0 <= 0 {
+                    Self::ZERO
+                } else {
+                    Self((((self.0 as $unsigned_wide) << $frac_bits).isqrt()) as $t)
+                }
+            }
+
+            /// Simultaneously computes the sine and cosine of `self` expressed
+            /// in multiples of *pi* radians, or half-turns.
+            ///
+            /// Results are exact at multiples of one half.
+            ///
+            /// Returns `(sin(self * pi), cos(self * pi))`
+            #[inline]
+            #[must_use]
+            pub fn sin_cos_pi(self) -> (Self, Self) {
+                let (s, c) = sin_cos_pi_fixed(self.0 as i128, $frac_bits);
+                (Self(s as $t), Self(c as $t))
+            }
+
+            /// Computes the sine of `self` expressed in multiples of *pi*
+            /// radians, or half-turns.
+            #[inline]
+            #[must_use]
+            pub fn sin_pi(self) -> Self {
+                self.sin_cos_pi().0
+            }
+
+            /// Computes the cosine of `self` expressed in multiples of *pi*
+            /// radians, or half-turns.
+            #[inline]
+            #[must_use]
+            pub fn cos_pi(self) -> Self {
+                self.sin_cos_pi().1
+            }
+        }
+
+        // Operators wrap on overflow in all build profiles, so that debug and
+        // release builds of a simulation stay in lockstep.
+
+        impl std::ops::Add for $name {
+            type Output = Self;
+            #[inline(always)]
+            fn add(self, rhs: Self) -> Self {
+                self.wrapping_add(rhs)
+            }
+        }
+
+        impl std::ops::Sub for $name {
+            type Output = Self;
+            #[inline(always)]
+            fn sub(self, rhs: Self) -> Self {
+                self.wrapping_sub(rhs)
+            }
+        }
+
+        impl std::ops::Mul for $name {
+            type Output = Self;
+            #[inline(always)]
+            fn mul(self, rhs: Self) -> Self {
+                self.wrapping_mul(rhs)
+            }
+        }
+
+        impl std::ops::Div for $name {
+            type Output = Self;
+            #[inline(always)]
+            fn div(self, rhs: Self) -> Self {
+                self.wrapping_div(rhs)
+            }
+        }
+
+        impl std::ops::Neg for $name {
+            type Output = Self;
+            #[inline(always)]
+            fn neg(self) -> Self {
+                Self(self.0.wrapping_neg())
+            }
+        }
+
+        impl std::ops::AddAssign for $name {
+            #[inline(always)]
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs
+            }
+        }
+
+        impl std::ops::SubAssign for $name {
+            #[inline(always)]
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs
+            }
+        }
+
+        impl std::ops::MulAssign for $name {
+            #[inline(always)]
+            fn mul_assign(&mut self, rhs: Self) {
+                *self = *self * rhs
+            }
+        }
+
+        impl std::ops::DivAssign for $name {
+            #[inline(always)]
+            fn div_assign(&mut self, rhs: Self) {
+                *self = *self / rhs
+            }
+        }
+    };
+}
+
+/// Signed Q16.16 fixed-point number.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+#[repr(transparent)]
+pub struct Fixed32(i32);
+
+/// Signed Q32.32 fixed-point number.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+#[repr(transparent)]
+pub struct Fixed64(i64);
+
+impl_fixed!(Fixed32, i32, i64, u64, 16);
+impl_fixed!(Fixed64, i64, i128, u128, 32);
+
+impl From<Fixed32> for Fixed64 {
+    #[inline(always)]
+    fn from(x: Fixed32) -> Self {
+        Self((x.0 as i64) << 16)
+    }
+}
+
+/// Computes `sqrt(sum(x * x))` of raw Q32.32 components without overflow.
+#[inline]
+fn length_raw(components: &[i64]) -> Fixed64 {
+    let sum = components
+        .iter()
+        .map(|&x| x.unsigned_abs() as u128 * x.unsigned_abs() as u128)
+        .sum::<u128>();
+    Fixed64(sum.isqrt().min(i64::MAX as u128) as i64)
+}
+
+/// Two-dimensional vector of [`Fixed64`] components.
+///
+/// Q32.32 leaves enough integer range for world-space positions, where the
+/// squares of Q16.16 coordinates would overflow beyond a few hundred units.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub struct FixedVec2 {
+    pub x: Fixed64,
+    pub y: Fixed64,
+}
+
+/// Three-dimensional vector of [`Fixed64`] components.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub struct FixedVec3 {
+    pub x: Fixed64,
+    pub y: Fixed64,
+    pub z: Fixed64,
+}
+
+impl FixedVec2 {
+    pub const ZERO: Self = Self::splat(Fixed64::ZERO);
+    pub const ONE: Self = Self::splat(Fixed64::ONE);
+    pub const X: Self = Self::new(Fixed64::ONE, Fixed64::ZERO);
+    pub const Y: Self = Self::new(Fixed64::ZERO, Fixed64::ONE);
+
+    #[inline(always)]
+    #[must_use]
+    pub const fn new(x: Fixed64, y: Fixed64) -> Self {
+        Self { x, y }
+    }
+
+    #[inline(always)]
+    #[must_use]
+    pub const fn splat(value: Fixed64) -> Self {
+        Self::new(value, value)
+    }
+
+    /// Converts from `Vec2`, rounding each component to nearest.
+    #[inline(always)]
+    #[must_use]
+    pub fn from_vec2(v: Vec2) -> Self {
+        Self::new(Fixed64::from_f32(v.x), Fixed64::from_f32(v.y))
+    }
+
+    #[inline(always)]
+    #[must_use]
+    pub fn as_vec2(self) -> Vec2 {
+        Vec2::new(self.x.to_f32(), self.y.to_f32())
+    }
+
+    #[inline(always)]
+    #[must_use]
+    pub fn dot(a: Self, b: Self) -> Fixed64 {
+        a.x * b.x + a.y * b.y
+    }
+
+    /// Returns the z component of the cross product of `a` and `b`, extended
+    /// into three dimensions.
+    #[inline(always)]
+    #[must_use]
+    pub fn perp_dot(a: Self, b: Self) -> Fixed64 {
+        a.x * b.y - a.y * b.x
+    }
+
+    #[inline(always)]
+    #[must_use]
+    pub fn length_sq(self) -> Fixed64 {
+        Self::dot(self, self)
+    }
+
+    /// Returns the length of the vector, computed without intermediate
+    /// overflow.
+    #[inline]
+    #[must_use]
+    pub fn length(self) -> Fixed64 {
+        length_raw(&[self.x.0, self.y.0])
+    }
+
+    /// Returns a vector with the same direction and unit length, or zero for
+    /// the zero vector.
+    #[inline]
+    #[must_use]
+    pub fn normalized(self) -> Self {
+        let length = self.length();
+        if length == Fixed64::ZERO {
+            Self::ZERO
+        } else {
+            self / length
+        }
+    }
+}
+
+impl FixedVec3 {
+    pub const ZERO: Self = Self::splat(Fixed64::ZERO);
+    pub const ONE: Self = Self::splat(Fixed64::ONE);
+    pub const X: Self = Self::new(Fixed64::ONE, Fixed64::ZERO, Fixed64::ZERO);
+    pub const Y: Self = Self::new(Fixed64::ZERO, Fixed64::ONE, Fixed64::ZERO);
+    pub const Z: Self = Self::new(Fixed64::ZERO, Fixed64::ZERO, Fixed64::ONE);
+
+    #[inline(always)]
+    #[must_use]
+    pub const fn new(x: Fixed64, y: Fixed64, z: Fixed64) -> Self {
+        Self { x, y, z }
+    }
+
+    #[inline(always)]
+    #[must_use]
+    pub const fn splat(value: Fixed64) -> Self {
+        Self::new(value, value, value)
+    }
+
+    /// Converts from `Vec3`, rounding each component to nearest.
+    #[inline(always)]
+    #[must_use]
+    pub fn from_vec3(v: Vec3) -> Self {
+        Self::new(
+            Fixed64::from_f32(v.x),
+            Fixed64::from_f32(v.y),
+            Fixed64::from_f32(v.z),
+        )
+    }
+
+    #[inline(always)]
+    #[must_use]
+    pub fn as_vec3(self) -> Vec3 {
+        Vec3::new(self.x.to_f32(), self.y.to_f32(), self.z.to_f32())
+    }
+
+    #[inline(always)]
+    #[must_use]
+    pub fn dot(a: Self, b: Self) -> Fixed64 {
+        a.x * b.x + a.y * b.y + a.z * b.z
+    }
+
+    #[inline(always)]
+    #[must_use]
+    pub fn cross(a: Self, b: Self) -> Self {
+        Self::new(
+            a.y * b.z - a.z * b.y,
+            a.z * b.x - a.x * b.z,
+            a.x * b.y - a.y * b.x,
+        )
+    }
+
+    #[inline(always)]
+    #[must_use]
+    pub fn length_sq(self) -> Fixed64 {
+        Self::dot(self, self)
+    }
+
+    /// Returns the length of the vector, computed without intermediate
+    /// overflow.
+    #[inline]
+    #[must_use]
+    pub fn length(self) -> Fixed64 {
+        length_raw(&[self.x.0, self.y.0, self.z.0])
+    }
+
+    /// Returns a vector with the same direction and unit length, or zero for
+    /// the zero vector.
+    #[inline]
+    #[must_use]
+    pub fn normalized(self) -> Self {
+        let length = self.length();
+        if length == Fixed64::ZERO {
+            Self::ZERO
+        } else {
+            self / length
+        }
+    }
+}
+
+macro_rules! impl_fixed_vector_ops {
+    ($name:ident, $($field:ident),+) => {
+        impl std::ops::Add for $name {
+            type Output = Self;
+            #[inline(always)]
+            fn add(self, rhs: Self) -> Self {
+                Self { $($field: self.$field + rhs.$field),+ }
+            }
+        }
+
+        impl std::ops::Sub for $name {
+            type Output = Self;
+            #[inline(always)]
+            fn sub(self, rhs: Self) -> Self {
+                Self { $($field: self.$field - rhs.$field),+ }
+            }
+        }
+
+        impl std::ops::Mul<Fixed64> for $name {
+            type Output = Self;
+            #[inline(always)]
+            fn mul(self, rhs: Fixed64) -> Self {
+                Self { $($field: self.$field * rhs),+ }
+            }
+        }
+
+        impl std::ops::Div<Fixed64> for $name {
+            type Output = Self;
+            #[inline(always)]
+            fn div(self, rhs: Fixed64) -> Self {
+                Self { $($field: self.$field / rhs),+ }
+            }
+        }
+
+        impl std::ops::Neg for $name {
+            type Output = Self;
+            #[inline(always)]
+            fn neg(self) -> Self {
+                Self { $($field: -self.$field),+ }
+            }
+        }
+
+        impl std::ops::AddAssign for $name {
+            #[inline(always)]
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs
+            }
+        }
+
+        impl std::ops::SubAssign for $name {
+            #[inline(always)]
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs
+            }
+        }
+
+        impl std::ops::MulAssign<Fixed64> for $name {
+            #[inline(always)]
+            fn mul_assign(&mut self, rhs: Fixed64) {
+                *self = *self * rhs
+            }
+        }
+
+        impl std::ops::DivAssign<Fixed64> for $name {
+            #[inline(always)]
+            fn div_assign(&mut self, rhs: Fixed64) {
+                *self = *self / rhs
+            }
+        }
+    };
+}
+
+impl_fixed_vector_ops!(FixedVec2, x, y);
+impl_fixed_vector_ops!(FixedVec3, x, y, z);
+
+#[cfg(test)]
+mod tests {
+    use narcissus_core::random::Pcg64;
+
+    use super::*;
+
+    #[test]
+    fn conversions() {
+        assert_eq!(Fixed32::ONE.to_bits(), 0x1_0000);
+        assert_eq!(Fixed64::ONE.to_bits(), 0x1_0000_0000);
+        assert_eq!(Fixed32::from_f32(1.5).to_bits(), 0x1_8000);
+        assert_eq!(Fixed32::from_f32(-0.25).to_f32(), -0.25);
+        assert_eq!(Fixed32::from_f32(1e9), Fixed32::MAX);
+        assert_eq!(Fixed32::from_f32(-1e9), Fixed32::MIN);
+        assert_eq!(Fixed32::from_f32(f32::NAN), Fixed32::ZERO);
+        assert_eq!(Fixed32::from_int(3).to_f32(), 3.0);
+        assert_eq!(Fixed32::from_int(40000), Fixed32::MAX);
+        assert_eq!(Fixed64::from_int(-7).to_int(), -7);
+        assert!((Fixed64::from_f64(123.456).to_f64() - 123.456).abs() < 1e-9);
+        assert_eq!(
+            Fixed64::from(Fixed32::from_f32(-2.75)),
+            Fixed64::from_f32(-2.75)
+        );
+    }
+
+    #[test]
+    fn rounding() {
+        let x = Fixed32::from_f32(-1.25);
+        assert_eq!(x.floor(), Fixed32::from_int(-2));
+        assert_eq!(x.ceil(), Fixed32::from_int(-1));
+        assert_eq!(x.round(), Fixed32::from_int(-1));
+        assert_eq!(x.fract(), Fixed32::from_f32(0.75));
+        assert_eq!(x.to_int(), -2);
+        assert_eq!(Fixed32::from_f32(2.5).round(), Fixed32::from_int(3));
+        assert_eq!(Fixed32::MAX.ceil(), Fixed32::MAX);
+        assert_eq!(Fixed32::MAX.round(), Fixed32::MAX);
+        assert_eq!(Fixed64::MAX.ceil(), Fixed64::MAX);
+        assert_eq!(Fixed32::MIN.ceil(), Fixed32::MIN);
+        assert_eq!(Fixed32::MIN.round(), Fixed32::MIN);
+        assert_eq!(Fixed32::from_int(5).ceil(), Fixed32::from_int(5));
+        assert_eq!(Fixed32::from_f32(-2.5).round(), Fixed32::from_int(-2));
+        assert_eq!(x.abs(), Fixed32::from_f32(1.25));
+        assert_eq!(Fixed32::MIN.abs(), Fixed32::MAX);
+        assert_eq!(x.signum(), -Fixed32::ONE);
+        assert_eq!(Fixed32::ZERO.signum(), Fixed32::ZERO);
+    }
+
+    #[test]
+    fn arithmetic() {
+        let a = Fixed32::from_f32(3.5);
+        let b = Fixed32::from_f32(-1.25);
+        assert_eq!(a + b, Fixed32::from_f32(2.25));
+        assert_eq!(a - b, Fixed32::from_f32(4.75));
+        assert_eq!(a * b, Fixed32::from_f32(-4.375));
+        assert_eq!(a / Fixed32::from_f32(-0.5), Fixed32::from_int(-7));
+        // Division rounds towards zero.
+        assert_eq!((a / b).to_bits(), -183500);
+
+        // Multiplication rounds to nearest.
+        let tiny = Fixed32::EPSILON;
+        assert_eq!(tiny * Fixed32::HALF, tiny);
+        assert_eq!(tiny * Fixed32::from_f32(0.25), Fixed32::ZERO);
+
+        let big = Fixed32::from_int(30000);
+        assert_eq!(big.saturating_add(big), Fixed32::MAX);
+        assert_eq!((-big).saturating_sub(big), Fixed32::MIN);
+        assert_eq!(big.saturating_mul(-big), Fixed32::MIN);
+        assert_eq!(big.saturating_div(Fixed32::EPSILON), Fixed32::MAX);
+        assert_eq!(big.saturating_div(Fixed32::ZERO), Fixed32::MAX);
+        assert_eq!((-big).saturating_div(Fixed32::ZERO), Fixed32::MIN);
+        assert_eq!(Fixed32::ZERO.saturating_div(Fixed32::ZERO), Fixed32::ZERO);
+
+        assert_eq!(big + big, big.wrapping_add(big));
+        assert_eq!(Fixed32::MIN - Fixed32::EPSILON, Fixed32::MAX);
+        assert_eq!(-Fixed32::MIN, Fixed32::MIN);
+
+        let mut rng = Pcg64::new();
+        for _ in 0..1000 {
+            let a = rng.next_f32_s() * 100.0;
+            let b = rng.next_f32_s() * 100.0;
+            let fa = Fixed64::from_f32(a);
+            let fb = Fixed64::from_f32(b);
+            let product = (fa * fb).to_f64();
+            assert!((product - a as f64 * b as f64).abs() <= 1e-9);
+            if b.abs() > 1e-3 {
+                let quotient = (fa / fb).to_f64();
+                assert!((quotient - a as f64 / b as f64).abs() <= 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn sqrt() {
+        assert_eq!(Fixed32::from_int(4).sqrt(), Fixed32::from_int(2));
+        assert_eq!(Fixed64::from_f32(0.25).sqrt(), Fixed64::HALF);
+        assert_eq!(Fixed32::from_int(-4).sqrt(), Fixed32::ZERO);
+        assert_eq!(Fixed32::ZERO.sqrt(), Fixed32::ZERO);
+
+        let mut rng = Pcg64::new();
+        for _ in 0..1000 {
+            let x = rng.next_f32() * 30000.0;
+            let s = Fixed32::from_f32(x);
+            let expected = s.to_f64().sqrt();
+            let actual = s.sqrt().to_f64();
+            assert!(actual <= expected && expected - actual < 1.0 / 65536.0);
+
+            let s = Fixed64::from_f32(x);
+            let expected = s.to_f64().sqrt();
+            let actual = s.sqrt().to_f64();
+            assert!((expected - actual).abs() < 1e-9);
+        }
+
+        let max = Fixed64::MAX.sqrt().to_f64();
+        assert!((max - Fixed64::MAX.to_f64().sqrt()).abs() < 1e-6);
+    }
+
+    #[test]
+    fn sin_cos_pi() {
+        for i in -8..=8 {
+            let x = Fixed32::from_int(i) * Fixed32::HALF;
+            let (s, c) = x.sin_cos_pi();
+            let expected = [(0, 1), (1, 0), (0, -1), (-1, 0)][i.rem_euclid(4) as usize];
+            assert_eq!(s, Fixed32::from_int(expected.0));
+            assert_eq!(c, Fixed32::from_int(expected.1));
+        }
+
+        let mut rng = Pcg64::new();
+        for _ in 0..10000 {
+            let x = rng.next_f32_s() * 16.0;
+
+            let f = Fixed32::from_f32(x);
+            let (s, c) = f.sin_cos_pi();
+            let angle = f.to_f64() * std::f64::consts::PI;
+            assert!((s.to_f64() - angle.sin()).abs() <= 1.0 / 65536.0);
+            assert!((c.to_f64() - angle.cos()).abs() <= 1.0 / 65536.0);
+
+            let f = Fixed64::from_f32(x);
+            let (s, c) = f.sin_cos_pi();
+            let angle = f.to_f64() * std::f64::consts::PI;
+            assert!((s.to_f64() - angle.sin()).abs() <= 1e-9);
+            assert!((c.to_f64() - angle.cos()).abs() <= 1e-9);
+            assert_eq!(s, f.sin_pi());
+            assert_eq!(c, f.cos_pi());
+        }
+
+        // Extremes must reduce without overflow.
+        let _ = Fixed32::MIN.sin_cos_pi();
+        let _ = Fixed64::MAX.sin_cos_pi();
+    }
+
+    #[test]
+    fn vectors() {
+        let v = FixedVec2::new(Fixed64::from_int(3), Fixed64::from_int(4));
+        assert_eq!(v.length(), Fixed64::from_int(5));
+        assert_eq!(v.length_sq(), Fixed64::from_int(25));
+        let n = v.normalized();
+        assert!((n.x - Fixed64::from_f64(0.6)).abs() <= Fixed64::EPSILON);
+        assert!((n.y - Fixed64::from_f64(0.8)).abs() <= Fixed64::EPSILON);
+        assert_eq!(FixedVec2::ZERO.normalized(), FixedVec2::ZERO);
+        assert_eq!(
+            FixedVec2::perp_dot(FixedVec2::X, FixedVec2::Y),
+            Fixed64::ONE
+        );
+        assert_eq!(v.as_vec2(), Vec2::new(3.0, 4.0));
+        assert_eq!(FixedVec2::from_vec2(Vec2::new(3.0, 4.0)), v);
+
+        // Lengths far beyond the range of the squared length.
+        let far = FixedVec3::new(
+            Fixed64::from_int(1 << 30),
+            Fixed64::from_int(1 << 30),
+            Fixed64::ZERO,
+        );
+        let expected = (1u64 << 30) as f64 * std::f64::consts::SQRT_2;
+        assert!((far.length().to_f64() - expected).abs() < 1e-6);
+        assert!((far.normalized().length() - Fixed64::ONE).abs() <= Fixed64::from_bits(2));
+
+        assert_eq!(FixedVec3::cross(FixedVec3::X, FixedVec3::Y), FixedVec3::Z);
+        assert_eq!(FixedVec3::cross(FixedVec3::Y, FixedVec3::X), -FixedVec3::Z);
+        let a = FixedVec3::from_vec3(Vec3::new(1.0, 2.0, 3.0));
+        let b = FixedVec3::from_vec3(Vec3::new(-2.0, 0.5, 4.0));
+        assert_eq!(FixedVec3::dot(a, b), Fixed64::from_int(11));
+        assert_eq!((a + b - b) * Fixed64::from_int(2) / Fixed64::from_int(2), a);
+    }
+}
@@ -0,0 +1,99 @@
+/// Computes fractal Brownian motion by summing `octaves` layers of `noise`.
+///
+/// Each octave has `lacunarity` times the frequency and `gain` times the
+/// amplitude of the previous one. `noise` is called with the octave index and
+/// frequency, and should return noise sampled at the input coordinate scaled
+/// by that frequency. Varying the seed or offset per octave avoids artifacts
+/// where the octaves line up.
+///
+/// The result is normalized by the sum of the amplitudes, so has the same range
+/// as `noise`.
+///
+/// # Examples
+///
+/// ```
+/// use narcissus_maths::{fbm_noise, perlin_noise3_wrap_seed};
+/// let (x, y, z) = (1.5, 2.25, 0.5);
+/// let n = fbm_noise(5, 2.0, 0.5, |octave, frequency| {
+///     perlin_noise3_wrap_seed(x * frequency, y * frequency, z * frequency, 0, 0, 0, octave as u8)
+/// });
+/// assert!((-1.0..=1.0).contains(&n));
+/// ```
+pub fn fbm_noise<F>(octaves: u32, lacunarity: f32, gain: f32, mut noise: F) -> f32
+where
+    F: FnMut(u32, f32) -> f32,
+{
+    let mut sum = 0.0;
+    let mut total = 0.0;
+    let mut amplitude = 1.0;
+    let mut frequency = 1.0;
+    for octave in 0..octaves {
+        sum += noise(octave, frequency) * amplitude;
+        total += amplitude;
+        amplitude *= gain;
+        frequency *= lacunarity;
+    }
+    if total > 0.0 { sum / total } else { 0.0 }
+}
+
+/// Computes ridged multifractal noise by summing `octaves` layers of `noise`.
+///
+/// Each octave folds the noise around zero to form sharp ridges, and is
+/// weighted by the previous octave so that detail accumulates along the ridges
+/// and the valleys stay smooth. `octaves`, `lacunarity`, `gain` and `noise`
+/// are as described for [`fbm_noise`], with `noise` expected to return values in
+/// \[-1,1\].
+///
+/// The result is in the range \[0,1\].
+pub fn ridged_noise<F>(octaves: u32, lacunarity: f32, gain: f32, mut noise: F) -> f32
+where
+    F: FnMut(u32, f32) -> f32,
+{
+    let mut sum = 0.0;
+    let mut total = 0.0;
+    let mut amplitude = 1.0;
+    let mut frequency = 1.0;
+    let mut weight = 1.0;
+    for octave in 0..octaves {
+        let signal = 1.0 - noise(octave, frequency).abs();
+        let signal = signal * signal * weight;
+        weight = (signal * 2.0).clamp(0.0, 1.0);
+        sum += signal * amplitude;
+        total += amplitude;
+        amplitude *= gain;
+        frequency *= lacunarity;
+    }
+    if total > 0.0 { sum / total } else { 0.0 }
+}
+
+#[cfg(test)]
+mod tests {
+    use narcissus_core::random::Pcg64;
+
+    use crate::{fbm_noise, ridged_noise, simplex_noise2_seed};
+
+    #[test]
+    fn basic() {
+        assert_eq!(fbm_noise(0, 2.0, 0.5, |_, _| 1.0), 0.0);
+        assert_eq!(fbm_noise(4, 2.0, 0.5, |_, _| 0.5), 0.5);
+        assert_eq!(ridged_noise(4, 2.0, 0.5, |_, _| 0.0), 1.0);
+        assert_eq!(ridged_noise(4, 2.0, 0.5, |_, _| 1.0), 0.0);
+
+        let mut frequencies = vec![];
+        fbm_noise(4, 3.0, 0.5, |octave, frequency| {
+            frequencies.push((octave, frequency));
+            0.0
+        });
+        assert_eq!(frequencies, [(0, 1.0), (1, 3.0), (2, 9.0), (3, 27.0)]);
+
+        let mut rng = Pcg64::new();
+        for _ in 0..1000 {
+            let [x, y] = std::array::from_fn(|_| rng.next_f32_s() * 100.0);
+            let noise = |octave: u32, frequency: f32| {
+                simplex_noise2_seed(x * frequency, y * frequency, octave as u8).clamp(-1.0, 1.0)
+            };
+            assert!((-1.0..=1.0).contains(&fbm_noise(6, 2.0, 0.5, noise)));
+            assert!((0.0..=1.0).contains(&ridged_noise(6, 2.0, 0.5, noise)));
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use crate::{Aabb3, Mat4, Plane, Point3, Sphere, Vec3};
+
+/// Convex volume bounded by six planes, typically the region of space visible
+/// to a camera.
+///
+/// Each plane's normal faces into the frustum, so points inside the frustum are
+/// in front of all six planes.
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[repr(C)]
+pub struct Frustum {
+    /// Left, right, top, bottom, depth zero and depth one planes, in that order.
+    ///
+    /// With reversed-z projections the depth zero plane is the far plane.
+    pub planes: [Plane; 6],
+}
+
+impl Frustum {
+    /// Extracts the frustum planes from the matrix `clip_from_world`, which
+    /// transforms points into vulkan clip space with depth range \[0,1\].
+    ///
+    /// For example, the product of a projection matrix such as
+    /// [`Mat4::perspective_rev_inf_zo`] and a view matrix gives a frustum in
+    /// world space, and the projection matrix alone gives a frustum in camera
+    /// space.
+    ///
+    /// Planes that lie at infinity, such as the far plane of an infinite
+    /// projection, have a zero normal and a distance of negative infinity so that
+    /// every point is considered in front of them.
+    pub fn from_mat4(clip_from_world: Mat4) -> Frustum {
+        let [r0, r1, r2, r3] = clip_from_world.as_rows().map(|row| row.map(f64::from));
+
+        let add = |a: [f64; 4], b: [f64; 4]| std::array::from_fn(|i| a[i] + b[i]);
+        let sub = |a: [f64; 4], b: [f64; 4]| std::array::from_fn(|i| a[i] - b[i]);
+
+        // A clip space point is visible when -w <= x <= w, -w <= y <= w and
+        // 0 <= z <= w. Each inequality is a half-space in world space.
+        let planes = [
+            add(r3, r0),
+            sub(r3, r0),
+            add(r3, r1),
+            sub(r3, r1),
+            r2,
+            sub(r3, r2),
+        ];
+
+        Frustum {
+            planes: planes.map(|[x, y, z, w]: [f64; 4]| {
+                let length = (x * x + y * y + z * z).sqrt();
+                if length == 0.0 {
+                    return Plane::new(
+                        Vec3::ZERO,
+                        if w >= 0.0 {
+                            f32::NEG_INFINITY
+                        } else {
+                            f32::INFINITY
+                        },
+                    );
+                }
+                Plane::new(
+                    Vec3::new(
+                        (x / length) as f32,
+                        (y / length) as f32,
+                        (z / length) as f32,
+                    ),
+                    (-w / length) as f32,
+                )
+            }),
+        }
+    }
+
+    /// Returns `true` if `point` is inside or on the boundary of `self`.
+    pub fn contains_point(&self, point: Point3) -> bool {
+        self.planes
+            .iter()
+            .all(|plane| plane.signed_distance(point) >= 0.0)
+    }
+
+    /// Returns `true` if `sphere` may intersect `self`.
+    ///
+    /// The test is conservative, a sphere near a corner of the frustum may be
+    /// reported as intersecting when it is actually outside.
+    pub fn intersects_sphere(&self, sphere: &Sphere) -> bool {
+        self.planes
+            .iter()
+            .all(|plane| plane.signed_distance(sphere.center) >= -sphere.radius)
+    }
+
+    /// Returns `true` if `aabb` may intersect `self`.
+    ///
+    /// The test is conservative, a box near an edge of the frustum may be
+    /// reported as intersecting when it is actually outside.
+    pub fn intersects_aabb3(&self, aabb: &Aabb3) -> bool {
+        let center = aabb.center();
+        let half_extents = aabb.half_extents();
+        self.planes.iter().all(|plane| {
+            // Distance from the center to the corner furthest along the normal.
+            let radius = Vec3::dot(plane.normal.abs(), half_extents);
+            plane.signed_distance(center) >= -radius
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Aabb3, Frustum, HalfTurn, Mat4, Point3, Sphere, Vec3, point3};
+
+    #[test]
+    fn perspective_rev_inf_zo() {
+        let frustum =
+            Frustum::from_mat4(Mat4::perspective_rev_inf_zo(HalfTurn::new(0.5), 1.0, 0.1));
+
+        assert!(frustum.contains_point(point3(0.0, 0.0, -1.0)));
+        assert!(frustum.contains_point(point3(0.0, 0.0, -1.0e30)));
+        assert!(frustum.contains_point(point3(0.9, -0.9, -1.0)));
+        assert!(!frustum.contains_point(point3(1.1, 0.0, -1.0)));
+        assert!(!frustum.contains_point(point3(0.0, -1.1, -1.0)));
+        assert!(!frustum.contains_point(point3(0.0, 0.0, -0.05)));
+        assert!(!frustum.contains_point(point3(0.0, 0.0, 1.0)));
+
+        assert!(frustum.intersects_sphere(&Sphere::new(point3(0.0, 0.0, -100.0), 1.0)));
+        assert!(frustum.intersects_sphere(&Sphere::new(point3(0.0, 0.0, 0.5), 1.0)));
+        assert!(!frustum.intersects_sphere(&Sphere::new(point3(0.0, 0.0, 2.0), 1.0)));
+        assert!(!frustum.intersects_sphere(&Sphere::new(point3(3.0, 0.0, -1.0), 1.0)));
+
+        let aabb = Aabb3::new(point3(1.5, -0.5, -2.5), point3(2.5, 0.5, -1.5));
+        assert!(frustum.intersects_aabb3(&aabb));
+        let aabb = Aabb3::new(point3(2.6, -0.5, -2.5), point3(3.5, 0.5, -1.5));
+        assert!(!frustum.intersects_aabb3(&aabb));
+        let aabb = Aabb3::new(point3(-0.5, -0.5, 0.5), point3(0.5, 0.5, 1.5));
+        assert!(!frustum.intersects_aabb3(&aabb));
+    }
+
+    #[test]
+    fn orthographic_zo_view() {
+        let clip_from_camera = Mat4::orthographic_zo(-1.0, 1.0, -2.0, 2.0, 1.0, 11.0);
+        let camera_from_world = Mat4::look_at(point3(10.0, 0.0, 0.0), Point3::ZERO, Vec3::Y);
+        let frustum = Frustum::from_mat4(clip_from_camera * camera_from_world);
+
+        assert!(frustum.contains_point(Point3::ZERO));
+        assert!(frustum.contains_point(point3(-0.9, 1.9, 0.9)));
+        assert!(!frustum.contains_point(point3(0.0, 0.0, 1.1)));
+        assert!(!frustum.contains_point(point3(0.0, 2.1, 0.0)));
+        assert!(!frustum.contains_point(point3(9.5, 0.0, 0.0)));
+        assert!(!frustum.contains_point(point3(-1.5, 0.0, 0.0)));
+
+        for plane in frustum.planes {
+            assert!((plane.normal.length() - 1.0).abs() < 1e-6);
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use crate::{Vec2, vec2};
+
+/// Returns the `index`th element of the Halton low discrepancy sequence with
+/// the given `base`, a value on \[0,1).
+///
+/// The sequence starts at `0.0` for index zero, so callers typically start
+/// from one.
+///
+/// # Panics
+///
+/// Panics if `base` is less than two.
+#[must_use]
+pub fn halton_f32(mut index: u32, base: u32) -> f32 {
+    assert!(base >= 2);
+    let inv_base = 1.0 / base as f32;
+    let mut scale = inv_base;
+    let mut result = 0.0;
+    while index > 0 {
+        result += (index % base) as f32 * scale;
+        index /= base;
+        scale *= inv_base;
+    }
+    // Long sequences can round up to one.
+    result.min(1.0 - f32::EPSILON / 2.0)
+}
+
+/// Returns the sub-pixel jitter for frame `frame` of a temporal anti-aliasing
+/// cycle of length `count`, as an offset in pixels on \[-0.5,0.5).
+///
+/// Uses the Halton (2, 3) sequence, skipping the first element which is
+/// always zero.
+///
+/// # Examples
+///
+/// ```
+/// use narcissus_maths::{HalfTurn, Mat4, halton_jitter, vec2};
+/// let (width, height) = (1920.0, 1080.0);
+/// let jitter = halton_jitter(7, 8) * 2.0 / vec2(width, height);
+/// let clip_from_camera =
+///     Mat4::perspective_rev_inf_zo(HalfTurn::new(1.0 / 3.0), width / height, 0.01)
+///         .jittered(jitter);
+/// ```
+#[must_use]
+pub fn halton_jitter(frame: u32, count: u32) -> Vec2 {
+    let index = frame % count.max(1) + 1;
+    vec2(halton_f32(index, 2) - 0.5, halton_f32(index, 3) - 0.5)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{halton_f32, halton_jitter, vec2};
+
+    #[test]
+    fn sequence() {
+        let base_2 = [0.0, 0.5, 0.25, 0.75, 0.125, 0.625, 0.375, 0.875];
+        for (i, &expected) in base_2.iter().enumerate() {
+            assert_eq!(halton_f32(i as u32, 2), expected);
+        }
+        let base_3 = [0.0, 1.0 / 3.0, 2.0 / 3.0, 1.0 / 9.0, 4.0 / 9.0, 7.0 / 9.0];
+        for (i, &expected) in base_3.iter().enumerate() {
+            assert!((halton_f32(i as u32, 3) - expected).abs() < 1e-7);
+        }
+        assert!(halton_f32(u32::MAX, 2) < 1.0);
+
+        assert_eq!(halton_jitter(0, 8), vec2(0.0, 1.0 / 3.0 - 0.5));
+        assert_eq!(halton_jitter(8, 8), halton_jitter(0, 8));
+        let mut mean = vec2(0.0, 0.0);
+        for frame in 0..16 {
+            let jitter = halton_jitter(frame, 16);
+            assert!(jitter.x >= -0.5 && jitter.x < 0.5);
+            assert!(jitter.y >= -0.5 && jitter.y < 0.5);
+            mean += jitter;
+        }
+        assert!((mean / 16.0).length() < 0.05);
+    }
+}
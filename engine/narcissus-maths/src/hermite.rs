@@ -0,0 +1,215 @@
+use crate::{CubicBezier, Curve, CurveVector, pow_f32};
+
+/// Cubic Hermite curve, defined by endpoints `p0` and `p1` and the tangents
+/// `m0` and `m1` at those endpoints.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Hermite<T> {
+    pub p0: T,
+    pub m0: T,
+    pub p1: T,
+    pub m1: T,
+}
+
+impl<T: CurveVector> Hermite<T> {
+    /// Constructs a new curve from the given endpoints and tangents.
+    #[inline(always)]
+    pub const fn new(p0: T, m0: T, p1: T, m1: T) -> Self {
+        Self { p0, m0, p1, m1 }
+    }
+
+    /// Returns the equivalent cubic Bézier curve.
+    #[inline(always)]
+    #[must_use]
+    pub fn to_cubic_bezier(&self) -> CubicBezier<T> {
+        CubicBezier::new(
+            self.p0,
+            self.p0 + self.m0 * (1.0 / 3.0),
+            self.p1 - self.m1 * (1.0 / 3.0),
+            self.p1,
+        )
+    }
+}
+
+impl<T: CurveVector> Curve for Hermite<T> {
+    type Vector = T;
+
+    #[inline]
+    fn eval(&self, t: f32) -> T {
+        let t2 = t * t;
+        let t3 = t2 * t;
+        let h00 = 2.0 * t3 - 3.0 * t2 + 1.0;
+        let h10 = t3 - 2.0 * t2 + t;
+        let h01 = 3.0 * t2 - 2.0 * t3;
+        let h11 = t3 - t2;
+        self.p0 * h00 + self.m0 * h10 + self.p1 * h01 + self.m1 * h11
+    }
+
+    #[inline]
+    fn derivative(&self, t: f32) -> T {
+        let t2 = t * t;
+        let h00 = 6.0 * (t2 - t);
+        let h10 = 3.0 * t2 - 4.0 * t + 1.0;
+        let h11 = 3.0 * t2 - 2.0 * t;
+        (self.p1 - self.p0) * -h00 + self.m0 * h10 + self.m1 * h11
+    }
+}
+
+/// Catmull-Rom spline segment, which passes through `p1` at `t = 0.0` and `p2`
+/// at `t = 1.0`, with tangents set by the neighbouring points `p0` and `p3`.
+///
+/// `alpha` selects the knot parameterization. `0.0` gives the uniform spline,
+/// while `0.5` gives the centripetal spline, which never forms cusps or loops
+/// within a segment and follows unevenly spaced points more closely.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct CatmullRom<T> {
+    pub p0: T,
+    pub p1: T,
+    pub p2: T,
+    pub p3: T,
+    pub alpha: f32,
+}
+
+impl<T: CurveVector> CatmullRom<T> {
+    /// Constructs a new uniform Catmull-Rom segment.
+    #[inline(always)]
+    pub const fn uniform(p0: T, p1: T, p2: T, p3: T) -> Self {
+        Self {
+            p0,
+            p1,
+            p2,
+            p3,
+            alpha: 0.0,
+        }
+    }
+
+    /// Constructs a new centripetal Catmull-Rom segment.
+    #[inline(always)]
+    pub const fn centripetal(p0: T, p1: T, p2: T, p3: T) -> Self {
+        Self {
+            p0,
+            p1,
+            p2,
+            p3,
+            alpha: 0.5,
+        }
+    }
+
+    /// Returns the equivalent Hermite curve.
+    #[must_use]
+    pub fn to_hermite(&self) -> Hermite<T> {
+        let Self {
+            p0,
+            p1,
+            p2,
+            p3,
+            alpha,
+        } = *self;
+
+        if alpha == 0.0 {
+            return Hermite::new(p1, (p2 - p0) * 0.5, p2, (p3 - p1) * 0.5);
+        }
+
+        // Knot intervals, where coincident points fall back to the uniform
+        // interval to avoid dividing by zero.
+        let knot = |a: T, b: T| {
+            let d = pow_f32((b - a).length_sq(), alpha * 0.5);
+            if d < 1e-6 { 1.0 } else { d }
+        };
+        let t01 = knot(p0, p1);
+        let t12 = knot(p1, p2);
+        let t23 = knot(p2, p3);
+
+        // Tangents of the non-uniform spline, scaled to the [p1,p2] interval.
+        let m1 = ((p1 - p0) * (1.0 / t01) - (p2 - p0) * (1.0 / (t01 + t12))) * t12 + (p2 - p1);
+        let m2 = ((p3 - p2) * (1.0 / t23) - (p3 - p1) * (1.0 / (t12 + t23))) * t12 + (p2 - p1);
+        Hermite::new(p1, m1, p2, m2)
+    }
+
+    /// Returns the equivalent cubic Bézier curve.
+    #[inline]
+    #[must_use]
+    pub fn to_cubic_bezier(&self) -> CubicBezier<T> {
+        self.to_hermite().to_cubic_bezier()
+    }
+}
+
+impl<T: CurveVector> Curve for CatmullRom<T> {
+    type Vector = T;
+
+    #[inline]
+    fn eval(&self, t: f32) -> T {
+        self.to_hermite().eval(t)
+    }
+
+    #[inline]
+    fn derivative(&self, t: f32) -> T {
+        self.to_hermite().derivative(t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CatmullRom, Curve, Hermite, Vec2, vec2};
+
+    #[test]
+    fn hermite_matches_bezier() {
+        let hermite = Hermite::new(
+            vec2(0.0, 0.0),
+            vec2(3.0, 0.0),
+            vec2(1.0, 1.0),
+            vec2(0.0, 3.0),
+        );
+        let bezier = hermite.to_cubic_bezier();
+        assert_eq!(bezier.p1, vec2(1.0, 0.0));
+        assert_eq!(bezier.p2, vec2(1.0, 0.0));
+        assert_eq!(hermite.derivative(0.0), hermite.m0);
+        assert_eq!(hermite.derivative(1.0), hermite.m1);
+        for i in 0..=8 {
+            let t = i as f32 / 8.0;
+            assert!((hermite.eval(t) - bezier.eval(t)).length() < 1e-6);
+            assert!((hermite.derivative(t) - bezier.derivative(t)).length() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn catmull_rom_interpolates() {
+        let points = [
+            vec2(0.0, 0.0),
+            vec2(1.0, 0.0),
+            vec2(1.1, 1.0),
+            vec2(4.0, 1.0),
+            vec2(4.0, 1.0),
+        ];
+        for window in points.windows(4) {
+            let [p0, p1, p2, p3] = [window[0], window[1], window[2], window[3]];
+            for segment in [
+                CatmullRom::uniform(p0, p1, p2, p3),
+                CatmullRom::centripetal(p0, p1, p2, p3),
+            ] {
+                assert!((segment.eval(0.0) - p1).length() < 1e-6);
+                assert!((segment.eval(1.0) - p2).length() < 1e-6);
+                let bezier = segment.to_cubic_bezier();
+                for i in 0..=8 {
+                    let t = i as f32 / 8.0;
+                    let p = segment.eval(t);
+                    assert!(p.x.is_finite() && p.y.is_finite());
+                    assert!((p - bezier.eval(t)).length() < 1e-5);
+                }
+            }
+        }
+
+        // Uniform tangents are half the difference of the neighbours.
+        let segment = CatmullRom::uniform(points[0], points[1], points[2], points[3]);
+        assert_eq!(segment.derivative(0.0), (points[2] - points[0]) * 0.5);
+
+        // Evenly spaced collinear points give the same curve for any alpha.
+        let p = |x: f32| vec2(x, 2.0 * x);
+        let uniform = CatmullRom::uniform(p(0.0), p(1.0), p(2.0), p(3.0));
+        let centripetal = CatmullRom::centripetal(p(0.0), p(1.0), p(2.0), p(3.0));
+        for i in 0..=8 {
+            let t = i as f32 / 8.0;
+            let d: Vec2 = uniform.eval(t) - centripetal.eval(t);
+            assert!(d.length() < 1e-5);
+        }
+    }
+}
@@ -0,0 +1,198 @@
+// HSL and HSV are transforms of the sRGB encoded color components, as defined
+// by CSS, rather than of linear light.
+
+use crate::Srgba;
+
+/// Returns the hue in turns on \[0,1), along with the max and min components.
+#[inline(always)]
+fn hue_max_min(color: Srgba) -> (f32, f32, f32) {
+    let Srgba { r, g, b, .. } = color;
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let d = max - min;
+    let h = if d == 0.0 {
+        0.0
+    } else if max == r {
+        let h = (g - b) / d;
+        if h < 0.0 { h + 6.0 } else { h }
+    } else if max == g {
+        (b - r) / d + 2.0
+    } else {
+        (r - g) / d + 4.0
+    };
+    (h * (1.0 / 6.0), max, min)
+}
+
+/// Color in the HSL color space.
+///
+/// `h` is hue in turns on \[0,1), while `s` is saturation and `l` is lightness,
+/// both on \[0,1\].
+#[derive(Clone, Copy, PartialEq, Default, Debug)]
+#[repr(C)]
+pub struct Hsl {
+    pub h: f32,
+    pub s: f32,
+    pub l: f32,
+}
+
+impl Hsl {
+    /// Constructs a new [`Hsl`] color from the given components.
+    #[inline(always)]
+    #[must_use]
+    pub const fn new(h: f32, s: f32, l: f32) -> Hsl {
+        Hsl { h, s, l }
+    }
+
+    /// Converts the color components of `color` to HSL, discarding alpha.
+    #[inline]
+    #[must_use]
+    pub fn from_srgba(color: Srgba) -> Hsl {
+        let (h, max, min) = hue_max_min(color);
+        let l = (max + min) * 0.5;
+        let d = max - min;
+        let s = if d == 0.0 {
+            0.0
+        } else {
+            d / (1.0 - (2.0 * l - 1.0).abs())
+        };
+        Hsl { h, s, l }
+    }
+
+    /// Converts `self` to sRGB with the given `alpha`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use narcissus_maths::{Hsl, Srgba};
+    /// let color = Hsl::new(1.0 / 3.0, 1.0, 0.5).to_srgba(1.0);
+    /// assert_eq!(color.to_rgba8(), 0xff00ff00);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn to_srgba(self, alpha: f32) -> Srgba {
+        let a = self.s * self.l.min(1.0 - self.l);
+        let f = |n: f32| {
+            let k = (n + self.h * 12.0).rem_euclid(12.0);
+            self.l - a * (k - 3.0).min(9.0 - k).clamp(-1.0, 1.0)
+        };
+        Srgba::new(f(0.0), f(8.0), f(4.0), alpha)
+    }
+}
+
+/// Color in the HSV color space.
+///
+/// `h` is hue in turns on \[0,1), while `s` is saturation and `v` is value,
+/// both on \[0,1\].
+#[derive(Clone, Copy, PartialEq, Default, Debug)]
+#[repr(C)]
+pub struct Hsv {
+    pub h: f32,
+    pub s: f32,
+    pub v: f32,
+}
+
+impl Hsv {
+    /// Constructs a new [`Hsv`] color from the given components.
+    #[inline(always)]
+    #[must_use]
+    pub const fn new(h: f32, s: f32, v: f32) -> Hsv {
+        Hsv { h, s, v }
+    }
+
+    /// Converts the color components of `color` to HSV, discarding alpha.
+    #[inline]
+    #[must_use]
+    pub fn from_srgba(color: Srgba) -> Hsv {
+        let (h, max, min) = hue_max_min(color);
+        let s = if max == 0.0 { 0.0 } else { (max - min) / max };
+        Hsv { h, s, v: max }
+    }
+
+    /// Converts `self` to sRGB with the given `alpha`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use narcissus_maths::{Hsv, Srgba};
+    /// let color = Hsv::new(2.0 / 3.0, 1.0, 1.0).to_srgba(1.0);
+    /// assert_eq!(color.to_rgba8(), 0xffff0000);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn to_srgba(self, alpha: f32) -> Srgba {
+        let f = |n: f32| {
+            let k = (n + self.h * 6.0).rem_euclid(6.0);
+            self.v - self.v * self.s * k.min(4.0 - k).clamp(0.0, 1.0)
+        };
+        Srgba::new(f(5.0), f(3.0), f(1.0), alpha)
+    }
+}
+
+impl From<Hsl> for Hsv {
+    #[inline]
+    fn from(hsl: Hsl) -> Self {
+        let v = hsl.l + hsl.s * hsl.l.min(1.0 - hsl.l);
+        let s = if v == 0.0 {
+            0.0
+        } else {
+            2.0 * (1.0 - hsl.l / v)
+        };
+        Hsv { h: hsl.h, s, v }
+    }
+}
+
+impl From<Hsv> for Hsl {
+    #[inline]
+    fn from(hsv: Hsv) -> Self {
+        let l = hsv.v * (1.0 - hsv.s * 0.5);
+        let s = if l == 0.0 || l == 1.0 {
+            0.0
+        } else {
+            (hsv.v - l) / l.min(1.0 - l)
+        };
+        Hsl { h: hsv.h, s, l }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Hsl, Hsv, Srgba};
+
+    #[test]
+    fn primaries() {
+        for (hex, h) in [
+            (0xff0000ff, 0.0),
+            (0xffff00ff, 1.0 / 6.0),
+            (0x00ff00ff, 2.0 / 6.0),
+            (0x00ffffff, 3.0 / 6.0),
+            (0x0000ffff, 4.0 / 6.0),
+            (0xff00ffff, 5.0 / 6.0),
+        ] {
+            let color = Srgba::from_hex(hex);
+            let hsl = Hsl::from_srgba(color);
+            let hsv = Hsv::from_srgba(color);
+            assert!((hsl.h - h).abs() < 1e-6 && hsv.h == hsl.h);
+            assert_eq!((hsl.s, hsl.l), (1.0, 0.5));
+            assert_eq!((hsv.s, hsv.v), (1.0, 1.0));
+        }
+
+        assert_eq!(Hsl::from_srgba(Srgba::BLACK), Hsl::new(0.0, 0.0, 0.0));
+        assert_eq!(Hsl::from_srgba(Srgba::WHITE), Hsl::new(0.0, 0.0, 1.0));
+        assert_eq!(Hsv::from_srgba(Srgba::BLACK), Hsv::new(0.0, 0.0, 0.0));
+        assert_eq!(Hsv::from_srgba(Srgba::WHITE), Hsv::new(0.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn round_trip() {
+        for i in 0..4096_u32 {
+            let color = Srgba::from_rgba8(i.wrapping_mul(0x9e37_79b9) | 0xff00_0000);
+            let hsl = Hsl::from_srgba(color);
+            let hsv = Hsv::from_srgba(color);
+            assert!((0.0..1.0).contains(&hsl.h));
+            assert_eq!(hsl.to_srgba(1.0).to_rgba8(), color.to_rgba8());
+            assert_eq!(hsv.to_srgba(1.0).to_rgba8(), color.to_rgba8());
+            assert_eq!(Hsv::from(hsl).to_srgba(1.0).to_rgba8(), color.to_rgba8());
+            assert_eq!(Hsl::from(hsv).to_srgba(1.0).to_rgba8(), color.to_rgba8());
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use crate::{UVec2, Vec2, impl_int_vector};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default, Debug)]
+#[repr(C)]
+pub struct IVec2 {
+    pub x: i32,
+    pub y: i32,
+}
+
+pub const fn ivec2(x: i32, y: i32) -> IVec2 {
+    IVec2 { x, y }
+}
+
+impl_int_vector!(IVec2, i32, 2);
+
+impl IVec2 {
+    pub const X: IVec2 = IVec2::new(1, 0);
+    pub const Y: IVec2 = IVec2::new(0, 1);
+
+    /// Constructs a new [`IVec2`] with the given `x` and `y` components.
+    #[inline(always)]
+    #[must_use]
+    pub const fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+
+    /// Returns a [`IVec2`] with the function `f` applied to each component in order.
+    #[inline(always)]
+    #[must_use]
+    pub fn map<F>(self, mut f: F) -> IVec2
+    where
+        F: FnMut(i32) -> i32,
+    {
+        IVec2 {
+            x: f(self.x),
+            y: f(self.y),
+        }
+    }
+
+    /// Returns a new [`IVec2`] with the function `f` applied to each pair of
+    /// components from `self` and `rhs` in order.
+    #[inline(always)]
+    #[must_use]
+    pub fn map2<F>(self, rhs: IVec2, mut f: F) -> IVec2
+    where
+        F: FnMut(i32, i32) -> i32,
+    {
+        IVec2 {
+            x: f(self.x, rhs.x),
+            y: f(self.y, rhs.y),
+        }
+    }
+
+    /// Returns the dot product of `a` and `b`.
+    #[inline(always)]
+    #[must_use]
+    pub fn dot(a: Self, b: Self) -> i32 {
+        a.x * b.x + a.y * b.y
+    }
+
+    /// Returns a [`IVec2`] where each element is the absolute value of the
+    /// corresponding element in `self`.
+    #[inline(always)]
+    #[must_use]
+    pub fn abs(self) -> IVec2 {
+        self.map(i32::abs)
+    }
+
+    /// Returns a [`IVec2`] where each element is the quotient of the
+    /// corresponding elements in `self` and `rhs`, rounded towards negative
+    /// infinity.
+    #[inline(always)]
+    #[must_use]
+    pub fn div_floor(self, rhs: IVec2) -> IVec2 {
+        self.map2(rhs, div_floor_i32)
+    }
+
+    /// Returns a [`IVec2`] where each element is the quotient of the
+    /// corresponding elements in `self` and `rhs`, rounded towards positive
+    /// infinity.
+    #[inline(always)]
+    #[must_use]
+    pub fn div_ceil(self, rhs: IVec2) -> IVec2 {
+        self.map2(rhs, div_ceil_i32)
+    }
+
+    /// Converts to a [`Vec2`], rounding to the nearest representable value.
+    #[inline(always)]
+    #[must_use]
+    pub fn as_vec2(self) -> Vec2 {
+        Vec2::new(self.x as f32, self.y as f32)
+    }
+
+    /// Converts to a [`UVec2`], wrapping negative components.
+    #[inline(always)]
+    #[must_use]
+    pub fn as_uvec2(self) -> UVec2 {
+        UVec2::new(self.x as u32, self.y as u32)
+    }
+}
+
+#[inline(always)]
+pub(crate) fn div_floor_i32(a: i32, b: i32) -> i32 {
+    let (q, r) = (a / b, a % b);
+    if r != 0 && (r < 0) != (b < 0) {
+        q - 1
+    } else {
+        q
+    }
+}
+
+#[inline(always)]
+pub(crate) fn div_ceil_i32(a: i32, b: i32) -> i32 {
+    let (q, r) = (a / b, a % b);
+    if r != 0 && (r < 0) == (b < 0) {
+        q + 1
+    } else {
+        q
+    }
+}
+
+impl std::ops::Neg for IVec2 {
+    type Output = IVec2;
+    #[inline(always)]
+    fn neg(self) -> Self::Output {
+        self.map(|x| -x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{IVec2, ivec2, uvec2, vec2};
+
+    #[test]
+    fn rounding_division() {
+        let a = ivec2(7, -7);
+        assert_eq!(a / IVec2::splat(2), ivec2(3, -3));
+        assert_eq!(a.div_floor(IVec2::splat(2)), ivec2(3, -4));
+        assert_eq!(a.div_ceil(IVec2::splat(2)), ivec2(4, -3));
+        assert_eq!(a.div_floor(IVec2::splat(-2)), ivec2(-4, 3));
+        assert_eq!(a.div_ceil(IVec2::splat(-2)), ivec2(-3, 4));
+        assert_eq!(ivec2(8, -8).div_floor(IVec2::splat(4)), ivec2(2, -2));
+        assert_eq!(ivec2(8, -8).div_ceil(IVec2::splat(4)), ivec2(2, -2));
+    }
+
+    #[test]
+    fn conversions() {
+        assert_eq!(ivec2(-3, 4).as_vec2(), vec2(-3.0, 4.0));
+        assert_eq!(vec2(-3.7, 4.7).as_ivec2(), ivec2(-3, 4));
+        assert_eq!(vec2(-3.7, 4.7).floor().as_ivec2(), ivec2(-4, 4));
+        assert_eq!(vec2(f32::NAN, 1e20).as_ivec2(), ivec2(0, i32::MAX));
+        assert_eq!(ivec2(1, 2).as_uvec2(), uvec2(1, 2));
+        assert_eq!(IVec2::from([1, 2]), ivec2(1, 2));
+        assert_eq!(<[i32; 2]>::from(-ivec2(1, 2)), [-1, -2]);
+        assert_eq!(
+            IVec2::clamp(ivec2(-5, 5), IVec2::ZERO, IVec2::splat(3)),
+            ivec2(0, 3)
+        );
+    }
+}
@@ -0,0 +1,106 @@
+use crate::ivec2::{div_ceil_i32, div_floor_i32};
+use crate::{Vec3, impl_int_vector};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default, Debug)]
+#[repr(C)]
+pub struct IVec3 {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+pub const fn ivec3(x: i32, y: i32, z: i32) -> IVec3 {
+    IVec3 { x, y, z }
+}
+
+impl_int_vector!(IVec3, i32, 3);
+
+impl IVec3 {
+    pub const X: IVec3 = IVec3::new(1, 0, 0);
+    pub const Y: IVec3 = IVec3::new(0, 1, 0);
+    pub const Z: IVec3 = IVec3::new(0, 0, 1);
+
+    /// Constructs a new [`IVec3`] with the given `x`, `y` and `z` components.
+    #[inline(always)]
+    #[must_use]
+    pub const fn new(x: i32, y: i32, z: i32) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Returns a [`IVec3`] with the function `f` applied to each component in order.
+    #[inline(always)]
+    #[must_use]
+    pub fn map<F>(self, mut f: F) -> IVec3
+    where
+        F: FnMut(i32) -> i32,
+    {
+        IVec3 {
+            x: f(self.x),
+            y: f(self.y),
+            z: f(self.z),
+        }
+    }
+
+    /// Returns a new [`IVec3`] with the function `f` applied to each pair of
+    /// components from `self` and `rhs` in order.
+    #[inline(always)]
+    #[must_use]
+    pub fn map2<F>(self, rhs: IVec3, mut f: F) -> IVec3
+    where
+        F: FnMut(i32, i32) -> i32,
+    {
+        IVec3 {
+            x: f(self.x, rhs.x),
+            y: f(self.y, rhs.y),
+            z: f(self.z, rhs.z),
+        }
+    }
+
+    /// Returns the dot product of `a` and `b`.
+    #[inline(always)]
+    #[must_use]
+    pub fn dot(a: Self, b: Self) -> i32 {
+        a.x * b.x + a.y * b.y + a.z * b.z
+    }
+
+    /// Returns a [`IVec3`] where each element is the absolute value of the
+    /// corresponding element in `self`.
+    #[inline(always)]
+    #[must_use]
+    pub fn abs(self) -> IVec3 {
+        self.map(i32::abs)
+    }
+
+    /// Returns a [`IVec3`] where each element is the quotient of the
+    /// corresponding elements in `self` and `rhs`, rounded towards negative
+    /// infinity.
+    #[inline(always)]
+    #[must_use]
+    pub fn div_floor(self, rhs: IVec3) -> IVec3 {
+        self.map2(rhs, div_floor_i32)
+    }
+
+    /// Returns a [`IVec3`] where each element is the quotient of the
+    /// corresponding elements in `self` and `rhs`, rounded towards positive
+    /// infinity.
+    #[inline(always)]
+    #[must_use]
+    pub fn div_ceil(self, rhs: IVec3) -> IVec3 {
+        self.map2(rhs, div_ceil_i32)
+    }
+
+    /// Converts to a [`Vec3`], rounding to the nearest representable value.
+    #[inline(always)]
+    #[must_use]
+    pub fn as_vec3(self) -> Vec3 {
+        Vec3::new(self.x as f32, self.y as f32, self.z as f32)
+    }
+}
+
+impl std::ops::Neg for IVec3 {
+    type Output = IVec3;
+    #[inline(always)]
+    fn neg(self) -> Self::Output {
+        self.map(|x| -x)
+    }
+}
@@ -1,34 +1,120 @@
+mod aabb2;
+mod aabb3;
 mod affine2;
 mod affine3;
+mod asin_acos_pi;
+mod atan_pi;
+mod bezier;
+mod bvh;
+mod color;
+mod curve;
+mod dynamic_bvh;
+mod easing;
 mod exp;
+mod fixed;
+mod fractal;
+mod frustum;
+mod halton;
+mod hermite;
+mod hsl;
+mod ivec2;
+mod ivec3;
+mod log;
 mod mat2;
 mod mat3;
 mod mat4;
+mod oklab;
 mod perlin;
+mod plane;
+mod pod;
 mod point2;
 mod point3;
+mod polygon;
+mod pow;
 mod quat;
+mod ray3;
+mod rect;
+#[cfg(test)]
+mod reference;
+mod simplex;
 mod sin_cos_pi;
+mod sphere;
+mod spring;
 mod tan_pi;
+mod uvec2;
 mod vec2;
 mod vec3;
 mod vec4;
+mod wide_f32x4;
+mod wide_f32x8;
+mod wide_vec3;
+mod worley;
 
+pub use aabb2::Aabb2;
+pub use aabb3::Aabb3;
 pub use affine2::Affine2;
 pub use affine3::Affine3;
+pub use asin_acos_pi::{acos_pi_f32, asin_pi_f32};
+pub use atan_pi::{atan_pi_f32, atan2_pi_f32};
+pub use bezier::{CubicBezier, QuadraticBezier};
+pub use bvh::{Bvh, BvhNode, TriangleBvh};
+pub use color::{
+    LinearRgba, Srgba, linear_to_srgb_f32, linear_to_srgb_fast_f32, srgb_to_linear_f32,
+    srgb_to_linear_fast_f32,
+};
+pub use curve::{ArcLength, Curve, CurveVector};
+pub use dynamic_bvh::{BvhProxy, DynamicBvh, DynamicBvhNode};
+pub use easing::{Easing, smootherstep, smoothstep};
 pub use exp::exp_f32;
+pub use fixed::{Fixed32, Fixed64, FixedVec2, FixedVec3};
+pub use fractal::{fbm_noise, ridged_noise};
+pub use frustum::Frustum;
+pub use halton::{halton_f32, halton_jitter};
+pub use hermite::{CatmullRom, Hermite};
+pub use hsl::{Hsl, Hsv};
+pub use ivec2::{IVec2, ivec2};
+pub use ivec3::{IVec3, ivec3};
+pub use log::{ln_f32, log2_f32};
 pub use mat2::Mat2;
 pub use mat3::Mat3;
 pub use mat4::Mat4;
-pub use perlin::{perlin_noise3, perlin_noise3_wrap, perlin_noise3_wrap_seed};
+pub use oklab::{Oklab, Oklch};
+pub use perlin::{
+    perlin_noise2, perlin_noise2_deriv, perlin_noise2_f32x4, perlin_noise2_wrap_seed,
+    perlin_noise3, perlin_noise3_deriv, perlin_noise3_f32x4, perlin_noise3_wrap,
+    perlin_noise3_wrap_seed, perlin_noise4, perlin_noise4_wrap_seed,
+};
+pub use plane::Plane;
 pub use point2::{Point2, point2};
 pub use point3::{Point3, point3};
+pub use polygon::{
+    LineCap, LineJoin, Winding, convex_hull, offset_polyline, point_in_polygon,
+    polygon_signed_area, polygon_winding, stroke_polyline, triangulate_polygon,
+};
+pub use pow::pow_f32;
 pub use quat::Quat;
+pub use ray3::Ray3;
+pub use rect::{IRect, Rect};
+pub use simplex::{
+    simplex_noise2, simplex_noise2_deriv, simplex_noise2_seed, simplex_noise3,
+    simplex_noise3_deriv, simplex_noise3_seed,
+};
 pub use sin_cos_pi::{cos_pi_f32, sin_cos_pi_f32, sin_cos_pi_f32x4, sin_pi_f32};
+pub use sphere::Sphere;
+pub use spring::{
+    SpringValue, critical_damping, critical_spring_damper, critical_spring_damper_quat,
+    damping_to_halflife, exp_smooth, exp_smooth_quat, frequency_to_stiffness, halflife_to_damping,
+    spring_damper, velocity_spring,
+};
 pub use tan_pi::tan_pi_f32;
+pub use uvec2::{UVec2, uvec2};
 pub use vec2::{Vec2, vec2};
 pub use vec3::{Vec3, vec3};
 pub use vec4::{Vec4, vec4};
+pub use wide_f32x4::{f32x4, m32x4};
+pub use wide_f32x8::{f32x8, m32x8};
+pub use wide_vec3::{Vec3x4, Vec3x8};
+pub use worley::{worley_noise2, worley_noise3};
 
 /// Unit type for an angle expressed in radians.
 #[derive(Clone, Copy, PartialEq, PartialOrd, Debug, Default)]
@@ -496,6 +582,169 @@ macro_rules! impl_vector {
     };
 }
 
+#[macro_export]
+macro_rules! impl_int_vector {
+    ($name:ty, $t:ty, $n:expr) => {
+        impl $name {
+            #[doc = concat!("[`", stringify!($name), "`] with all elements initialized to `0`.")]
+            pub const ZERO: $name = Self::splat(0);
+            #[doc = concat!("[`", stringify!($name), "`] with all elements initialized to `1`.")]
+            pub const ONE: $name = Self::splat(1);
+
+            #[doc = concat!("Constructs a new [`", stringify!($name), "`] where each element is initialized with the given `value`.")]
+            #[inline(always)]
+            #[must_use]
+            pub const fn splat(value: $t) -> $name {
+                // SAFETY: $name is repr(C) struct with $n elements of type $t, so the transmute is always valid.
+                unsafe { std::mem::transmute([value; $n]) }
+            }
+
+            #[doc = concat!("Returns a [`", stringify!($name), "`] where each element is initialized with the minimum of the corresponding elements in `a` and `b`.")]
+            #[inline(always)]
+            #[must_use]
+            pub fn min(a: $name, b: $name) -> $name {
+                a.map2(b, #[inline(always)] |a, b| a.min(b))
+            }
+
+            #[doc = concat!("Returns a [`", stringify!($name), "`] where each element is initialized with the maximum of the corresponding elements in `a` and `b`.")]
+            #[inline(always)]
+            #[must_use]
+            pub fn max(a: $name, b: $name) -> $name {
+                a.map2(b, #[inline(always)] |a, b| a.max(b))
+            }
+
+            #[doc = concat!("Returns a [`", stringify!($name), "`] where the `i`th element `x[i]` is clamped between the corresponding elements `lo[i]` and `hi[i]`.\n\n# Panics\n\nPanics if any element of `lo` is greater than its corresponding element in `hi`.")]
+            #[inline(always)]
+            #[must_use]
+            pub fn clamp(x: $name, lo: $name, hi: $name) -> $name {
+                Self::max(Self::min(x, hi), lo)
+            }
+        }
+
+        impl From<[$t; $n]> for $name {
+            #[inline(always)]
+            fn from(x: [$t; $n]) -> $name {
+                unsafe { std::mem::transmute(x) }
+            }
+        }
+
+        impl From<$name> for [$t; $n] {
+            #[inline(always)]
+            fn from(x: $name) -> [$t; $n] {
+                unsafe { std::mem::transmute(x) }
+            }
+        }
+
+        impl std::ops::Add for $name {
+            type Output = $name;
+            #[inline(always)]
+            fn add(self, rhs: Self) -> Self::Output {
+                self.map2(rhs, #[inline(always)] |a, b| a + b)
+            }
+        }
+
+        impl std::ops::Sub for $name {
+            type Output = $name;
+            #[inline(always)]
+            fn sub(self, rhs: Self) -> Self::Output {
+                self.map2(rhs, #[inline(always)] |a, b| a - b)
+            }
+        }
+
+        impl std::ops::Mul for $name {
+            type Output = $name;
+            #[inline(always)]
+            fn mul(self, rhs: Self) -> Self::Output {
+                self.map2(rhs, #[inline(always)] |a, b| a * b)
+            }
+        }
+
+        impl std::ops::Div for $name {
+            type Output = $name;
+            #[inline(always)]
+            fn div(self, rhs: Self) -> Self::Output {
+                self.map2(rhs, #[inline(always)] |a, b| a / b)
+            }
+        }
+
+        impl std::ops::Rem for $name {
+            type Output = $name;
+            #[inline(always)]
+            fn rem(self, rhs: Self) -> Self::Output {
+                self.map2(rhs, #[inline(always)] |a, b| a % b)
+            }
+        }
+
+        impl std::ops::Add<$t> for $name {
+            type Output = $name;
+            #[inline(always)]
+            fn add(self, rhs: $t) -> Self::Output {
+                self.map(#[inline(always)] |x| x + rhs)
+            }
+        }
+
+        impl std::ops::Sub<$t> for $name {
+            type Output = $name;
+            #[inline(always)]
+            fn sub(self, rhs: $t) -> Self::Output {
+                self.map(#[inline(always)] |x| x - rhs)
+            }
+        }
+
+        impl std::ops::Mul<$t> for $name {
+            type Output = $name;
+            #[inline(always)]
+            fn mul(self, rhs: $t) -> Self::Output {
+                self.map(#[inline(always)] |x| x * rhs)
+            }
+        }
+
+        impl std::ops::Div<$t> for $name {
+            type Output = $name;
+            #[inline(always)]
+            fn div(self, rhs: $t) -> Self::Output {
+                self.map(#[inline(always)] |x| x / rhs)
+            }
+        }
+
+        impl std::ops::Rem<$t> for $name {
+            type Output = $name;
+            #[inline(always)]
+            fn rem(self, rhs: $t) -> Self::Output {
+                self.map(#[inline(always)] |x| x % rhs)
+            }
+        }
+
+        impl std::ops::AddAssign for $name {
+            #[inline(always)]
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl std::ops::SubAssign for $name {
+            #[inline(always)]
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl std::ops::MulAssign for $name {
+            #[inline(always)]
+            fn mul_assign(&mut self, rhs: Self) {
+                *self = *self * rhs;
+            }
+        }
+
+        impl std::ops::DivAssign for $name {
+            #[inline(always)]
+            fn div_assign(&mut self, rhs: Self) {
+                *self = *self / rhs;
+            }
+        }
+    };
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{dequantize_unorm_u8, quantize_unorm_u8};
@@ -508,3 +757,634 @@ mod tests {
         assert_eq!(dequantize_unorm_u8(0), 0.0);
     }
 }
+
+/// Implements the lane-wise operations of a wide `f32` type `$f` and its mask type
+/// `$m`, each wrapping an array of `$n` lanes.
+///
+/// Operations use the given intrinsics when `$feature` is enabled (and `fma` for
+/// fused multiply-add, `$round_feature` for rounding), otherwise falling back to
+/// scalar code.
+#[macro_export]
+macro_rules! impl_wide {
+    (
+        $f:ident, $m:ident, $n:literal, $v:ty, $feature:literal, $round_feature:literal,
+        {
+            add: $add:expr,
+            sub: $sub:expr,
+            mul: $mul:expr,
+            div: $div:expr,
+            min: $min:expr,
+            max: $max:expr,
+            sqrt: $sqrt:expr,
+            floor: $floor:expr,
+            ceil: $ceil:expr,
+            fmadd: $fmadd:expr,
+            cmp_eq: $cmp_eq:expr,
+            cmp_ne: $cmp_ne:expr,
+            cmp_lt: $cmp_lt:expr,
+            cmp_le: $cmp_le:expr,
+            movemask: $movemask:expr,
+            set1: $set1:expr,
+            and: $and:expr,
+            andnot: $andnot:expr,
+            or: $or:expr,
+            xor: $xor:expr $(,)?
+        }
+    ) => {
+        impl $f {
+            pub const LANES: usize = $n;
+
+            #[doc = concat!("[`", stringify!($f), "`] with all lanes initialized to `0.0`.")]
+            pub const ZERO: $f = $f::splat(0.0);
+            #[doc = concat!("[`", stringify!($f), "`] with all lanes initialized to `1.0`.")]
+            pub const ONE: $f = $f::splat(1.0);
+
+            #[doc = concat!("Constructs a new [`", stringify!($f), "`] where each lane is initialized with the given `value`.")]
+            #[inline(always)]
+            #[must_use]
+            pub const fn splat(value: f32) -> $f {
+                $f([value; $n])
+            }
+
+            #[inline(always)]
+            #[must_use]
+            pub const fn from_array(array: [f32; $n]) -> $f {
+                $f(array)
+            }
+
+            #[inline(always)]
+            #[must_use]
+            pub const fn to_array(self) -> [f32; $n] {
+                self.0
+            }
+
+            /// Loads the lanes from `src`, for example from a structure-of-arrays block.
+            #[inline(always)]
+            #[must_use]
+            pub fn load(src: &[f32; $n]) -> $f {
+                $f(*src)
+            }
+
+            /// Stores the lanes of `self` into `dst`.
+            #[inline(always)]
+            pub fn store(self, dst: &mut [f32; $n]) {
+                *dst = self.0
+            }
+
+            #[doc = concat!("Returns a [`", stringify!($f), "`] with the function `f` applied to each lane in order.")]
+            #[inline(always)]
+            #[must_use]
+            pub fn map<F>(self, f: F) -> $f
+            where
+                F: FnMut(f32) -> f32,
+            {
+                $f(self.0.map(f))
+            }
+
+            #[doc = concat!("Returns a [`", stringify!($f), "`] with the function `f` applied to each pair of lanes from `self` and `rhs` in order.")]
+            #[inline(always)]
+            #[must_use]
+            pub fn map2<F>(self, rhs: $f, mut f: F) -> $f
+            where
+                F: FnMut(f32, f32) -> f32,
+            {
+                $f(std::array::from_fn(|i| f(self.0[i], rhs.0[i])))
+            }
+
+            #[allow(unused)]
+            #[inline(always)]
+            fn cmp<F>(self, rhs: $f, f: F) -> $m
+            where
+                F: Fn(f32, f32) -> bool,
+            {
+                $m(std::array::from_fn(|i| {
+                    if f(self.0[i], rhs.0[i]) { !0 } else { 0 }
+                }))
+            }
+
+            /// Returns the lane-wise minimum of `a` and `b`.
+            ///
+            /// If either lane is `NaN` the result is platform dependent, see
+            /// [`crate::min`].
+            #[inline(always)]
+            #[must_use]
+            pub fn min(a: $f, b: $f) -> $f {
+                #[cfg(not(all(target_arch = "x86_64", target_feature = $feature)))]
+                {
+                    a.map2(b, $crate::min)
+                }
+
+                #[cfg(all(target_arch = "x86_64", target_feature = $feature))]
+                {
+                    let min = $min;
+                    unsafe {
+                        min(a.into(), b.into()).into()
+                    }
+                }
+            }
+
+            /// Returns the lane-wise maximum of `a` and `b`.
+            ///
+            /// If either lane is `NaN` the result is platform dependent, see
+            /// [`crate::max`].
+            #[inline(always)]
+            #[must_use]
+            pub fn max(a: $f, b: $f) -> $f {
+                #[cfg(not(all(target_arch = "x86_64", target_feature = $feature)))]
+                {
+                    a.map2(b, $crate::max)
+                }
+
+                #[cfg(all(target_arch = "x86_64", target_feature = $feature))]
+                {
+                    let max = $max;
+                    unsafe {
+                        max(a.into(), b.into()).into()
+                    }
+                }
+            }
+
+            /// Returns each lane of `x` clamped between the corresponding lanes of `lo`
+            /// and `hi`.
+            #[inline(always)]
+            #[must_use]
+            pub fn clamp(x: $f, lo: $f, hi: $f) -> $f {
+                Self::max(Self::min(x, hi), lo)
+            }
+
+            #[inline(always)]
+            #[must_use]
+            pub fn abs(self) -> $f {
+                #[cfg(not(all(target_arch = "x86_64", target_feature = $feature)))]
+                {
+                    self.map(f32::abs)
+                }
+
+                #[cfg(all(target_arch = "x86_64", target_feature = $feature))]
+                {
+                    let andnot = $andnot;
+                    let set1 = $set1;
+                    unsafe {
+                        andnot(set1(-0.0), self.into()).into()
+                    }
+                }
+            }
+
+            #[inline(always)]
+            #[must_use]
+            pub fn sqrt(self) -> $f {
+                #[cfg(not(all(target_arch = "x86_64", target_feature = $feature)))]
+                {
+                    self.map(f32::sqrt)
+                }
+
+                #[cfg(all(target_arch = "x86_64", target_feature = $feature))]
+                {
+                    let sqrt = $sqrt;
+                    unsafe {
+                        sqrt(self.into()).into()
+                    }
+                }
+            }
+
+            #[inline(always)]
+            #[must_use]
+            pub fn floor(self) -> $f {
+                #[cfg(not(all(target_arch = "x86_64", target_feature = $round_feature)))]
+                {
+                    self.map(f32::floor)
+                }
+
+                #[cfg(all(target_arch = "x86_64", target_feature = $round_feature))]
+                {
+                    let floor = $floor;
+                    unsafe {
+                        floor(self.into()).into()
+                    }
+                }
+            }
+
+            #[inline(always)]
+            #[must_use]
+            pub fn ceil(self) -> $f {
+                #[cfg(not(all(target_arch = "x86_64", target_feature = $round_feature)))]
+                {
+                    self.map(f32::ceil)
+                }
+
+                #[cfg(all(target_arch = "x86_64", target_feature = $round_feature))]
+                {
+                    let ceil = $ceil;
+                    unsafe {
+                        ceil(self.into()).into()
+                    }
+                }
+            }
+
+            /// Computes `self * a + b` with a single rounding.
+            #[inline(always)]
+            #[must_use]
+            pub fn mul_add(self, a: $f, b: $f) -> $f {
+                #[cfg(not(all(target_arch = "x86_64", target_feature = "fma")))]
+                {
+                    $f(std::array::from_fn(|i| self.0[i].mul_add(a.0[i], b.0[i])))
+                }
+
+                #[cfg(all(target_arch = "x86_64", target_feature = "fma"))]
+                {
+                    let fmadd = $fmadd;
+                    unsafe {
+                        fmadd(self.into(), a.into(), b.into()).into()
+                    }
+                }
+            }
+
+            /// Returns the sum of all lanes.
+            ///
+            /// Adjacent lanes are summed pairwise.
+            #[inline(always)]
+            #[must_use]
+            pub fn reduce_add(self) -> f32 {
+                let mut lanes = self.0;
+                let mut n = $n;
+                while n > 1 {
+                    n /= 2;
+                    for i in 0..n {
+                        lanes[i] = lanes[2 * i] + lanes[2 * i + 1];
+                    }
+                }
+                lanes[0]
+            }
+
+            /// Returns the minimum of all lanes.
+            #[inline(always)]
+            #[must_use]
+            pub fn reduce_min(self) -> f32 {
+                self.0.into_iter().reduce($crate::min).unwrap()
+            }
+
+            /// Returns the maximum of all lanes.
+            #[inline(always)]
+            #[must_use]
+            pub fn reduce_max(self) -> f32 {
+                self.0.into_iter().reduce($crate::max).unwrap()
+            }
+
+            /// Returns a mask of the lanes where `self == rhs`.
+            #[inline(always)]
+            #[must_use]
+            pub fn cmp_eq(self, rhs: $f) -> $m {
+                #[cfg(not(all(target_arch = "x86_64", target_feature = $feature)))]
+                {
+                    self.cmp(rhs, |a, b| a == b)
+                }
+
+                #[cfg(all(target_arch = "x86_64", target_feature = $feature))]
+                {
+                    let cmp_eq = $cmp_eq;
+                    unsafe {
+                        cmp_eq(self.into(), rhs.into()).into()
+                    }
+                }
+            }
+
+            /// Returns a mask of the lanes where `self != rhs`, including lanes where
+            /// either value is `NaN`.
+            #[inline(always)]
+            #[must_use]
+            pub fn cmp_ne(self, rhs: $f) -> $m {
+                #[cfg(not(all(target_arch = "x86_64", target_feature = $feature)))]
+                {
+                    self.cmp(rhs, |a, b| a != b)
+                }
+
+                #[cfg(all(target_arch = "x86_64", target_feature = $feature))]
+                {
+                    let cmp_ne = $cmp_ne;
+                    unsafe {
+                        cmp_ne(self.into(), rhs.into()).into()
+                    }
+                }
+            }
+
+            /// Returns a mask of the lanes where `self < rhs`.
+            #[inline(always)]
+            #[must_use]
+            pub fn cmp_lt(self, rhs: $f) -> $m {
+                #[cfg(not(all(target_arch = "x86_64", target_feature = $feature)))]
+                {
+                    self.cmp(rhs, |a, b| a < b)
+                }
+
+                #[cfg(all(target_arch = "x86_64", target_feature = $feature))]
+                {
+                    let cmp_lt = $cmp_lt;
+                    unsafe {
+                        cmp_lt(self.into(), rhs.into()).into()
+                    }
+                }
+            }
+
+            /// Returns a mask of the lanes where `self <= rhs`.
+            #[inline(always)]
+            #[must_use]
+            pub fn cmp_le(self, rhs: $f) -> $m {
+                #[cfg(not(all(target_arch = "x86_64", target_feature = $feature)))]
+                {
+                    self.cmp(rhs, |a, b| a <= b)
+                }
+
+                #[cfg(all(target_arch = "x86_64", target_feature = $feature))]
+                {
+                    let cmp_le = $cmp_le;
+                    unsafe {
+                        cmp_le(self.into(), rhs.into()).into()
+                    }
+                }
+            }
+
+            /// Returns a mask of the lanes where `self > rhs`.
+            #[inline(always)]
+            #[must_use]
+            pub fn cmp_gt(self, rhs: $f) -> $m {
+                rhs.cmp_lt(self)
+            }
+
+            /// Returns a mask of the lanes where `self >= rhs`.
+            #[inline(always)]
+            #[must_use]
+            pub fn cmp_ge(self, rhs: $f) -> $m {
+                rhs.cmp_le(self)
+            }
+        }
+
+        impl $m {
+            pub const LANES: usize = $n;
+
+            #[doc = concat!("[`", stringify!($m), "`] with all lanes set to `true`.")]
+            pub const TRUE: $m = $m([!0; $n]);
+            #[doc = concat!("[`", stringify!($m), "`] with all lanes set to `false`.")]
+            pub const FALSE: $m = $m([0; $n]);
+
+            #[doc = concat!("Constructs a new [`", stringify!($m), "`] where each lane is initialized with the given `value`.")]
+            #[inline(always)]
+            #[must_use]
+            pub const fn splat(value: bool) -> $m {
+                if value { Self::TRUE } else { Self::FALSE }
+            }
+
+            #[inline(always)]
+            #[must_use]
+            pub fn from_array(array: [bool; $n]) -> $m {
+                $m(array.map(|x| if x { !0 } else { 0 }))
+            }
+
+            #[inline(always)]
+            #[must_use]
+            pub fn to_array(self) -> [bool; $n] {
+                self.0.map(|x| x != 0)
+            }
+
+            #[doc = concat!("Constructs a mask from the low ", stringify!($n), " bits of `bitmask`, where bit `i` sets lane `i`.")]
+            #[inline(always)]
+            #[must_use]
+            pub fn from_bitmask(bitmask: u8) -> $m {
+                $m(std::array::from_fn(|i| {
+                    if bitmask & (1 << i) != 0 { !0 } else { 0 }
+                }))
+            }
+
+            /// Returns a bitmask where bit `i` is set if lane `i` is `true`.
+            #[inline(always)]
+            #[must_use]
+            pub fn to_bitmask(self) -> u8 {
+                #[cfg(not(all(target_arch = "x86_64", target_feature = $feature)))]
+                {
+                    self.0
+                        .iter()
+                        .enumerate()
+                        .fold(0, |acc, (i, &x)| acc | ((x >> 31) as u8) << i)
+                }
+
+                #[cfg(all(target_arch = "x86_64", target_feature = $feature))]
+                {
+                    let movemask = $movemask;
+                    unsafe {
+                        movemask(self.into()) as u8
+                    }
+                }
+            }
+
+            /// Returns `true` if any lane is `true`.
+            #[inline(always)]
+            #[must_use]
+            pub fn any(self) -> bool {
+                self.to_bitmask() != 0
+            }
+
+            /// Returns `true` if all lanes are `true`.
+            #[inline(always)]
+            #[must_use]
+            pub fn all(self) -> bool {
+                self.to_bitmask() == u8::MAX >> (8 - $n)
+            }
+
+            /// Returns `true` if no lanes are `true`.
+            #[inline(always)]
+            #[must_use]
+            pub fn none(self) -> bool {
+                self.to_bitmask() == 0
+            }
+
+            #[doc = concat!("Returns a [`", stringify!($f), "`] where each lane is taken from `if_true` if the corresponding lane in `self` is `true`, and from `if_false` otherwise.")]
+            #[inline(always)]
+            #[must_use]
+            pub fn select(self, if_true: $f, if_false: $f) -> $f {
+                #[cfg(not(all(target_arch = "x86_64", target_feature = $feature)))]
+                {
+                    $f(std::array::from_fn(|i| {
+                        f32::from_bits(
+                            (if_true.0[i].to_bits() & self.0[i])
+                                | (if_false.0[i].to_bits() & !self.0[i]),
+                        )
+                    }))
+                }
+
+                #[cfg(all(target_arch = "x86_64", target_feature = $feature))]
+                {
+                    let or = $or;
+                    let and = $and;
+                    let andnot = $andnot;
+                    unsafe {
+                        let mask = self.into();
+                        or(and(mask, if_true.into()), andnot(mask, if_false.into())).into()
+                    }
+                }
+            }
+        }
+
+        impl std::fmt::Debug for $f {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.debug_tuple(stringify!($f)).field(&self.0).finish()
+            }
+        }
+
+        impl std::fmt::Debug for $m {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.debug_tuple(stringify!($m)).field(&self.to_array()).finish()
+            }
+        }
+
+        impl From<[f32; $n]> for $f {
+            #[inline(always)]
+            fn from(x: [f32; $n]) -> $f {
+                $f(x)
+            }
+        }
+
+        impl From<$f> for [f32; $n] {
+            #[inline(always)]
+            fn from(x: $f) -> [f32; $n] {
+                x.0
+            }
+        }
+
+        #[cfg(all(target_arch = "x86_64", target_feature = $feature))]
+        impl From<$v> for $f {
+            #[inline(always)]
+            fn from(x: $v) -> Self {
+                // SAFETY: Both types are plain data of the same size.
+                unsafe { std::mem::transmute::<$v, $f>(x) }
+            }
+        }
+
+        #[cfg(all(target_arch = "x86_64", target_feature = $feature))]
+        impl From<$f> for $v {
+            #[inline(always)]
+            fn from(x: $f) -> Self {
+                // SAFETY: Both types are plain data of the same size.
+                unsafe { std::mem::transmute::<$f, $v>(x) }
+            }
+        }
+
+        #[cfg(all(target_arch = "x86_64", target_feature = $feature))]
+        impl From<$v> for $m {
+            #[inline(always)]
+            fn from(x: $v) -> Self {
+                // SAFETY: Both types are plain data of the same size.
+                unsafe { std::mem::transmute::<$v, $m>(x) }
+            }
+        }
+
+        #[cfg(all(target_arch = "x86_64", target_feature = $feature))]
+        impl From<$m> for $v {
+            #[inline(always)]
+            fn from(x: $m) -> Self {
+                // SAFETY: Both types are plain data of the same size.
+                unsafe { std::mem::transmute::<$m, $v>(x) }
+            }
+        }
+
+        $crate::impl_wide!(@binary_op $f, $feature, Add, add, AddAssign, add_assign, $add, +);
+        $crate::impl_wide!(@binary_op $f, $feature, Sub, sub, SubAssign, sub_assign, $sub, -);
+        $crate::impl_wide!(@binary_op $f, $feature, Mul, mul, MulAssign, mul_assign, $mul, *);
+        $crate::impl_wide!(@binary_op $f, $feature, Div, div, DivAssign, div_assign, $div, /);
+
+        impl std::ops::Neg for $f {
+            type Output = $f;
+
+            #[inline(always)]
+            fn neg(self) -> $f {
+                #[cfg(not(all(target_arch = "x86_64", target_feature = $feature)))]
+                {
+                    self.map(|x| -x)
+                }
+
+                #[cfg(all(target_arch = "x86_64", target_feature = $feature))]
+                {
+                    let xor = $xor;
+                    let set1 = $set1;
+                    unsafe {
+                        xor(self.into(), set1(-0.0)).into()
+                    }
+                }
+            }
+        }
+
+        $crate::impl_wide!(@mask_op $m, BitAnd, bitand, BitAndAssign, bitand_assign, &);
+        $crate::impl_wide!(@mask_op $m, BitOr, bitor, BitOrAssign, bitor_assign, |);
+        $crate::impl_wide!(@mask_op $m, BitXor, bitxor, BitXorAssign, bitxor_assign, ^);
+
+        impl std::ops::Not for $m {
+            type Output = $m;
+
+            #[inline(always)]
+            fn not(self) -> $m {
+                $m(self.0.map(|x| !x))
+            }
+        }
+    };
+
+    (@binary_op $f:ident, $feature:literal, $trait:ident, $fn:ident, $assign_trait:ident, $assign_fn:ident, $intrinsic:expr, $op:tt) => {
+        impl std::ops::$trait for $f {
+            type Output = $f;
+
+            #[inline(always)]
+            fn $fn(self, rhs: $f) -> $f {
+                #[cfg(not(all(target_arch = "x86_64", target_feature = $feature)))]
+                {
+                    self.map2(rhs, |a, b| a $op b)
+                }
+
+                #[cfg(all(target_arch = "x86_64", target_feature = $feature))]
+                {
+                    let intrinsic = $intrinsic;
+                    unsafe {
+                        intrinsic(self.into(), rhs.into()).into()
+                    }
+                }
+            }
+        }
+
+        impl std::ops::$trait<f32> for $f {
+            type Output = $f;
+
+            #[inline(always)]
+            fn $fn(self, rhs: f32) -> $f {
+                self $op $f::splat(rhs)
+            }
+        }
+
+        impl std::ops::$assign_trait for $f {
+            #[inline(always)]
+            fn $assign_fn(&mut self, rhs: $f) {
+                *self = *self $op rhs
+            }
+        }
+
+        impl std::ops::$assign_trait<f32> for $f {
+            #[inline(always)]
+            fn $assign_fn(&mut self, rhs: f32) {
+                *self = *self $op rhs
+            }
+        }
+    };
+
+    (@mask_op $m:ident, $trait:ident, $fn:ident, $assign_trait:ident, $assign_fn:ident, $op:tt) => {
+        impl std::ops::$trait for $m {
+            type Output = $m;
+
+            #[inline(always)]
+            fn $fn(self, rhs: $m) -> $m {
+                $m(std::array::from_fn(|i| self.0[i] $op rhs.0[i]))
+            }
+        }
+
+        impl std::ops::$assign_trait for $m {
+            #[inline(always)]
+            fn $assign_fn(&mut self, rhs: $m) {
+                *self = *self $op rhs
+            }
+        }
+    };
+}
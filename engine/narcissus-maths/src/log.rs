@@ -0,0 +1,180 @@
+// Range reduction follows Norbert Juffa's logf, with minimax polynomials
+// fitted for relative error.
+
+// constants for ln(1 + f) = f - f^2 / 2 + f^3 p(f) for f on [-1/3,1/3]
+const F32_LN_K: [f32; 9] = unsafe {
+    std::mem::transmute::<[u32; 9], _>([
+        0x3eaaaaab, // 0x1.555556p-2
+        0xbe7fff87, // -0x1.ffff0ep-3
+        0x3e4ccbbf, // 0x1.99977ep-3
+        0xbe2acc84, // -0x1.559908p-3
+        0x3e127a45, // 0x1.24f48ap-3
+        0xbdf9c4fb, // -0x1.f389f6p-4
+        0x3ddc3f4c, // 0x1.b87e98p-4
+        0xbe038899, // -0x1.071132p-3
+        0x3df5e789, // 0x1.ebcf12p-4
+    ])
+};
+
+// constants for log2(1 + f) = f * log2(e) + f^2 p(f) for f on [-1/3,1/3]
+const F32_LOG2_K: [f32; 10] = unsafe {
+    std::mem::transmute::<[u32; 10], _>([
+        0xbf38aa3c, // -0x1.715478p-1
+        0x3ef63851, // 0x1.ec70a2p-2
+        0xbeb8a991, // -0x1.715322p-2
+        0x3e93ba49, // 0x1.277492p-2
+        0xbe767b04, // -0x1.ecf608p-3
+        0x3e536348, // 0x1.a6c69p-3
+        0xbe336a5d, // -0x1.66d4bap-3
+        0x3e1e3956, // 0x1.3c72acp-3
+        0xbe4091b1, // -0x1.812362p-3
+        0x3e33a964, // 0x1.6752c8p-3
+    ])
+};
+
+// log2(e) - LOG2_E, for extra precision in the linear term.
+const LOG2_E_LO: f32 = 1.925_963e-8; // 0x1.4ae0cp-26
+
+/// Splits positive, finite `a` into `(i, f)` such that `a = 2^i * (1 + f)` with
+/// `f` on [-1/3,1/3].
+#[inline(always)]
+fn log_reduce(a: f32) -> (f32, f32) {
+    // Normalize subnormal inputs.
+    let (a, i) = if a < f32::MIN_POSITIVE {
+        (a * 8388608.0, -23.0) // 0x1.0p23
+    } else {
+        (a, 0.0)
+    };
+
+    // Move the mantissa into [2/3,4/3).
+    let ia = a.to_bits();
+    let e = ia.wrapping_sub(0x3f2aaaab) & 0xff800000; // 0x1.555556p-1
+    let m = f32::from_bits(ia.wrapping_sub(e));
+    let i = (e as i32 as f32).mul_add(1.192_092_9e-7, i); // 0x1.0p-23
+
+    (i, m - 1.0)
+}
+
+/// Handles zero, negative, infinite and `NaN` arguments.
+#[inline(always)]
+fn log_special(a: f32, r: f32) -> f32 {
+    if a > 0.0 && a <= f32::MAX {
+        r
+    } else if a == 0.0 {
+        f32::NEG_INFINITY
+    } else if a < 0.0 {
+        f32::NAN
+    } else {
+        // +Inf or NaN.
+        a + a
+    }
+}
+
+/// Computes the natural logarithm of `a`.
+///
+/// Returns `NaN` for negative `a`, and `-Inf` for zero.
+///
+/// Error <= 0.91740 ulp.
+///
+/// # Examples
+///
+/// ```
+/// use narcissus_maths::ln_f32;
+/// assert_eq!(ln_f32(1.0), 0.0);
+/// assert_eq!(ln_f32(std::f32::consts::E), 1.0);
+/// ```
+pub fn ln_f32(a: f32) -> f32 {
+    const T: [f32; 9] = F32_LN_K;
+
+    let (i, f) = log_reduce(a);
+
+    let p = T[8];
+    let p = p.mul_add(f, T[7]);
+    let p = p.mul_add(f, T[6]);
+    let p = p.mul_add(f, T[5]);
+    let p = p.mul_add(f, T[4]);
+    let p = p.mul_add(f, T[3]);
+    let p = p.mul_add(f, T[2]);
+    let p = p.mul_add(f, T[1]);
+    let p = p.mul_add(f, T[0]);
+
+    let r = p.mul_add(f, -0.5);
+    let r = r.mul_add(f * f, f);
+    let r = i.mul_add(std::f32::consts::LN_2, r);
+
+    log_special(a, r)
+}
+
+/// Computes the base 2 logarithm of `a`.
+///
+/// Returns `NaN` for negative `a`, and `-Inf` for zero. Exact for powers of
+/// two.
+///
+/// Error <= 1.02458 ulp.
+///
+/// # Examples
+///
+/// ```
+/// use narcissus_maths::log2_f32;
+/// assert_eq!(log2_f32(1.0), 0.0);
+/// assert_eq!(log2_f32(0.125), -3.0);
+/// ```
+pub fn log2_f32(a: f32) -> f32 {
+    const T: [f32; 10] = F32_LOG2_K;
+
+    let (i, f) = log_reduce(a);
+
+    let p = T[9];
+    let p = p.mul_add(f, T[8]);
+    let p = p.mul_add(f, T[7]);
+    let p = p.mul_add(f, T[6]);
+    let p = p.mul_add(f, T[5]);
+    let p = p.mul_add(f, T[4]);
+    let p = p.mul_add(f, T[3]);
+    let p = p.mul_add(f, T[2]);
+    let p = p.mul_add(f, T[1]);
+    let p = p.mul_add(f, T[0]);
+
+    // Sum the integer part and the exact product f * LOG2_E in extended
+    // precision, so cancellation when `i` and `f` have opposite signs doesn't
+    // expose rounding error.
+    let t = p.mul_add(f * f, LOG2_E_LO * f);
+    let ph = f * std::f32::consts::LOG2_E;
+    let pl = f.mul_add(std::f32::consts::LOG2_E, -ph);
+    let hi = i + ph;
+    let lo = (ph - (hi - i)) + pl;
+    let r = hi + (lo + t);
+
+    log_special(a, r)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ln_f32, log2_f32};
+
+    #[test]
+    fn basics() {
+        assert_eq!(ln_f32(1.0), 0.0);
+        assert_eq!(ln_f32(0.0), f32::NEG_INFINITY);
+        assert_eq!(ln_f32(-0.0), f32::NEG_INFINITY);
+        assert_eq!(ln_f32(f32::INFINITY), f32::INFINITY);
+        assert!(ln_f32(-1.0).is_nan());
+        assert!(ln_f32(f32::NEG_INFINITY).is_nan());
+        assert!(ln_f32(f32::NAN).is_nan());
+
+        assert_eq!(log2_f32(0.0), f32::NEG_INFINITY);
+        assert_eq!(log2_f32(f32::INFINITY), f32::INFINITY);
+        assert!(log2_f32(-1.0).is_nan());
+        assert!(log2_f32(f32::NAN).is_nan());
+    }
+
+    #[test]
+    fn powers_of_two() {
+        for i in 0..23 {
+            assert_eq!(log2_f32(f32::from_bits(1 << i)), i as f32 - 149.0);
+        }
+        for i in 1..255 {
+            assert_eq!(log2_f32(f32::from_bits(i << 23)), i as f32 - 127.0);
+        }
+    }
+}
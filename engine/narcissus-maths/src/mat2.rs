@@ -89,6 +89,44 @@ impl Mat2 {
         Mat2::from_rows([[m[0], m[2]], [m[1], m[3]]])
     }
 
+    /// Returns the determinant of `self`.
+    #[must_use]
+    #[inline]
+    pub fn determinant(&self) -> f32 {
+        let [[a, b], [c, d]] = *self.as_rows();
+        a * d - b * c
+    }
+
+    /// Returns the inverse of `self`.
+    ///
+    /// If `self` is singular the result will contain non-finite values. See
+    /// [`Mat2::checked_inverse`].
+    #[must_use]
+    #[inline]
+    pub fn inverse(self) -> Mat2 {
+        self.inverse_with_determinant(self.determinant())
+    }
+
+    /// Returns the inverse of `self`, or `None` if `self` is singular or the
+    /// inverse is not finite.
+    #[must_use]
+    #[inline]
+    pub fn checked_inverse(self) -> Option<Mat2> {
+        let determinant = self.determinant();
+        if determinant == 0.0 {
+            return None;
+        }
+        let inverse = self.inverse_with_determinant(determinant);
+        inverse.is_finite().then_some(inverse)
+    }
+
+    #[inline(always)]
+    fn inverse_with_determinant(self, determinant: f32) -> Mat2 {
+        let [[a, b], [c, d]] = *self.as_rows();
+        let inv_det = 1.0 / determinant;
+        Mat2::from_rows([[d * inv_det, -b * inv_det], [-c * inv_det, a * inv_det]])
+    }
+
     #[must_use]
     #[inline]
     pub fn transform_point2(self: &Mat2, point: Point2) -> Point2 {
@@ -154,7 +192,12 @@ impl std::ops::Mul<Point2> for Mat2 {
 
 #[cfg(test)]
 mod tests {
-    use crate::Mat2;
+    use narcissus_core::random::Pcg64;
+
+    use crate::{
+        Mat2,
+        reference::{assert_close, determinant_inverse},
+    };
 
     const I: Mat2 = Mat2::IDENTITY;
     const M: Mat2 = Mat2::from_rows([[1.0, 2.0], [3.0, 4.0]]);
@@ -174,4 +217,30 @@ mod tests {
         assert_eq!(M * I, M);
         assert_eq!(I * M, M);
     }
+
+    #[test]
+    fn inverse() {
+        assert_eq!(I.inverse(), I);
+        assert_eq!(M.determinant(), -2.0);
+        assert_eq!(M.inverse(), Mat2::from_rows([[-2.0, 1.0], [1.5, -0.5]]));
+        assert_eq!(M * M.inverse(), I);
+
+        let singular = Mat2::from_rows([[1.0, 2.0], [2.0, 4.0]]);
+        assert_eq!(singular.determinant(), 0.0);
+        assert_eq!(singular.checked_inverse(), None);
+        assert!(!singular.inverse().is_finite());
+        assert_eq!(Mat2::ZERO.checked_inverse(), None);
+
+        let mut rng = Pcg64::new();
+        for _ in 0..1000 {
+            let m = Mat2(std::array::from_fn(|_| rng.next_f32_s() * 10.0));
+            let (det, inv) = determinant_inverse(*m.as_rows());
+            assert_close(&[m.determinant()], &[det], 1e-5);
+            if det.abs() < 0.1 {
+                continue;
+            }
+            let inv = inv.unwrap();
+            assert_close(&m.checked_inverse().unwrap().0, inv.as_flattened(), 1e-5);
+        }
+    }
 }
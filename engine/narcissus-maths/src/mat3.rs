@@ -1,4 +1,4 @@
-use crate::{HalfTurn, Point2, Point3, Vec2, Vec3, sin_cos_pi_f32};
+use crate::{HalfTurn, Point2, Point3, Quat, Vec2, Vec3, sin_cos_pi_f32};
 
 /// 3x3 matrix.
 #[derive(Clone, Copy, PartialEq)]
@@ -87,6 +87,41 @@ impl Mat3 {
         ])
     }
 
+    /// Constructs a rotation matrix from the unit quaternion `quat`.
+    pub fn from_quat(quat: Quat) -> Mat3 {
+        let Quat {
+            a: x,
+            b: y,
+            c: z,
+            d: w,
+        } = quat;
+        let (x2, y2, z2) = (x + x, y + y, z + z);
+        let (xx, yy, zz) = (x * x2, y * y2, z * z2);
+        let (xy, xz, yz) = (x * y2, x * z2, y * z2);
+        let (wx, wy, wz) = (w * x2, w * y2, w * z2);
+        Mat3::from_rows([
+            [1.0 - (yy + zz), xy - wz, xz + wy],
+            [xy + wz, 1.0 - (xx + zz), yz - wx],
+            [xz - wy, yz + wx, 1.0 - (xx + yy)],
+        ])
+    }
+
+    /// Constructs a matrix from the given `columns`.
+    pub fn from_columns(columns: [Vec3; 3]) -> Mat3 {
+        let [x, y, z] = columns;
+        Mat3::from_rows([[x.x, y.x, z.x], [x.y, y.y, z.y], [x.z, y.z, z.z]])
+    }
+
+    /// Returns the columns of `self`.
+    pub fn to_columns(&self) -> [Vec3; 3] {
+        let m = &self.0;
+        [
+            Vec3::new(m[0], m[3], m[6]),
+            Vec3::new(m[1], m[4], m[7]),
+            Vec3::new(m[2], m[5], m[8]),
+        ]
+    }
+
     /// Returns `true` if all elements are finite.
     ///
     /// If any element is `NaN`, positive infinity, or negative infinity, returns
@@ -126,6 +161,54 @@ impl Mat3 {
         Mat3::from_rows([[m[0], m[3], m[6]], [m[1], m[4], m[7]], [m[2], m[5], m[8]]])
     }
 
+    /// Returns the determinant of `self`.
+    #[must_use]
+    #[inline]
+    pub fn determinant(&self) -> f32 {
+        let [r0, r1, r2] = self.as_rows().map(Vec3::from);
+        Vec3::dot(r0, Vec3::cross(r1, r2))
+    }
+
+    /// Returns the inverse of `self`.
+    ///
+    /// If `self` is singular the result will contain non-finite values. See
+    /// [`Mat3::checked_inverse`].
+    #[must_use]
+    #[inline]
+    pub fn inverse(self) -> Mat3 {
+        let (determinant, adjugate) = self.determinant_adjugate();
+        adjugate.scale(1.0 / determinant)
+    }
+
+    /// Returns the inverse of `self`, or `None` if `self` is singular or the
+    /// inverse is not finite.
+    #[must_use]
+    #[inline]
+    pub fn checked_inverse(self) -> Option<Mat3> {
+        let (determinant, adjugate) = self.determinant_adjugate();
+        if determinant == 0.0 {
+            return None;
+        }
+        let inverse = adjugate.scale(1.0 / determinant);
+        inverse.is_finite().then_some(inverse)
+    }
+
+    /// Returns the determinant and adjugate (transposed cofactor matrix) of
+    /// `self`.
+    #[inline(always)]
+    fn determinant_adjugate(self) -> (f32, Mat3) {
+        let [r0, r1, r2] = self.as_rows().map(Vec3::from);
+        let c0 = Vec3::cross(r1, r2);
+        let c1 = Vec3::cross(r2, r0);
+        let c2 = Vec3::cross(r0, r1);
+        (Vec3::dot(r0, c0), Mat3::from_columns([c0, c1, c2]))
+    }
+
+    #[inline(always)]
+    fn scale(self, scale: f32) -> Mat3 {
+        Mat3(self.0.map(|x| x * scale))
+    }
+
     #[must_use]
     #[inline]
     pub fn transform_point2(self: &Mat3, point: Point2) -> Point2 {
@@ -238,7 +321,12 @@ impl std::ops::Mul<Point2> for Mat3 {
 
 #[cfg(test)]
 mod tests {
-    use crate::{HalfTurn, Mat3, Vec2, Vec3};
+    use narcissus_core::random::Pcg64;
+
+    use crate::{
+        HalfTurn, Mat3, Quat, Vec2, Vec3,
+        reference::{assert_close, determinant_inverse},
+    };
 
     const I: Mat3 = Mat3::IDENTITY;
     const M: Mat3 = Mat3::from_rows([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]);
@@ -287,4 +375,48 @@ mod tests {
         assert_eq!(M * I, M);
         assert_eq!(I * M, M);
     }
+
+    #[test]
+    fn inverse() {
+        assert_eq!(I.inverse(), I);
+        assert_eq!(M.determinant(), 0.0);
+        assert_eq!(M.checked_inverse(), None);
+        assert!(!M.inverse().is_finite());
+        assert_eq!(Mat3::ZERO.checked_inverse(), None);
+
+        let scale = Mat3::from_scale(Vec3::new(2.0, 4.0, 8.0));
+        assert_eq!(scale.determinant(), 64.0);
+        assert_eq!(
+            scale.inverse(),
+            Mat3::from_scale(Vec3::new(0.5, 0.25, 0.125))
+        );
+
+        let mut rng = Pcg64::new();
+        for _ in 0..1000 {
+            let m = Mat3(std::array::from_fn(|_| rng.next_f32_s() * 10.0));
+            let (det, inv) = determinant_inverse(*m.as_rows());
+            // Cancellation in the determinant makes it less precise than the inverse.
+            assert_close(&[m.determinant()], &[det], 1e-3);
+            if det.abs() < 1.0 {
+                continue;
+            }
+            let inv = inv.unwrap();
+            assert_close(&m.checked_inverse().unwrap().0, inv.as_flattened(), 1e-4);
+        }
+    }
+
+    #[test]
+    fn from_quat() {
+        for (axis, rotation) in [
+            (Vec3::X, 0.25),
+            (Vec3::Y, -0.5),
+            (Vec3::Z, 1.0),
+            (Vec3::new(1.0, 2.0, 3.0).normalized(), 0.3),
+        ] {
+            let rotation = HalfTurn::new(rotation);
+            let expected = Mat3::from_axis_rotation(axis, rotation);
+            let m = Mat3::from_quat(Quat::from_axis_rotation(axis, rotation));
+            assert_close(&m.0, &expected.0.map(|x| x as f64), 1e-6);
+        }
+    }
 }
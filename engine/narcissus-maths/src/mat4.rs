@@ -1,4 +1,6 @@
-use crate::{HalfTurn, Point2, Point3, Vec2, Vec3, Vec4, sin_cos_pi_f32, tan_pi_f32};
+use crate::{
+    HalfTurn, Mat3, Plane, Point2, Point3, Quat, Vec2, Vec3, Vec4, sin_cos_pi_f32, tan_pi_f32,
+};
 
 /// 4x4 matrix.
 ///
@@ -150,6 +152,11 @@ impl Mat4 {
         ])
     }
 
+    /// Constructs a rotation matrix from the unit quaternion `quat`.
+    pub fn from_quat(quat: Quat) -> Mat4 {
+        Mat4::from_mat3_translation(Mat3::from_quat(quat), Vec3::ZERO)
+    }
+
     /// Constructs a 'look at' transformation from the given `eye` position, look
     /// at `center` point, and `up` vector.
     ///
@@ -193,7 +200,7 @@ impl Mat4 {
         let fmn = far - near;
         Mat4::from_rows([
             [2.0 / rml, 0.0, 0.0, -(rpl / rml)],
-            [0.0, -2.0 / tmb, 0.0, -(tpb / tmb)],
+            [0.0, -2.0 / tmb, 0.0, tpb / tmb],
             [0.0, 0.0, -1.0 / fmn, -(near / fmn)],
             [0.0, 0.0, 0.0, 1.0],
         ])
@@ -218,6 +225,215 @@ impl Mat4 {
         ])
     }
 
+    /// Returns the inverse of [`Mat4::orthographic_zo`] with the same
+    /// parameters.
+    pub fn orthographic_zo_inverse(
+        left: f32,
+        right: f32,
+        bottom: f32,
+        top: f32,
+        near: f32,
+        far: f32,
+    ) -> Mat4 {
+        let rml = right - left;
+        let rpl = right + left;
+        let tmb = top - bottom;
+        let tpb = top + bottom;
+        let fmn = far - near;
+        Mat4::from_rows([
+            [rml / 2.0, 0.0, 0.0, rpl / 2.0],
+            [0.0, -tmb / 2.0, 0.0, tpb / 2.0],
+            [0.0, 0.0, -fmn, -near],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    /// Returns the inverse of [`Mat4::perspective_rev_inf_zo`] with the same
+    /// parameters.
+    pub fn perspective_rev_inf_zo_inverse(
+        vertical_fov: HalfTurn,
+        aspect_ratio: f32,
+        z_near: f32,
+    ) -> Mat4 {
+        let tan = tan_pi_f32(vertical_fov.as_f32() / 2.0);
+        Mat4::from_rows([
+            [tan * aspect_ratio, 0.0, 0.0, 0.0],
+            [0.0, -tan, 0.0, 0.0],
+            [0.0, 0.0, 0.0, -1.0],
+            [0.0, 0.0, 1.0 / z_near, 0.0],
+        ])
+    }
+
+    /// Creates a perspective projection matrix with reversed z and \[0,1\]
+    /// depth range, mapping `z_near` to depth one and `z_far` to depth zero.
+    ///
+    /// Destination coordinate space matches native vulkan clip space.
+    ///
+    /// Src coordinate space: right-handed, +y up.
+    /// Dst coordinate space: right-handed, -y up, depth range \[0,1\].
+    pub fn perspective_rev_zo(
+        vertical_fov: HalfTurn,
+        aspect_ratio: f32,
+        z_near: f32,
+        z_far: f32,
+    ) -> Mat4 {
+        let tan = tan_pi_f32(vertical_fov.as_f32() / 2.0);
+        let sy = 1.0 / tan;
+        let sx = sy / aspect_ratio;
+        let (a, b) = rev_z_depth(z_near, z_far);
+        Mat4::from_rows([
+            [sx, 0.0, 0.0, 0.0],
+            [0.0, -sy, 0.0, 0.0],
+            [0.0, 0.0, a, b],
+            [0.0, 0.0, -1.0, 0.0],
+        ])
+    }
+
+    /// Returns the inverse of [`Mat4::perspective_rev_zo`] with the same
+    /// parameters.
+    pub fn perspective_rev_zo_inverse(
+        vertical_fov: HalfTurn,
+        aspect_ratio: f32,
+        z_near: f32,
+        z_far: f32,
+    ) -> Mat4 {
+        let tan = tan_pi_f32(vertical_fov.as_f32() / 2.0);
+        let (a, b) = rev_z_depth(z_near, z_far);
+        Mat4::from_rows([
+            [tan * aspect_ratio, 0.0, 0.0, 0.0],
+            [0.0, -tan, 0.0, 0.0],
+            [0.0, 0.0, 0.0, -1.0],
+            [0.0, 0.0, 1.0 / b, a / b],
+        ])
+    }
+
+    /// Creates an off-center perspective projection matrix with reversed z and
+    /// \[0,1\] depth range, where `left`, `right`, `bottom` and `top` give the
+    /// extents of the view window on the near plane.
+    ///
+    /// Asymmetric frustums select a sub-rectangle of a larger view, for
+    /// example when splitting the screen or rendering in tiles.
+    ///
+    /// Destination coordinate space matches native vulkan clip space.
+    ///
+    /// Src coordinate space: right-handed, +y up.
+    /// Dst coordinate space: right-handed, -y up, depth range \[0,1\].
+    pub fn frustum_rev_zo(
+        left: f32,
+        right: f32,
+        bottom: f32,
+        top: f32,
+        z_near: f32,
+        z_far: f32,
+    ) -> Mat4 {
+        let (a, b) = rev_z_depth(z_near, z_far);
+        frustum_rows(left, right, bottom, top, z_near, a, b)
+    }
+
+    /// Returns the inverse of [`Mat4::frustum_rev_zo`] with the same
+    /// parameters.
+    pub fn frustum_rev_zo_inverse(
+        left: f32,
+        right: f32,
+        bottom: f32,
+        top: f32,
+        z_near: f32,
+        z_far: f32,
+    ) -> Mat4 {
+        let (a, b) = rev_z_depth(z_near, z_far);
+        frustum_inverse_rows(left, right, bottom, top, z_near, a, b)
+    }
+
+    /// Creates an off-center perspective projection matrix with reversed
+    /// infinite z and \[0,1\] depth range. See [`Mat4::frustum_rev_zo`].
+    ///
+    /// Destination coordinate space matches native vulkan clip space.
+    ///
+    /// Src coordinate space: right-handed, +y up.
+    /// Dst coordinate space: right-handed, -y up, depth range \[0,1\].
+    pub fn frustum_rev_inf_zo(left: f32, right: f32, bottom: f32, top: f32, z_near: f32) -> Mat4 {
+        frustum_rows(left, right, bottom, top, z_near, 0.0, z_near)
+    }
+
+    /// Returns the inverse of [`Mat4::frustum_rev_inf_zo`] with the same
+    /// parameters.
+    pub fn frustum_rev_inf_zo_inverse(
+        left: f32,
+        right: f32,
+        bottom: f32,
+        top: f32,
+        z_near: f32,
+    ) -> Mat4 {
+        frustum_inverse_rows(left, right, bottom, top, z_near, 0.0, z_near)
+    }
+
+    /// Returns the reversed z projection `self` with its near plane replaced
+    /// by `clip_plane`, given in camera space, so that geometry behind the
+    /// plane is clipped by the hardware near plane. Typically used to clip
+    /// the scene at the surface when rendering reflections.
+    ///
+    /// The far plane is tilted to pass through the corner of the original
+    /// frustum furthest in front of `clip_plane`, which keeps as much depth
+    /// precision as possible. The camera must be behind `clip_plane`. The
+    /// result is no longer a simple perspective matrix, so invert it with
+    /// [`Mat4::inverse`].
+    ///
+    /// Eric Lengyel, "Oblique View Frustum Depth Projection and Clipping",
+    /// Journal of Game Development, Vol. 1, No. 2 (2005).
+    #[must_use]
+    pub fn oblique_rev_zo(self, clip_plane: Plane) -> Mat4 {
+        let c = Vec4::new(
+            clip_plane.normal.x,
+            clip_plane.normal.y,
+            clip_plane.normal.z,
+            -clip_plane.distance,
+        );
+        let inverse = self.inverse();
+
+        // Transform the plane into clip space to find the corner on the depth
+        // zero plane which is furthest in front of it.
+        let c_clip = inverse.transpose() * c;
+        let corner = Vec4::new(c_clip.x.signum(), c_clip.y.signum(), 0.0, 1.0);
+        let q = inverse * corner;
+
+        // Replace the depth one plane, `w - z`, with the scaled clip plane,
+        // choosing the scale so `q` stays on the depth zero plane.
+        let mut rows = *self.as_rows();
+        let r3 = Vec4::from(rows[3]);
+        let scale = Vec4::dot(r3, q) / Vec4::dot(c, q);
+        rows[2] = (r3 - c * scale).into();
+        Mat4::from_rows(rows)
+    }
+
+    /// Returns the projection `self` offset by `offset` in normalized device
+    /// coordinates, for example to apply sub-pixel jitter for temporal
+    /// anti-aliasing.
+    ///
+    /// An offset of `jitter` pixels on a `width` by `height` target is
+    /// `jitter * 2.0 / vec2(width, height)`.
+    #[must_use]
+    pub fn jittered(self, offset: Vec2) -> Mat4 {
+        let mut rows = *self.as_rows();
+        let r3 = rows[3];
+        for i in 0..4 {
+            rows[0][i] = offset.x.mul_add(r3[i], rows[0][i]);
+            rows[1][i] = offset.y.mul_add(r3[i], rows[1][i]);
+        }
+        Mat4::from_rows(rows)
+    }
+
+    /// Returns the inverse projection `self` adjusted to undo an `offset`
+    /// applied with [`Mat4::jittered`], so that `p.jittered(offset).inverse()`
+    /// is equivalent to `p.inverse().jittered_inverse(offset)`.
+    #[must_use]
+    pub fn jittered_inverse(self, offset: Vec2) -> Mat4 {
+        let mut rows = *self.as_rows();
+        for row in &mut rows {
+            row[3] -= offset.x * row[0] + offset.y * row[1];
+        }
+        Mat4::from_rows(rows)
+    }
+
     /// Returns `true` if all elements are finite.
     ///
     /// If any element is `NaN`, positive infinity, or negative infinity, returns
@@ -287,6 +503,147 @@ impl Mat4 {
         }
     }
 
+    /// Returns the determinant of `self`.
+    #[must_use]
+    pub fn determinant(&self) -> f32 {
+        let (s, c) = self.sub_determinants();
+        s[0] * c[5] - s[1] * c[4] + s[2] * c[3] + s[3] * c[2] - s[4] * c[1] + s[5] * c[0]
+    }
+
+    /// Returns the inverse of `self`.
+    ///
+    /// If `self` is singular the result will contain non-finite values. See
+    /// [`Mat4::checked_inverse`].
+    ///
+    /// When `self` is known to be an affine transform, [`Mat4::affine_inverse`]
+    /// and [`Mat4::orthonormal_inverse`] are cheaper.
+    #[must_use]
+    pub fn inverse(self) -> Mat4 {
+        let (determinant, adjugate) = self.determinant_adjugate();
+        Mat4(adjugate.0.map(|x| x * (1.0 / determinant)))
+    }
+
+    /// Returns the inverse of `self`, or `None` if `self` is singular or the
+    /// inverse is not finite.
+    #[must_use]
+    pub fn checked_inverse(self) -> Option<Mat4> {
+        let (determinant, adjugate) = self.determinant_adjugate();
+        if determinant == 0.0 {
+            return None;
+        }
+        let inverse = Mat4(adjugate.0.map(|x| x * (1.0 / determinant)));
+        inverse.is_finite().then_some(inverse)
+    }
+
+    /// Returns the inverse of `self`, assuming `self` is an affine transform,
+    /// that is, the last row is `[0.0, 0.0, 0.0, 1.0]`.
+    ///
+    /// If the linear part of `self` is singular the result will contain
+    /// non-finite values.
+    #[must_use]
+    pub fn affine_inverse(self) -> Mat4 {
+        debug_assert!(self.as_rows()[3] == [0.0, 0.0, 0.0, 1.0]);
+        let (matrix, translation) = self.to_mat3_translation();
+        let matrix = matrix.inverse();
+        Mat4::from_mat3_translation(matrix, -(matrix * translation))
+    }
+
+    /// Returns the inverse of `self`, assuming `self` is an affine transform
+    /// whose linear part is orthonormal, that is, a rotation and translation
+    /// only, such as the result of [`Mat4::look_at`].
+    #[must_use]
+    pub fn orthonormal_inverse(self) -> Mat4 {
+        debug_assert!(self.as_rows()[3] == [0.0, 0.0, 0.0, 1.0]);
+        let (matrix, translation) = self.to_mat3_translation();
+        let matrix = matrix.transpose();
+        Mat4::from_mat3_translation(matrix, -(matrix * translation))
+    }
+
+    #[inline(always)]
+    fn to_mat3_translation(self) -> (Mat3, Vec3) {
+        let [r0, r1, r2, _] = *self.as_rows();
+        (
+            Mat3::from_rows([
+                [r0[0], r0[1], r0[2]],
+                [r1[0], r1[1], r1[2]],
+                [r2[0], r2[1], r2[2]],
+            ]),
+            Vec3::new(r0[3], r1[3], r2[3]),
+        )
+    }
+
+    #[inline(always)]
+    fn from_mat3_translation(matrix: Mat3, translation: Vec3) -> Mat4 {
+        let [r0, r1, r2] = *matrix.as_rows();
+        Mat4::from_rows([
+            [r0[0], r0[1], r0[2], translation.x],
+            [r1[0], r1[1], r1[2], translation.y],
+            [r2[0], r2[1], r2[2], translation.z],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    /// Returns the 2x2 sub-determinants of the top two rows, and of the bottom
+    /// two rows, used to expand the determinant and adjugate.
+    #[inline(always)]
+    fn sub_determinants(&self) -> ([f32; 6], [f32; 6]) {
+        let [a0, a1, a2, a3] = *self.as_rows();
+        let s = [
+            a0[0] * a1[1] - a0[1] * a1[0],
+            a0[0] * a1[2] - a0[2] * a1[0],
+            a0[0] * a1[3] - a0[3] * a1[0],
+            a0[1] * a1[2] - a0[2] * a1[1],
+            a0[1] * a1[3] - a0[3] * a1[1],
+            a0[2] * a1[3] - a0[3] * a1[2],
+        ];
+        let c = [
+            a2[0] * a3[1] - a2[1] * a3[0],
+            a2[0] * a3[2] - a2[2] * a3[0],
+            a2[0] * a3[3] - a2[3] * a3[0],
+            a2[1] * a3[2] - a2[2] * a3[1],
+            a2[1] * a3[3] - a2[3] * a3[1],
+            a2[2] * a3[3] - a2[3] * a3[2],
+        ];
+        (s, c)
+    }
+
+    /// Returns the determinant and adjugate (transposed cofactor matrix) of
+    /// `self`.
+    #[inline(always)]
+    fn determinant_adjugate(self) -> (f32, Mat4) {
+        let [a0, a1, a2, a3] = *self.as_rows();
+        let (s, c) = self.sub_determinants();
+        let determinant =
+            s[0] * c[5] - s[1] * c[4] + s[2] * c[3] + s[3] * c[2] - s[4] * c[1] + s[5] * c[0];
+        let adjugate = Mat4::from_rows([
+            [
+                a1[1] * c[5] - a1[2] * c[4] + a1[3] * c[3],
+                -a0[1] * c[5] + a0[2] * c[4] - a0[3] * c[3],
+                a3[1] * s[5] - a3[2] * s[4] + a3[3] * s[3],
+                -a2[1] * s[5] + a2[2] * s[4] - a2[3] * s[3],
+            ],
+            [
+                -a1[0] * c[5] + a1[2] * c[2] - a1[3] * c[1],
+                a0[0] * c[5] - a0[2] * c[2] + a0[3] * c[1],
+                -a3[0] * s[5] + a3[2] * s[2] - a3[3] * s[1],
+                a2[0] * s[5] - a2[2] * s[2] + a2[3] * s[1],
+            ],
+            [
+                a1[0] * c[4] - a1[1] * c[2] + a1[3] * c[0],
+                -a0[0] * c[4] + a0[1] * c[2] - a0[3] * c[0],
+                a3[0] * s[4] - a3[1] * s[2] + a3[3] * s[0],
+                -a2[0] * s[4] + a2[1] * s[2] - a2[3] * s[0],
+            ],
+            [
+                -a1[0] * c[3] + a1[1] * c[1] - a1[2] * c[0],
+                a0[0] * c[3] - a0[1] * c[1] + a0[2] * c[0],
+                -a3[0] * s[3] + a3[1] * s[1] - a3[2] * s[0],
+                a2[0] * s[3] - a2[1] * s[1] + a2[2] * s[0],
+            ],
+        ]);
+        (determinant, adjugate)
+    }
+
     /// Transforms the given [`Vec2`] `vec` by `self`.
     #[must_use]
     #[inline]
@@ -323,6 +680,17 @@ impl Mat4 {
         Point3::new(vec.x, vec.y, vec.z)
     }
 
+    /// Transforms the given [`Point3`] `point` by the projection `self`,
+    /// dividing by the resulting `w` component.
+    #[must_use]
+    #[inline]
+    pub fn project_point3(&self, point: Point3) -> Point3 {
+        let vec = Vec4::new(point.x, point.y, point.z, 1.0);
+        let vec = self.transform_vec4(vec);
+        let inv_w = 1.0 / vec.w;
+        Point3::new(vec.x * inv_w, vec.y * inv_w, vec.z * inv_w)
+    }
+
     #[inline(always)]
     #[allow(dead_code)]
     fn transform_vec4_base(&self, vec: Vec4) -> Vec4 {
@@ -371,6 +739,51 @@ impl Mat4 {
     }
 }
 
+/// Returns the depth row coefficients `(a, b)` for a reversed z projection
+/// where `z_clip = a * z + b` and `w_clip = -z`.
+#[inline(always)]
+fn rev_z_depth(z_near: f32, z_far: f32) -> (f32, f32) {
+    let inv_fmn = 1.0 / (z_far - z_near);
+    (z_near * inv_fmn, z_near * z_far * inv_fmn)
+}
+
+#[inline(always)]
+fn frustum_rows(left: f32, right: f32, bottom: f32, top: f32, z_near: f32, a: f32, b: f32) -> Mat4 {
+    let rml = right - left;
+    let rpl = right + left;
+    let tmb = top - bottom;
+    let tpb = top + bottom;
+    Mat4::from_rows([
+        [2.0 * z_near / rml, 0.0, rpl / rml, 0.0],
+        [0.0, -2.0 * z_near / tmb, -tpb / tmb, 0.0],
+        [0.0, 0.0, a, b],
+        [0.0, 0.0, -1.0, 0.0],
+    ])
+}
+
+#[inline(always)]
+fn frustum_inverse_rows(
+    left: f32,
+    right: f32,
+    bottom: f32,
+    top: f32,
+    z_near: f32,
+    a: f32,
+    b: f32,
+) -> Mat4 {
+    let rml = right - left;
+    let rpl = right + left;
+    let tmb = top - bottom;
+    let tpb = top + bottom;
+    let inv_2n = 0.5 / z_near;
+    Mat4::from_rows([
+        [rml * inv_2n, 0.0, 0.0, rpl * inv_2n],
+        [0.0, -tmb * inv_2n, 0.0, tpb * inv_2n],
+        [0.0, 0.0, 0.0, -1.0],
+        [0.0, 0.0, 1.0 / b, a / b],
+    ])
+}
+
 #[allow(dead_code)]
 #[inline(always)]
 fn mul_mat4_base(lhs: Mat4, rhs: Mat4) -> Mat4 {
@@ -536,7 +949,11 @@ impl std::ops::Mul<Point2> for Mat4 {
 
 #[cfg(test)]
 mod tests {
+    use narcissus_core::random::Pcg64;
+
     use super::*;
+    use crate::lerp;
+    use crate::reference::{assert_close, determinant_inverse};
 
     const I: Mat4 = Mat4::IDENTITY;
     const M: Mat4 = Mat4::from_rows([
@@ -684,4 +1101,243 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn inverse() {
+        assert_eq!(I.inverse(), I);
+        assert_eq!(M.determinant(), 0.0);
+        assert_eq!(M.checked_inverse(), None);
+        assert_eq!(Mat4::ZERO.checked_inverse(), None);
+        assert_eq!(SCALE.determinant(), 8.0);
+        assert_eq!(SCALE.inverse(), Mat4::from_scale(Vec3::splat(0.5)));
+        assert_eq!(
+            TRANSLATE.inverse(),
+            Mat4::from_translation(Vec3::new(-1.0, -2.0, -3.0))
+        );
+
+        let mut rng = Pcg64::new();
+        for _ in 0..1000 {
+            let m = Mat4(std::array::from_fn(|_| rng.next_f32_s() * 10.0));
+            let (det, inv) = determinant_inverse(*m.as_rows());
+            // Cancellation in the determinant makes it less precise than the inverse.
+            assert_close(&[m.determinant()], &[det], 1e-3);
+            if det.abs() < 10.0 {
+                continue;
+            }
+            let inv = inv.unwrap();
+            assert_close(&m.checked_inverse().unwrap().0, inv.as_flattened(), 1e-4);
+        }
+    }
+
+    #[test]
+    fn affine_inverse() {
+        let mut rng = Pcg64::new();
+        for _ in 0..1000 {
+            let axis = Vec3::new(rng.next_f32_s(), rng.next_f32_s(), rng.next_f32_s());
+            if axis.length_sq() < 0.01 {
+                continue;
+            }
+            let rotation =
+                Mat4::from_axis_rotation(axis.normalized(), HalfTurn::new(rng.next_f32_s()));
+            let translation = Vec3::new(rng.next_f32_s(), rng.next_f32_s(), rng.next_f32_s());
+            let scale = Vec3::new(rng.next_f32(), rng.next_f32(), rng.next_f32()) + 0.5;
+
+            let rigid = Mat4::from_translation(translation * 100.0) * rotation;
+            let (_, expected) = determinant_inverse(*rigid.as_rows());
+            let expected = expected.unwrap();
+            assert_close(
+                &rigid.orthonormal_inverse().0,
+                expected.as_flattened(),
+                1e-5,
+            );
+            assert_close(&rigid.affine_inverse().0, expected.as_flattened(), 1e-5);
+
+            let affine = rigid * Mat4::from_scale(scale);
+            let (_, expected) = determinant_inverse(*affine.as_rows());
+            assert_close(
+                &affine.affine_inverse().0,
+                expected.unwrap().as_flattened(),
+                1e-5,
+            );
+        }
+
+        let view = Mat4::look_at(
+            Point3::new(1.0, 2.0, 3.0),
+            Point3::new(-3.0, 0.5, 0.0),
+            Vec3::Y,
+        );
+        let (_, expected) = determinant_inverse(*view.as_rows());
+        assert_close(
+            &view.orthonormal_inverse().0,
+            expected.unwrap().as_flattened(),
+            1e-6,
+        );
+    }
+
+    fn assert_close_f32(actual: &[f32], expected: &[f32], tolerance: f64) {
+        let expected: Vec<f64> = expected.iter().copied().map(f64::from).collect();
+        assert_close(actual, &expected, tolerance);
+    }
+
+    /// Checks that `inverse` undoes `projection` for random points inside the
+    /// view volume, and agrees with the general inverse.
+    fn check_projection(projection: Mat4, inverse: Mat4, points: &[Point3]) {
+        let general = projection.inverse();
+        for &point in points {
+            let ndc = projection.project_point3(point);
+            assert!(ndc.x.abs() <= 1.0 + 1e-5 && ndc.y.abs() <= 1.0 + 1e-5);
+            assert!((-1e-5..=1.0 + 1e-5).contains(&ndc.z), "{ndc:?}");
+            for inverse in [inverse, general] {
+                let p = inverse.project_point3(ndc);
+                assert!(
+                    Point3::distance(p, point) <= 1e-4 * point.z.abs(),
+                    "{p:?} {point:?}"
+                );
+            }
+        }
+        assert_close_f32(&(projection * inverse).0, &Mat4::IDENTITY.0, 1e-5);
+    }
+
+    /// Random camera space points inside a frustum with the given extents on
+    /// the near plane, between depths `near` and `far`.
+    fn frustum_points(l: f32, r: f32, b: f32, t: f32, near: f32, far: f32) -> Vec<Point3> {
+        let mut rng = Pcg64::new();
+        (0..256)
+            .map(|_| {
+                let depth = near + (far - near) * rng.next_f32();
+                let x = lerp(rng.next_f32(), l, r) * depth / near;
+                let y = lerp(rng.next_f32(), b, t) * depth / near;
+                Point3::new(x, y, -depth)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn projection_orthographic() {
+        let (l, r, b, t, n, f) = (-2.0, 6.0, -1.0, 3.0, 0.5, 20.0);
+        let projection = Mat4::orthographic_zo(l, r, b, t, n, f);
+        let inverse = Mat4::orthographic_zo_inverse(l, r, b, t, n, f);
+        assert_eq!(
+            projection * Point3::new(r, t, -f),
+            Point3::new(1.0, -1.0, 1.0)
+        );
+        for point in frustum_points(l, r, b, t, n, n) {
+            for z in [-n, -f, -10.0] {
+                let point = Point3::new(point.x, point.y, z);
+                // Affine, so there's no need for the perspective divide.
+                let p = inverse.transform_point3(projection.transform_point3(point));
+                assert!(Point3::distance(p, point) < 1e-5);
+            }
+        }
+        assert_close_f32(&(projection * inverse).0, &Mat4::IDENTITY.0, 1e-6);
+    }
+
+    #[test]
+    fn projection_perspective() {
+        let fov = HalfTurn::new(1.0 / 3.0);
+        let (aspect, n, f) = (16.0 / 9.0, 0.1, 100.0);
+        let tan = tan_pi_f32(fov.as_f32() / 2.0);
+        let (t, r) = (n * tan, n * tan * aspect);
+
+        let projection = Mat4::perspective_rev_zo(fov, aspect, n, f);
+        let inverse = Mat4::perspective_rev_zo_inverse(fov, aspect, n, f);
+        assert_eq!(projection.project_point3(Point3::new(0.0, 0.0, -n)).z, 1.0);
+        assert_eq!(projection.project_point3(Point3::new(0.0, 0.0, -f)).z, 0.0);
+        check_projection(projection, inverse, &frustum_points(-r, r, -t, t, n, f));
+        assert_close_f32(
+            &Mat4::frustum_rev_zo(-r, r, -t, t, n, f).0,
+            &projection.0,
+            1e-5,
+        );
+
+        let projection = Mat4::perspective_rev_inf_zo(fov, aspect, n);
+        let inverse = Mat4::perspective_rev_inf_zo_inverse(fov, aspect, n);
+        check_projection(projection, inverse, &frustum_points(-r, r, -t, t, n, 1e4));
+        assert_close_f32(
+            &Mat4::frustum_rev_inf_zo(-r, r, -t, t, n).0,
+            &projection.0,
+            1e-5,
+        );
+    }
+
+    #[test]
+    fn projection_off_center() {
+        let (l, r, b, t, n, f) = (-0.02, 0.1, 0.01, 0.05, 0.1, 50.0);
+        let projection = Mat4::frustum_rev_zo(l, r, b, t, n, f);
+        let inverse = Mat4::frustum_rev_zo_inverse(l, r, b, t, n, f);
+        // Near plane corners map to the corners of clip space, with +y down.
+        let corner = projection.project_point3(Point3::new(l, t, -n));
+        assert_close_f32(
+            &<[f32; 3]>::from(corner.as_vec3()),
+            &[-1.0, -1.0, 1.0],
+            1e-6,
+        );
+        let corner = projection.project_point3(Point3::new(r * f / n, b * f / n, -f));
+        assert_close_f32(&<[f32; 3]>::from(corner.as_vec3()), &[1.0, 1.0, 0.0], 1e-5);
+        check_projection(projection, inverse, &frustum_points(l, r, b, t, n, f));
+
+        let projection = Mat4::frustum_rev_inf_zo(l, r, b, t, n);
+        let inverse = Mat4::frustum_rev_inf_zo_inverse(l, r, b, t, n);
+        check_projection(projection, inverse, &frustum_points(l, r, b, t, n, 1e4));
+    }
+
+    #[test]
+    fn projection_jittered() {
+        let fov = HalfTurn::new(0.5);
+        let projection = Mat4::perspective_rev_inf_zo(fov, 1.5, 0.1);
+        let inverse = Mat4::perspective_rev_inf_zo_inverse(fov, 1.5, 0.1);
+        let offset = Vec2::new(0.25, -0.125) * 2.0 / Vec2::new(1920.0, 1080.0);
+        let jittered = projection.jittered(offset);
+        let jittered_inverse = inverse.jittered_inverse(offset);
+        for point in frustum_points(-0.15, 0.15, -0.1, 0.1, 0.1, 100.0) {
+            let a = projection.project_point3(point);
+            let b = jittered.project_point3(point);
+            assert!((b.x - a.x - offset.x).abs() < 1e-6);
+            assert!((b.y - a.y - offset.y).abs() < 1e-6);
+            assert_eq!(a.z, b.z);
+            let p = jittered_inverse.project_point3(b);
+            assert!(Point3::distance(p, point) <= 1e-4 * point.z.abs());
+        }
+        assert_close_f32(&(jittered * jittered_inverse).0, &Mat4::IDENTITY.0, 1e-5);
+    }
+
+    #[test]
+    fn projection_oblique() {
+        let fov = HalfTurn::new(0.5);
+        // Camera below a tilted water surface, keeping what is above it.
+        let normal = Vec3::new(0.1, 1.0, 0.2).normalized();
+        let plane = Plane::from_point_normal(Point3::new(0.0, 1.0, -2.0), normal);
+        for projection in [
+            Mat4::perspective_rev_zo(fov, 1.0, 0.1, 100.0),
+            Mat4::perspective_rev_inf_zo(fov, 1.0, 0.1),
+            Mat4::frustum_rev_zo(-0.05, 0.15, -0.1, 0.1, 0.1, 100.0),
+        ] {
+            let oblique = projection.oblique_rev_zo(plane);
+            let inverse = oblique.inverse();
+            let mut rng = Pcg64::new();
+            for _ in 0..256 {
+                let x = rng.next_f32_s() * 10.0;
+                let z = -1.0 - rng.next_f32() * 20.0;
+                let on_plane = plane.closest_point(Point3::new(x, 0.0, z));
+                let ndc = oblique.project_point3(on_plane);
+                assert!((ndc.z - 1.0).abs() < 1e-3, "{ndc:?}");
+
+                let above = on_plane + normal * (0.01 + rng.next_f32());
+                let below = on_plane - normal * (0.01 + rng.next_f32());
+                let above_ndc = oblique.project_point3(above);
+                assert!(above_ndc.z < 1.0);
+                assert!(oblique.project_point3(below).z > 1.0);
+
+                // x and y are unaffected.
+                let original = projection.project_point3(above);
+                assert_eq!((above_ndc.x, above_ndc.y), (original.x, original.y));
+
+                let p = inverse.project_point3(above_ndc);
+                assert!(
+                    Point3::distance(p, above) < 1e-3 * above.z.abs(),
+                    "{p:?} {above:?}"
+                );
+            }
+        }
+    }
 }
@@ -0,0 +1,243 @@
+// Conversion matrices from Björn Ottosson's "A perceptual color space for image
+// processing", https://bottosson.github.io/posts/oklab/
+
+use crate::{LinearRgba, Mat3, Vec3, atan2_pi_f32, lerp, sin_cos_pi_f32, vec3};
+
+/// Linear sRGB to approximate cone responses.
+const RGB_TO_LMS: Mat3 = Mat3::from_rows([
+    [0.412_221_46, 0.536_332_55, 0.051_445_995],
+    [0.211_903_5, 0.680_699_5, 0.107_396_96],
+    [0.088_302_46, 0.281_718_85, 0.629_978_7],
+]);
+
+/// Non-linear cone responses to Oklab.
+const LMS_TO_LAB: Mat3 = Mat3::from_rows([
+    [0.210_454_26, 0.793_617_8, -0.004_072_047],
+    [1.977_998_5, -2.428_592_2, 0.450_593_7],
+    [0.025_904_037, 0.782_771_77, -0.808_675_77],
+]);
+
+/// Oklab to non-linear cone responses.
+const LAB_TO_LMS: Mat3 = Mat3::from_rows([
+    [1.0, 0.396_337_78, 0.215_803_76],
+    [1.0, -0.105_561_346, -0.063_854_17],
+    [1.0, -0.089_484_18, -1.291_485_5],
+]);
+
+/// Approximate cone responses to linear sRGB.
+const LMS_TO_RGB: Mat3 = Mat3::from_rows([
+    [4.076_741_7, -3.307_711_6, 0.230_969_94],
+    [-1.268_438, 2.609_757_4, -0.341_319_38],
+    [-0.004_196_086_3, -0.703_418_6, 1.707_614_7],
+]);
+
+/// Color in the Oklab perceptual color space.
+///
+/// `l` is perceived lightness on \[0,1\], while `a` and `b` are the green-red
+/// and blue-yellow opponent axes. Interpolating in Oklab gives gradients with
+/// an even perceived lightness and without the muddy midpoints of sRGB.
+#[derive(Clone, Copy, PartialEq, Default, Debug)]
+#[repr(C)]
+pub struct Oklab {
+    pub l: f32,
+    pub a: f32,
+    pub b: f32,
+}
+
+impl Oklab {
+    /// Constructs a new [`Oklab`] color from the given components.
+    #[inline(always)]
+    #[must_use]
+    pub const fn new(l: f32, a: f32, b: f32) -> Oklab {
+        Oklab { l, a, b }
+    }
+
+    /// Converts the color components of `color` to Oklab, discarding alpha.
+    #[inline]
+    #[must_use]
+    pub fn from_linear_rgba(color: LinearRgba) -> Oklab {
+        let lms = RGB_TO_LMS.transform_vec3(vec3(color.r, color.g, color.b));
+        let lab = LMS_TO_LAB.transform_vec3(lms.map(f32::cbrt));
+        Oklab::new(lab.x, lab.y, lab.z)
+    }
+
+    /// Converts `self` to linear sRGB with the given `alpha`.
+    ///
+    /// Colors outside of the sRGB gamut give components outside of \[0,1\].
+    #[inline]
+    #[must_use]
+    pub fn to_linear_rgba(self, alpha: f32) -> LinearRgba {
+        let lms = LAB_TO_LMS.transform_vec3(vec3(self.l, self.a, self.b));
+        let rgb = LMS_TO_RGB.transform_vec3(lms * lms * lms);
+        LinearRgba::new(rgb.x, rgb.y, rgb.z, alpha)
+    }
+
+    /// Linearly interpolates each component between `a` and `b` using `t` as a
+    /// control value.
+    #[inline(always)]
+    #[must_use]
+    pub fn lerp(t: f32, a: Oklab, b: Oklab) -> Oklab {
+        Oklab {
+            l: lerp(t, a.l, b.l),
+            a: lerp(t, a.a, b.a),
+            b: lerp(t, a.b, b.b),
+        }
+    }
+
+    /// Returns the euclidean distance between `a` and `b`, a measure of how
+    /// different the two colors appear.
+    #[inline(always)]
+    #[must_use]
+    pub fn distance(a: Oklab, b: Oklab) -> f32 {
+        (Vec3::new(a.l, a.a, a.b) - Vec3::new(b.l, b.a, b.b)).length()
+    }
+}
+
+/// Color in the Oklch perceptual color space, the polar form of [`Oklab`].
+///
+/// `l` is perceived lightness on \[0,1\], `c` is chroma, and `h` is hue in
+/// turns on \[0,1).
+#[derive(Clone, Copy, PartialEq, Default, Debug)]
+#[repr(C)]
+pub struct Oklch {
+    pub l: f32,
+    pub c: f32,
+    pub h: f32,
+}
+
+impl Oklch {
+    /// Constructs a new [`Oklch`] color from the given components.
+    #[inline(always)]
+    #[must_use]
+    pub const fn new(l: f32, c: f32, h: f32) -> Oklch {
+        Oklch { l, c, h }
+    }
+
+    /// Converts the color components of `color` to Oklch, discarding alpha.
+    #[inline]
+    #[must_use]
+    pub fn from_linear_rgba(color: LinearRgba) -> Oklch {
+        Oklab::from_linear_rgba(color).into()
+    }
+
+    /// Converts `self` to linear sRGB with the given `alpha`.
+    #[inline]
+    #[must_use]
+    pub fn to_linear_rgba(self, alpha: f32) -> LinearRgba {
+        Oklab::from(self).to_linear_rgba(alpha)
+    }
+
+    /// Interpolates between `a` and `b` using `t` as a control value, taking
+    /// the shortest path around the hue circle.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use narcissus_maths::Oklch;
+    /// let a = Oklch::new(0.5, 0.1, 0.9);
+    /// let b = Oklch::new(0.5, 0.1, 0.1);
+    /// assert_eq!(Oklch::lerp(0.5, a, b).h, 0.0);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn lerp(t: f32, a: Oklch, b: Oklch) -> Oklch {
+        let dh = b.h - a.h;
+        let dh = dh - dh.round();
+        let h = t.mul_add(dh, a.h);
+        Oklch {
+            l: lerp(t, a.l, b.l),
+            c: lerp(t, a.c, b.c),
+            h: h - h.floor(),
+        }
+    }
+}
+
+impl From<Oklab> for Oklch {
+    #[inline]
+    fn from(lab: Oklab) -> Self {
+        let c = lab.a.hypot(lab.b);
+        let h = atan2_pi_f32(lab.b, lab.a) * 0.5;
+        let h = if h < 0.0 { h + 1.0 } else { h };
+        Oklch { l: lab.l, c, h }
+    }
+}
+
+impl From<Oklch> for Oklab {
+    #[inline]
+    fn from(lch: Oklch) -> Self {
+        let (sin, cos) = sin_cos_pi_f32(lch.h * 2.0);
+        Oklab {
+            l: lch.l,
+            a: lch.c * cos,
+            b: lch.c * sin,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{LinearRgba, Oklab, Oklch, Srgba};
+
+    #[test]
+    fn reference_values() {
+        // Reference values from the Oklab post.
+        for (rgb, lab) in [
+            ([0.950, 1.000, 1.089], [1.000, 0.000, 0.000]),
+            ([1.000, 0.000, 0.000], [0.450, 1.236, -0.019]),
+            ([0.000, 1.000, 0.000], [0.922, -0.671, 0.263]),
+            ([0.000, 0.000, 1.000], [0.153, -1.415, -0.449]),
+        ] {
+            // The reference values are given in XYZ, convert to linear sRGB.
+            let [x, y, z] = rgb;
+            let r = 3.240_97 * x - 1.537_383 * y - 0.498_611 * z;
+            let g = -0.969_244 * x + 1.875_968 * y + 0.041_555 * z;
+            let b = 0.055_63 * x - 0.203_977 * y + 1.056_972 * z;
+            let result = Oklab::from_linear_rgba(LinearRgba::rgb(r, g, b));
+            assert!((result.l - lab[0]).abs() < 2e-3, "{result:?}");
+            assert!((result.a - lab[1]).abs() < 2e-3, "{result:?}");
+            assert!((result.b - lab[2]).abs() < 2e-3, "{result:?}");
+        }
+
+        let white = Oklab::from_linear_rgba(LinearRgba::WHITE);
+        assert!((white.l - 1.0).abs() < 1e-6);
+        assert!(white.a.abs() < 1e-6 && white.b.abs() < 1e-6);
+    }
+
+    #[test]
+    fn round_trip() {
+        for i in 0..4096_u32 {
+            let color = Srgba::from_rgba8(i.wrapping_mul(0x9e37_79b9) | 0xff00_0000).to_linear();
+            let lab = Oklab::from_linear_rgba(color);
+            let result = lab.to_linear_rgba(1.0);
+            assert!((result.r - color.r).abs() < 1e-5, "{color:?} {result:?}");
+            assert!((result.g - color.g).abs() < 1e-5, "{color:?} {result:?}");
+            assert!((result.b - color.b).abs() < 1e-5, "{color:?} {result:?}");
+
+            let lch = Oklch::from(lab);
+            assert!((0.0..1.0).contains(&lch.h));
+            let result = Oklab::from(lch);
+            assert!((result.l - lab.l).abs() < 1e-6);
+            assert!((result.a - lab.a).abs() < 1e-6);
+            assert!((result.b - lab.b).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn lerp() {
+        let a = Oklab::from_linear_rgba(LinearRgba::BLACK);
+        let b = Oklab::from_linear_rgba(LinearRgba::WHITE);
+        assert_eq!(Oklab::lerp(0.0, a, b), a);
+        assert_eq!(Oklab::lerp(1.0, a, b), b);
+        assert!((Oklab::lerp(0.5, a, b).l - 0.5).abs() < 1e-6);
+        assert!((Oklab::distance(a, b) - 1.0).abs() < 1e-6);
+
+        let a = Oklch::new(0.5, 0.1, 0.75);
+        let b = Oklch::new(0.7, 0.2, 0.25);
+        let mid = Oklch::lerp(0.5, a, b);
+        assert_eq!(mid, Oklch::new(0.6, 0.15, 0.0));
+        let a = Oklch::new(0.5, 0.1, 0.2);
+        let b = Oklch::new(0.5, 0.1, 0.4);
+        assert!((Oklch::lerp(0.5, a, b).h - 0.3).abs() < 1e-6);
+        assert_eq!(Oklch::lerp(0.25, b, a).h, 0.35);
+    }
+}
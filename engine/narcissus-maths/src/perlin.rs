@@ -11,11 +11,11 @@
 //    Jordan Peck - seeded noise
 //
 
-use crate::lerp;
+use crate::{Vec2, Vec3, f32x4, lerp};
 
 // Not same permutation table as Perlin's reference to avoid copyright issues.
 // Perlin's table can be found at http://mrl.nyu.edu/~perlin/noise/
-const PERMUTE: [u8; 512] = [
+pub(crate) const PERMUTE: [u8; 512] = [
     23, 125, 161, 52, 103, 117, 70, 37, 247, 101, 203, 169, 124, 126, 44, 123, 152, 238, 145, 45,
     171, 114, 253, 10, 192, 136, 4, 157, 249, 30, 35, 72, 175, 63, 77, 90, 181, 16, 96, 111, 133,
     104, 75, 162, 93, 56, 66, 240, 8, 50, 84, 229, 49, 210, 173, 239, 141, 1, 87, 18, 2, 198, 143,
@@ -73,27 +73,28 @@ const INDICES: [u8; 512] = [
     9, 0, 11, 5, 10, 3, 2, 3, 5, 9, 7, 9, 8, 4, 6, 5,
 ];
 
-fn grad(index: usize, x: f32, y: f32, z: f32) -> f32 {
-    const BASIS: [[f32; 3]; 12] = [
-        [1.0, 1.0, 0.0],
-        [-1.0, 1.0, 0.0],
-        [1.0, -1.0, 0.0],
-        [-1.0, -1.0, 0.0],
-        [1.0, 0.0, 1.0],
-        [-1.0, 0.0, 1.0],
-        [1.0, 0.0, -1.0],
-        [-1.0, 0.0, -1.0],
-        [0.0, 1.0, 1.0],
-        [0.0, -1.0, 1.0],
-        [0.0, 1.0, -1.0],
-        [0.0, -1.0, -1.0],
-    ];
+const BASIS: [[f32; 3]; 12] = [
+    [1.0, 1.0, 0.0],
+    [-1.0, 1.0, 0.0],
+    [1.0, -1.0, 0.0],
+    [-1.0, -1.0, 0.0],
+    [1.0, 0.0, 1.0],
+    [-1.0, 0.0, 1.0],
+    [1.0, 0.0, -1.0],
+    [-1.0, 0.0, -1.0],
+    [0.0, 1.0, 1.0],
+    [0.0, -1.0, 1.0],
+    [0.0, 1.0, -1.0],
+    [0.0, -1.0, -1.0],
+];
 
+#[inline(always)]
+pub(crate) fn grad_vector(index: usize) -> [f32; 3] {
     // Unfortunately the compiler does not track the range of values in INDICES,
     // and so cannot see a bounds check is superflous here. Do it ourselves.
     //
     // SAFETY: const loop asserts all values in INDICES are in bounds for BASIS.
-    let &[a, b, c] = unsafe {
+    unsafe {
         const _: () = {
             let mut i = 0;
             while i < INDICES.len() {
@@ -101,11 +102,56 @@ fn grad(index: usize, x: f32, y: f32, z: f32) -> f32 {
                 i += 1;
             }
         };
-        BASIS.get_unchecked(INDICES[index] as usize)
-    };
+        *BASIS.get_unchecked(INDICES[index] as usize)
+    }
+}
+
+#[inline(always)]
+fn grad(index: usize, x: f32, y: f32, z: f32) -> f32 {
+    let [a, b, c] = grad_vector(index);
     a * x + b * y + c * z
 }
 
+/// 4d gradients are the 32 vectors with one zero component and the other three
+/// components `±1`.
+#[inline(always)]
+fn grad4(hash: usize, x: f32, y: f32, z: f32, w: f32) -> f32 {
+    let [a, b, c] = match (hash >> 3) & 3 {
+        0 => [y, z, w],
+        1 => [x, z, w],
+        2 => [x, y, w],
+        _ => [x, y, z],
+    };
+    let a = if hash & 1 != 0 { -a } else { a };
+    let b = if hash & 2 != 0 { -b } else { b };
+    let c = if hash & 4 != 0 { -c } else { c };
+    a + b + c
+}
+
+#[inline(always)]
+fn ease(a: f32) -> f32 {
+    a.mul_add(6.0, -15.0).mul_add(a, 10.0) * a * a * a
+}
+
+#[inline(always)]
+fn ease_deriv(a: f32) -> f32 {
+    30.0 * a * a * (a - 1.0) * (a - 1.0)
+}
+
+#[inline(always)]
+fn ease_f32x4(a: f32x4) -> f32x4 {
+    a.mul_add(f32x4::splat(6.0), f32x4::splat(-15.0))
+        .mul_add(a, f32x4::splat(10.0))
+        * a
+        * a
+        * a
+}
+
+#[inline(always)]
+fn lerp_f32x4(t: f32x4, a: f32x4, b: f32x4) -> f32x4 {
+    t.mul_add(b, t.mul_add(-a, a))
+}
+
 /// Computes a random value at the coordinate (x,y,z)
 ///
 /// Adjacent random values are continuous but the noise fluctuates its
@@ -143,11 +189,6 @@ pub fn perlin_noise3_wrap_seed(
     let z0 = pz as i32 as usize & z_mask;
     let z1 = (pz + 1.0) as i32 as usize & z_mask;
 
-    #[inline(always)]
-    fn ease(a: f32) -> f32 {
-        a.mul_add(6.0, -15.0).mul_add(a, 10.0) * a * a * a
-    }
-
     let x = x - px;
     let u = ease(x);
     let y = y - py;
@@ -215,6 +256,457 @@ pub fn perlin_noise3(x: f32, y: f32, z: f32) -> f32 {
     perlin_noise3_wrap_seed(x, y, z, 0, 0, 0, 0)
 }
 
+/// Computes a random value at the coordinate (x,y)
+///
+/// Equivalent to [`perlin_noise3_wrap_seed`] with `z` fixed at `0.0`, but
+/// cheaper to evaluate.
+///
+/// The `wrap` parameters can be used to create wraparound noise that wraps at
+/// powers of two. The numbers MUST be powers of two. Specify `0` to mean
+/// "don't care". The noise always wraps every 256 due to details of the
+/// implementation, even if you ask for larger or no wrapping.
+///
+/// `seed` selects from multiple different variations of the noise function.
+pub fn perlin_noise2_wrap_seed(x: f32, y: f32, x_wrap: usize, y_wrap: usize, seed: u8) -> f32 {
+    let seed = seed as usize;
+    let x_mask = x_wrap.wrapping_sub(1) & 255;
+    let y_mask = y_wrap.wrapping_sub(1) & 255;
+    let px = x.floor();
+    let py = y.floor();
+    let x0 = px as i32 as usize & x_mask;
+    let x1 = (px + 1.0) as i32 as usize & x_mask;
+    let y0 = py as i32 as usize & y_mask;
+    let y1 = (py + 1.0) as i32 as usize & y_mask;
+
+    let x = x - px;
+    let u = ease(x);
+    let y = y - py;
+    let v = ease(y);
+
+    let r0 = PERMUTE[x0 + seed] as usize;
+    let r1 = PERMUTE[x1 + seed] as usize;
+
+    let n00 = grad(PERMUTE[r0 + y0] as usize, x, y, 0.0);
+    let n01 = grad(PERMUTE[r0 + y1] as usize, x, y - 1.0, 0.0);
+    let n10 = grad(PERMUTE[r1 + y0] as usize, x - 1.0, y, 0.0);
+    let n11 = grad(PERMUTE[r1 + y1] as usize, x - 1.0, y - 1.0, 0.0);
+
+    let n0 = lerp(v, n00, n01);
+    let n1 = lerp(v, n10, n11);
+
+    lerp(u, n0, n1)
+}
+
+/// Computes a random value at the coordinate (x,y)
+///
+/// See [`perlin_noise2_wrap_seed`].
+pub fn perlin_noise2(x: f32, y: f32) -> f32 {
+    perlin_noise2_wrap_seed(x, y, 0, 0, 0)
+}
+
+/// Computes [`perlin_noise2`] and its partial derivatives at the coordinate
+/// (x,y)
+///
+/// Returns `(noise, (d/dx, d/dy))`.
+pub fn perlin_noise2_deriv(x: f32, y: f32) -> (f32, Vec2) {
+    let px = x.floor();
+    let py = y.floor();
+    let x0 = px as i32 as usize & 255;
+    let x1 = (x0 + 1) & 255;
+    let y0 = py as i32 as usize & 255;
+    let y1 = (y0 + 1) & 255;
+
+    let x = x - px;
+    let y = y - py;
+    let u = ease(x);
+    let v = ease(y);
+    let du = ease_deriv(x);
+    let dv = ease_deriv(y);
+
+    let r0 = PERMUTE[x0] as usize;
+    let r1 = PERMUTE[x1] as usize;
+
+    let g = |index: usize| {
+        let [a, b, _] = grad_vector(PERMUTE[index] as usize);
+        Vec2::new(a, b)
+    };
+    let g00 = g(r0 + y0);
+    let g01 = g(r0 + y1);
+    let g10 = g(r1 + y0);
+    let g11 = g(r1 + y1);
+
+    let n00 = g00.x * x + g00.y * y;
+    let n01 = g01.x * x + g01.y * (y - 1.0);
+    let n10 = g10.x * (x - 1.0) + g10.y * y;
+    let n11 = g11.x * (x - 1.0) + g11.y * (y - 1.0);
+
+    // Bilinear interpolation written as a polynomial in the eased coordinates,
+    // so it can be differentiated directly.
+    let k0 = n00;
+    let k1 = n10 - n00;
+    let k2 = n01 - n00;
+    let k3 = n00 - n10 - n01 + n11;
+
+    let noise = k0 + k1 * u + k2 * v + k3 * u * v;
+
+    let gradient = g00 + (g10 - g00) * u + (g01 - g00) * v + (g00 - g10 - g01 + g11) * (u * v);
+    let deriv = gradient + Vec2::new(du * (k1 + k3 * v), dv * (k2 + k3 * u));
+
+    (noise, deriv)
+}
+
+/// Computes [`perlin_noise3`] and its partial derivatives at the coordinate
+/// (x,y,z)
+///
+/// Returns `(noise, (d/dx, d/dy, d/dz))`.
+pub fn perlin_noise3_deriv(x: f32, y: f32, z: f32) -> (f32, Vec3) {
+    let px = x.floor();
+    let py = y.floor();
+    let pz = z.floor();
+    let x0 = px as i32 as usize & 255;
+    let x1 = (x0 + 1) & 255;
+    let y0 = py as i32 as usize & 255;
+    let y1 = (y0 + 1) & 255;
+    let z0 = pz as i32 as usize & 255;
+    let z1 = (z0 + 1) & 255;
+
+    let x = x - px;
+    let y = y - py;
+    let z = z - pz;
+    let u = ease(x);
+    let v = ease(y);
+    let w = ease(z);
+    let du = ease_deriv(x);
+    let dv = ease_deriv(y);
+    let dw = ease_deriv(z);
+
+    let r0 = PERMUTE[x0] as usize;
+    let r1 = PERMUTE[x1] as usize;
+
+    let r00 = PERMUTE[r0 + y0] as usize;
+    let r01 = PERMUTE[r0 + y1] as usize;
+    let r10 = PERMUTE[r1 + y0] as usize;
+    let r11 = PERMUTE[r1 + y1] as usize;
+
+    let g = |index: usize| Vec3::from(grad_vector(index));
+    let g000 = g(r00 + z0);
+    let g001 = g(r00 + z1);
+    let g010 = g(r01 + z0);
+    let g011 = g(r01 + z1);
+    let g100 = g(r10 + z0);
+    let g101 = g(r10 + z1);
+    let g110 = g(r11 + z0);
+    let g111 = g(r11 + z1);
+
+    let n = |g: Vec3, x: f32, y: f32, z: f32| g.x * x + g.y * y + g.z * z;
+    let n000 = n(g000, x, y, z);
+    let n001 = n(g001, x, y, z - 1.0);
+    let n010 = n(g010, x, y - 1.0, z);
+    let n011 = n(g011, x, y - 1.0, z - 1.0);
+    let n100 = n(g100, x - 1.0, y, z);
+    let n101 = n(g101, x - 1.0, y, z - 1.0);
+    let n110 = n(g110, x - 1.0, y - 1.0, z);
+    let n111 = n(g111, x - 1.0, y - 1.0, z - 1.0);
+
+    // Trilinear interpolation written as a polynomial in the eased
+    // coordinates, so it can be differentiated directly.
+    let k0 = n000;
+    let k1 = n100 - n000;
+    let k2 = n010 - n000;
+    let k3 = n001 - n000;
+    let k4 = n000 - n100 - n010 + n110;
+    let k5 = n000 - n010 - n001 + n011;
+    let k6 = n000 - n100 - n001 + n101;
+    let k7 = -n000 + n100 + n010 - n110 + n001 - n101 - n011 + n111;
+
+    let noise =
+        k0 + k1 * u + k2 * v + k3 * w + k4 * u * v + k5 * v * w + k6 * w * u + k7 * u * v * w;
+
+    let gradient = g000
+        + (g100 - g000) * u
+        + (g010 - g000) * v
+        + (g001 - g000) * w
+        + (g000 - g100 - g010 + g110) * (u * v)
+        + (g000 - g010 - g001 + g011) * (v * w)
+        + (g000 - g100 - g001 + g101) * (w * u)
+        + (-g000 + g100 + g010 - g110 + g001 - g101 - g011 + g111) * (u * v * w);
+    let deriv = gradient
+        + Vec3::new(
+            du * (k1 + k4 * v + k6 * w + k7 * v * w),
+            dv * (k2 + k5 * w + k4 * u + k7 * w * u),
+            dw * (k3 + k6 * u + k5 * v + k7 * u * v),
+        );
+
+    (noise, deriv)
+}
+
+/// Computes a random value at the coordinate (x,y,z,w)
+///
+/// The 4d extension of [`perlin_noise3_wrap_seed`], useful for animating 3d
+/// noise or sampling looping noise on a torus.
+///
+/// The `wrap` parameters can be used to create wraparound noise that wraps at
+/// powers of two. The numbers MUST be powers of two. Specify `0` to mean
+/// "don't care". The noise always wraps every 256 due to details of the
+/// implementation, even if you ask for larger or no wrapping.
+///
+/// `seed` selects from multiple different variations of the noise function.
+pub fn perlin_noise4_wrap_seed(
+    x: f32,
+    y: f32,
+    z: f32,
+    w: f32,
+    x_wrap: usize,
+    y_wrap: usize,
+    z_wrap: usize,
+    w_wrap: usize,
+    seed: u8,
+) -> f32 {
+    let seed = seed as usize;
+    let p = [x.floor(), y.floor(), z.floor(), w.floor()];
+    let masks = [x_wrap, y_wrap, z_wrap, w_wrap].map(|wrap| wrap.wrapping_sub(1) & 255);
+    let i0: [usize; 4] = std::array::from_fn(|i| p[i] as i32 as usize & masks[i]);
+    let i1: [usize; 4] = std::array::from_fn(|i| (p[i] + 1.0) as i32 as usize & masks[i]);
+    let f = [x - p[0], y - p[1], z - p[2], w - p[3]];
+    let e = f.map(ease);
+
+    // Corner `c` takes the upper coordinate on axis `a` if bit `3 - a` is set.
+    let n: [f32; 16] = std::array::from_fn(|c| {
+        let corner = |axis: usize| (c >> (3 - axis)) & 1;
+        let index = |axis: usize| {
+            if corner(axis) == 0 {
+                i0[axis]
+            } else {
+                i1[axis]
+            }
+        };
+        let offset = |axis: usize| f[axis] - corner(axis) as f32;
+        let r = PERMUTE[index(0) + seed] as usize;
+        let r = PERMUTE[r + index(1)] as usize;
+        let r = PERMUTE[r + index(2)] as usize;
+        let r = PERMUTE[r + index(3)] as usize;
+        grad4(r, offset(0), offset(1), offset(2), offset(3))
+    });
+
+    let n: [f32; 8] = std::array::from_fn(|i| lerp(e[3], n[i * 2], n[i * 2 + 1]));
+    let n: [f32; 4] = std::array::from_fn(|i| lerp(e[2], n[i * 2], n[i * 2 + 1]));
+    let n: [f32; 2] = std::array::from_fn(|i| lerp(e[1], n[i * 2], n[i * 2 + 1]));
+    lerp(e[0], n[0], n[1])
+}
+
+/// Computes a random value at the coordinate (x,y,z,w)
+///
+/// See [`perlin_noise4_wrap_seed`].
+pub fn perlin_noise4(x: f32, y: f32, z: f32, w: f32) -> f32 {
+    perlin_noise4_wrap_seed(x, y, z, w, 0, 0, 0, 0, 0)
+}
+
+/// Computes [`perlin_noise2`] for four coordinates at once.
+pub fn perlin_noise2_f32x4(x: &[f32; 4], y: &[f32; 4], out: &mut [f32; 4]) {
+    let x = f32x4::load(x);
+    let y = f32x4::load(y);
+    let px = x.floor();
+    let py = y.floor();
+    let x = x - px;
+    let y = y - py;
+    let u = ease_f32x4(x);
+    let v = ease_f32x4(y);
+
+    // Table lookups are done per lane, the remaining arithmetic is vectorized.
+    let px = px.to_array();
+    let py = py.to_array();
+    let mut gradients = [[[0.0; 4]; 2]; 4];
+    for lane in 0..4 {
+        let x0 = px[lane] as i32 as usize & 255;
+        let x1 = (x0 + 1) & 255;
+        let y0 = py[lane] as i32 as usize & 255;
+        let y1 = (y0 + 1) & 255;
+        let r0 = PERMUTE[x0] as usize;
+        let r1 = PERMUTE[x1] as usize;
+        let hashes = [r0 + y0, r0 + y1, r1 + y0, r1 + y1];
+        for (gradient, hash) in gradients.iter_mut().zip(hashes) {
+            let [a, b, _] = grad_vector(PERMUTE[hash] as usize);
+            gradient[0][lane] = a;
+            gradient[1][lane] = b;
+        }
+    }
+
+    let n = |corner: usize, x: f32x4, y: f32x4| {
+        let [a, b] = gradients[corner].map(f32x4::from_array);
+        a * x + b * y
+    };
+    let one = f32x4::ONE;
+    let n00 = n(0, x, y);
+    let n01 = n(1, x, y - one);
+    let n10 = n(2, x - one, y);
+    let n11 = n(3, x - one, y - one);
+
+    let n0 = lerp_f32x4(v, n00, n01);
+    let n1 = lerp_f32x4(v, n10, n11);
+
+    lerp_f32x4(u, n0, n1).store(out)
+}
+
+/// Computes [`perlin_noise3`] for four coordinates at once.
+pub fn perlin_noise3_f32x4(x: &[f32; 4], y: &[f32; 4], z: &[f32; 4], out: &mut [f32; 4]) {
+    let x = f32x4::load(x);
+    let y = f32x4::load(y);
+    let z = f32x4::load(z);
+    let px = x.floor();
+    let py = y.floor();
+    let pz = z.floor();
+    let x = x - px;
+    let y = y - py;
+    let z = z - pz;
+    let u = ease_f32x4(x);
+    let v = ease_f32x4(y);
+    let w = ease_f32x4(z);
+
+    // Table lookups are done per lane, the remaining arithmetic is vectorized.
+    let px = px.to_array();
+    let py = py.to_array();
+    let pz = pz.to_array();
+    let mut gradients = [[[0.0; 4]; 3]; 8];
+    for lane in 0..4 {
+        let x0 = px[lane] as i32 as usize & 255;
+        let x1 = (x0 + 1) & 255;
+        let y0 = py[lane] as i32 as usize & 255;
+        let y1 = (y0 + 1) & 255;
+        let z0 = pz[lane] as i32 as usize & 255;
+        let z1 = (z0 + 1) & 255;
+        let r0 = PERMUTE[x0] as usize;
+        let r1 = PERMUTE[x1] as usize;
+        let r00 = PERMUTE[r0 + y0] as usize;
+        let r01 = PERMUTE[r0 + y1] as usize;
+        let r10 = PERMUTE[r1 + y0] as usize;
+        let r11 = PERMUTE[r1 + y1] as usize;
+        let hashes = [
+            r00 + z0,
+            r00 + z1,
+            r01 + z0,
+            r01 + z1,
+            r10 + z0,
+            r10 + z1,
+            r11 + z0,
+            r11 + z1,
+        ];
+        for (gradient, hash) in gradients.iter_mut().zip(hashes) {
+            let [a, b, c] = grad_vector(hash);
+            gradient[0][lane] = a;
+            gradient[1][lane] = b;
+            gradient[2][lane] = c;
+        }
+    }
+
+    let n = |corner: usize, x: f32x4, y: f32x4, z: f32x4| {
+        let [a, b, c] = gradients[corner].map(f32x4::from_array);
+        a * x + b * y + c * z
+    };
+    let one = f32x4::ONE;
+    let n000 = n(0, x, y, z);
+    let n001 = n(1, x, y, z - one);
+    let n010 = n(2, x, y - one, z);
+    let n011 = n(3, x, y - one, z - one);
+    let n100 = n(4, x - one, y, z);
+    let n101 = n(5, x - one, y, z - one);
+    let n110 = n(6, x - one, y - one, z);
+    let n111 = n(7, x - one, y - one, z - one);
+
+    let n00 = lerp_f32x4(w, n000, n001);
+    let n01 = lerp_f32x4(w, n010, n011);
+    let n10 = lerp_f32x4(w, n100, n101);
+    let n11 = lerp_f32x4(w, n110, n111);
+
+    let n0 = lerp_f32x4(v, n00, n01);
+    let n1 = lerp_f32x4(v, n10, n11);
+
+    lerp_f32x4(u, n0, n1).store(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use narcissus_core::random::Pcg64;
+
+    use super::*;
+
+    #[test]
+    fn noise2_matches_noise3() {
+        let mut rng = Pcg64::new();
+        for _ in 0..10_000 {
+            let x = rng.next_f32_s() * 300.0;
+            let y = rng.next_f32_s() * 300.0;
+            assert_eq!(perlin_noise2(x, y), perlin_noise3(x, y, 0.0));
+            assert_eq!(
+                perlin_noise2_wrap_seed(x, y, 4, 8, 7),
+                perlin_noise3_wrap_seed(x, y, 0.0, 4, 8, 0, 7)
+            );
+        }
+    }
+
+    #[test]
+    fn noise4() {
+        let mut rng = Pcg64::new();
+        for _ in 0..10_000 {
+            // Quantize so that offsetting by whole numbers is exact.
+            let p: [f32; 4] =
+                std::array::from_fn(|_| (rng.next_f32_s() * 300.0 * 1024.0).round() / 1024.0);
+            let n = perlin_noise4(p[0], p[1], p[2], p[3]);
+            assert!((-2.0..=2.0).contains(&n));
+            // Wraps every 256 regardless, and at the requested power of two.
+            assert_eq!(n, perlin_noise4(p[0] + 256.0, p[1], p[2], p[3]));
+            assert_eq!(
+                perlin_noise4_wrap_seed(p[0], p[1], p[2], p[3], 0, 0, 0, 4, 1),
+                perlin_noise4_wrap_seed(p[0], p[1], p[2], p[3] - 4.0, 0, 0, 0, 4, 1)
+            );
+        }
+        // Zero at integer lattice points.
+        assert_eq!(perlin_noise4(3.0, -2.0, 7.0, 1.0), 0.0);
+    }
+
+    #[test]
+    fn derivatives() {
+        const H: f64 = 1e-3;
+        let mut rng = Pcg64::new();
+        for _ in 0..10_000 {
+            let [x, y, z] = std::array::from_fn(|_| rng.next_f32_s() * 300.0);
+
+            let (n, d) = perlin_noise2_deriv(x, y);
+            assert!((n - perlin_noise2(x, y)).abs() < 1e-5);
+            let central = |f: &dyn Fn(f64) -> f32| (f(H) - f(-H)) as f64 / (2.0 * H);
+            let dx = central(&|h| perlin_noise2((x as f64 + h) as f32, y));
+            let dy = central(&|h| perlin_noise2(x, (y as f64 + h) as f32));
+            assert!((d.x as f64 - dx).abs() < 0.05, "{d:?} {dx} {dy}");
+            assert!((d.y as f64 - dy).abs() < 0.05, "{d:?} {dx} {dy}");
+
+            let (n, d) = perlin_noise3_deriv(x, y, z);
+            assert!((n - perlin_noise3(x, y, z)).abs() < 1e-5);
+            let dx = central(&|h| perlin_noise3((x as f64 + h) as f32, y, z));
+            let dy = central(&|h| perlin_noise3(x, (y as f64 + h) as f32, z));
+            let dz = central(&|h| perlin_noise3(x, y, (z as f64 + h) as f32));
+            assert!((d.x as f64 - dx).abs() < 0.05, "{d:?} {dx} {dy} {dz}");
+            assert!((d.y as f64 - dy).abs() < 0.05, "{d:?} {dx} {dy} {dz}");
+            assert!((d.z as f64 - dz).abs() < 0.05, "{d:?} {dx} {dy} {dz}");
+        }
+    }
+
+    #[test]
+    fn f32x4_matches_scalar() {
+        let mut rng = Pcg64::new();
+        for _ in 0..10_000 {
+            let [x, y, z] =
+                std::array::from_fn(|_| std::array::from_fn(|_| rng.next_f32_s() * 300.0));
+            let mut out = [0.0; 4];
+            perlin_noise2_f32x4(&x, &y, &mut out);
+            assert_eq!(out, std::array::from_fn(|i| perlin_noise2(x[i], y[i])));
+            perlin_noise3_f32x4(&x, &y, &z, &mut out);
+            assert_eq!(
+                out,
+                std::array::from_fn(|i| perlin_noise3(x[i], y[i], z[i]))
+            );
+        }
+    }
+}
+
 /*
 ------------------------------------------------------------------------------
 This software is available under 2 licenses -- choose whichever you prefer.
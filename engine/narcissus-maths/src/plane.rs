@@ -0,0 +1,97 @@
+use crate::{Point3, Vec3};
+
+/// Plane in 3d space, containing all points `p` where `dot(normal, p) ==
+/// distance`.
+///
+/// Points where `dot(normal, p) > distance` are considered in front of the
+/// plane.
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[repr(C)]
+pub struct Plane {
+    pub normal: Vec3,
+    pub distance: f32,
+}
+
+impl Plane {
+    /// Constructs a new plane with the given `normal` and `distance` from the
+    /// origin along that normal.
+    #[inline(always)]
+    pub const fn new(normal: Vec3, distance: f32) -> Self {
+        Self { normal, distance }
+    }
+
+    /// Constructs a new plane passing through `point` with the given `normal`.
+    #[inline(always)]
+    pub fn from_point_normal(point: Point3, normal: Vec3) -> Self {
+        Self {
+            normal,
+            distance: Vec3::dot(normal, point.as_vec3()),
+        }
+    }
+
+    /// Constructs a new plane passing through the points `a`, `b` and `c`, with a
+    /// unit normal facing the side from which the points appear counter-clockwise.
+    ///
+    /// The result contains non-finite values if the points are collinear.
+    #[inline(always)]
+    pub fn from_points(a: Point3, b: Point3, c: Point3) -> Self {
+        let normal = Vec3::cross(b - a, c - a).normalized();
+        Self::from_point_normal(a, normal)
+    }
+
+    /// Returns a plane equivalent to `self` with a unit length normal.
+    #[must_use]
+    #[inline(always)]
+    pub fn normalized(&self) -> Plane {
+        let inv_length = 1.0 / self.normal.length();
+        Self {
+            normal: self.normal * inv_length,
+            distance: self.distance * inv_length,
+        }
+    }
+
+    /// Returns the signed distance from `self` to `point`, which is positive if
+    /// the point is in front of the plane.
+    ///
+    /// The result is scaled by the length of the plane's normal.
+    #[inline(always)]
+    pub fn signed_distance(&self, point: Point3) -> f32 {
+        Vec3::dot(self.normal, point.as_vec3()) - self.distance
+    }
+
+    /// Returns the point on `self` closest to `point`.
+    ///
+    /// Requires the plane's normal to be unit length.
+    #[inline(always)]
+    pub fn closest_point(&self, point: Point3) -> Point3 {
+        point - self.normal * self.signed_distance(point)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Plane, Point3, Vec3, point3};
+
+    #[test]
+    fn basic() {
+        let plane = Plane::from_points(
+            point3(0.0, 1.0, 0.0),
+            point3(1.0, 1.0, 0.0),
+            point3(0.0, 1.0, -1.0),
+        );
+        assert_eq!(plane, Plane::new(Vec3::Y, 1.0));
+        assert_eq!(plane.signed_distance(Point3::ZERO), -1.0);
+        assert_eq!(plane.signed_distance(point3(5.0, 3.0, 2.0)), 2.0);
+        assert_eq!(
+            plane.closest_point(point3(5.0, 3.0, 2.0)),
+            point3(5.0, 1.0, 2.0)
+        );
+
+        let scaled = Plane::new(Vec3::new(0.0, 0.0, -4.0), 2.0);
+        assert_eq!(scaled.normalized(), Plane::new(-Vec3::Z, 0.5));
+        assert_eq!(
+            Plane::from_point_normal(point3(1.0, 2.0, 3.0), Vec3::X),
+            Plane::new(Vec3::X, 1.0)
+        );
+    }
+}
@@ -0,0 +1,51 @@
+use narcissus_core::serialize::Pod;
+
+use crate::{Affine2, Affine3, Mat2, Mat3, Mat4, Point2, Point3, Quat, Vec2, Vec3, Vec4};
+
+// SAFETY: All types are `#[repr(C)]` and composed entirely of `f32`s, so have no
+// padding and every bit pattern is valid.
+unsafe impl Pod for Vec2 {}
+unsafe impl Pod for Vec3 {}
+unsafe impl Pod for Vec4 {}
+unsafe impl Pod for Point2 {}
+unsafe impl Pod for Point3 {}
+unsafe impl Pod for Quat {}
+unsafe impl Pod for Mat2 {}
+unsafe impl Pod for Mat3 {}
+unsafe impl Pod for Mat4 {}
+unsafe impl Pod for Affine2 {}
+unsafe impl Pod for Affine3 {}
+
+#[cfg(test)]
+mod tests {
+    use narcissus_core::{
+        Uuid,
+        serialize::{Reader, Schema, Writer},
+    };
+
+    use crate::{Affine3, Mat3, Mat4, Point3, Quat, Vec3, vec3};
+
+    const SCHEMA_ID: Uuid = Uuid::parse_str_unwrap("0f5d8f0e-5a9b-4c1e-8f7e-3c2b1a09d8e7");
+
+    #[test]
+    fn roundtrip() {
+        let positions = [vec3(1.0, 2.0, 3.0), vec3(4.0, 5.0, 6.0)];
+        let transform = Affine3::new(Mat3::IDENTITY, vec3(7.0, 8.0, 9.0));
+
+        let mut w = Writer::new(Schema::new(SCHEMA_ID, 1));
+        w.write(&Point3::new(1.0, 2.0, 3.0));
+        w.write(&Quat::IDENTITY);
+        w.write(&transform);
+        w.write(&Mat4::IDENTITY);
+        w.write_slice(&positions);
+        let bytes = w.finish();
+
+        let mut r = Reader::new(&bytes, SCHEMA_ID).unwrap();
+        assert_eq!(r.read::<Point3>().unwrap(), Point3::new(1.0, 2.0, 3.0));
+        assert_eq!(r.read::<Quat>().unwrap(), Quat::IDENTITY);
+        assert_eq!(r.read::<Affine3>().unwrap(), transform);
+        assert_eq!(r.read::<Mat4>().unwrap(), Mat4::IDENTITY);
+        assert_eq!(r.read_vec::<Vec3>().unwrap(), positions);
+        assert!(r.is_empty());
+    }
+}
@@ -0,0 +1,1037 @@
+use crate::{Point2, Vec2, acos_pi_f32, atan2_pi_f32, sin_cos_pi_f32, vec2};
+
+/// Orientation of a closed polygon.
+///
+/// Counter-clockwise assumes a y-up coordinate system; in y-down screen space
+/// the visual winding is reversed.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Winding {
+    CounterClockwise,
+    Clockwise,
+}
+
+/// Shape drawn at the interior vertices of a stroked or offset polyline.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum LineJoin {
+    /// Connects the outer edges with a straight line.
+    Bevel,
+    /// Extends the outer edges until they meet, falling back to a bevel when
+    /// the miter length exceeds `limit` times the offset distance.
+    Miter { limit: f32 },
+    /// Connects the outer edges with a circular arc, flattened such that no
+    /// point deviates from the arc by more than `tolerance`.
+    Round { tolerance: f32 },
+}
+
+/// Shape drawn at the end points of an open stroked polyline.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum LineCap {
+    /// Ends the stroke flush with the end point.
+    Butt,
+    /// Extends the stroke by half its width past the end point.
+    Square,
+    /// Caps the stroke with a half disc, flattened such that no point deviates
+    /// from the arc by more than `tolerance`.
+    Round { tolerance: f32 },
+}
+
+#[inline(always)]
+fn cross(a: Vec2, b: Vec2) -> f32 {
+    a.x * b.y - a.y * b.x
+}
+
+/// Twice the signed area of the triangle `a`, `b`, `c`, positive when the
+/// vertices turn counter-clockwise.
+#[inline(always)]
+fn orient(a: Point2, b: Point2, c: Point2) -> f32 {
+    cross(b - a, c - a)
+}
+
+/// Rotates `v` counter-clockwise by `angle` half-turns.
+#[inline(always)]
+fn rotate(v: Vec2, angle: f32) -> Vec2 {
+    let (s, c) = sin_cos_pi_f32(angle);
+    vec2(v.x * c - v.y * s, v.x * s + v.y * c)
+}
+
+/// Returns the signed area of the closed polygon `points`.
+///
+/// The area is positive for counter-clockwise polygons and negative for
+/// clockwise polygons. Self-intersecting polygons yield the sum of the signed
+/// areas of their loops.
+pub fn polygon_signed_area(points: &[Point2]) -> f32 {
+    let Some(&last) = points.last() else {
+        return 0.0;
+    };
+    let mut prev = last;
+    let mut area = 0.0;
+    for &p in points {
+        area += prev.x * p.y - p.x * prev.y;
+        prev = p;
+    }
+    area * 0.5
+}
+
+/// Returns the winding of the closed polygon `points`, or `None` if the
+/// polygon has no area.
+pub fn polygon_winding(points: &[Point2]) -> Option<Winding> {
+    let area = polygon_signed_area(points);
+    if area > 0.0 {
+        Some(Winding::CounterClockwise)
+    } else if area < 0.0 {
+        Some(Winding::Clockwise)
+    } else {
+        None
+    }
+}
+
+/// Tests whether `point` lies inside the closed polygon `points`, using the
+/// even-odd rule.
+///
+/// Points exactly on an edge may be classified either way.
+pub fn point_in_polygon(point: Point2, points: &[Point2]) -> bool {
+    let Some(&last) = points.last() else {
+        return false;
+    };
+    let mut inside = false;
+    let mut a = last;
+    for &b in points {
+        if (a.y > point.y) != (b.y > point.y) {
+            let x = a.x + (point.y - a.y) * (b.x - a.x) / (b.y - a.y);
+            if point.x < x {
+                inside = !inside;
+            }
+        }
+        a = b;
+    }
+    inside
+}
+
+/// Computes the convex hull of `points` using Andrew's monotone chain
+/// algorithm.
+///
+/// The hull is returned counter-clockwise starting from the lowest leftmost
+/// point, without collinear or duplicate vertices. Fewer than three points are
+/// returned when the input is degenerate.
+pub fn convex_hull(points: &[Point2]) -> Vec<Point2> {
+    let mut sorted = points.to_vec();
+    sorted.sort_unstable_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+    sorted.dedup();
+
+    if sorted.len() < 3 {
+        return sorted;
+    }
+
+    let mut hull: Vec<Point2> = Vec::with_capacity(sorted.len() + 1);
+
+    // Lower hull.
+    for &p in &sorted {
+        while hull.len() >= 2 && orient(hull[hull.len() - 2], hull[hull.len() - 1], p) <= 0.0 {
+            hull.pop();
+        }
+        hull.push(p);
+    }
+
+    // Upper hull, which must not pop any of the lower hull.
+    let lower_len = hull.len() + 1;
+    for &p in sorted.iter().rev().skip(1) {
+        while hull.len() >= lower_len
+            && orient(hull[hull.len() - 2], hull[hull.len() - 1], p) <= 0.0
+        {
+            hull.pop();
+        }
+        hull.push(p);
+    }
+
+    // The last point is the first point again.
+    hull.pop();
+    hull
+}
+
+/// Triangulates a polygon with holes using ear clipping.
+///
+/// Appends the indices of counter-clockwise triangles to `indices`. Indices
+/// refer to the concatenation of `outer` followed by each of `holes` in order.
+/// The outer ring and holes may be given in either winding, but must be simple
+/// and holes must lie inside the outer ring without touching each other.
+///
+/// Runs in quadratic time in the number of vertices, which is suited to the
+/// modest polygons found in UI shapes and editor footprints.
+pub fn triangulate_polygon(outer: &[Point2], holes: &[&[Point2]], indices: &mut Vec<u32>) {
+    if outer.len() < 3 {
+        return;
+    }
+
+    let mut points = Vec::with_capacity(outer.len() + holes.iter().map(|h| h.len()).sum::<usize>());
+    points.extend_from_slice(outer);
+
+    // The outer ring is wound counter-clockwise.
+    let mut ring: Vec<u32> = (0..outer.len() as u32).collect();
+    if polygon_signed_area(outer) < 0.0 {
+        ring.reverse();
+    }
+
+    // Holes are wound clockwise, so that bridging them into the outer ring
+    // keeps the interior on the left.
+    let mut hole_rings = Vec::with_capacity(holes.len());
+    for hole in holes {
+        let base = points.len() as u32;
+        points.extend_from_slice(hole);
+        if hole.len() < 3 {
+            continue;
+        }
+        let mut hole_ring: Vec<u32> = (base..base + hole.len() as u32).collect();
+        if polygon_signed_area(hole) > 0.0 {
+            hole_ring.reverse();
+        }
+        hole_rings.push(hole_ring);
+    }
+
+    // Bridge holes from right to left, so each bridge only needs to consider
+    // the outer ring and the holes already merged into it.
+    let rightmost = |hole: &Vec<u32>| {
+        hole.iter()
+            .map(|&i| points[i as usize].x)
+            .fold(f32::MIN, f32::max)
+    };
+    hole_rings.sort_by(|a, b| rightmost(b).total_cmp(&rightmost(a)));
+
+    for hole in &hole_rings {
+        bridge_hole(&points, &mut ring, hole);
+    }
+
+    ear_clip(&points, &ring, indices);
+}
+
+/// Tests whether the direction from `a` towards `b` lies inside the interior
+/// angle of a counter-clockwise ring at `a`.
+fn locally_inside(prev: Point2, a: Point2, next: Point2, b: Point2) -> bool {
+    if orient(prev, a, next) >= 0.0 {
+        orient(a, next, b) >= 0.0 && orient(a, b, prev) >= 0.0
+    } else {
+        orient(a, prev, b) < 0.0 || orient(a, b, next) < 0.0
+    }
+}
+
+/// Merges `hole` into `ring` with a pair of coincident edges between the
+/// rightmost hole vertex and a mutually visible ring vertex, following
+/// Eberly's "Triangulation by Ear Clipping".
+fn bridge_hole(points: &[Point2], ring: &mut Vec<u32>, hole: &[u32]) {
+    let point = |i: u32| points[i as usize];
+    let len = ring.len();
+
+    let (m_index, m) = hole
+        .iter()
+        .enumerate()
+        .map(|(i, &v)| (i, point(v)))
+        .max_by(|(_, a), (_, b)| a.x.total_cmp(&b.x).then(b.y.total_cmp(&a.y)))
+        .unwrap();
+
+    // Find the closest ring edge hit by a ray from `m` towards +x.
+    let mut hit_x = f32::INFINITY;
+    let mut hit_edge = None;
+    for i in 0..len {
+        let a = point(ring[i]);
+        let b = point(ring[(i + 1) % len]);
+        // Only edges crossing upwards face the hole from the interior.
+        if !(a.y <= m.y && b.y >= m.y && a.y != b.y) {
+            continue;
+        }
+        let x = a.x + (m.y - a.y) * (b.x - a.x) / (b.y - a.y);
+        if x >= m.x && x < hit_x {
+            hit_x = x;
+            hit_edge = Some(i);
+        }
+    }
+
+    let bridge = if let Some(edge) = hit_edge {
+        // The endpoint of the hit edge with the largest x is a candidate.
+        let next = (edge + 1) % len;
+        let mut best = if point(ring[edge]).x > point(ring[next]).x {
+            edge
+        } else {
+            next
+        };
+
+        // Any ring vertex inside the triangle formed by `m`, the hit point and
+        // the candidate may occlude it. The occluder making the smallest angle
+        // with the ray is visible.
+        let hit = Point2::new(hit_x, m.y);
+        let p = point(ring[best]);
+        if p != hit {
+            let (t0, t1, t2) = if p.y < m.y { (m, p, hit) } else { (m, hit, p) };
+            let mut best_tan = f32::INFINITY;
+            let mut best_distance = f32::INFINITY;
+            for i in 0..len {
+                let r = point(ring[i]);
+                if r.x < m.x
+                    || orient(t0, t1, r) < 0.0
+                    || orient(t1, t2, r) < 0.0
+                    || orient(t2, t0, r) < 0.0
+                {
+                    continue;
+                }
+                let prev = point(ring[(i + len - 1) % len]);
+                let next = point(ring[(i + 1) % len]);
+                if !locally_inside(prev, r, next, m) {
+                    continue;
+                }
+                let d = r - m;
+                let tan = d.y.abs() / d.x;
+                let distance = d.length_sq();
+                if tan < best_tan || tan == best_tan && distance < best_distance {
+                    best = i;
+                    best_tan = tan;
+                    best_distance = distance;
+                }
+            }
+        }
+        best
+    } else {
+        // The hole isn't enclosed by the ring, connect to the closest vertex.
+        (0..len)
+            .min_by(|&a, &b| {
+                let da = (point(ring[a]) - m).length_sq();
+                let db = (point(ring[b]) - m).length_sq();
+                da.total_cmp(&db)
+            })
+            .unwrap()
+    };
+
+    let bridge_vertex = ring[bridge];
+    let mut merged = Vec::with_capacity(len + hole.len() + 2);
+    merged.extend_from_slice(&ring[..=bridge]);
+    merged.extend_from_slice(&hole[m_index..]);
+    merged.extend_from_slice(&hole[..=m_index]);
+    merged.push(bridge_vertex);
+    merged.extend_from_slice(&ring[bridge + 1..]);
+    *ring = merged;
+}
+
+/// Clips ears from the counter-clockwise `ring` until a single triangle
+/// remains.
+fn ear_clip(points: &[Point2], ring: &[u32], indices: &mut Vec<u32>) {
+    let point = |i: usize| points[ring[i] as usize];
+    let n = ring.len();
+    if n < 3 {
+        return;
+    }
+
+    let mut prev: Vec<usize> = (0..n).map(|i| (i + n - 1) % n).collect();
+    let mut next: Vec<usize> = (0..n).map(|i| (i + 1) % n).collect();
+
+    let mut remaining = n;
+    let mut current = 0;
+    let mut stalled = 0;
+
+    while remaining > 3 {
+        let p = prev[current];
+        let q = next[current];
+        let (a, b, c) = (point(p), point(current), point(q));
+        let area = orient(a, b, c);
+
+        let clip = if stalled < remaining {
+            area > 0.0 && is_ear(points, ring, &next, p, current, q)
+        } else {
+            // No ear was found in a full pass, which happens with degenerate or
+            // self-intersecting input. Clip the vertex anyway so that the loop
+            // terminates.
+            true
+        };
+
+        if clip {
+            if area > 0.0 {
+                indices.extend_from_slice(&[ring[p], ring[current], ring[q]]);
+            }
+            next[p] = q;
+            prev[q] = p;
+            remaining -= 1;
+            stalled = 0;
+            current = q;
+        } else {
+            current = q;
+            stalled += 1;
+        }
+    }
+
+    let p = prev[current];
+    let q = next[current];
+    if orient(point(p), point(current), point(q)) > 0.0 {
+        indices.extend_from_slice(&[ring[p], ring[current], ring[q]]);
+    }
+}
+
+/// Tests whether no other remaining vertex lies within the triangle formed by
+/// `p`, `current` and `q`.
+fn is_ear(
+    points: &[Point2],
+    ring: &[u32],
+    next: &[usize],
+    p: usize,
+    current: usize,
+    q: usize,
+) -> bool {
+    let point = |i: usize| points[ring[i] as usize];
+    let (a, b, c) = (point(p), point(current), point(q));
+
+    let mut i = next[q];
+    while i != p {
+        let r = point(i);
+        // Bridges duplicate vertices, which never block an ear.
+        if r != a
+            && r != b
+            && r != c
+            && orient(a, b, r) >= 0.0
+            && orient(b, c, r) >= 0.0
+            && orient(c, a, r) >= 0.0
+        {
+            return false;
+        }
+        i = next[i];
+    }
+    true
+}
+
+/// Appends points along the arc from `center + from`, rotating by `angle`
+/// half-turns. The start point is not included.
+fn push_arc(center: Point2, from: Vec2, angle: f32, tolerance: f32, points: &mut Vec<Point2>) {
+    let radius = from.length();
+    let step = if tolerance < radius {
+        2.0 * acos_pi_f32(1.0 - tolerance / radius)
+    } else {
+        0.5
+    };
+    let segments = (angle.abs() / step).ceil().clamp(1.0, 1024.0) as u32;
+    for i in 1..=segments {
+        points.push(center + rotate(from, angle * i as f32 / segments as f32));
+    }
+}
+
+/// Appends a triangle fan around `center` through `rim`, wound
+/// counter-clockwise.
+fn push_fan(center: Point2, rim: &[Point2], vertices: &mut Vec<Point2>, indices: &mut Vec<u32>) {
+    if rim.len() < 2 {
+        return;
+    }
+    let base = vertices.len() as u32;
+    vertices.push(center);
+    vertices.extend_from_slice(rim);
+    let flip = orient(center, rim[0], rim[1]) < 0.0;
+    for i in 1..rim.len() as u32 {
+        let (b, c) = if flip { (i + 1, i) } else { (i, i + 1) };
+        indices.extend_from_slice(&[base, base + b, base + c]);
+    }
+}
+
+/// Removes consecutive duplicates, and the closing point of closed polylines.
+fn dedup_polyline(points: &[Point2], closed: bool) -> Vec<Point2> {
+    let mut points = points.to_vec();
+    points.dedup();
+    if closed && points.len() > 1 && points.first() == points.last() {
+        points.pop();
+    }
+    points
+}
+
+/// Converts a polyline into a triangle mesh covering all points within
+/// `width / 2` of the line.
+///
+/// Appends vertices and the indices of counter-clockwise triangles to
+/// `vertices` and `indices`. When `closed` is true the last point connects
+/// back to the first and `cap` is ignored.
+///
+/// Segments and joins are emitted as separate triangles which overlap on the
+/// inside of each turn, so translucent strokes should be drawn without
+/// blending between triangles of the same stroke.
+pub fn stroke_polyline(
+    points: &[Point2],
+    closed: bool,
+    width: f32,
+    join: LineJoin,
+    cap: LineCap,
+    vertices: &mut Vec<Point2>,
+    indices: &mut Vec<u32>,
+) {
+    let mut points = dedup_polyline(points, closed);
+    let n = points.len();
+    if n < 2 || (closed && n < 3) {
+        return;
+    }
+
+    let half_width = width * 0.5;
+    let segments = if closed { n } else { n - 1 };
+    let direction = |points: &[Point2], i: usize| (points[(i + 1) % n] - points[i]).normalized();
+    let normal = |d: Vec2| vec2(-d.y, d.x) * half_width;
+
+    if !closed && cap == LineCap::Square {
+        let d0 = direction(&points, 0);
+        let d1 = direction(&points, n - 2);
+        points[0] -= d0 * half_width;
+        points[n - 1] += d1 * half_width;
+    }
+
+    for i in 0..segments {
+        let a = points[i];
+        let b = points[(i + 1) % n];
+        let offset = normal(direction(&points, i));
+        let base = vertices.len() as u32;
+        vertices.extend_from_slice(&[a + offset, a - offset, b + offset, b - offset]);
+        indices.extend_from_slice(&[base, base + 1, base + 2, base + 2, base + 1, base + 3]);
+    }
+
+    let mut rim = Vec::new();
+
+    let joins = if closed { 0..n } else { 1..n - 1 };
+    for i in joins {
+        let p = points[i];
+        let d0 = direction(&points, (i + n - 1) % n);
+        let d1 = direction(&points, i);
+        let turn = cross(d0, d1);
+        if turn == 0.0 && Vec2::dot(d0, d1) > 0.0 {
+            continue;
+        }
+
+        // The join fills the gap on the outside of the turn.
+        let side = if turn > 0.0 { -1.0 } else { 1.0 };
+        let o0 = normal(d0) * side;
+        let o1 = normal(d1) * side;
+
+        rim.clear();
+        rim.push(p + o0);
+        match join {
+            LineJoin::Bevel => {}
+            LineJoin::Miter { limit } => {
+                // Ratio of the miter length to the half width is 1 / cos(θ/2).
+                let cos_half_sq = (1.0 + Vec2::dot(d0, d1)) * 0.5;
+                if cos_half_sq * limit * limit >= 1.0 {
+                    let miter = (o0 + o1) * (half_width * half_width / Vec2::dot(o0 + o1, o0));
+                    rim.push(p + miter);
+                }
+            }
+            LineJoin::Round { tolerance } => {
+                let angle = if turn == 0.0 {
+                    // Reversals have no outside, so sweep forward around the
+                    // tip.
+                    -side
+                } else {
+                    atan2_pi_f32(cross(o0, o1), Vec2::dot(o0, o1))
+                };
+                push_arc(p, o0, angle, tolerance, &mut rim);
+                rim.pop();
+            }
+        }
+        rim.push(p + o1);
+        push_fan(p, &rim, vertices, indices);
+    }
+
+    if let (false, LineCap::Round { tolerance }) = (closed, cap) {
+        let start = normal(direction(&points, 0));
+        rim.clear();
+        rim.push(points[0] + start);
+        push_arc(points[0], start, 1.0, tolerance, &mut rim);
+        push_fan(points[0], &rim, vertices, indices);
+
+        let end = -normal(direction(&points, n - 2));
+        rim.clear();
+        rim.push(points[n - 1] + end);
+        push_arc(points[n - 1], end, 1.0, tolerance, &mut rim);
+        push_fan(points[n - 1], &rim, vertices, indices);
+    }
+}
+
+/// Offsets a polyline sideways by `distance`, appending the resulting polyline
+/// to `out`.
+///
+/// Positive distances offset to the right of the direction of travel, which
+/// grows counter-clockwise polygons and shrinks clockwise ones. When `closed`
+/// is true the last point connects back to the first and the output is also
+/// closed.
+///
+/// Joins are applied to the outside of each turn, while the inside of each turn
+/// uses the intersection of the offset edges. Self-intersections caused by
+/// offsets larger than the local feature size are not removed.
+pub fn offset_polyline(
+    points: &[Point2],
+    closed: bool,
+    distance: f32,
+    join: LineJoin,
+    out: &mut Vec<Point2>,
+) {
+    let points = dedup_polyline(points, closed);
+    let n = points.len();
+    if n < 2 || (closed && n < 3) {
+        return;
+    }
+
+    let direction = |i: usize| (points[(i + 1) % n] - points[i]).normalized();
+    let normal = |d: Vec2| vec2(d.y, -d.x) * distance;
+
+    if !closed {
+        out.push(points[0] + normal(direction(0)));
+    }
+
+    let joins = if closed { 0..n } else { 1..n - 1 };
+    for i in joins {
+        let p = points[i];
+        let d0 = direction((i + n - 1) % n);
+        let d1 = direction(i);
+        let o0 = normal(d0);
+        let o1 = normal(d1);
+        let turn = cross(d0, d1);
+        let cos = Vec2::dot(d0, d1);
+
+        if turn == 0.0 && cos > 0.0 {
+            out.push(p + o0);
+            continue;
+        }
+
+        let miter = || p + (o0 + o1) * (distance * distance / Vec2::dot(o0 + o1, o0));
+
+        if turn * distance < 0.0 {
+            // Inside of the turn.
+            if cos > -1.0 + f32::EPSILON {
+                out.push(miter());
+            } else {
+                out.extend_from_slice(&[p + o0, p + o1]);
+            }
+            continue;
+        }
+
+        match join {
+            LineJoin::Bevel => out.extend_from_slice(&[p + o0, p + o1]),
+            LineJoin::Miter { limit } => {
+                if (1.0 + cos) * 0.5 * limit * limit >= 1.0 {
+                    out.push(miter());
+                } else {
+                    out.extend_from_slice(&[p + o0, p + o1]);
+                }
+            }
+            LineJoin::Round { tolerance } => {
+                let angle = if turn == 0.0 {
+                    // Reversals have no outside, so sweep forward around the
+                    // tip.
+                    distance.signum()
+                } else {
+                    atan2_pi_f32(cross(o0, o1), Vec2::dot(o0, o1))
+                };
+                out.push(p + o0);
+                push_arc(p, o0, angle, tolerance, out);
+            }
+        }
+    }
+
+    if !closed {
+        out.push(points[n - 1] + normal(direction(n - 2)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use narcissus_core::random::Pcg64;
+
+    use super::*;
+    use crate::point2;
+
+    const SQUARE: [Point2; 4] = [
+        Point2::new(0.0, 0.0),
+        Point2::new(1.0, 0.0),
+        Point2::new(1.0, 1.0),
+        Point2::new(0.0, 1.0),
+    ];
+
+    fn triangles_area(points: &[Point2], indices: &[u32]) -> f32 {
+        indices
+            .chunks_exact(3)
+            .map(|t| {
+                let area = orient(
+                    points[t[0] as usize],
+                    points[t[1] as usize],
+                    points[t[2] as usize],
+                ) * 0.5;
+                assert!(area > 0.0);
+                area
+            })
+            .sum()
+    }
+
+    fn covered(point: Point2, vertices: &[Point2], indices: &[u32]) -> bool {
+        indices.chunks_exact(3).any(|t| {
+            let (a, b, c) = (
+                vertices[t[0] as usize],
+                vertices[t[1] as usize],
+                vertices[t[2] as usize],
+            );
+            orient(a, b, point) >= 0.0 && orient(b, c, point) >= 0.0 && orient(c, a, point) >= 0.0
+        })
+    }
+
+    fn distance_to_polyline(point: Point2, points: &[Point2]) -> f32 {
+        points
+            .windows(2)
+            .map(|s| {
+                let ab = s[1] - s[0];
+                let t = (Vec2::dot(point - s[0], ab) / ab.length_sq()).clamp(0.0, 1.0);
+                (point - (s[0] + ab * t)).length()
+            })
+            .fold(f32::INFINITY, f32::min)
+    }
+
+    #[test]
+    fn area_and_winding() {
+        assert_eq!(polygon_signed_area(&SQUARE), 1.0);
+        assert_eq!(polygon_winding(&SQUARE), Some(Winding::CounterClockwise));
+
+        let mut reversed = SQUARE;
+        reversed.reverse();
+        assert_eq!(polygon_signed_area(&reversed), -1.0);
+        assert_eq!(polygon_winding(&reversed), Some(Winding::Clockwise));
+
+        assert_eq!(polygon_signed_area(&[]), 0.0);
+        assert_eq!(polygon_winding(&SQUARE[..2]), None);
+    }
+
+    #[test]
+    fn point_in_concave_polygon() {
+        // An L shape.
+        let l = [
+            point2(0.0, 0.0),
+            point2(2.0, 0.0),
+            point2(2.0, 1.0),
+            point2(1.0, 1.0),
+            point2(1.0, 2.0),
+            point2(0.0, 2.0),
+        ];
+        assert!(point_in_polygon(point2(0.5, 0.5), &l));
+        assert!(point_in_polygon(point2(1.5, 0.5), &l));
+        assert!(point_in_polygon(point2(0.5, 1.5), &l));
+        assert!(!point_in_polygon(point2(1.5, 1.5), &l));
+        assert!(!point_in_polygon(point2(-0.5, 0.5), &l));
+        assert!(!point_in_polygon(point2(0.5, 2.5), &l));
+        assert!(!point_in_polygon(point2(0.5, 0.5), &[]));
+    }
+
+    #[test]
+    fn hull() {
+        let mut rng = Pcg64::new();
+        let points = (0..200)
+            .map(|_| point2(rng.next_f32_s(), rng.next_f32_s()))
+            .collect::<Vec<_>>();
+        let hull = convex_hull(&points);
+        assert!(hull.len() >= 3);
+
+        for i in 0..hull.len() {
+            let a = hull[i];
+            let b = hull[(i + 1) % hull.len()];
+            for &p in &points {
+                assert!(orient(a, b, p) >= -1e-6);
+            }
+        }
+
+        // Collinear and duplicate points are removed.
+        let points = [
+            point2(0.0, 0.0),
+            point2(0.5, 0.0),
+            point2(1.0, 0.0),
+            point2(1.0, 1.0),
+            point2(1.0, 1.0),
+            point2(0.0, 1.0),
+            point2(0.5, 0.5),
+        ];
+        assert_eq!(
+            convex_hull(&points),
+            [
+                point2(0.0, 0.0),
+                point2(1.0, 0.0),
+                point2(1.0, 1.0),
+                point2(0.0, 1.0)
+            ]
+        );
+
+        assert_eq!(convex_hull(&[point2(1.0, 1.0); 3]), [point2(1.0, 1.0)]);
+    }
+
+    #[test]
+    fn triangulate_simple() {
+        let mut indices = vec![];
+        triangulate_polygon(&SQUARE, &[], &mut indices);
+        assert_eq!(indices.len(), 6);
+        assert_eq!(triangles_area(&SQUARE, &indices), 1.0);
+
+        // Clockwise input still produces counter-clockwise triangles.
+        let mut reversed = SQUARE;
+        reversed.reverse();
+        indices.clear();
+        triangulate_polygon(&reversed, &[], &mut indices);
+        assert_eq!(triangles_area(&reversed, &indices), 1.0);
+
+        // A star with alternating reflex vertices.
+        let star = (0..10)
+            .map(|i| {
+                let (s, c) = sin_cos_pi_f32(i as f32 * 0.2);
+                let r = if i % 2 == 0 { 1.0 } else { 0.4 };
+                point2(c * r, s * r)
+            })
+            .collect::<Vec<_>>();
+        indices.clear();
+        triangulate_polygon(&star, &[], &mut indices);
+        assert_eq!(indices.len(), (star.len() - 2) * 3);
+        let area = triangles_area(&star, &indices);
+        assert!((area - polygon_signed_area(&star)).abs() < 1e-5);
+    }
+
+    #[test]
+    fn triangulate_holes() {
+        let outer = [
+            point2(0.0, 0.0),
+            point2(10.0, 0.0),
+            point2(10.0, 10.0),
+            point2(0.0, 10.0),
+        ];
+        let hole0 = [
+            point2(2.0, 2.0),
+            point2(4.0, 2.0),
+            point2(4.0, 4.0),
+            point2(2.0, 4.0),
+        ];
+        // Wound clockwise, and sharing a y coordinate with the first hole.
+        let hole1 = [
+            point2(6.0, 2.0),
+            point2(6.0, 8.0),
+            point2(8.0, 8.0),
+            point2(8.0, 2.0),
+        ];
+        // A triangle left of the others.
+        let hole2 = [point2(1.0, 6.0), point2(3.0, 6.0), point2(2.0, 8.0)];
+
+        let mut points = outer.to_vec();
+        points.extend_from_slice(&hole0);
+        points.extend_from_slice(&hole1);
+        points.extend_from_slice(&hole2);
+
+        let mut indices = vec![];
+        triangulate_polygon(&outer, &[&hole0, &hole1, &hole2], &mut indices);
+
+        let area = triangles_area(&points, &indices);
+        assert!((area - (100.0 - 4.0 - 12.0 - 2.0)).abs() < 1e-4);
+
+        for t in indices.chunks_exact(3) {
+            let centroid = point2(
+                (points[t[0] as usize].x + points[t[1] as usize].x + points[t[2] as usize].x) / 3.0,
+                (points[t[0] as usize].y + points[t[1] as usize].y + points[t[2] as usize].y) / 3.0,
+            );
+            assert!(point_in_polygon(centroid, &outer));
+            assert!(!point_in_polygon(centroid, &hole0));
+            assert!(!point_in_polygon(centroid, &hole1));
+            assert!(!point_in_polygon(centroid, &hole2));
+        }
+    }
+
+    #[test]
+    fn stroke_straight() {
+        let line = [point2(0.0, 0.0), point2(4.0, 0.0)];
+        let mut vertices = vec![];
+        let mut indices = vec![];
+        stroke_polyline(
+            &line,
+            false,
+            2.0,
+            LineJoin::Bevel,
+            LineCap::Butt,
+            &mut vertices,
+            &mut indices,
+        );
+        assert_eq!(triangles_area(&vertices, &indices), 8.0);
+
+        vertices.clear();
+        indices.clear();
+        stroke_polyline(
+            &line,
+            false,
+            2.0,
+            LineJoin::Bevel,
+            LineCap::Square,
+            &mut vertices,
+            &mut indices,
+        );
+        assert_eq!(triangles_area(&vertices, &indices), 12.0);
+
+        vertices.clear();
+        indices.clear();
+        stroke_polyline(
+            &line,
+            false,
+            2.0,
+            LineJoin::Bevel,
+            LineCap::Round { tolerance: 0.001 },
+            &mut vertices,
+            &mut indices,
+        );
+        let area = triangles_area(&vertices, &indices);
+        assert!((area - (8.0 + std::f32::consts::PI)).abs() < 0.01);
+    }
+
+    #[test]
+    fn stroke_coverage() {
+        let zigzag = [
+            point2(0.0, 0.0),
+            point2(3.0, 2.0),
+            point2(4.0, -1.0),
+            point2(8.0, 0.5),
+            point2(5.0, 3.0),
+        ];
+        let half_width = 0.5;
+        let miter_limit = 4.0;
+
+        // Bevels cut the corners, so only points within `half_width` times
+        // the cosine of half the sharpest turn are guaranteed to be covered.
+        for (join, min_extent, max_extent) in [
+            (LineJoin::Bevel, half_width * 0.45, half_width),
+            (
+                LineJoin::Miter { limit: miter_limit },
+                half_width,
+                half_width * miter_limit,
+            ),
+            (LineJoin::Round { tolerance: 0.001 }, half_width, half_width),
+        ] {
+            let mut vertices = vec![];
+            let mut indices = vec![];
+            stroke_polyline(
+                &zigzag,
+                false,
+                half_width * 2.0,
+                join,
+                LineCap::Butt,
+                &mut vertices,
+                &mut indices,
+            );
+
+            let mut rng = Pcg64::new();
+            for _ in 0..2000 {
+                let p = point2(rng.next_f32() * 10.0 - 1.0, rng.next_f32() * 6.0 - 2.0);
+                let distance = distance_to_polyline(p, &zigzag);
+                // Butt caps leave the region beyond the end points uncovered.
+                let beyond_ends = Vec2::dot(p - zigzag[0], zigzag[1] - zigzag[0]) < 0.0
+                    || Vec2::dot(p - zigzag[4], zigzag[3] - zigzag[4]) < 0.0;
+                if distance < min_extent * 0.99 && !beyond_ends {
+                    assert!(covered(p, &vertices, &indices), "{join:?} {p:?}");
+                }
+                if distance > max_extent * 1.01 {
+                    assert!(!covered(p, &vertices, &indices), "{join:?} {p:?}");
+                }
+            }
+        }
+
+        // Reversals have no turn direction, but round joins must still cap
+        // the tip whichever way the polyline is oriented.
+        for (tip, back) in [
+            (point2(4.0, 0.0), point2(0.0, 0.0)),
+            (point2(0.0, 0.0), point2(4.0, 0.0)),
+            (point2(0.0, 4.0), point2(0.0, 0.0)),
+            (point2(0.0, 0.0), point2(0.0, 4.0)),
+        ] {
+            let mut vertices = vec![];
+            let mut indices = vec![];
+            stroke_polyline(
+                &[back, tip, back],
+                false,
+                1.6,
+                LineJoin::Round { tolerance: 0.001 },
+                LineCap::Butt,
+                &mut vertices,
+                &mut indices,
+            );
+            let forward = (tip - back).normalized();
+            for i in -7..=7 {
+                let (s, c) = sin_cos_pi_f32(i as f32 / 16.0);
+                let offset = vec2(forward.x * c - forward.y * s, forward.x * s + forward.y * c);
+                let p = tip + offset * 0.79;
+                assert!(covered(p, &vertices, &indices), "{tip:?} {p:?}");
+                assert!(!covered(tip + offset * 0.81, &vertices, &indices));
+            }
+        }
+    }
+
+    #[test]
+    fn offset_square() {
+        let mut out = vec![];
+        offset_polyline(&SQUARE, true, 0.5, LineJoin::Miter { limit: 2.0 }, &mut out);
+        assert_eq!(
+            out,
+            [
+                point2(-0.5, -0.5),
+                point2(1.5, -0.5),
+                point2(1.5, 1.5),
+                point2(-0.5, 1.5)
+            ]
+        );
+
+        // A right angle needs a miter limit of at least √2.
+        out.clear();
+        offset_polyline(&SQUARE, true, 0.5, LineJoin::Miter { limit: 1.4 }, &mut out);
+        assert_eq!(out.len(), 8);
+        assert!((polygon_signed_area(&out) - 3.5).abs() < 1e-5);
+
+        out.clear();
+        offset_polyline(&SQUARE, true, 0.5, LineJoin::Bevel, &mut out);
+        assert!((polygon_signed_area(&out) - 3.5).abs() < 1e-5);
+
+        out.clear();
+        offset_polyline(
+            &SQUARE,
+            true,
+            0.5,
+            LineJoin::Round { tolerance: 0.0001 },
+            &mut out,
+        );
+        let expected = 1.0 + 2.0 + std::f32::consts::PI * 0.25;
+        assert!((polygon_signed_area(&out) - expected).abs() < 1e-3);
+
+        // Negative distances shrink counter-clockwise polygons.
+        out.clear();
+        offset_polyline(
+            &SQUARE,
+            true,
+            -0.25,
+            LineJoin::Round { tolerance: 0.01 },
+            &mut out,
+        );
+        assert!((polygon_signed_area(&out) - 0.25).abs() < 1e-5);
+    }
+
+    #[test]
+    fn offset_open() {
+        let line = [point2(0.0, 0.0), point2(1.0, 0.0), point2(1.0, 1.0)];
+        let mut out = vec![];
+        offset_polyline(&line, false, 0.5, LineJoin::Bevel, &mut out);
+        assert_eq!(
+            out,
+            [
+                point2(0.0, -0.5),
+                point2(1.0, -0.5),
+                point2(1.5, 0.0),
+                point2(1.5, 1.0)
+            ]
+        );
+
+        out.clear();
+        offset_polyline(&line, false, -0.5, LineJoin::Bevel, &mut out);
+        assert_eq!(out, [point2(0.0, 0.5), point2(0.5, 0.5), point2(0.5, 1.0)]);
+
+        // Round joins at reversals sweep forward around the tip on either side.
+        let reversal = [point2(0.0, 0.0), point2(4.0, 0.0), point2(0.0, 0.0)];
+        for distance in [-1.0, 1.0] {
+            out.clear();
+            offset_polyline(
+                &reversal,
+                false,
+                distance,
+                LineJoin::Round { tolerance: 0.001 },
+                &mut out,
+            );
+            let max_x = out.iter().map(|p| p.x).fold(f32::MIN, f32::max);
+            assert!((max_x - 5.0).abs() < 1e-5);
+            assert_eq!(out.first().unwrap().y, -distance);
+            assert_eq!(out.last().unwrap().y, distance);
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{HalfTurn, Vec3, sin_cos_pi_f32};
+use crate::{HalfTurn, Mat3, Mat4, Vec3, asin_pi_f32, atan2_pi_f32, sin_cos_pi_f32};
 
 #[derive(Clone, Copy, PartialEq, Debug)]
 #[repr(C)]
@@ -50,6 +50,244 @@ impl Quat {
         }
     }
 
+    /// Returns the shortest rotation which rotates the unit vector `from` onto the
+    /// unit vector `to`.
+    ///
+    /// If the vectors point in opposite directions, returns a half turn around
+    /// an arbitrary axis perpendicular to `from`.
+    pub fn from_rotation_arc(from: Vec3, to: Vec3) -> Self {
+        // Rotating `from` onto the half-way vector, then again by the same amount,
+        // gives `to`. Using the half-way vector avoids the catastrophic
+        // cancellation of `1.0 + dot(from, to)` for nearly opposite vectors.
+        let half = from + to;
+        let half_len_sq = half.length_sq();
+        if half_len_sq < 1e-12 {
+            let other = if from.x.abs() < 0.9 { Vec3::X } else { Vec3::Y };
+            let axis = Vec3::cross(other, from).normalized();
+            return Self::new(axis.x, axis.y, axis.z, 0.0);
+        }
+        let half = half / half_len_sq.sqrt();
+        let v = Vec3::cross(from, half);
+        Self::new(v.x, v.y, v.z, Vec3::dot(from, half))
+    }
+
+    /// Returns a rotation which orients the local `-Z` axis along `dir`, and the
+    /// local `+Y` axis as close to `up` as possible.
+    ///
+    /// Neither `dir` nor `up` need to be normalized, but they must not be
+    /// parallel.
+    ///
+    /// Src coordinate space: right-handed, +y-up, -z-forward.
+    pub fn look_rotation(dir: Vec3, up: Vec3) -> Self {
+        let f = dir.normalized();
+        let r = Vec3::cross(f, up).normalized();
+        let u = Vec3::cross(r, f);
+        Self::from_mat3(Mat3::from_columns([r, u, -f]))
+    }
+
+    /// Returns a quaternion from Euler angles, rotating by `roll` around the `Z`
+    /// axis, then by `pitch` around the `X` axis, then by `yaw` around the `Y`
+    /// axis.
+    pub fn from_euler(yaw: HalfTurn, pitch: HalfTurn, roll: HalfTurn) -> Self {
+        Self::from_axis_rotation(Vec3::Y, yaw)
+            * Self::from_axis_rotation(Vec3::X, pitch)
+            * Self::from_axis_rotation(Vec3::Z, roll)
+    }
+
+    /// Returns the Euler angles `(yaw, pitch, roll)` of the unit quaternion
+    /// `self`, such that [`Quat::from_euler`] reconstructs the same rotation.
+    ///
+    /// Pitch is in the range `[-0.5, 0.5]`, yaw and roll are in the range
+    /// `[-1.0, 1.0]`. When pitch is a quarter turn yaw and roll are ambiguous,
+    /// and roll is returned as zero.
+    pub fn to_euler(self) -> (HalfTurn, HalfTurn, HalfTurn) {
+        let [[m00, _, m02], [m10, m11, m12], [m20, _, m22]] = *Mat3::from_quat(self).as_rows();
+        let pitch = HalfTurn::new(asin_pi_f32((-m12).clamp(-1.0, 1.0)));
+        if m12.abs() < 1.0 - 1e-6 {
+            let yaw = HalfTurn::new(atan2_pi_f32(m02, m22));
+            let roll = HalfTurn::new(atan2_pi_f32(m10, m11));
+            (yaw, pitch, roll)
+        } else {
+            let yaw = HalfTurn::new(atan2_pi_f32(-m20, m00));
+            (yaw, pitch, HalfTurn::new(0.0))
+        }
+    }
+
+    /// Returns a quaternion representing a rotation around the axis `v`, by
+    /// the length of `v` in half turns.
+    ///
+    /// The inverse of [`Quat::to_scaled_axis`]. Scaled axis vectors can be
+    /// added and scaled like ordinary vectors, which makes them suitable for
+    /// angular velocities.
+    pub fn from_scaled_axis(v: Vec3) -> Self {
+        let rotation = v.length();
+        if rotation < 1e-12 {
+            // sin(x) ~= x for small x.
+            let v = v * (std::f32::consts::PI * 0.5);
+            return Self::new(v.x, v.y, v.z, 1.0).normalized();
+        }
+        Self::from_axis_rotation(v / rotation, HalfTurn::new(rotation))
+    }
+
+    /// Returns the rotation of the unit quaternion `self` as a vector along
+    /// the axis of rotation, with a length equal to the rotation in half turns.
+    ///
+    /// Takes the shorter rotation, so the length is in the range `[0.0, 1.0]`.
+    pub fn to_scaled_axis(self) -> Vec3 {
+        let q = if self.d < 0.0 { -self } else { self };
+        let v = Vec3::new(q.a, q.b, q.c);
+        let sin = v.length();
+        if sin < 1e-12 {
+            return v * (2.0 / std::f32::consts::PI);
+        }
+        v * (2.0 * atan2_pi_f32(sin, q.d) / sin)
+    }
+
+    /// Returns a quaternion representing the same rotation as the rotation
+    /// matrix `m`.
+    ///
+    /// The result is unspecified if `m` is not a pure rotation.
+    pub fn from_mat3(m: Mat3) -> Self {
+        let [[m00, m01, m02], [m10, m11, m12], [m20, m21, m22]] = *m.as_rows();
+        // Choose the largest of the diagonal terms to divide by, for stability.
+        let trace = m00 + m11 + m22;
+        if trace > 0.0 {
+            let s = (trace + 1.0).sqrt() * 2.0;
+            Self::new((m21 - m12) / s, (m02 - m20) / s, (m10 - m01) / s, 0.25 * s)
+        } else if m00 > m11 && m00 > m22 {
+            let s = (1.0 + m00 - m11 - m22).sqrt() * 2.0;
+            Self::new(0.25 * s, (m01 + m10) / s, (m02 + m20) / s, (m21 - m12) / s)
+        } else if m11 > m22 {
+            let s = (1.0 + m11 - m00 - m22).sqrt() * 2.0;
+            Self::new((m01 + m10) / s, 0.25 * s, (m12 + m21) / s, (m02 - m20) / s)
+        } else {
+            let s = (1.0 + m22 - m00 - m11).sqrt() * 2.0;
+            Self::new((m02 + m20) / s, (m12 + m21) / s, 0.25 * s, (m10 - m01) / s)
+        }
+    }
+
+    /// Returns a quaternion representing the same rotation as the upper 3x3 part of
+    /// the transform `m`.
+    ///
+    /// The result is unspecified if the upper 3x3 part of `m` is not a pure
+    /// rotation.
+    pub fn from_mat4(m: Mat4) -> Self {
+        let [r0, r1, r2, _] = *m.as_rows();
+        Self::from_mat3(Mat3::from_rows([
+            [r0[0], r0[1], r0[2]],
+            [r1[0], r1[1], r1[2]],
+            [r2[0], r2[1], r2[2]],
+        ]))
+    }
+
+    /// Returns the dot product of `a` and `b`.
+    #[inline(always)]
+    pub fn dot(a: Quat, b: Quat) -> f32 {
+        a.a * b.a + a.b * b.b + a.c * b.c + a.d * b.d
+    }
+
+    /// Returns the length of `self`.
+    #[inline(always)]
+    pub fn length(self) -> f32 {
+        self.length_sq().sqrt()
+    }
+
+    /// Returns the squared length of `self`.
+    #[inline(always)]
+    pub fn length_sq(self) -> f32 {
+        Self::dot(self, self)
+    }
+
+    /// Returns a quaternion with the same direction as `self` but with unit
+    /// length.
+    #[must_use]
+    #[inline(always)]
+    pub fn normalized(self) -> Self {
+        self.scale(1.0 / self.length())
+    }
+
+    /// Returns the conjugate of `self`. For unit quaternions this is the
+    /// inverse rotation.
+    #[must_use]
+    #[inline(always)]
+    pub fn conjugate(self) -> Self {
+        Self::new(-self.a, -self.b, -self.c, self.d)
+    }
+
+    /// Returns the inverse of `self`.
+    ///
+    /// Prefer [`Quat::conjugate`] when `self` is known to be normalized.
+    #[must_use]
+    #[inline(always)]
+    pub fn inverse(self) -> Self {
+        self.conjugate().scale(1.0 / self.length_sq())
+    }
+
+    /// Returns `true` if all elements are finite.
+    pub fn is_finite(self) -> bool {
+        self.a.is_finite() && self.b.is_finite() && self.c.is_finite() && self.d.is_finite()
+    }
+
+    /// Normalized linear interpolation between `a` and `b` with the control
+    /// value `t`, taking the shortest path.
+    ///
+    /// Cheaper than [`Quat::slerp`], but doesn't have constant angular
+    /// velocity.
+    #[must_use]
+    pub fn nlerp(t: f32, a: Quat, b: Quat) -> Quat {
+        let b = if Self::dot(a, b) < 0.0 { -b } else { b };
+        Self::lerp_unnormalized(t, a, b).normalized()
+    }
+
+    /// Spherical linear interpolation between `a` and `b` with the control value
+    /// `t`, taking the shortest path.
+    #[must_use]
+    pub fn slerp(t: f32, a: Quat, b: Quat) -> Quat {
+        let mut cos = Self::dot(a, b);
+        let b = if cos < 0.0 {
+            cos = -cos;
+            -b
+        } else {
+            b
+        };
+
+        // Nearly parallel, fall back to nlerp to avoid dividing by zero.
+        if cos > 0.9995 {
+            return Self::lerp_unnormalized(t, a, b).normalized();
+        }
+
+        let theta = cos.acos();
+        let inv_sin = 1.0 / (1.0 - cos * cos).sqrt();
+        let wa = ((1.0 - t) * theta).sin() * inv_sin;
+        let wb = (t * theta).sin() * inv_sin;
+        a.scale(wa).add(b.scale(wb))
+    }
+
+    #[inline(always)]
+    fn lerp_unnormalized(t: f32, a: Quat, b: Quat) -> Quat {
+        a.scale(1.0 - t).add(b.scale(t))
+    }
+
+    #[inline(always)]
+    fn scale(self, scale: f32) -> Quat {
+        Self::new(
+            self.a * scale,
+            self.b * scale,
+            self.c * scale,
+            self.d * scale,
+        )
+    }
+
+    #[inline(always)]
+    fn add(self, rhs: Quat) -> Quat {
+        Self::new(
+            self.a + rhs.a,
+            self.b + rhs.b,
+            self.c + rhs.c,
+            self.d + rhs.d,
+        )
+    }
+
     /// Rotates `rhs` by `self`.
     pub fn transform_vec3(self, rhs: Vec3) -> Vec3 {
         let d = self.d;
@@ -60,6 +298,52 @@ impl Quat {
     }
 }
 
+impl std::ops::Neg for Quat {
+    type Output = Quat;
+
+    #[inline(always)]
+    fn neg(self) -> Self::Output {
+        Quat::new(-self.a, -self.b, -self.c, -self.d)
+    }
+}
+
+/// Composes two rotations, such that `(p * q) * v == p * (q * v)`.
+impl std::ops::Mul for Quat {
+    type Output = Quat;
+
+    #[inline(always)]
+    fn mul(self, rhs: Self) -> Self::Output {
+        let (p, q) = (self, rhs);
+        Quat::new(
+            p.d * q.a + p.a * q.d + p.b * q.c - p.c * q.b,
+            p.d * q.b - p.a * q.c + p.b * q.d + p.c * q.a,
+            p.d * q.c + p.a * q.b - p.b * q.a + p.c * q.d,
+            p.d * q.d - p.a * q.a - p.b * q.b - p.c * q.c,
+        )
+    }
+}
+
+impl std::ops::MulAssign for Quat {
+    #[inline(always)]
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs
+    }
+}
+
+impl From<Quat> for Mat3 {
+    #[inline(always)]
+    fn from(quat: Quat) -> Self {
+        Mat3::from_quat(quat)
+    }
+}
+
+impl From<Quat> for Mat4 {
+    #[inline(always)]
+    fn from(quat: Quat) -> Self {
+        Mat4::from_quat(quat)
+    }
+}
+
 impl std::ops::Mul<Vec3> for Quat {
     type Output = Vec3;
 
@@ -71,7 +355,37 @@ impl std::ops::Mul<Vec3> for Quat {
 
 #[cfg(test)]
 mod tests {
-    use super::{HalfTurn, Quat, Vec3};
+    use narcissus_core::random::Pcg64;
+
+    use super::{HalfTurn, Mat3, Mat4, Quat, Vec3};
+
+    fn assert_vec3_close(a: Vec3, b: Vec3) {
+        assert!((a - b).length() < 1e-5, "{a:?} != {b:?}");
+    }
+
+    /// Asserts `a` and `b` represent the same rotation.
+    fn assert_rotation_close(a: Quat, b: Quat) {
+        assert!(Quat::dot(a, b).abs() > 1.0 - 1e-5, "{a:?} != {b:?}");
+    }
+
+    fn random_quat(rng: &mut Pcg64) -> Quat {
+        Quat::new(
+            rng.next_f32_s(),
+            rng.next_f32_s(),
+            rng.next_f32_s(),
+            rng.next_f32_s(),
+        )
+        .normalized()
+    }
+
+    fn random_unit_vec3(rng: &mut Pcg64) -> Vec3 {
+        loop {
+            let v = Vec3::new(rng.next_f32_s(), rng.next_f32_s(), rng.next_f32_s());
+            if v.length_sq() > 0.01 && v.length_sq() <= 1.0 {
+                return v.normalized();
+            }
+        }
+    }
 
     #[test]
     fn constructors() {
@@ -104,4 +418,152 @@ mod tests {
         assert_eq!(rot_180_z * Vec3::Y, -Vec3::Y);
         assert_eq!(rot_180_z * Vec3::Z, Vec3::Z);
     }
+
+    #[test]
+    fn composition() {
+        let mut rng = Pcg64::new();
+        for _ in 0..1000 {
+            let p = random_quat(&mut rng);
+            let q = random_quat(&mut rng);
+            let v = random_unit_vec3(&mut rng);
+            assert_vec3_close((p * q) * v, p * (q * v));
+            assert_vec3_close(p.conjugate() * (p * v), v);
+            assert_vec3_close(p.inverse() * (p * v), v);
+            assert_rotation_close(p * p.conjugate(), Quat::IDENTITY);
+            assert_vec3_close(Mat3::from(p) * v, p * v);
+            assert_vec3_close(Mat4::from(p) * v, p * v);
+        }
+
+        let q = Quat::new(1.0, 2.0, 3.0, 4.0);
+        assert!((q * q.inverse()).a.abs() < 1e-6);
+        assert!((q.normalized().length() - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn scaled_axis() {
+        assert_eq!(Quat::from_scaled_axis(Vec3::ZERO), Quat::IDENTITY);
+        assert_eq!(Quat::IDENTITY.to_scaled_axis(), Vec3::ZERO);
+        assert_vec3_close(
+            Quat::from_axis_rotation(Vec3::Y, HalfTurn::new(0.5)).to_scaled_axis(),
+            Vec3::new(0.0, 0.5, 0.0),
+        );
+
+        let mut rng = Pcg64::new();
+        for _ in 0..1000 {
+            let q = random_quat(&mut rng);
+            let v = q.to_scaled_axis();
+            assert!(v.length() <= 1.0 + 1e-6);
+            assert_rotation_close(Quat::from_scaled_axis(v), q);
+
+            let v = random_unit_vec3(&mut rng) * rng.next_f32() * 1e-3;
+            assert_vec3_close(Quat::from_scaled_axis(v).to_scaled_axis(), v);
+        }
+    }
+
+    #[test]
+    fn matrix_conversion() {
+        let mut rng = Pcg64::new();
+        for _ in 0..1000 {
+            let q = random_quat(&mut rng);
+            assert_rotation_close(Quat::from_mat3(q.into()), q);
+            assert_rotation_close(Quat::from_mat4(q.into()), q);
+        }
+        // Exercise each branch of the matrix conversion.
+        for axis in [Vec3::X, Vec3::Y, Vec3::Z] {
+            for rotation in [0.0, 0.5, 0.99, 1.0] {
+                let q = Quat::from_axis_rotation(axis, HalfTurn::new(rotation));
+                assert_rotation_close(Quat::from_mat3(q.into()), q);
+            }
+        }
+    }
+
+    #[test]
+    fn interpolation() {
+        let a = Quat::IDENTITY;
+        let b = Quat::from_axis_rotation(Vec3::Y, HalfTurn::new(0.5));
+        let half = Quat::from_axis_rotation(Vec3::Y, HalfTurn::new(0.25));
+        assert_rotation_close(Quat::slerp(0.0, a, b), a);
+        assert_rotation_close(Quat::slerp(1.0, a, b), b);
+        assert_rotation_close(Quat::slerp(0.5, a, b), half);
+        assert_rotation_close(Quat::nlerp(0.5, a, b), half);
+
+        // Both take the shortest path, even when the inputs are in opposite
+        // hemispheres.
+        assert_rotation_close(Quat::slerp(0.5, a, -b), half);
+        assert_rotation_close(Quat::nlerp(0.5, a, -b), half);
+
+        // Slerp has constant angular velocity.
+        let quarter = Quat::from_axis_rotation(Vec3::Y, HalfTurn::new(0.125));
+        assert_rotation_close(Quat::slerp(0.25, a, b), quarter);
+
+        // Nearly identical inputs.
+        let c = Quat::from_axis_rotation(Vec3::Y, HalfTurn::new(1e-4));
+        assert!(Quat::slerp(0.5, a, c).is_finite());
+    }
+
+    #[test]
+    fn rotation_arc() {
+        let mut rng = Pcg64::new();
+        for _ in 0..1000 {
+            let from = random_unit_vec3(&mut rng);
+            let to = random_unit_vec3(&mut rng);
+            assert_vec3_close(Quat::from_rotation_arc(from, to) * from, to);
+            assert_vec3_close(Quat::from_rotation_arc(from, -from) * from, -from);
+            assert_rotation_close(Quat::from_rotation_arc(from, from), Quat::IDENTITY);
+        }
+        assert_vec3_close(
+            Quat::from_rotation_arc(Vec3::X, -Vec3::X) * Vec3::X,
+            -Vec3::X,
+        );
+    }
+
+    #[test]
+    fn look_rotation() {
+        let mut rng = Pcg64::new();
+        for _ in 0..1000 {
+            let dir = random_unit_vec3(&mut rng);
+            if Vec3::cross(dir, Vec3::Y).length_sq() < 0.01 {
+                continue;
+            }
+            let q = Quat::look_rotation(dir * 3.0, Vec3::Y);
+            assert_vec3_close(q * -Vec3::Z, dir);
+            // Up stays in the plane of `dir` and `up`, and points upward.
+            let up = q * Vec3::Y;
+            assert!(Vec3::dot(Vec3::cross(dir, Vec3::Y), up).abs() < 1e-5);
+            assert!(up.y > 0.0);
+        }
+        assert_rotation_close(Quat::look_rotation(-Vec3::Z, Vec3::Y), Quat::IDENTITY);
+    }
+
+    #[test]
+    fn euler() {
+        let yaw = Quat::from_euler(HalfTurn::new(0.5), HalfTurn::new(0.0), HalfTurn::new(0.0));
+        assert_vec3_close(yaw * -Vec3::Z, -Vec3::X);
+        let pitch = Quat::from_euler(HalfTurn::new(0.0), HalfTurn::new(0.5), HalfTurn::new(0.0));
+        assert_vec3_close(pitch * -Vec3::Z, Vec3::Y);
+        let roll = Quat::from_euler(HalfTurn::new(0.0), HalfTurn::new(0.0), HalfTurn::new(0.5));
+        assert_vec3_close(roll * Vec3::X, Vec3::Y);
+
+        let mut rng = Pcg64::new();
+        for _ in 0..1000 {
+            let q = random_quat(&mut rng);
+            let (yaw, pitch, roll) = q.to_euler();
+            assert!(pitch.as_f32().abs() <= 0.5);
+            assert_rotation_close(Quat::from_euler(yaw, pitch, roll), q);
+
+            let yaw = HalfTurn::new(rng.next_f32_s());
+            let pitch = HalfTurn::new(rng.next_f32_s() * 0.49);
+            let roll = HalfTurn::new(rng.next_f32_s());
+            let (y, p, r) = Quat::from_euler(yaw, pitch, roll).to_euler();
+            assert!((y.as_f32() - yaw.as_f32()).abs() < 1e-4);
+            assert!((p.as_f32() - pitch.as_f32()).abs() < 1e-4);
+            assert!((r.as_f32() - roll.as_f32()).abs() < 1e-4);
+        }
+
+        // Gimbal lock.
+        let q = Quat::from_euler(HalfTurn::new(0.25), HalfTurn::new(0.5), HalfTurn::new(0.1));
+        let (yaw, pitch, roll) = q.to_euler();
+        assert_eq!(roll, HalfTurn::new(0.0));
+        assert_rotation_close(Quat::from_euler(yaw, pitch, roll), q);
+    }
 }
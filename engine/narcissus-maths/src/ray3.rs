@@ -0,0 +1,214 @@
+use crate::{Aabb3, Plane, Point3, Sphere, Vec3};
+
+/// Half-line in 3d space, starting at `origin` and extending along `dir`.
+///
+/// Intersection queries return the ray parameter `t` of the nearest hit, where
+/// the hit point is `origin + dir * t`. Only hits with `t >= 0.0` are reported.
+/// `dir` is not required to be unit length.
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[repr(C)]
+pub struct Ray3 {
+    pub origin: Point3,
+    pub dir: Vec3,
+}
+
+impl Ray3 {
+    /// Constructs a new ray starting at `origin` and extending along `dir`.
+    #[inline(always)]
+    pub const fn new(origin: Point3, dir: Vec3) -> Self {
+        Self { origin, dir }
+    }
+
+    /// Returns the point at parameter `t` along the ray.
+    #[inline(always)]
+    pub fn at(&self, t: f32) -> Point3 {
+        self.origin + self.dir * t
+    }
+
+    /// Returns the point on `self` closest to `point`.
+    #[inline(always)]
+    pub fn closest_point(&self, point: Point3) -> Point3 {
+        let t = Vec3::dot(point - self.origin, self.dir) / self.dir.length_sq();
+        self.at(t.max(0.0))
+    }
+
+    /// Intersects `self` with the triangle `a`, `b`, `c`, from either side.
+    ///
+    /// Uses the Möller–Trumbore algorithm.
+    pub fn intersect_triangle(&self, a: Point3, b: Point3, c: Point3) -> Option<f32> {
+        let edge1 = b - a;
+        let edge2 = c - a;
+        let p = Vec3::cross(self.dir, edge2);
+        let det = Vec3::dot(edge1, p);
+        // `det` scales with the lengths of the edges and the direction, so compare
+        // the sine of the angle between the ray and the triangle's plane instead.
+        let scale = edge1.length() * edge2.length() * self.dir.length();
+        if det.abs() <= f32::EPSILON * scale {
+            // Ray is parallel to the triangle, or the triangle is degenerate.
+            return None;
+        }
+        let inv_det = 1.0 / det;
+
+        let s = self.origin - a;
+        let u = Vec3::dot(s, p) * inv_det;
+        if !(0.0..=1.0).contains(&u) {
+            return None;
+        }
+
+        let q = Vec3::cross(s, edge1);
+        let v = Vec3::dot(self.dir, q) * inv_det;
+        if v < 0.0 || u + v > 1.0 {
+            return None;
+        }
+
+        let t = Vec3::dot(edge2, q) * inv_det;
+        (t >= 0.0).then_some(t)
+    }
+
+    /// Intersects `self` with `aabb` using the slab method.
+    ///
+    /// Returns `Some(0.0)` if the ray starts inside the box.
+    pub fn intersect_aabb3(&self, aabb: &Aabb3) -> Option<f32> {
+        let inv_dir = self.dir.map(|x| 1.0 / x);
+        let t0 = (aabb.min - self.origin) * inv_dir;
+        let t1 = (aabb.max - self.origin) * inv_dir;
+        let t_near = Vec3::min(t0, t1);
+        let t_far = Vec3::max(t0, t1);
+        let t_near = t_near.x.max(t_near.y).max(t_near.z).max(0.0);
+        let t_far = t_far.x.min(t_far.y).min(t_far.z);
+        (t_near <= t_far).then_some(t_near)
+    }
+
+    /// Intersects `self` with `sphere`.
+    ///
+    /// Returns `Some(0.0)` if the ray starts inside the sphere.
+    pub fn intersect_sphere(&self, sphere: &Sphere) -> Option<f32> {
+        let m = self.origin - sphere.center;
+        let b = Vec3::dot(m, self.dir);
+        let c = m.length_sq() - sphere.radius * sphere.radius;
+        if c > 0.0 && b > 0.0 {
+            // Starts outside and points away.
+            return None;
+        }
+        let a = self.dir.length_sq();
+        let discriminant = b * b - a * c;
+        if discriminant < 0.0 {
+            return None;
+        }
+        let t = (-b - discriminant.sqrt()) / a;
+        Some(t.max(0.0))
+    }
+
+    /// Intersects `self` with `plane`, from either side.
+    pub fn intersect_plane(&self, plane: &Plane) -> Option<f32> {
+        let denom = Vec3::dot(plane.normal, self.dir);
+        if denom == 0.0 {
+            return None;
+        }
+        let t = -plane.signed_distance(self.origin) / denom;
+        (t >= 0.0).then_some(t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Aabb3, Plane, Point3, Ray3, Sphere, Vec3, point3};
+
+    #[test]
+    fn triangle() {
+        let a = point3(-1.0, -1.0, 0.0);
+        let b = point3(1.0, -1.0, 0.0);
+        let c = point3(0.0, 1.0, 0.0);
+
+        let ray = Ray3::new(point3(0.0, 0.0, 2.0), -Vec3::Z);
+        assert_eq!(ray.intersect_triangle(a, b, c), Some(2.0));
+        // Back face.
+        let ray = Ray3::new(point3(0.0, 0.0, -3.0), Vec3::Z * 2.0);
+        assert_eq!(ray.intersect_triangle(a, b, c), Some(1.5));
+        // Behind the origin.
+        let ray = Ray3::new(point3(0.0, 0.0, 2.0), Vec3::Z);
+        assert_eq!(ray.intersect_triangle(a, b, c), None);
+        // Outside an edge.
+        let ray = Ray3::new(point3(0.6, 0.5, 2.0), -Vec3::Z);
+        assert_eq!(ray.intersect_triangle(a, b, c), None);
+        // Parallel.
+        let ray = Ray3::new(point3(0.0, 0.0, 0.0), Vec3::X);
+        assert_eq!(ray.intersect_triangle(a, b, c), None);
+
+        // The parallel test is relative to the size of the triangle and ray, so
+        // scaling everything uniformly doesn't change the result.
+        for scale in [1e-8, 1.0, 1e4] {
+            let a = point3(-scale, -scale, 0.0);
+            let b = point3(scale, -scale, 0.0);
+            let c = point3(0.0, scale, 0.0);
+            let ray = Ray3::new(point3(0.0, 0.0, 2.0 * scale), -Vec3::Z * scale);
+            let t = ray.intersect_triangle(a, b, c).unwrap();
+            assert!((t - 2.0).abs() < 1e-6);
+            // Grazing the plane at a tiny angle counts as parallel.
+            let ray = Ray3::new(
+                point3(0.0, -0.5 * scale, 1e-10 * scale),
+                Vec3::new(0.0, scale, -1e-10 * scale),
+            );
+            assert_eq!(ray.intersect_triangle(a, b, c), None);
+        }
+    }
+
+    #[test]
+    fn aabb3() {
+        let aabb = Aabb3::new(Point3::splat(-1.0), Point3::splat(1.0));
+
+        let ray = Ray3::new(point3(-3.0, 0.0, 0.0), Vec3::X);
+        assert_eq!(ray.intersect_aabb3(&aabb), Some(2.0));
+        let ray = Ray3::new(point3(3.0, 3.0, 0.0), Vec3::new(-1.0, -1.0, 0.0));
+        assert_eq!(ray.intersect_aabb3(&aabb), Some(2.0));
+        let ray = Ray3::new(Point3::ZERO, Vec3::Y);
+        assert_eq!(ray.intersect_aabb3(&aabb), Some(0.0));
+        let ray = Ray3::new(point3(-3.0, 2.0, 0.0), Vec3::X);
+        assert_eq!(ray.intersect_aabb3(&aabb), None);
+        let ray = Ray3::new(point3(3.0, 0.0, 0.0), Vec3::X);
+        assert_eq!(ray.intersect_aabb3(&aabb), None);
+    }
+
+    #[test]
+    fn sphere() {
+        let sphere = Sphere::new(point3(0.0, 0.0, -5.0), 1.0);
+
+        let ray = Ray3::new(Point3::ZERO, -Vec3::Z);
+        assert_eq!(ray.intersect_sphere(&sphere), Some(4.0));
+        let ray = Ray3::new(Point3::ZERO, -Vec3::Z * 2.0);
+        assert_eq!(ray.intersect_sphere(&sphere), Some(2.0));
+        let ray = Ray3::new(point3(0.0, 0.0, -5.0), Vec3::X);
+        assert_eq!(ray.intersect_sphere(&sphere), Some(0.0));
+        let ray = Ray3::new(Point3::ZERO, Vec3::Z);
+        assert_eq!(ray.intersect_sphere(&sphere), None);
+        let ray = Ray3::new(point3(0.0, 1.5, 0.0), -Vec3::Z);
+        assert_eq!(ray.intersect_sphere(&sphere), None);
+    }
+
+    #[test]
+    fn plane() {
+        let plane = Plane::new(Vec3::Y, 1.0);
+        let ray = Ray3::new(point3(1.0, 3.0, 0.0), -Vec3::Y);
+        assert_eq!(ray.intersect_plane(&plane), Some(2.0));
+        assert_eq!(ray.at(2.0), point3(1.0, 1.0, 0.0));
+        let ray = Ray3::new(Point3::ZERO, Vec3::Y * 0.5);
+        assert_eq!(ray.intersect_plane(&plane), Some(2.0));
+        let ray = Ray3::new(Point3::ZERO, -Vec3::Y);
+        assert_eq!(ray.intersect_plane(&plane), None);
+        let ray = Ray3::new(Point3::ZERO, Vec3::X);
+        assert_eq!(ray.intersect_plane(&plane), None);
+    }
+
+    #[test]
+    fn closest_point() {
+        let ray = Ray3::new(point3(1.0, 0.0, 0.0), Vec3::X * 2.0);
+        assert_eq!(
+            ray.closest_point(point3(4.0, 2.0, 0.0)),
+            point3(4.0, 0.0, 0.0)
+        );
+        assert_eq!(
+            ray.closest_point(point3(-4.0, 2.0, 0.0)),
+            point3(1.0, 0.0, 0.0)
+        );
+    }
+}
@@ -0,0 +1,524 @@
+use crate::{IVec2, UVec2, Vec2};
+
+/// Axis aligned rectangle in 2d screen space, covering the half-open region
+/// from `min` up to but not including `max`.
+///
+/// Unlike [`crate::Aabb2`], a rectangle with `min == max` on either axis is
+/// empty, which matches the behavior of scissor and viewport rectangles.
+#[derive(Clone, Copy, PartialEq, Default, Debug)]
+#[repr(C)]
+pub struct Rect {
+    pub min: Vec2,
+    pub max: Vec2,
+}
+
+impl Rect {
+    /// Empty rectangle, with `min` greater than `max`, which contains nothing
+    /// and is the identity for [`Rect::union`].
+    pub const EMPTY: Rect = Rect {
+        min: Vec2::splat(f32::INFINITY),
+        max: Vec2::splat(f32::NEG_INFINITY),
+    };
+
+    /// Constructs a new rectangle with the given `min` and `max` corners.
+    #[inline(always)]
+    pub const fn new(min: Vec2, max: Vec2) -> Self {
+        Self { min, max }
+    }
+
+    /// Constructs a new rectangle with its `min` corner at `origin` and with
+    /// the given `size`.
+    #[inline(always)]
+    pub fn from_origin_size(origin: Vec2, size: Vec2) -> Self {
+        Self {
+            min: origin,
+            max: origin + size,
+        }
+    }
+
+    /// Constructs a new rectangle with the given `center` and `half_extents`.
+    #[inline(always)]
+    pub fn from_center_half_extents(center: Vec2, half_extents: Vec2) -> Self {
+        Self {
+            min: center - half_extents,
+            max: center + half_extents,
+        }
+    }
+
+    /// Returns `true` if the rectangle has no area.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        !(self.min.x < self.max.x && self.min.y < self.max.y)
+    }
+
+    /// Returns the width of the rectangle, or zero if it's empty.
+    #[inline(always)]
+    pub fn width(&self) -> f32 {
+        (self.max.x - self.min.x).max(0.0)
+    }
+
+    /// Returns the height of the rectangle, or zero if it's empty.
+    #[inline(always)]
+    pub fn height(&self) -> f32 {
+        (self.max.y - self.min.y).max(0.0)
+    }
+
+    /// Returns the size of the rectangle, with zero extents if it's empty.
+    #[inline(always)]
+    pub fn size(&self) -> Vec2 {
+        Vec2::new(self.width(), self.height())
+    }
+
+    #[inline(always)]
+    pub fn center(&self) -> Vec2 {
+        Vec2::lerp(0.5, self.min, self.max)
+    }
+
+    /// Returns the area of the rectangle, or zero if it's empty.
+    #[inline(always)]
+    pub fn area(&self) -> f32 {
+        self.width() * self.height()
+    }
+
+    /// Returns the smallest rectangle containing both `a` and `b`.
+    #[must_use]
+    #[inline(always)]
+    pub fn union(a: Rect, b: Rect) -> Rect {
+        Self {
+            min: Vec2::min(a.min, b.min),
+            max: Vec2::max(a.max, b.max),
+        }
+    }
+
+    /// Returns the overlapping region of `a` and `b`, which is empty if they
+    /// don't overlap.
+    #[must_use]
+    #[inline(always)]
+    pub fn intersection(a: Rect, b: Rect) -> Rect {
+        Self {
+            min: Vec2::max(a.min, b.min),
+            max: Vec2::min(a.max, b.max),
+        }
+    }
+
+    /// Returns `true` if `point` is inside `self`, where points on the `max`
+    /// edges are outside.
+    #[inline(always)]
+    pub fn contains_point(&self, point: Vec2) -> bool {
+        point.x >= self.min.x
+            && point.x < self.max.x
+            && point.y >= self.min.y
+            && point.y < self.max.y
+    }
+
+    /// Returns `true` if `rect` lies entirely within `self`.
+    #[inline(always)]
+    pub fn contains_rect(&self, rect: &Rect) -> bool {
+        rect.min.x >= self.min.x
+            && rect.max.x <= self.max.x
+            && rect.min.y >= self.min.y
+            && rect.max.y <= self.max.y
+    }
+
+    /// Returns `true` if `a` and `b` share some area.
+    #[inline(always)]
+    pub fn overlaps(a: &Rect, b: &Rect) -> bool {
+        !Rect::intersection(*a, *b).is_empty()
+    }
+
+    /// Returns the rectangle shrunk by `amount` on every side. Negative values
+    /// grow the rectangle instead.
+    #[must_use]
+    #[inline(always)]
+    pub fn inset(&self, amount: Vec2) -> Rect {
+        Self {
+            min: self.min + amount,
+            max: self.max - amount,
+        }
+    }
+
+    /// Splits the rectangle with a vertical line at `x`, returning the left
+    /// and right parts.
+    ///
+    /// `x` is clamped to the rectangle, so one of the parts is empty when `x`
+    /// lies outside. Both parts are empty if `self` is empty.
+    #[inline(always)]
+    pub fn split_x(&self, x: f32) -> (Rect, Rect) {
+        let x = x.max(self.min.x).min(self.max.x);
+        (
+            Rect::new(self.min, Vec2::new(x, self.max.y)),
+            Rect::new(Vec2::new(x, self.min.y), self.max),
+        )
+    }
+
+    /// Splits the rectangle with a horizontal line at `y`, returning the top
+    /// and bottom parts.
+    ///
+    /// `y` is clamped to the rectangle, so one of the parts is empty when `y`
+    /// lies outside. Both parts are empty if `self` is empty.
+    #[inline(always)]
+    pub fn split_y(&self, y: f32) -> (Rect, Rect) {
+        let y = y.max(self.min.y).min(self.max.y);
+        (
+            Rect::new(self.min, Vec2::new(self.max.x, y)),
+            Rect::new(Vec2::new(self.min.x, y), self.max),
+        )
+    }
+
+    /// Returns the smallest integer rectangle containing `self`.
+    #[inline(always)]
+    pub fn round_out(&self) -> IRect {
+        IRect::new(self.min.floor().as_ivec2(), self.max.ceil().as_ivec2())
+    }
+
+    /// Returns the range of square tiles of `tile_size` pixels touched by
+    /// `self`, as a rectangle in tile coordinates.
+    ///
+    /// The result is not bounded by the tile grid, so intersect it with the
+    /// grid dimensions before use. Empty rectangles touch no tiles.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tile_size` is zero or greater than `i32::MAX`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use narcissus_maths::{IRect, Rect, ivec2, uvec2, vec2};
+    /// let rect = Rect::new(vec2(-10.0, 40.0), vec2(70.0, 64.0));
+    /// let grid = IRect::from_size(uvec2(60, 34));
+    /// let tiles = IRect::intersection(rect.tiles(32), grid);
+    /// assert_eq!(tiles, IRect::new(ivec2(0, 1), ivec2(3, 2)));
+    /// ```
+    #[inline(always)]
+    pub fn tiles(&self, tile_size: u32) -> IRect {
+        assert!(tile_size != 0 && tile_size <= i32::MAX as u32);
+        if self.is_empty() {
+            return IRect::EMPTY;
+        }
+        let tile_size = tile_size as f32;
+        IRect::new(
+            (self.min / tile_size).floor().as_ivec2(),
+            (self.max / tile_size).ceil().as_ivec2(),
+        )
+    }
+}
+
+/// Axis aligned rectangle on the integer grid, covering the half-open region
+/// from `min` up to but not including `max`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+#[repr(C)]
+pub struct IRect {
+    pub min: IVec2,
+    pub max: IVec2,
+}
+
+impl IRect {
+    /// Empty rectangle, with `min` greater than `max`, which contains nothing
+    /// and is the identity for [`IRect::union`].
+    pub const EMPTY: IRect = IRect {
+        min: IVec2::splat(i32::MAX),
+        max: IVec2::splat(i32::MIN),
+    };
+
+    /// Constructs a new rectangle with the given `min` and `max` corners.
+    #[inline(always)]
+    pub const fn new(min: IVec2, max: IVec2) -> Self {
+        Self { min, max }
+    }
+
+    /// Constructs a new rectangle with its `min` corner at `origin` and with
+    /// the given `size`.
+    #[inline(always)]
+    pub fn from_origin_size(origin: IVec2, size: IVec2) -> Self {
+        Self {
+            min: origin,
+            max: origin + size,
+        }
+    }
+
+    /// Constructs a new rectangle with its `min` corner at the origin, and
+    /// with the given `size`.
+    #[inline(always)]
+    pub fn from_size(size: UVec2) -> Self {
+        Self {
+            min: IVec2::ZERO,
+            max: size.as_ivec2(),
+        }
+    }
+
+    /// Returns `true` if the rectangle has no area.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        !(self.min.x < self.max.x && self.min.y < self.max.y)
+    }
+
+    /// Returns the width of the rectangle, or zero if it's empty.
+    #[inline(always)]
+    pub fn width(&self) -> i32 {
+        self.max.x.saturating_sub(self.min.x).max(0)
+    }
+
+    /// Returns the height of the rectangle, or zero if it's empty.
+    #[inline(always)]
+    pub fn height(&self) -> i32 {
+        self.max.y.saturating_sub(self.min.y).max(0)
+    }
+
+    /// Returns the size of the rectangle, with zero extents if it's empty.
+    #[inline(always)]
+    pub fn size(&self) -> IVec2 {
+        IVec2::new(self.width(), self.height())
+    }
+
+    /// Returns the smallest rectangle containing both `a` and `b`.
+    #[must_use]
+    #[inline(always)]
+    pub fn union(a: IRect, b: IRect) -> IRect {
+        Self {
+            min: IVec2::min(a.min, b.min),
+            max: IVec2::max(a.max, b.max),
+        }
+    }
+
+    /// Returns the overlapping region of `a` and `b`, which is empty if they
+    /// don't overlap.
+    #[must_use]
+    #[inline(always)]
+    pub fn intersection(a: IRect, b: IRect) -> IRect {
+        Self {
+            min: IVec2::max(a.min, b.min),
+            max: IVec2::min(a.max, b.max),
+        }
+    }
+
+    /// Returns `true` if `point` is inside `self`, where points on the `max`
+    /// edges are outside.
+    #[inline(always)]
+    pub fn contains_point(&self, point: IVec2) -> bool {
+        point.x >= self.min.x
+            && point.x < self.max.x
+            && point.y >= self.min.y
+            && point.y < self.max.y
+    }
+
+    /// Returns `true` if `rect` lies entirely within `self`.
+    #[inline(always)]
+    pub fn contains_rect(&self, rect: &IRect) -> bool {
+        rect.min.x >= self.min.x
+            && rect.max.x <= self.max.x
+            && rect.min.y >= self.min.y
+            && rect.max.y <= self.max.y
+    }
+
+    /// Returns `true` if `a` and `b` share some area.
+    #[inline(always)]
+    pub fn overlaps(a: &IRect, b: &IRect) -> bool {
+        !IRect::intersection(*a, *b).is_empty()
+    }
+
+    /// Returns the rectangle shrunk by `amount` on every side. Negative values
+    /// grow the rectangle instead.
+    #[must_use]
+    #[inline(always)]
+    pub fn inset(&self, amount: IVec2) -> IRect {
+        Self {
+            min: IVec2::new(
+                self.min.x.saturating_add(amount.x),
+                self.min.y.saturating_add(amount.y),
+            ),
+            max: IVec2::new(
+                self.max.x.saturating_sub(amount.x),
+                self.max.y.saturating_sub(amount.y),
+            ),
+        }
+    }
+
+    /// Splits the rectangle with a vertical line at `x`, returning the left
+    /// and right parts.
+    ///
+    /// `x` is clamped to the rectangle, so one of the parts is empty when `x`
+    /// lies outside. Both parts are empty if `self` is empty.
+    #[inline(always)]
+    pub fn split_x(&self, x: i32) -> (IRect, IRect) {
+        let x = x.max(self.min.x).min(self.max.x);
+        (
+            IRect::new(self.min, IVec2::new(x, self.max.y)),
+            IRect::new(IVec2::new(x, self.min.y), self.max),
+        )
+    }
+
+    /// Splits the rectangle with a horizontal line at `y`, returning the top
+    /// and bottom parts.
+    ///
+    /// `y` is clamped to the rectangle, so one of the parts is empty when `y`
+    /// lies outside. Both parts are empty if `self` is empty.
+    #[inline(always)]
+    pub fn split_y(&self, y: i32) -> (IRect, IRect) {
+        let y = y.max(self.min.y).min(self.max.y);
+        (
+            IRect::new(self.min, IVec2::new(self.max.x, y)),
+            IRect::new(IVec2::new(self.min.x, y), self.max),
+        )
+    }
+
+    /// Converts to a [`Rect`] covering the same area.
+    #[inline(always)]
+    pub fn as_rect(&self) -> Rect {
+        Rect::new(self.min.as_vec2(), self.max.as_vec2())
+    }
+
+    /// Returns the range of square tiles of `tile_size` pixels touched by
+    /// `self`, as a rectangle in tile coordinates.
+    ///
+    /// The result is not bounded by the tile grid, so intersect it with the
+    /// grid dimensions before use. Empty rectangles touch no tiles.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tile_size` is zero or greater than `i32::MAX`.
+    #[inline(always)]
+    pub fn tiles(&self, tile_size: u32) -> IRect {
+        assert!(tile_size != 0 && tile_size <= i32::MAX as u32);
+        if self.is_empty() {
+            return IRect::EMPTY;
+        }
+        let tile_size = IVec2::splat(tile_size as i32);
+        IRect::new(self.min.div_floor(tile_size), self.max.div_ceil(tile_size))
+    }
+
+    /// Returns an iterator over every point in the rectangle, in row-major
+    /// order.
+    pub fn points(&self) -> impl Iterator<Item = IVec2> + use<> {
+        let IRect { min, max } = *self;
+        let xs = min.x..max.x;
+        (min.y..max.y).flat_map(move |y| xs.clone().map(move |x| IVec2::new(x, y)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{IRect, IVec2, Rect, Vec2, ivec2, uvec2, vec2};
+
+    #[test]
+    fn basic() {
+        assert!(Rect::EMPTY.is_empty());
+        assert!(IRect::EMPTY.is_empty());
+        assert_eq!(IRect::EMPTY.size(), IVec2::ZERO);
+        assert_eq!(IRect::new(ivec2(4, 0), ivec2(1, 3)).width(), 0);
+        assert!(Rect::new(vec2(1.0, 1.0), vec2(1.0, 2.0)).is_empty());
+        assert_eq!(Rect::EMPTY.size(), Vec2::ZERO);
+        assert_eq!(Rect::EMPTY.area(), 0.0);
+        assert_eq!(IRect::EMPTY.inset(ivec2(1, 1)), IRect::EMPTY);
+        assert_eq!(
+            IRect::EMPTY.inset(ivec2(-1, -1)),
+            IRect::new(IVec2::splat(i32::MAX - 1), IVec2::splat(i32::MIN + 1))
+        );
+
+        let a = Rect::from_origin_size(vec2(1.0, 2.0), vec2(4.0, 2.0));
+        assert_eq!(a, Rect::new(vec2(1.0, 2.0), vec2(5.0, 4.0)));
+        assert_eq!(a.size(), vec2(4.0, 2.0));
+        assert_eq!(a.center(), vec2(3.0, 3.0));
+        assert_eq!(a.area(), 8.0);
+        assert_eq!(Rect::union(Rect::EMPTY, a), a);
+        assert!(a.contains_point(vec2(1.0, 2.0)));
+        assert!(!a.contains_point(vec2(5.0, 3.0)));
+        assert!(a.contains_rect(&a.inset(vec2(1.0, 0.5))));
+        assert!(!a.contains_rect(&a.inset(vec2(-1.0, 0.0))));
+        assert_eq!(a.inset(vec2(1.0, 1.0)).size(), vec2(2.0, 0.0));
+        assert!(a.inset(vec2(1.0, 1.0)).is_empty());
+
+        let b = Rect::new(vec2(4.0, 0.0), vec2(8.0, 8.0));
+        assert_eq!(
+            Rect::intersection(a, b),
+            Rect::new(vec2(4.0, 2.0), vec2(5.0, 4.0))
+        );
+        assert!(Rect::overlaps(&a, &b));
+        // Touching edges share no area.
+        let c = Rect::new(vec2(5.0, 0.0), vec2(8.0, 8.0));
+        assert!(!Rect::overlaps(&a, &c));
+        // Disjoint rectangles have an empty intersection with no area.
+        let d = Rect::new(vec2(6.0, 5.0), vec2(8.0, 8.0));
+        let empty = Rect::intersection(a, d);
+        assert!(empty.is_empty());
+        assert_eq!(empty.width(), 0.0);
+        assert_eq!(empty.height(), 0.0);
+        assert_eq!(empty.area(), 0.0);
+
+        let i = IRect::from_size(uvec2(8, 4));
+        assert_eq!(i.as_rect().round_out(), i);
+        assert_eq!(
+            Rect::new(vec2(-0.5, 0.5), vec2(1.5, 2.0)).round_out(),
+            IRect::new(ivec2(-1, 0), ivec2(2, 2))
+        );
+        assert_eq!(IRect::union(IRect::EMPTY, i), i);
+        assert!(i.contains_point(ivec2(7, 3)));
+        assert!(!i.contains_point(ivec2(8, 3)));
+    }
+
+    #[test]
+    fn split() {
+        let a = Rect::new(vec2(0.0, 0.0), vec2(4.0, 2.0));
+        let (l, r) = a.split_x(1.0);
+        assert_eq!(l, Rect::new(vec2(0.0, 0.0), vec2(1.0, 2.0)));
+        assert_eq!(r, Rect::new(vec2(1.0, 0.0), vec2(4.0, 2.0)));
+        let (t, b) = a.split_y(5.0);
+        assert_eq!(t, a);
+        assert!(b.is_empty());
+
+        let a = IRect::new(ivec2(0, 0), ivec2(4, 2));
+        let (t, b) = a.split_y(1);
+        assert_eq!(t, IRect::new(ivec2(0, 0), ivec2(4, 1)));
+        assert_eq!(b, IRect::new(ivec2(0, 1), ivec2(4, 2)));
+        let (l, r) = a.split_x(-1);
+        assert!(l.is_empty());
+        assert_eq!(r, a);
+
+        // Splitting an empty rectangle gives two empty parts.
+        for (a, b) in [Rect::EMPTY.split_x(1.0), Rect::EMPTY.split_y(1.0)] {
+            assert!(a.is_empty() && b.is_empty());
+        }
+        for (a, b) in [IRect::EMPTY.split_x(1), IRect::EMPTY.split_y(1)] {
+            assert!(a.is_empty() && b.is_empty());
+        }
+    }
+
+    #[test]
+    fn tiles() {
+        let grid = IRect::from_size(uvec2(1920, 1080).div_ceil(crate::UVec2::splat(32)));
+        assert_eq!(grid.size(), ivec2(60, 34));
+
+        // Exactly covering a tile touches only that tile.
+        let r = Rect::new(vec2(32.0, 64.0), vec2(64.0, 96.0));
+        assert_eq!(r.tiles(32), IRect::new(ivec2(1, 2), ivec2(2, 3)));
+        assert_eq!(r.round_out().tiles(32), r.tiles(32));
+
+        // Rectangles partially off screen are clipped by the grid.
+        let r = Rect::new(vec2(-40.0, 1070.0), vec2(1.0, 1200.0));
+        let tiles = IRect::intersection(r.tiles(32), grid);
+        assert_eq!(tiles, IRect::new(ivec2(0, 33), ivec2(1, 34)));
+        assert_eq!(r.tiles(32).min, ivec2(-2, 33));
+
+        // Entirely off screen rectangles produce an empty range.
+        let r = Rect::new(vec2(-40.0, -40.0), vec2(-1.0, -1.0));
+        assert!(IRect::intersection(r.tiles(32), grid).is_empty());
+        assert!(Rect::EMPTY.tiles(32).is_empty());
+        assert_eq!(IRect::EMPTY.tiles(32).points().count(), 0);
+
+        let r = IRect::new(ivec2(31, 0), ivec2(65, 1));
+        let points: Vec<IVec2> = r.tiles(32).points().collect();
+        assert_eq!(points, [ivec2(0, 0), ivec2(1, 0), ivec2(2, 0)]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn tiles_zero_size() {
+        let _ = IRect::from_size(uvec2(8, 8)).tiles(0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn tiles_oversized() {
+        let _ = Rect::new(vec2(0.0, 0.0), vec2(8.0, 8.0)).tiles(u32::MAX);
+    }
+}
@@ -0,0 +1,56 @@
+//! Double precision reference implementations used to check results in tests.
+
+/// Returns the determinant and, if non-singular, the inverse of the row-major
+/// matrix `m` using Gauss-Jordan elimination with partial pivoting.
+pub fn determinant_inverse<const N: usize>(m: [[f32; N]; N]) -> (f64, Option<[[f64; N]; N]>) {
+    let mut a = m.map(|row| row.map(|x| x as f64));
+    let mut inv = std::array::from_fn(|i| std::array::from_fn(|j| (i == j) as u32 as f64));
+    let mut det = 1.0;
+
+    for col in 0..N {
+        let pivot = (col..N)
+            .max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))
+            .unwrap();
+        if a[pivot][col] == 0.0 {
+            return (0.0, None);
+        }
+        if pivot != col {
+            a.swap(pivot, col);
+            inv.swap(pivot, col);
+            det = -det;
+        }
+
+        let p = a[col][col];
+        det *= p;
+        for j in 0..N {
+            a[col][j] /= p;
+            inv[col][j] /= p;
+        }
+
+        for i in 0..N {
+            if i != col {
+                let f = a[i][col];
+                for j in 0..N {
+                    a[i][j] -= f * a[col][j];
+                    inv[i][j] -= f * inv[col][j];
+                }
+            }
+        }
+    }
+
+    (det, Some(inv))
+}
+
+/// Asserts that each element of `actual` is within `tolerance` of `expected`,
+/// relative to the largest magnitude in `expected`.
+#[track_caller]
+pub fn assert_close(actual: &[f32], expected: &[f64], tolerance: f64) {
+    assert_eq!(actual.len(), expected.len());
+    let scale = expected.iter().fold(1.0_f64, |acc, x| acc.max(x.abs()));
+    for (a, e) in actual.iter().zip(expected) {
+        assert!(
+            (*a as f64 - e).abs() <= tolerance * scale,
+            "expected {expected:?}\nfound {actual:?}"
+        );
+    }
+}
@@ -0,0 +1,245 @@
+//! Ken Perlin's simplex noise, following Stefan Gustavson's "Simplex noise
+//! demystified".
+//!
+//! The patent covering simplex noise in three and more dimensions expired in
+//! 2022.
+//!
+//! Unlike some reference implementations, the 3d kernel uses a squared radius
+//! of `0.5` rather than `0.6`, which avoids discontinuities where a kernel
+//! extends past the neighboring simplices.
+
+use crate::{
+    Vec2, Vec3,
+    perlin::{PERMUTE, grad_vector},
+};
+
+#[inline(always)]
+fn simplex_noise2_impl(x: f32, y: f32, seed: u8) -> (f32, Vec2) {
+    const F2: f32 = 0.366_025_42; // (sqrt(3) - 1) / 2
+    const G2: f32 = 0.211_324_87; // (3 - sqrt(3)) / 6
+
+    let seed = seed as usize;
+
+    // Skew the input space to find the containing simplex cell.
+    let s = (x + y) * F2;
+    let i = (x + s).floor();
+    let j = (y + s).floor();
+    let t = (i + j) * G2;
+    let x0 = x - (i - t);
+    let y0 = y - (j - t);
+
+    // Pick the middle corner of the triangle.
+    let (i1, j1) = if x0 > y0 { (1, 0) } else { (0, 1) };
+
+    let ii = i as i32 as usize & 255;
+    let jj = j as i32 as usize & 255;
+
+    let corners = [
+        (0, 0, x0, y0),
+        (i1, j1, x0 - i1 as f32 + G2, y0 - j1 as f32 + G2),
+        (1, 1, x0 - 1.0 + 2.0 * G2, y0 - 1.0 + 2.0 * G2),
+    ];
+
+    let mut noise = 0.0;
+    let mut deriv = Vec2::ZERO;
+    for (io, jo, x, y) in corners {
+        let t = 0.5 - x * x - y * y;
+        if t > 0.0 {
+            let r = PERMUTE[ii + io + seed] as usize;
+            let [gx, gy, _] = grad_vector(r + jj + jo);
+            let g = Vec2::new(gx, gy);
+            let g_dot = gx * x + gy * y;
+            let t2 = t * t;
+            let t4 = t2 * t2;
+            noise += t4 * g_dot;
+            deriv += g * t4 - Vec2::new(x, y) * (8.0 * t2 * t * g_dot);
+        }
+    }
+
+    // Scale the result to cover approximately [-1,1].
+    (70.0 * noise, deriv * 70.0)
+}
+
+#[inline(always)]
+fn simplex_noise3_impl(x: f32, y: f32, z: f32, seed: u8) -> (f32, Vec3) {
+    const F3: f32 = 1.0 / 3.0;
+    const G3: f32 = 1.0 / 6.0;
+
+    let seed = seed as usize;
+
+    // Skew the input space to find the containing simplex cell.
+    let s = (x + y + z) * F3;
+    let i = (x + s).floor();
+    let j = (y + s).floor();
+    let k = (z + s).floor();
+    let t = (i + j + k) * G3;
+    let x0 = x - (i - t);
+    let y0 = y - (j - t);
+    let z0 = z - (k - t);
+
+    // Pick the second and third corners of the tetrahedron by ranking the
+    // coordinates.
+    let ((i1, j1, k1), (i2, j2, k2)) = if x0 >= y0 {
+        if y0 >= z0 {
+            ((1, 0, 0), (1, 1, 0))
+        } else if x0 >= z0 {
+            ((1, 0, 0), (1, 0, 1))
+        } else {
+            ((0, 0, 1), (1, 0, 1))
+        }
+    } else if y0 < z0 {
+        ((0, 0, 1), (0, 1, 1))
+    } else if x0 < z0 {
+        ((0, 1, 0), (0, 1, 1))
+    } else {
+        ((0, 1, 0), (1, 1, 0))
+    };
+
+    let ii = i as i32 as usize & 255;
+    let jj = j as i32 as usize & 255;
+    let kk = k as i32 as usize & 255;
+
+    let offset = |io: usize, jo: usize, ko: usize, g: f32| {
+        (
+            io,
+            jo,
+            ko,
+            Vec3::new(x0 - io as f32 + g, y0 - jo as f32 + g, z0 - ko as f32 + g),
+        )
+    };
+    let corners = [
+        offset(0, 0, 0, 0.0),
+        offset(i1, j1, k1, G3),
+        offset(i2, j2, k2, 2.0 * G3),
+        offset(1, 1, 1, 3.0 * G3),
+    ];
+
+    let mut noise = 0.0;
+    let mut deriv = Vec3::ZERO;
+    for (io, jo, ko, d) in corners {
+        let t = 0.5 - d.length_sq();
+        if t > 0.0 {
+            let r = PERMUTE[ii + io + seed] as usize;
+            let r = PERMUTE[r + jj + jo] as usize;
+            let g = Vec3::from(grad_vector(r + kk + ko));
+            let g_dot = Vec3::dot(g, d);
+            let t2 = t * t;
+            let t4 = t2 * t2;
+            noise += t4 * g_dot;
+            deriv += g * t4 - d * (8.0 * t2 * t * g_dot);
+        }
+    }
+
+    // Scale the result to cover approximately [-1,1].
+    (76.0 * noise, deriv * 76.0)
+}
+
+/// Computes 2d simplex noise at the coordinate (x,y)
+///
+/// Compared to [`crate::perlin_noise2`], simplex noise has fewer directional
+/// artifacts. The result is approximately in the range \[-1,1\], and repeats
+/// every 256 units along each axis of the skewed simplex grid.
+///
+/// `seed` selects from multiple different variations of the noise function.
+pub fn simplex_noise2_seed(x: f32, y: f32, seed: u8) -> f32 {
+    simplex_noise2_impl(x, y, seed).0
+}
+
+/// Computes 2d simplex noise at the coordinate (x,y)
+///
+/// See [`simplex_noise2_seed`].
+pub fn simplex_noise2(x: f32, y: f32) -> f32 {
+    simplex_noise2_impl(x, y, 0).0
+}
+
+/// Computes [`simplex_noise2`] and its partial derivatives at the coordinate
+/// (x,y)
+///
+/// Returns `(noise, (d/dx, d/dy))`.
+pub fn simplex_noise2_deriv(x: f32, y: f32) -> (f32, Vec2) {
+    simplex_noise2_impl(x, y, 0)
+}
+
+/// Computes 3d simplex noise at the coordinate (x,y,z)
+///
+/// Compared to [`crate::perlin_noise3`], simplex noise has fewer directional
+/// artifacts and is cheaper to evaluate. The result is approximately in the
+/// range \[-1,1\].
+///
+/// `seed` selects from multiple different variations of the noise function.
+pub fn simplex_noise3_seed(x: f32, y: f32, z: f32, seed: u8) -> f32 {
+    simplex_noise3_impl(x, y, z, seed).0
+}
+
+/// Computes 3d simplex noise at the coordinate (x,y,z)
+///
+/// See [`simplex_noise3_seed`].
+pub fn simplex_noise3(x: f32, y: f32, z: f32) -> f32 {
+    simplex_noise3_impl(x, y, z, 0).0
+}
+
+/// Computes [`simplex_noise3`] and its partial derivatives at the coordinate
+/// (x,y,z)
+///
+/// Returns `(noise, (d/dx, d/dy, d/dz))`.
+pub fn simplex_noise3_deriv(x: f32, y: f32, z: f32) -> (f32, Vec3) {
+    simplex_noise3_impl(x, y, z, 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use narcissus_core::random::Pcg64;
+
+    use crate::{
+        simplex_noise2, simplex_noise2_deriv, simplex_noise2_seed, simplex_noise3,
+        simplex_noise3_deriv, simplex_noise3_seed,
+    };
+
+    #[test]
+    fn range() {
+        let mut rng = Pcg64::new();
+        let mut min = f32::MAX;
+        let mut max = f32::MIN;
+        for _ in 0..100_000 {
+            let [x, y, z] = std::array::from_fn(|_| rng.next_f32_s() * 300.0);
+            for n in [simplex_noise2(x, y), simplex_noise3(x, y, z)] {
+                min = min.min(n);
+                max = max.max(n);
+            }
+        }
+        assert!((-1.1..-0.8).contains(&min), "{min}");
+        assert!((0.8..1.1).contains(&max), "{max}");
+
+        assert_ne!(simplex_noise2_seed(0.3, 0.7, 1), simplex_noise2(0.3, 0.7));
+        assert_ne!(
+            simplex_noise3_seed(0.3, 0.7, 0.1, 1),
+            simplex_noise3(0.3, 0.7, 0.1)
+        );
+    }
+
+    #[test]
+    fn derivatives() {
+        const H: f64 = 1e-3;
+        let central = |f: &dyn Fn(f64) -> f32| (f(H) - f(-H)) as f64 / (2.0 * H);
+        let mut rng = Pcg64::new();
+        for _ in 0..10_000 {
+            let [x, y, z] = std::array::from_fn(|_| rng.next_f32_s() * 300.0);
+
+            let (n, d) = simplex_noise2_deriv(x, y);
+            assert_eq!(n, simplex_noise2(x, y));
+            let dx = central(&|h| simplex_noise2((x as f64 + h) as f32, y));
+            let dy = central(&|h| simplex_noise2(x, (y as f64 + h) as f32));
+            assert!((d.x as f64 - dx).abs() < 0.1, "{d:?} {dx} {dy}");
+            assert!((d.y as f64 - dy).abs() < 0.1, "{d:?} {dx} {dy}");
+
+            let (n, d) = simplex_noise3_deriv(x, y, z);
+            assert_eq!(n, simplex_noise3(x, y, z));
+            let dx = central(&|h| simplex_noise3((x as f64 + h) as f32, y, z));
+            let dy = central(&|h| simplex_noise3(x, (y as f64 + h) as f32, z));
+            let dz = central(&|h| simplex_noise3(x, y, (z as f64 + h) as f32));
+            assert!((d.x as f64 - dx).abs() < 0.1, "{d:?} {dx} {dy} {dz}");
+            assert!((d.y as f64 - dy).abs() < 0.1, "{d:?} {dx} {dy} {dz}");
+            assert!((d.z as f64 - dz).abs() < 0.1, "{d:?} {dx} {dy} {dz}");
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use crate::{Aabb3, Point3, Vec3};
+
+/// Sphere in 3d space, defined by a center point and radius.
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[repr(C)]
+pub struct Sphere {
+    pub center: Point3,
+    pub radius: f32,
+}
+
+impl Sphere {
+    /// Constructs a new sphere with the given `center` and `radius`.
+    #[inline(always)]
+    pub const fn new(center: Point3, radius: f32) -> Self {
+        Self { center, radius }
+    }
+
+    /// Returns the smallest box containing `self`.
+    #[inline(always)]
+    pub fn aabb3(&self) -> Aabb3 {
+        Aabb3::from_center_half_extents(self.center, Vec3::splat(self.radius))
+    }
+
+    /// Returns `true` if `point` is inside or on the surface of `self`.
+    #[inline(always)]
+    pub fn contains_point(&self, point: Point3) -> bool {
+        Point3::distance_sq(self.center, point) <= self.radius * self.radius
+    }
+
+    /// Returns `true` if `a` and `b` overlap, including touching surfaces.
+    #[inline(always)]
+    pub fn overlaps(a: &Sphere, b: &Sphere) -> bool {
+        let radius = a.radius + b.radius;
+        Point3::distance_sq(a.center, b.center) <= radius * radius
+    }
+
+    /// Returns `true` if `self` and `aabb` overlap, including touching surfaces.
+    #[inline(always)]
+    pub fn overlaps_aabb3(&self, aabb: &Aabb3) -> bool {
+        self.contains_point(aabb.closest_point(self.center))
+    }
+
+    /// Returns the point inside `self` closest to `point`.
+    #[inline(always)]
+    pub fn closest_point(&self, point: Point3) -> Point3 {
+        let delta = point - self.center;
+        let distance_sq = delta.length_sq();
+        if distance_sq <= self.radius * self.radius {
+            point
+        } else {
+            self.center + delta * (self.radius / distance_sq.sqrt())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Aabb3, Point3, Sphere, point3};
+
+    #[test]
+    fn overlap() {
+        let a = Sphere::new(Point3::ZERO, 1.0);
+        let b = Sphere::new(point3(2.0, 0.0, 0.0), 1.0);
+        let c = Sphere::new(point3(2.0, 0.1, 0.0), 1.0);
+        assert!(Sphere::overlaps(&a, &b));
+        assert!(!Sphere::overlaps(&a, &c));
+        assert!(a.contains_point(point3(0.0, -1.0, 0.0)));
+        assert!(!a.contains_point(point3(0.0, -1.0, 0.1)));
+
+        assert_eq!(
+            a.aabb3(),
+            Aabb3::new(Point3::splat(-1.0), Point3::splat(1.0))
+        );
+        let aabb = Aabb3::new(point3(0.5, 0.5, 0.5), Point3::splat(2.0));
+        assert!(a.overlaps_aabb3(&aabb));
+        // The corner of the box is outside the sphere, even though the box
+        // overlaps the sphere's bounds.
+        let aabb = Aabb3::new(point3(0.6, 0.6, 0.6), Point3::splat(2.0));
+        assert!(Aabb3::overlaps(&a.aabb3(), &aabb));
+        assert!(!a.overlaps_aabb3(&aabb));
+    }
+
+    #[test]
+    fn closest_point() {
+        let a = Sphere::new(point3(1.0, 0.0, 0.0), 2.0);
+        assert_eq!(
+            a.closest_point(point3(1.0, 0.0, 10.0)),
+            point3(1.0, 0.0, 2.0)
+        );
+        assert_eq!(
+            a.closest_point(point3(0.5, 0.5, 0.5)),
+            point3(0.5, 0.5, 0.5)
+        );
+    }
+}
@@ -0,0 +1,320 @@
+// Exact spring damper solutions following Daniel Holden's "Spring-It-On: The
+// Game Developer's Spring-Roll-Call", https://theorangeduck.com/page/spring-roll-call
+//
+// Each function advances the closed-form solution by `delta_time`, so the
+// result is independent of the frame rate, and stable for any time step.
+
+use std::ops::{Add, Mul, Sub};
+
+use crate::{Quat, Vec3, exp_f32, sin_cos_pi_f32};
+
+/// Values which springs can operate on.
+///
+/// Implemented for any type supporting addition, subtraction and scaling by
+/// `f32`, such as `f32`, [`crate::Vec2`] and [`crate::Vec3`].
+pub trait SpringValue:
+    Copy + Add<Output = Self> + Sub<Output = Self> + Mul<f32, Output = Self>
+{
+}
+
+impl<T> SpringValue for T where T: Copy + Add<Output = T> + Sub<Output = T> + Mul<f32, Output = T> {}
+
+/// Returns the damping coefficient of a critically damped spring which halves
+/// the distance to its goal every `halflife` seconds.
+#[inline(always)]
+pub const fn halflife_to_damping(halflife: f32) -> f32 {
+    (4.0 * std::f32::consts::LN_2) / halflife
+}
+
+/// Returns the halflife in seconds of a critically damped spring with the
+/// given `damping` coefficient.
+#[inline(always)]
+pub const fn damping_to_halflife(damping: f32) -> f32 {
+    (4.0 * std::f32::consts::LN_2) / damping
+}
+
+/// Returns the stiffness coefficient of an undamped spring which oscillates
+/// `frequency` times per second.
+#[inline(always)]
+pub fn frequency_to_stiffness(frequency: f32) -> f32 {
+    let w = std::f32::consts::TAU * frequency;
+    w * w
+}
+
+/// Returns the damping coefficient which critically damps a spring with the
+/// given `stiffness`.
+#[inline(always)]
+pub fn critical_damping(stiffness: f32) -> f32 {
+    2.0 * stiffness.sqrt()
+}
+
+/// Moves `x` towards `goal` with a critically damped spring, returning the new
+/// position and velocity after `delta_time` seconds.
+///
+/// Critically damped springs reach the goal as fast as possible without
+/// overshooting. The spring halves the distance to `goal` roughly every
+/// `halflife` seconds.
+///
+/// # Examples
+///
+/// ```
+/// use narcissus_maths::critical_spring_damper;
+/// let (mut x, mut v) = (0.0, 0.0);
+/// for _ in 0..60 {
+///     (x, v) = critical_spring_damper(x, v, 1.0, 0.1, 1.0 / 60.0);
+/// }
+/// assert!((x - 1.0).abs() < 1e-3);
+/// ```
+#[inline]
+pub fn critical_spring_damper<T: SpringValue>(
+    x: T,
+    velocity: T,
+    goal: T,
+    halflife: f32,
+    delta_time: f32,
+) -> (T, T) {
+    let y = halflife_to_damping(halflife) * 0.5;
+    let j0 = x - goal;
+    let j1 = velocity + j0 * y;
+    let eydt = exp_f32(-y * delta_time);
+    (
+        (j0 + j1 * delta_time) * eydt + goal,
+        (velocity - j1 * (y * delta_time)) * eydt,
+    )
+}
+
+/// Moves `x` towards `goal` with a spring of the given `stiffness` and
+/// `damping`, returning the new position and velocity after `delta_time`
+/// seconds.
+///
+/// Handles under-damped springs, which overshoot and oscillate around the
+/// goal, as well as critically damped and over-damped springs. See
+/// [`frequency_to_stiffness`] and [`critical_damping`] for choosing
+/// coefficients.
+pub fn spring_damper<T: SpringValue>(
+    x: T,
+    velocity: T,
+    goal: T,
+    stiffness: f32,
+    damping: f32,
+    delta_time: f32,
+) -> (T, T) {
+    let y = damping * 0.5;
+    let j0 = x - goal;
+    let discriminant = stiffness - y * y;
+
+    if discriminant.abs() < 1e-5 {
+        // Critically damped.
+        let j1 = velocity + j0 * y;
+        let eydt = exp_f32(-y * delta_time);
+        (
+            (j0 + j1 * delta_time) * eydt + goal,
+            (velocity - j1 * (y * delta_time)) * eydt,
+        )
+    } else if discriminant > 0.0 {
+        // Under-damped, x(t) = goal + e^(-yt) (a cos(wt) + b sin(wt)).
+        let w = discriminant.sqrt();
+        let a = j0;
+        let b = (velocity + j0 * y) * (1.0 / w);
+        let eydt = exp_f32(-y * delta_time);
+        let (sin, cos) = sin_cos_pi_f32(w * delta_time * std::f32::consts::FRAC_1_PI);
+        (
+            (a * cos + b * sin) * eydt + goal,
+            ((b * w - a * y) * cos - (a * w + b * y) * sin) * eydt,
+        )
+    } else {
+        // Over-damped, x(t) = goal + a e^(-y0 t) + b e^(-y1 t).
+        let s = (-discriminant).sqrt();
+        let y0 = y + s;
+        let y1 = y - s;
+        let b = (j0 * y0 + velocity) * (1.0 / (y0 - y1));
+        let a = j0 - b;
+        let e0 = exp_f32(-y0 * delta_time);
+        let e1 = exp_f32(-y1 * delta_time);
+        (a * e0 + b * e1 + goal, a * (-y0 * e0) + b * (-y1 * e1))
+    }
+}
+
+/// Smooths changes in velocity with a critically damped spring, returning the
+/// new position, velocity and acceleration after `delta_time` seconds.
+///
+/// Suitable for characters and cameras which follow a desired velocity from
+/// input, rather than a desired position.
+#[inline]
+pub fn velocity_spring<T: SpringValue>(
+    x: T,
+    velocity: T,
+    acceleration: T,
+    goal_velocity: T,
+    halflife: f32,
+    delta_time: f32,
+) -> (T, T, T) {
+    let y = halflife_to_damping(halflife) * 0.5;
+    let j0 = velocity - goal_velocity;
+    let j1 = acceleration + j0 * y;
+    let eydt = exp_f32(-y * delta_time);
+
+    let x = x
+        + goal_velocity * delta_time
+        + (j0 * (1.0 - eydt) + j1 * (1.0 / y) * (1.0 - eydt * (1.0 + y * delta_time))) * (1.0 / y);
+    let velocity = (j0 + j1 * delta_time) * eydt + goal_velocity;
+    let acceleration = (acceleration - j1 * (y * delta_time)) * eydt;
+    (x, velocity, acceleration)
+}
+
+/// Rotates `rotation` towards `goal` with a critically damped spring,
+/// returning the new rotation and angular velocity after `delta_time` seconds.
+///
+/// Angular velocity is a scaled axis vector in half turns per second, see
+/// [`Quat::from_scaled_axis`].
+pub fn critical_spring_damper_quat(
+    rotation: Quat,
+    angular_velocity: Vec3,
+    goal: Quat,
+    halflife: f32,
+    delta_time: f32,
+) -> (Quat, Vec3) {
+    let y = halflife_to_damping(halflife) * 0.5;
+    let j0 = (rotation * goal.conjugate()).to_scaled_axis();
+    let j1 = angular_velocity + j0 * y;
+    let eydt = exp_f32(-y * delta_time);
+    let rotation = Quat::from_scaled_axis((j0 + j1 * delta_time) * eydt) * goal;
+    let angular_velocity = (angular_velocity - j1 * (y * delta_time)) * eydt;
+    (rotation, angular_velocity)
+}
+
+/// Returns the fraction of the remaining distance covered after `delta_time`
+/// seconds when smoothing with the given `halflife`.
+#[inline(always)]
+fn exp_smooth_factor(halflife: f32, delta_time: f32) -> f32 {
+    1.0 - exp_f32(-std::f32::consts::LN_2 * delta_time / halflife)
+}
+
+/// Moves `x` towards `goal` with exponential decay, halving the distance every
+/// `halflife` seconds.
+///
+/// Unlike `lerp(x, goal, c)` with a constant `c` each frame, the result
+/// doesn't depend on the frame rate.
+///
+/// # Examples
+///
+/// ```
+/// use narcissus_maths::exp_smooth;
+/// let x = exp_smooth(0.0, 1.0, 0.5, 0.5);
+/// assert!((x - 0.5).abs() < 1e-6);
+/// ```
+#[inline(always)]
+pub fn exp_smooth<T: SpringValue>(x: T, goal: T, halflife: f32, delta_time: f32) -> T {
+    x + (goal - x) * exp_smooth_factor(halflife, delta_time)
+}
+
+/// Rotates `rotation` towards `goal` with exponential decay, halving the
+/// remaining angle every `halflife` seconds.
+#[inline]
+pub fn exp_smooth_quat(rotation: Quat, goal: Quat, halflife: f32, delta_time: f32) -> Quat {
+    Quat::slerp(exp_smooth_factor(halflife, delta_time), rotation, goal)
+}
+
+#[cfg(test)]
+mod tests {
+    use narcissus_core::random::Pcg64;
+
+    use crate::{
+        HalfTurn, Quat, Vec3, critical_damping, critical_spring_damper,
+        critical_spring_damper_quat, exp_smooth, exp_smooth_quat, frequency_to_stiffness,
+        halflife_to_damping, spring_damper, vec2, vec3, velocity_spring,
+    };
+
+    /// Steps a spring many times with a small time step, and once with the
+    /// total, checking the results match.
+    fn check_frame_rate_independent<F>(mut step: F)
+    where
+        F: FnMut(f32, f32, f32) -> (f32, f32),
+    {
+        let (mut x, mut v) = (2.0, -1.0);
+        for _ in 0..100 {
+            (x, v) = step(x, v, 0.01);
+        }
+        let (x1, v1) = step(2.0, -1.0, 1.0);
+        assert!((x - x1).abs() < 1e-4, "{x} != {x1}");
+        assert!((v - v1).abs() < 1e-4, "{v} != {v1}");
+    }
+
+    #[test]
+    fn frame_rate_independence() {
+        check_frame_rate_independent(|x, v, dt| critical_spring_damper(x, v, 0.5, 0.3, dt));
+        for damping in [0.5, 2.0 * 10.0_f32.sqrt(), 20.0] {
+            check_frame_rate_independent(|x, v, dt| spring_damper(x, v, 0.5, 10.0, damping, dt));
+        }
+        check_frame_rate_independent(|x, _, dt| (exp_smooth(x, 0.5, 0.3, dt), 0.0));
+
+        let (mut x, mut v, mut a) = (0.0, 0.0, 0.0);
+        for _ in 0..100 {
+            (x, v, a) = velocity_spring(x, v, a, 2.0, 0.2, 0.01);
+        }
+        let (x1, v1, a1) = velocity_spring(0.0, 0.0, 0.0, 2.0, 0.2, 1.0);
+        assert!((x - x1).abs() < 1e-4 && (v - v1).abs() < 1e-4 && (a - a1).abs() < 1e-4);
+        assert!((v - 2.0).abs() < 0.05);
+    }
+
+    #[test]
+    fn matches_differential_equation() {
+        // Integrate x'' = s (g - x) - d x' with small semi-implicit Euler steps.
+        for (stiffness, damping) in [(10.0, 0.5), (10.0, critical_damping(10.0)), (10.0, 20.0)] {
+            let (stiffness_f64, damping_f64) = (stiffness as f64, damping as f64);
+            let (mut x, mut v) = (2.0_f64, -1.0_f64);
+            let dt = 1e-5;
+            for _ in 0..50_000 {
+                v += (stiffness_f64 * (0.5 - x) - damping_f64 * v) * dt;
+                x += v * dt;
+            }
+            let (x1, v1) = spring_damper(2.0, -1.0, 0.5, stiffness, damping, 0.5);
+            assert!((x - x1 as f64).abs() < 1e-3, "{x} != {x1}");
+            assert!((v - v1 as f64).abs() < 1e-3, "{v} != {v1}");
+        }
+
+        // A critically damped spring_damper matches critical_spring_damper.
+        let damping = halflife_to_damping(0.25);
+        let stiffness = damping * damping / 4.0;
+        let (x, v) = spring_damper(2.0, -1.0, 0.5, stiffness, damping, 0.3);
+        let (x1, v1) = critical_spring_damper(2.0, -1.0, 0.5, 0.25, 0.3);
+        assert!((x - x1).abs() < 1e-5 && (v - v1).abs() < 1e-5);
+    }
+
+    #[test]
+    fn halflife() {
+        assert!((exp_smooth(0.0, 1.0, 0.5, 1.0) - 0.75).abs() < 1e-6);
+        let (x, _) =
+            critical_spring_damper(vec2(0.0, 0.0), vec2(0.0, 0.0), vec2(1.0, 2.0), 0.5, 1.0);
+        assert!(x.x > 0.0 && x.x < 1.0);
+        assert!((x.y - 2.0 * x.x).abs() < 1e-6);
+
+        // Under-damped springs oscillate at roughly the given frequency.
+        let stiffness = frequency_to_stiffness(1.0);
+        let (x, _) = spring_damper(1.0, 0.0, 0.0, stiffness, 0.0, 1.0);
+        assert!((x - 1.0).abs() < 1e-4);
+        let (x, _) = spring_damper(1.0, 0.0, 0.0, stiffness, 0.0, 0.5);
+        assert!((x + 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn quat_springs() {
+        let mut rng = Pcg64::new();
+        for _ in 0..100 {
+            let axis = vec3(rng.next_f32_s(), rng.next_f32_s(), rng.next_f32_s()).normalized();
+            let goal = Quat::from_axis_rotation(axis, HalfTurn::new(rng.next_f32_s()));
+            let mut rotation = Quat::IDENTITY;
+            let mut angular_velocity = Vec3::ZERO;
+            for _ in 0..200 {
+                (rotation, angular_velocity) =
+                    critical_spring_damper_quat(rotation, angular_velocity, goal, 0.1, 1.0 / 60.0);
+            }
+            assert!(Quat::dot(rotation, goal).abs() > 1.0 - 1e-5);
+            assert!(angular_velocity.length() < 1e-2);
+
+            let rotation = exp_smooth_quat(Quat::IDENTITY, goal, 0.5, 0.5);
+            let expected = Quat::slerp(0.5, Quat::IDENTITY, goal);
+            assert!(Quat::dot(rotation, expected).abs() > 1.0 - 1e-5);
+        }
+    }
+}
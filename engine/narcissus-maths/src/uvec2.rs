@@ -0,0 +1,99 @@
+use crate::{IVec2, Vec2, impl_int_vector};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default, Debug)]
+#[repr(C)]
+pub struct UVec2 {
+    pub x: u32,
+    pub y: u32,
+}
+
+pub const fn uvec2(x: u32, y: u32) -> UVec2 {
+    UVec2 { x, y }
+}
+
+impl_int_vector!(UVec2, u32, 2);
+
+impl UVec2 {
+    pub const X: UVec2 = UVec2::new(1, 0);
+    pub const Y: UVec2 = UVec2::new(0, 1);
+
+    /// Constructs a new [`UVec2`] with the given `x` and `y` components.
+    #[inline(always)]
+    #[must_use]
+    pub const fn new(x: u32, y: u32) -> Self {
+        Self { x, y }
+    }
+
+    /// Returns a [`UVec2`] with the function `f` applied to each component in order.
+    #[inline(always)]
+    #[must_use]
+    pub fn map<F>(self, mut f: F) -> UVec2
+    where
+        F: FnMut(u32) -> u32,
+    {
+        UVec2 {
+            x: f(self.x),
+            y: f(self.y),
+        }
+    }
+
+    /// Returns a new [`UVec2`] with the function `f` applied to each pair of
+    /// components from `self` and `rhs` in order.
+    #[inline(always)]
+    #[must_use]
+    pub fn map2<F>(self, rhs: UVec2, mut f: F) -> UVec2
+    where
+        F: FnMut(u32, u32) -> u32,
+    {
+        UVec2 {
+            x: f(self.x, rhs.x),
+            y: f(self.y, rhs.y),
+        }
+    }
+
+    /// Returns the dot product of `a` and `b`.
+    #[inline(always)]
+    #[must_use]
+    pub fn dot(a: Self, b: Self) -> u32 {
+        a.x * b.x + a.y * b.y
+    }
+
+    /// Returns the product of the components, for example the number of
+    /// elements in a grid with dimensions `self`.
+    #[inline(always)]
+    #[must_use]
+    pub fn element_product(self) -> u32 {
+        self.x * self.y
+    }
+
+    /// Returns a [`UVec2`] where each element is the quotient of the
+    /// corresponding elements in `self` and `rhs`, rounded up.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use narcissus_maths::{UVec2, uvec2};
+    /// let tile_resolution = uvec2(1920, 1080).div_ceil(UVec2::splat(32));
+    /// assert_eq!(tile_resolution, uvec2(60, 34));
+    /// ```
+    #[inline(always)]
+    #[must_use]
+    pub fn div_ceil(self, rhs: UVec2) -> UVec2 {
+        self.map2(rhs, u32::div_ceil)
+    }
+
+    /// Converts to a [`Vec2`], rounding to the nearest representable value.
+    #[inline(always)]
+    #[must_use]
+    pub fn as_vec2(self) -> Vec2 {
+        Vec2::new(self.x as f32, self.y as f32)
+    }
+
+    /// Converts to an [`IVec2`], wrapping components greater than
+    /// [`i32::MAX`].
+    #[inline(always)]
+    #[must_use]
+    pub fn as_ivec2(self) -> IVec2 {
+        IVec2::new(self.x as i32, self.y as i32)
+    }
+}
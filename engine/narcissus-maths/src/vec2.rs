@@ -1,4 +1,4 @@
-use crate::{Point2, impl_shared, impl_vector};
+use crate::{IVec2, Point2, UVec2, impl_shared, impl_vector};
 
 #[derive(Clone, Copy, PartialEq, PartialOrd, Default, Debug)]
 #[repr(C)]
@@ -32,6 +32,22 @@ impl Vec2 {
         Point2::new(self.x, self.y)
     }
 
+    /// Converts to an [`IVec2`], truncating towards zero and saturating at the
+    /// bounds of `i32`, with `NaN` converting to `0`.
+    #[inline(always)]
+    #[must_use]
+    pub fn as_ivec2(self) -> IVec2 {
+        IVec2::new(self.x as i32, self.y as i32)
+    }
+
+    /// Converts to a [`UVec2`], truncating towards zero and saturating at the
+    /// bounds of `u32`, with `NaN` converting to `0`.
+    #[inline(always)]
+    #[must_use]
+    pub fn as_uvec2(self) -> UVec2 {
+        UVec2::new(self.x as u32, self.y as u32)
+    }
+
     /// Returns a [`Vec2`] with the function `f` applied to each component in order.
     #[inline(always)]
     #[must_use]
@@ -1,4 +1,4 @@
-use crate::{Point3, impl_shared, impl_vector};
+use crate::{IVec3, Point3, impl_shared, impl_vector};
 
 #[derive(Clone, Copy, PartialEq, PartialOrd, Default, Debug)]
 #[repr(C)]
@@ -34,6 +34,14 @@ impl Vec3 {
         Point3::new(self.x, self.y, self.z)
     }
 
+    /// Converts to an [`IVec3`], truncating towards zero and saturating at the
+    /// bounds of `i32`, with `NaN` converting to `0`.
+    #[inline(always)]
+    #[must_use]
+    pub fn as_ivec3(self) -> IVec3 {
+        IVec3::new(self.x as i32, self.y as i32, self.z as i32)
+    }
+
     /// Returns a [`Vec3`] with the function `f` applied to each component in order.
     #[inline(always)]
     #[must_use]
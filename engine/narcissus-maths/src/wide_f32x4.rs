@@ -0,0 +1,137 @@
+#[cfg(all(target_arch = "x86_64", target_feature = "sse2"))]
+use std::arch::x86_64::*;
+
+use crate::impl_wide;
+
+/// Four `f32` lanes operated on in parallel.
+///
+/// Uses SSE instructions when available, falling back to scalar code
+/// otherwise.
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy, PartialEq, Default)]
+#[repr(C, align(16))]
+pub struct f32x4([f32; 4]);
+
+/// Four lane mask, typically produced by comparing [`f32x4`] values.
+///
+/// Each lane is either all ones (`true`) or all zeros (`false`).
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+#[repr(C, align(16))]
+pub struct m32x4([u32; 4]);
+
+impl_wide!(f32x4, m32x4, 4, __m128, "sse2", "sse4.1", {
+    add: _mm_add_ps,
+    sub: _mm_sub_ps,
+    mul: _mm_mul_ps,
+    div: _mm_div_ps,
+    min: _mm_min_ps,
+    max: _mm_max_ps,
+    sqrt: _mm_sqrt_ps,
+    floor: _mm_floor_ps,
+    ceil: _mm_ceil_ps,
+    fmadd: _mm_fmadd_ps,
+    cmp_eq: _mm_cmpeq_ps,
+    cmp_ne: _mm_cmpneq_ps,
+    cmp_lt: _mm_cmplt_ps,
+    cmp_le: _mm_cmple_ps,
+    movemask: _mm_movemask_ps,
+    set1: _mm_set1_ps,
+    and: _mm_and_ps,
+    andnot: _mm_andnot_ps,
+    or: _mm_or_ps,
+    xor: _mm_xor_ps,
+});
+
+#[cfg(test)]
+mod tests {
+    use narcissus_core::random::Pcg64;
+
+    use crate::{f32x4, m32x4};
+
+    fn random_f32x4(rng: &mut Pcg64) -> f32x4 {
+        f32x4::from_array(std::array::from_fn(|_| rng.next_f32_s() * 100.0))
+    }
+
+    #[test]
+    fn arithmetic() {
+        let mut rng = Pcg64::new();
+        for _ in 0..1000 {
+            let a = random_f32x4(&mut rng);
+            let b = random_f32x4(&mut rng);
+            let c = random_f32x4(&mut rng);
+            let [a_s, b_s, c_s] = [a, b, c].map(f32x4::to_array);
+            let expected = |f: &dyn Fn(usize) -> f32| std::array::from_fn::<f32, 4, _>(f);
+
+            assert_eq!((a + b).to_array(), expected(&|i| a_s[i] + b_s[i]));
+            assert_eq!((a - b).to_array(), expected(&|i| a_s[i] - b_s[i]));
+            assert_eq!((a * b).to_array(), expected(&|i| a_s[i] * b_s[i]));
+            assert_eq!((a / b).to_array(), expected(&|i| a_s[i] / b_s[i]));
+            assert_eq!((a * 2.0).to_array(), expected(&|i| a_s[i] * 2.0));
+            assert_eq!((-a).to_array(), expected(&|i| -a_s[i]));
+            assert_eq!(a.abs().to_array(), expected(&|i| a_s[i].abs()));
+            assert_eq!(
+                a.abs().sqrt().to_array(),
+                expected(&|i| a_s[i].abs().sqrt())
+            );
+            assert_eq!(a.floor().to_array(), expected(&|i| a_s[i].floor()));
+            assert_eq!(a.ceil().to_array(), expected(&|i| a_s[i].ceil()));
+            assert_eq!(
+                a.mul_add(b, c).to_array(),
+                expected(&|i| a_s[i].mul_add(b_s[i], c_s[i]))
+            );
+            assert_eq!(
+                f32x4::min(a, b).to_array(),
+                expected(&|i| a_s[i].min(b_s[i]))
+            );
+            assert_eq!(
+                f32x4::max(a, b).to_array(),
+                expected(&|i| a_s[i].max(b_s[i]))
+            );
+            assert_eq!(a.reduce_min(), a_s.iter().copied().fold(f32::MAX, f32::min));
+            assert_eq!(a.reduce_max(), a_s.iter().copied().fold(f32::MIN, f32::max));
+        }
+
+        assert_eq!(f32x4::from_array([1.0, 2.0, 3.0, 4.0]).reduce_add(), 10.0);
+    }
+
+    #[test]
+    fn load_store() {
+        let src = [1.0, 2.0, 3.0, 4.0];
+        let mut dst = [0.0; 4];
+        (f32x4::load(&src) + f32x4::ONE).store(&mut dst);
+        assert_eq!(dst, [2.0, 3.0, 4.0, 5.0]);
+    }
+
+    #[test]
+    fn masks() {
+        let a = f32x4::from_array([1.0, 2.0, f32::NAN, 4.0]);
+        let b = f32x4::from_array([2.0, 2.0, 3.0, 3.0]);
+
+        assert_eq!(a.cmp_lt(b).to_array(), [true, false, false, false]);
+        assert_eq!(a.cmp_le(b).to_array(), [true, true, false, false]);
+        assert_eq!(a.cmp_gt(b).to_array(), [false, false, false, true]);
+        assert_eq!(a.cmp_ge(b).to_array(), [false, true, false, true]);
+        assert_eq!(a.cmp_eq(b).to_array(), [false, true, false, false]);
+        assert_eq!(a.cmp_ne(b).to_array(), [true, false, true, true]);
+
+        let mask = a.cmp_le(b);
+        assert_eq!(mask.to_bitmask(), 0b0011);
+        assert_eq!(m32x4::from_bitmask(0b0011), mask);
+        assert_eq!((!mask).to_bitmask(), 0b1100);
+        assert_eq!((mask & a.cmp_eq(b)).to_bitmask(), 0b0010);
+        assert_eq!((mask | a.cmp_gt(b)).to_bitmask(), 0b1011);
+        assert_eq!((mask ^ m32x4::TRUE).to_bitmask(), 0b1100);
+        assert!(mask.any() && !mask.all() && !mask.none());
+        assert!(m32x4::splat(true).all());
+        assert!(m32x4::splat(false).none());
+
+        assert_eq!(mask.select(a, f32x4::ZERO).to_array(), [1.0, 2.0, 0.0, 0.0]);
+        assert_eq!(
+            m32x4::from_array([false, true, false, true])
+                .select(a, b)
+                .to_array(),
+            [2.0, 2.0, 3.0, 4.0]
+        );
+    }
+}
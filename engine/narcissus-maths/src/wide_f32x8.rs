@@ -0,0 +1,155 @@
+#[cfg(all(target_arch = "x86_64", target_feature = "avx"))]
+use std::arch::x86_64::*;
+
+use crate::impl_wide;
+
+/// Eight `f32` lanes operated on in parallel.
+///
+/// Uses AVX instructions when available, falling back to scalar code
+/// otherwise.
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy, PartialEq, Default)]
+#[repr(C, align(32))]
+pub struct f32x8([f32; 8]);
+
+/// Eight lane mask, typically produced by comparing [`f32x8`] values.
+///
+/// Each lane is either all ones (`true`) or all zeros (`false`).
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+#[repr(C, align(32))]
+pub struct m32x8([u32; 8]);
+
+impl_wide!(f32x8, m32x8, 8, __m256, "avx", "avx", {
+    add: _mm256_add_ps,
+    sub: _mm256_sub_ps,
+    mul: _mm256_mul_ps,
+    div: _mm256_div_ps,
+    min: _mm256_min_ps,
+    max: _mm256_max_ps,
+    sqrt: _mm256_sqrt_ps,
+    floor: _mm256_floor_ps,
+    ceil: _mm256_ceil_ps,
+    fmadd: _mm256_fmadd_ps,
+    cmp_eq: _mm256_cmp_ps::<_CMP_EQ_OQ>,
+    cmp_ne: _mm256_cmp_ps::<_CMP_NEQ_UQ>,
+    cmp_lt: _mm256_cmp_ps::<_CMP_LT_OQ>,
+    cmp_le: _mm256_cmp_ps::<_CMP_LE_OQ>,
+    movemask: _mm256_movemask_ps,
+    set1: _mm256_set1_ps,
+    and: _mm256_and_ps,
+    andnot: _mm256_andnot_ps,
+    or: _mm256_or_ps,
+    xor: _mm256_xor_ps,
+});
+
+#[cfg(test)]
+mod tests {
+    use narcissus_core::random::Pcg64;
+
+    use crate::{f32x8, m32x8};
+
+    fn random_f32x8(rng: &mut Pcg64) -> f32x8 {
+        f32x8::from_array(std::array::from_fn(|_| rng.next_f32_s() * 100.0))
+    }
+
+    #[test]
+    fn arithmetic() {
+        let mut rng = Pcg64::new();
+        for _ in 0..1000 {
+            let a = random_f32x8(&mut rng);
+            let b = random_f32x8(&mut rng);
+            let c = random_f32x8(&mut rng);
+            let [a_s, b_s, c_s] = [a, b, c].map(f32x8::to_array);
+            let expected = |f: &dyn Fn(usize) -> f32| std::array::from_fn::<f32, 8, _>(f);
+
+            assert_eq!((a + b).to_array(), expected(&|i| a_s[i] + b_s[i]));
+            assert_eq!((a - b).to_array(), expected(&|i| a_s[i] - b_s[i]));
+            assert_eq!((a * b).to_array(), expected(&|i| a_s[i] * b_s[i]));
+            assert_eq!((a / b).to_array(), expected(&|i| a_s[i] / b_s[i]));
+            assert_eq!((a * 2.0).to_array(), expected(&|i| a_s[i] * 2.0));
+            assert_eq!((-a).to_array(), expected(&|i| -a_s[i]));
+            assert_eq!(a.abs().to_array(), expected(&|i| a_s[i].abs()));
+            assert_eq!(
+                a.abs().sqrt().to_array(),
+                expected(&|i| a_s[i].abs().sqrt())
+            );
+            assert_eq!(a.floor().to_array(), expected(&|i| a_s[i].floor()));
+            assert_eq!(a.ceil().to_array(), expected(&|i| a_s[i].ceil()));
+            assert_eq!(
+                a.mul_add(b, c).to_array(),
+                expected(&|i| a_s[i].mul_add(b_s[i], c_s[i]))
+            );
+            assert_eq!(
+                f32x8::min(a, b).to_array(),
+                expected(&|i| a_s[i].min(b_s[i]))
+            );
+            assert_eq!(
+                f32x8::max(a, b).to_array(),
+                expected(&|i| a_s[i].max(b_s[i]))
+            );
+            assert_eq!(a.reduce_min(), a_s.iter().copied().fold(f32::MAX, f32::min));
+            assert_eq!(a.reduce_max(), a_s.iter().copied().fold(f32::MIN, f32::max));
+        }
+
+        assert_eq!(
+            f32x8::from_array([1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]).reduce_add(),
+            36.0
+        );
+    }
+
+    #[test]
+    fn load_store() {
+        let src = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0];
+        let mut dst = [0.0; 8];
+        (f32x8::load(&src) + f32x8::ONE).store(&mut dst);
+        assert_eq!(dst, [2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0]);
+    }
+
+    #[test]
+    fn masks() {
+        let a = f32x8::from_array([1.0, 2.0, f32::NAN, 4.0, 0.0, 0.0, 0.0, 0.0]);
+        let b = f32x8::from_array([2.0, 2.0, 3.0, 3.0, 0.0, 1.0, -1.0, f32::NAN]);
+
+        assert_eq!(
+            a.cmp_lt(b).to_array(),
+            [true, false, false, false, false, true, false, false]
+        );
+        assert_eq!(
+            a.cmp_le(b).to_array(),
+            [true, true, false, false, true, true, false, false]
+        );
+        assert_eq!(
+            a.cmp_gt(b).to_array(),
+            [false, false, false, true, false, false, true, false]
+        );
+        assert_eq!(
+            a.cmp_ge(b).to_array(),
+            [false, true, false, true, true, false, true, false]
+        );
+        assert_eq!(
+            a.cmp_eq(b).to_array(),
+            [false, true, false, false, true, false, false, false]
+        );
+        assert_eq!(
+            a.cmp_ne(b).to_array(),
+            [true, false, true, true, false, true, true, true]
+        );
+
+        let mask = a.cmp_le(b);
+        assert_eq!(mask.to_bitmask(), 0b0011_0011);
+        assert_eq!(m32x8::from_bitmask(0b0011_0011), mask);
+        assert_eq!((!mask).to_bitmask(), 0b1100_1100);
+        assert_eq!((mask & a.cmp_eq(b)).to_bitmask(), 0b0001_0010);
+        assert_eq!((mask | a.cmp_gt(b)).to_bitmask(), 0b0111_1011);
+        assert_eq!((mask ^ m32x8::TRUE).to_bitmask(), 0b1100_1100);
+        assert!(mask.any() && !mask.all() && !mask.none());
+        assert!(m32x8::splat(true).all());
+        assert!(m32x8::splat(false).none());
+
+        assert_eq!(
+            mask.select(b, f32x8::ZERO).to_array(),
+            [2.0, 2.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0]
+        );
+    }
+}
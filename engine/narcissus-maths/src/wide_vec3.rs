@@ -0,0 +1,330 @@
+use crate::{Vec3, f32x4, f32x8, m32x4, m32x8};
+
+/// Four [`Vec3`] values stored in structure-of-arrays form and operated on in
+/// parallel.
+#[derive(Clone, Copy, PartialEq, Default, Debug)]
+#[repr(C)]
+pub struct Vec3x4 {
+    pub x: f32x4,
+    pub y: f32x4,
+    pub z: f32x4,
+}
+
+/// Eight [`Vec3`] values stored in structure-of-arrays form and operated on in
+/// parallel.
+#[derive(Clone, Copy, PartialEq, Default, Debug)]
+#[repr(C)]
+pub struct Vec3x8 {
+    pub x: f32x8,
+    pub y: f32x8,
+    pub z: f32x8,
+}
+
+macro_rules! impl_vec3_wide {
+    ($name:ident, $f:ident, $m:ident, $n:expr) => {
+        impl $name {
+            pub const LANES: usize = $n;
+
+            #[doc = concat!("[`", stringify!($name), "`] with all elements initialized to `0.0`.")]
+            pub const ZERO: $name = $name::new($f::ZERO, $f::ZERO, $f::ZERO);
+            #[doc = concat!("[`", stringify!($name), "`] with all elements initialized to `1.0`.")]
+            pub const ONE: $name = $name::new($f::ONE, $f::ONE, $f::ONE);
+
+            #[doc = concat!("Constructs a new [`", stringify!($name), "`] with the given `x`, `y` and `z` lanes.")]
+            #[inline(always)]
+            #[must_use]
+            pub const fn new(x: $f, y: $f, z: $f) -> $name {
+                $name { x, y, z }
+            }
+
+            #[doc = concat!("Constructs a new [`", stringify!($name), "`] where every lane is initialized with `value`.")]
+            #[inline(always)]
+            #[must_use]
+            pub const fn splat(value: Vec3) -> $name {
+                $name::new($f::splat(value.x), $f::splat(value.y), $f::splat(value.z))
+            }
+
+            /// Loads lanes from separate `x`, `y` and `z` arrays, for example from a
+            /// structure-of-arrays block.
+            #[inline(always)]
+            #[must_use]
+            pub fn load(x: &[f32; $n], y: &[f32; $n], z: &[f32; $n]) -> $name {
+                $name::new($f::load(x), $f::load(y), $f::load(z))
+            }
+
+            /// Stores the lanes of `self` into separate `x`, `y` and `z` arrays.
+            #[inline(always)]
+            pub fn store(self, x: &mut [f32; $n], y: &mut [f32; $n], z: &mut [f32; $n]) {
+                self.x.store(x);
+                self.y.store(y);
+                self.z.store(z);
+            }
+
+            /// Transposes an array of vectors into lanes.
+            #[inline(always)]
+            #[must_use]
+            pub fn from_array(array: [Vec3; $n]) -> $name {
+                $name::new(
+                    $f::from_array(array.map(|v| v.x)),
+                    $f::from_array(array.map(|v| v.y)),
+                    $f::from_array(array.map(|v| v.z)),
+                )
+            }
+
+            /// Transposes lanes into an array of vectors.
+            #[inline(always)]
+            #[must_use]
+            pub fn to_array(self) -> [Vec3; $n] {
+                let (x, y, z) = (self.x.to_array(), self.y.to_array(), self.z.to_array());
+                std::array::from_fn(|i| Vec3::new(x[i], y[i], z[i]))
+            }
+
+            /// Returns the lane-wise dot product of `a` and `b`.
+            #[inline(always)]
+            #[must_use]
+            pub fn dot(a: $name, b: $name) -> $f {
+                a.x.mul_add(b.x, a.y.mul_add(b.y, a.z * b.z))
+            }
+
+            /// Returns the lane-wise cross product of `a` and `b`.
+            #[inline(always)]
+            #[must_use]
+            pub fn cross(a: $name, b: $name) -> $name {
+                $name::new(
+                    a.y * b.z - a.z * b.y,
+                    a.z * b.x - a.x * b.z,
+                    a.x * b.y - a.y * b.x,
+                )
+            }
+
+            /// Calculates the length of each lane of `self`.
+            #[inline(always)]
+            #[must_use]
+            pub fn length(self) -> $f {
+                self.length_sq().sqrt()
+            }
+
+            /// Calculates the squared length of each lane of `self`.
+            #[inline(always)]
+            #[must_use]
+            pub fn length_sq(self) -> $f {
+                Self::dot(self, self)
+            }
+
+            /// Returns each lane of `self` scaled to unit length.
+            #[inline(always)]
+            #[must_use]
+            pub fn normalized(self) -> $name {
+                self / self.length()
+            }
+
+            /// Returns the lane-wise euclidean distance between `a` and `b`.
+            #[inline(always)]
+            #[must_use]
+            pub fn distance(a: $name, b: $name) -> $f {
+                (b - a).length()
+            }
+
+            /// Returns the lane-wise squared euclidean distance between `a` and `b`.
+            #[inline(always)]
+            #[must_use]
+            pub fn distance_sq(a: $name, b: $name) -> $f {
+                (b - a).length_sq()
+            }
+
+            /// Returns the element-wise minimum of `a` and `b`.
+            #[inline(always)]
+            #[must_use]
+            pub fn min(a: $name, b: $name) -> $name {
+                $name::new($f::min(a.x, b.x), $f::min(a.y, b.y), $f::min(a.z, b.z))
+            }
+
+            /// Returns the element-wise maximum of `a` and `b`.
+            #[inline(always)]
+            #[must_use]
+            pub fn max(a: $name, b: $name) -> $name {
+                $name::new($f::max(a.x, b.x), $f::max(a.y, b.y), $f::max(a.z, b.z))
+            }
+
+            /// Returns the element-wise absolute value of `self`.
+            #[inline(always)]
+            #[must_use]
+            pub fn abs(self) -> $name {
+                $name::new(self.x.abs(), self.y.abs(), self.z.abs())
+            }
+
+            /// Returns a vector where each lane is taken from `if_true` if the
+            /// corresponding lane in `mask` is `true`, and from `if_false`
+            /// otherwise.
+            #[inline(always)]
+            #[must_use]
+            pub fn select(mask: $m, if_true: $name, if_false: $name) -> $name {
+                $name::new(
+                    mask.select(if_true.x, if_false.x),
+                    mask.select(if_true.y, if_false.y),
+                    mask.select(if_true.z, if_false.z),
+                )
+            }
+        }
+
+        impl std::ops::Neg for $name {
+            type Output = $name;
+
+            #[inline(always)]
+            fn neg(self) -> $name {
+                $name::new(-self.x, -self.y, -self.z)
+            }
+        }
+
+        impl_vec3_wide!(@op $name, $f, Add, add, AddAssign, add_assign, +);
+        impl_vec3_wide!(@op $name, $f, Sub, sub, SubAssign, sub_assign, -);
+        impl_vec3_wide!(@op $name, $f, Mul, mul, MulAssign, mul_assign, *);
+        impl_vec3_wide!(@op $name, $f, Div, div, DivAssign, div_assign, /);
+    };
+
+    (@op $name:ident, $f:ident, $trait:ident, $fn:ident, $assign_trait:ident, $assign_fn:ident, $op:tt) => {
+        impl std::ops::$trait for $name {
+            type Output = $name;
+
+            #[inline(always)]
+            fn $fn(self, rhs: $name) -> $name {
+                $name::new(self.x $op rhs.x, self.y $op rhs.y, self.z $op rhs.z)
+            }
+        }
+
+        impl std::ops::$trait<$f> for $name {
+            type Output = $name;
+
+            #[inline(always)]
+            fn $fn(self, rhs: $f) -> $name {
+                $name::new(self.x $op rhs, self.y $op rhs, self.z $op rhs)
+            }
+        }
+
+        impl std::ops::$trait<f32> for $name {
+            type Output = $name;
+
+            #[inline(always)]
+            fn $fn(self, rhs: f32) -> $name {
+                self $op $f::splat(rhs)
+            }
+        }
+
+        impl std::ops::$assign_trait for $name {
+            #[inline(always)]
+            fn $assign_fn(&mut self, rhs: $name) {
+                *self = *self $op rhs
+            }
+        }
+
+        impl std::ops::$assign_trait<$f> for $name {
+            #[inline(always)]
+            fn $assign_fn(&mut self, rhs: $f) {
+                *self = *self $op rhs
+            }
+        }
+
+        impl std::ops::$assign_trait<f32> for $name {
+            #[inline(always)]
+            fn $assign_fn(&mut self, rhs: f32) {
+                *self = *self $op rhs
+            }
+        }
+    };
+}
+
+impl_vec3_wide!(Vec3x4, f32x4, m32x4, 4);
+impl_vec3_wide!(Vec3x8, f32x8, m32x8, 8);
+
+#[cfg(test)]
+mod tests {
+    use narcissus_core::random::Pcg64;
+
+    use crate::{Vec3, Vec3x4, Vec3x8, f32x4, f32x8, m32x8};
+
+    fn random_vec3(rng: &mut Pcg64) -> Vec3 {
+        Vec3::new(rng.next_f32_s(), rng.next_f32_s(), rng.next_f32_s()) * 10.0
+    }
+
+    #[track_caller]
+    fn assert_close(actual: &[f32], expected: &[f32]) {
+        for (a, e) in actual.iter().zip(expected) {
+            assert!(
+                (a - e).abs() <= 1e-5 * e.abs().max(1.0),
+                "expected {expected:?}\nfound {actual:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn matches_scalar() {
+        let mut rng = Pcg64::new();
+        for _ in 0..1000 {
+            let a: [Vec3; 8] = std::array::from_fn(|_| random_vec3(&mut rng));
+            let b: [Vec3; 8] = std::array::from_fn(|_| random_vec3(&mut rng));
+            let (wide_a, wide_b) = (Vec3x8::from_array(a), Vec3x8::from_array(b));
+            assert_eq!(wide_a.to_array(), a);
+
+            let dot = Vec3x8::dot(wide_a, wide_b).to_array();
+            let length = wide_a.length().to_array();
+            let cross = Vec3x8::cross(wide_a, wide_b).to_array();
+            let normalized = wide_a.normalized().to_array();
+            let sum = (wide_a + wide_b * 2.0).to_array();
+            for i in 0..8 {
+                assert_close(&[dot[i]], &[Vec3::dot(a[i], b[i])]);
+                assert_close(&[length[i]], &[a[i].length()]);
+                assert_close(
+                    &<[f32; 3]>::from(cross[i]),
+                    &<[f32; 3]>::from(Vec3::cross(a[i], b[i])),
+                );
+                assert_close(
+                    &<[f32; 3]>::from(normalized[i]),
+                    &<[f32; 3]>::from(a[i].normalized()),
+                );
+                assert_eq!(sum[i], a[i] + b[i] * 2.0);
+            }
+
+            let a: [Vec3; 4] = std::array::from_fn(|i| a[i]);
+            let b: [Vec3; 4] = std::array::from_fn(|i| b[i]);
+            let dot = Vec3x4::dot(Vec3x4::from_array(a), Vec3x4::from_array(b)).to_array();
+            for i in 0..4 {
+                assert_close(&[dot[i]], &[Vec3::dot(a[i], b[i])]);
+            }
+        }
+    }
+
+    #[test]
+    fn load_store_select() {
+        let mut x = [0.0, 1.0, 2.0, 3.0];
+        let mut y = [4.0, 5.0, 6.0, 7.0];
+        let mut z = [8.0, 9.0, 10.0, 11.0];
+        let velocity = Vec3x4::splat(Vec3::new(1.0, 0.0, -1.0));
+        let position = Vec3x4::load(&x, &y, &z) + velocity * f32x4::splat(0.5);
+        position.store(&mut x, &mut y, &mut z);
+        assert_eq!(x, [0.5, 1.5, 2.5, 3.5]);
+        assert_eq!(y, [4.0, 5.0, 6.0, 7.0]);
+        assert_eq!(z, [7.5, 8.5, 9.5, 10.5]);
+
+        let a = Vec3x8::splat(Vec3::X);
+        let b = Vec3x8::splat(Vec3::Y);
+        let mask = m32x8::from_bitmask(0b1010_0101);
+        let selected = Vec3x8::select(mask, a, b).to_array();
+        assert_eq!(
+            selected,
+            [
+                Vec3::X,
+                Vec3::Y,
+                Vec3::X,
+                Vec3::Y,
+                Vec3::Y,
+                Vec3::X,
+                Vec3::Y,
+                Vec3::X
+            ]
+        );
+        assert_eq!(
+            Vec3x8::distance_sq(a, b).to_array(),
+            f32x8::splat(2.0).to_array()
+        );
+    }
+}
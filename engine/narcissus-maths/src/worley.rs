@@ -0,0 +1,125 @@
+/// Hashes integer cell coordinates and a seed into 32 random bits.
+#[inline(always)]
+fn hash(x: i32, y: i32, z: i32, seed: u32) -> u32 {
+    let h = seed
+        ^ (x as u32).wrapping_mul(0x8da6_b343)
+        ^ (y as u32).wrapping_mul(0xd816_3841)
+        ^ (z as u32).wrapping_mul(0xcb1a_b31f);
+    let h = (h ^ (h >> 16)).wrapping_mul(0x7feb_352d);
+    let h = (h ^ (h >> 15)).wrapping_mul(0x846c_a68b);
+    h ^ (h >> 16)
+}
+
+#[inline(always)]
+fn unorm(bits: u32, count: u32) -> f32 {
+    (bits & ((1 << count) - 1)) as f32 / (1 << count) as f32
+}
+
+/// Computes 2d cellular (Worley) noise at the coordinate (x,y)
+///
+/// Each unit cell contains one randomly placed feature point. Returns the
+/// euclidean distances to the nearest and second nearest feature points,
+/// usually called `F1` and `F2`. `F1` alone gives a cell-like pattern, while
+/// `F2 - F1` highlights the cell borders.
+///
+/// Only the neighboring cells are searched, so rarely the results may be
+/// slightly larger than the true distances.
+///
+/// `seed` selects from multiple different variations of the noise function.
+pub fn worley_noise2(x: f32, y: f32, seed: u32) -> (f32, f32) {
+    let px = x.floor();
+    let py = y.floor();
+    let (cx, cy) = (px as i32, py as i32);
+    let (x, y) = (x - px, y - py);
+
+    let mut f1 = f32::MAX;
+    let mut f2 = f32::MAX;
+    for j in -1..=1 {
+        for i in -1..=1 {
+            let h = hash(cx.wrapping_add(i), cy.wrapping_add(j), 0, seed);
+            let dx = i as f32 + unorm(h, 16) - x;
+            let dy = j as f32 + unorm(h >> 16, 16) - y;
+            let d = dx * dx + dy * dy;
+            if d < f1 {
+                f2 = f1;
+                f1 = d;
+            } else if d < f2 {
+                f2 = d;
+            }
+        }
+    }
+
+    (f1.sqrt(), f2.sqrt())
+}
+
+/// Computes 3d cellular (Worley) noise at the coordinate (x,y,z)
+///
+/// See [`worley_noise2`].
+pub fn worley_noise3(x: f32, y: f32, z: f32, seed: u32) -> (f32, f32) {
+    let px = x.floor();
+    let py = y.floor();
+    let pz = z.floor();
+    let (cx, cy, cz) = (px as i32, py as i32, pz as i32);
+    let (x, y, z) = (x - px, y - py, z - pz);
+
+    let mut f1 = f32::MAX;
+    let mut f2 = f32::MAX;
+    for k in -1..=1 {
+        for j in -1..=1 {
+            for i in -1..=1 {
+                let h = hash(
+                    cx.wrapping_add(i),
+                    cy.wrapping_add(j),
+                    cz.wrapping_add(k),
+                    seed,
+                );
+                let dx = i as f32 + unorm(h, 10) - x;
+                let dy = j as f32 + unorm(h >> 10, 10) - y;
+                let dz = k as f32 + unorm(h >> 20, 10) - z;
+                let d = dx * dx + dy * dy + dz * dz;
+                if d < f1 {
+                    f2 = f1;
+                    f1 = d;
+                } else if d < f2 {
+                    f2 = d;
+                }
+            }
+        }
+    }
+
+    (f1.sqrt(), f2.sqrt())
+}
+
+#[cfg(test)]
+mod tests {
+    use narcissus_core::random::Pcg64;
+
+    use crate::{worley_noise2, worley_noise3};
+
+    #[test]
+    fn basic() {
+        let mut rng = Pcg64::new();
+        for _ in 0..10_000 {
+            let [x, y, z] = std::array::from_fn(|_| rng.next_f32_s() * 1000.0);
+            let (f1, f2) = worley_noise2(x, y, 0);
+            assert!(f1 <= f2);
+            assert!((0.0..=std::f32::consts::SQRT_2).contains(&f1));
+            let (f1, f2) = worley_noise3(x, y, z, 0);
+            assert!(f1 <= f2);
+            assert!((0.0..=3.0_f32.sqrt()).contains(&f1));
+        }
+
+        // Continuous, deterministic, and varies with the seed.
+        let (a, _) = worley_noise2(10.25, -3.5, 7);
+        let (b, _) = worley_noise2(10.25 + 1e-4, -3.5, 7);
+        assert!((a - b).abs() <= 1e-4);
+        assert_eq!(
+            worley_noise3(1.5, 2.5, 3.5, 7),
+            worley_noise3(1.5, 2.5, 3.5, 7)
+        );
+        assert_ne!(
+            worley_noise3(1.5, 2.5, 3.5, 7),
+            worley_noise3(1.5, 2.5, 3.5, 8)
+        );
+    }
+}
@@ -5,7 +5,11 @@ use std::{
     sync::atomic::{AtomicUsize, Ordering},
 };
 
-use narcissus_maths::{exp_f32, sin_cos_pi_f32, tan_pi_f32};
+use narcissus_core::random::Pcg64;
+use narcissus_maths::{
+    acos_pi_f32, asin_pi_f32, atan_pi_f32, atan2_pi_f32, exp_f32, ln_f32, log2_f32, pow_f32,
+    sin_cos_pi_f32, tan_pi_f32,
+};
 
 use gmp_mpfr_sys::mpfr;
 
@@ -128,6 +132,36 @@ impl Float {
         unsafe { mpfr::exp(&mut self.0, &self.0, round.to_raw()) }
     }
 
+    pub fn ln_mut(&mut self, round: Round) -> i32 {
+        unsafe { mpfr::log(&mut self.0, &self.0, round.to_raw()) }
+    }
+
+    pub fn log2_mut(&mut self, round: Round) -> i32 {
+        unsafe { mpfr::log2(&mut self.0, &self.0, round.to_raw()) }
+    }
+
+    pub fn atan_pi_mut(&mut self, round: Round) -> i32 {
+        unsafe { mpfr::atanpi(&mut self.0, &self.0, round.to_raw()) }
+    }
+
+    pub fn asin_pi_mut(&mut self, round: Round) -> i32 {
+        unsafe { mpfr::asinpi(&mut self.0, &self.0, round.to_raw()) }
+    }
+
+    pub fn acos_pi_mut(&mut self, round: Round) -> i32 {
+        unsafe { mpfr::acospi(&mut self.0, &self.0, round.to_raw()) }
+    }
+
+    /// Sets self to atan2(y, x) / pi.
+    pub fn set_atan2_pi(&mut self, y: &Float, x: &Float, round: Round) -> i32 {
+        unsafe { mpfr::atan2pi(&mut self.0, &y.0, &x.0, round.to_raw()) }
+    }
+
+    /// Sets self to x raised to the power y.
+    pub fn set_pow(&mut self, x: &Float, y: &Float, round: Round) -> i32 {
+        unsafe { mpfr::pow(&mut self.0, &x.0, &y.0, round.to_raw()) }
+    }
+
     pub fn abs_mut(&mut self, round: Round) -> i32 {
         unsafe { mpfr::abs(&mut self.0, &self.0, round.to_raw()) }
     }
@@ -413,6 +447,119 @@ fn check_exhaustive_f32(
     errors
 }
 
+#[derive(Clone, Copy, Debug, Default)]
+struct SampledErrors {
+    num_errors: u32,
+    num_errors_2: u32,
+    max_error_ulp: u32,
+    max_error_args: (f32, f32),
+}
+
+/// Distance between `a` and `b` in units of representable values, where
+/// infinity is the value after `f32::MAX`.
+fn ulp_distance(a: f32, b: f32) -> u32 {
+    if a == b || a.is_nan() && b.is_nan() {
+        return 0;
+    }
+    if a.is_nan() || b.is_nan() {
+        return u32::MAX;
+    }
+    let ordered = |x: f32| {
+        let bits = x.to_bits() as i32;
+        if bits < 0 { i32::MIN - bits } else { bits }
+    };
+    (ordered(a) as i64 - ordered(b) as i64)
+        .unsigned_abs()
+        .min(u32::MAX as u64) as u32
+}
+
+fn random_finite_f32(rng: &mut Pcg64) -> f32 {
+    loop {
+        let x = f32::from_bits(rng.next_u64() as u32);
+        if x.is_finite() {
+            return x;
+        }
+    }
+}
+
+/// Two argument functions can't be tested exhaustively, so check a fixed
+/// random sample of arguments instead. The sample is split into chunks with
+/// their own seeds, so the result doesn't depend on the number of threads.
+fn check_sampled_f32x2(
+    ref_fn: fn(&mut Float, &Float, &Float) -> i32,
+    our_fn: fn(f32, f32) -> f32,
+    sample_fn: fn(&mut Pcg64) -> (f32, f32),
+) -> SampledErrors {
+    const SPLIT: u32 = 256;
+    const PER_SPLIT: u32 = 65536;
+
+    let work_index = AtomicUsize::new(0);
+    let mut errors = SampledErrors::default();
+
+    std::thread::scope(|s| {
+        let num_threads = std::thread::available_parallelism().unwrap().get();
+
+        let threads = (0..num_threads)
+            .map(|_| {
+                s.spawn(|| {
+                    unsafe {
+                        mpfr::set_emin(-148);
+                        mpfr::set_emax(128);
+                    }
+
+                    let mut a = Float::new(PREC);
+                    let mut b = Float::new(PREC);
+                    let mut tmp = Float::new(PREC);
+                    let mut errors = SampledErrors::default();
+                    loop {
+                        let index = work_index.fetch_add(1, Ordering::SeqCst) as u32;
+                        if index >= SPLIT {
+                            break;
+                        }
+
+                        let mut rng = Pcg64::with_seed(index as u128);
+                        for _ in 0..PER_SPLIT {
+                            let (x, y) = sample_fn(&mut rng);
+                            let our_value = our_fn(x, y);
+
+                            a.set_f32(x, Round::TiesToEven);
+                            b.set_f32(y, Round::TiesToEven);
+                            let inex = ref_fn(&mut tmp, &a, &b);
+                            tmp.subnormalize(inex, Round::TiesToEven);
+                            let ref_value = tmp.to_f32(Round::TiesToEven);
+
+                            let err = ulp_distance(our_value, ref_value);
+                            if err != 0 {
+                                errors.num_errors += 1;
+                                if err > 1 {
+                                    errors.num_errors_2 += 1;
+                                }
+                                if err > errors.max_error_ulp {
+                                    errors.max_error_ulp = err;
+                                    errors.max_error_args = (x, y);
+                                }
+                            }
+                        }
+                    }
+                    errors
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for thread in threads {
+            let thread_errors = thread.join().unwrap();
+            errors.num_errors += thread_errors.num_errors;
+            errors.num_errors_2 += thread_errors.num_errors_2;
+            if thread_errors.max_error_ulp > errors.max_error_ulp {
+                errors.max_error_ulp = thread_errors.max_error_ulp;
+                errors.max_error_args = thread_errors.max_error_args;
+            }
+        }
+    });
+
+    errors
+}
+
 fn ref_sin_pi_f32(x: &mut Float, pi: &Float) -> i32 {
     x.mul_assign(pi, Round::TiesToEven);
     x.sin_mut(Round::TiesToEven)
@@ -432,6 +579,58 @@ fn ref_exp_f32(x: &mut Float, _: &Float) -> i32 {
     x.exp_mut(Round::TiesToEven)
 }
 
+fn ref_ln_f32(x: &mut Float, _: &Float) -> i32 {
+    x.ln_mut(Round::TiesToEven)
+}
+
+fn ref_log2_f32(x: &mut Float, _: &Float) -> i32 {
+    x.log2_mut(Round::TiesToEven)
+}
+
+fn ref_atan_pi_f32(x: &mut Float, _: &Float) -> i32 {
+    x.atan_pi_mut(Round::TiesToEven)
+}
+
+fn ref_asin_pi_f32(x: &mut Float, _: &Float) -> i32 {
+    x.asin_pi_mut(Round::TiesToEven)
+}
+
+fn ref_acos_pi_f32(x: &mut Float, _: &Float) -> i32 {
+    x.acos_pi_mut(Round::TiesToEven)
+}
+
+fn ref_atan2_pi_f32(r: &mut Float, y: &Float, x: &Float) -> i32 {
+    r.set_atan2_pi(y, x, Round::TiesToEven)
+}
+
+fn ref_pow_f32(r: &mut Float, x: &Float, y: &Float) -> i32 {
+    r.set_pow(x, y, Round::TiesToEven)
+}
+
+/// Arbitrary finite pairs, and pairs with a similar magnitude which exercise
+/// the full range of the core approximation.
+fn sample_atan2(rng: &mut Pcg64) -> (f32, f32) {
+    let y = random_finite_f32(rng);
+    if rng.next_u64() & 1 == 0 {
+        (y, random_finite_f32(rng))
+    } else {
+        (y, y * rng.next_f32_s() * 4.0)
+    }
+}
+
+/// Positive and negative bases over the full range, with exponents mostly
+/// keeping the result in range. One in four exponents is an integer.
+fn sample_pow(rng: &mut Pcg64) -> (f32, f32) {
+    let sign = (rng.next_u64() as u32) & 0x8000_0000;
+    let x = f32::from_bits(rng.next_bound_u64(0x7f80_0000) as u32 | sign);
+    let y = rng.next_f32_s() * 2.0;
+    if rng.next_u64() & 3 == 0 {
+        (x, (y * 32.0).round())
+    } else {
+        (x, y)
+    }
+}
+
 #[test]
 #[ignore]
 pub fn exhaustive_sin_pi() {
@@ -467,3 +666,66 @@ pub fn exhaustive_exp() {
     assert_eq!(errors.max_error_ulp, 1);
     assert_eq!(errors.num_errors, 11_067_510);
 }
+
+#[test]
+#[ignore]
+pub fn exhaustive_ln() {
+    let errors = check_exhaustive_f32(ref_ln_f32, ln_f32, false);
+    println!("LN: {errors:?}");
+    assert_eq!(errors.max_error_ulp, 1);
+    assert_eq!(errors.num_errors, 72_310_398);
+}
+
+#[test]
+#[ignore]
+pub fn exhaustive_log2() {
+    let errors = check_exhaustive_f32(ref_log2_f32, log2_f32, false);
+    println!("LOG2: {errors:?}");
+    assert_eq!(errors.max_error_ulp, 1);
+    assert_eq!(errors.num_errors, 2_316_673);
+}
+
+#[test]
+#[ignore]
+pub fn exhaustive_atan_pi() {
+    let errors = check_exhaustive_f32(ref_atan_pi_f32, atan_pi_f32, false);
+    println!("ATAN: {errors:?}");
+    assert_eq!(errors.max_error_ulp, 2);
+    assert_eq!(errors.num_errors, 34_417_190);
+}
+
+#[test]
+#[ignore]
+pub fn exhaustive_asin_pi() {
+    let errors = check_exhaustive_f32(ref_asin_pi_f32, asin_pi_f32, false);
+    println!("ASIN: {errors:?}");
+    assert_eq!(errors.max_error_ulp, 1);
+    assert_eq!(errors.num_errors, 25_217_544);
+}
+
+#[test]
+#[ignore]
+pub fn exhaustive_acos_pi() {
+    let errors = check_exhaustive_f32(ref_acos_pi_f32, acos_pi_f32, false);
+    println!("ACOS: {errors:?}");
+    assert_eq!(errors.max_error_ulp, 1);
+    assert_eq!(errors.num_errors, 7_862_988);
+}
+
+#[test]
+#[ignore]
+pub fn sampled_atan2_pi() {
+    let errors = check_sampled_f32x2(ref_atan2_pi_f32, atan2_pi_f32, sample_atan2);
+    println!("ATAN2: {errors:?}");
+    assert_eq!(errors.max_error_ulp, 2);
+    assert_eq!(errors.num_errors, 1_967_976);
+}
+
+#[test]
+#[ignore]
+pub fn sampled_pow() {
+    let errors = check_sampled_f32x2(ref_pow_f32, pow_f32, sample_pow);
+    println!("POW: {errors:?}");
+    assert_eq!(errors.max_error_ulp, 1);
+    assert_eq!(errors.num_errors, 5_013);
+}
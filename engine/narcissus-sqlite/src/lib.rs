@@ -9,6 +9,8 @@ use std::{
 
 use std::sync::OnceLock;
 
+use narcissus_core::log::Level;
+
 use sqlite_sys as ffi;
 
 static SQLITE_GLOBAL_INIT: OnceLock<()> = OnceLock::new();
@@ -23,10 +25,17 @@ unsafe fn initialize() {
 
         #[cfg(debug_assertions)]
         {
-            extern "C" fn log(_user: *mut c_void, _result: c_int, msg: *const c_char) {
+            extern "C" fn log(_user: *mut c_void, result: c_int, msg: *const c_char) {
                 let msg = unsafe { CStr::from_ptr(msg) };
                 let msg = msg.to_string_lossy();
-                println!("sqlite3: {}", msg);
+                // Notices and warnings are reported with their own primary result codes,
+                // anything else is an error.
+                let level = match result & 0xff {
+                    ffi::SQLITE_NOTICE => Level::Info,
+                    ffi::SQLITE_WARNING => Level::Warn,
+                    _ => Level::Error,
+                };
+                narcissus_core::log!(level, category: "sqlite3", "{msg}");
             }
 
             let ret = ffi::sqlite3_config(
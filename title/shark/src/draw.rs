@@ -22,7 +22,7 @@ use narcissus_gpu::{
     RenderingDesc, Scissor, ShaderStageFlags, StoreOp, ThreadToken, TypedBind, Viewport,
 };
 use narcissus_image as image;
-use narcissus_maths::{Affine3, HalfTurn, Mat3, Mat4, Vec3, vec3};
+use narcissus_maths::{Affine3, HalfTurn, Mat3, Mat4, Quat, UVec2, Vec3, uvec2, vec3};
 
 pub struct Model<'a> {
     indices: u32,
@@ -323,8 +323,7 @@ pub struct DrawState<'gpu> {
     width: u32,
     height: u32,
 
-    tile_resolution_x: u32,
-    tile_resolution_y: u32,
+    tile_resolution: UVec2,
 
     depth_image: Image,
     color_image: Image,
@@ -351,8 +350,7 @@ impl<'gpu> DrawState<'gpu> {
             gpu,
             width: 0,
             height: 0,
-            tile_resolution_x: 0,
-            tile_resolution_y: 0,
+            tile_resolution: UVec2::ZERO,
             depth_image: default(),
             color_image: default(),
             ui_image: default(),
@@ -380,14 +378,8 @@ impl<'gpu> DrawState<'gpu> {
         let half_turn_y = Mat3::from_axis_rotation(Vec3::Y, HalfTurn::new(0.5));
         let scale = Mat3::from_scale(Vec3::splat(0.4));
 
-        fn rotate_dir(dir: Vec3, up: Vec3) -> Mat3 {
-            let f = dir.normalized();
-            let r = Vec3::cross(f, up).normalized();
-            let u = Vec3::cross(r, f);
-            Mat3::from_rows([[r.x, u.x, -f.x], [r.y, u.y, -f.y], [r.z, u.z, -f.z]])
-        }
-
-        let matrix = rotate_dir(game_state.player.heading, Vec3::Y) * half_turn_y;
+        let matrix =
+            Mat3::from_quat(Quat::look_rotation(game_state.player.heading, Vec3::Y)) * half_turn_y;
         let translation = game_state.player.position.as_vec3();
         self.transforms.push(Affine3::new(matrix, translation));
 
@@ -414,7 +406,8 @@ impl<'gpu> DrawState<'gpu> {
 
                     let translation = vec3(block.position_x[j], 0.0, block.position_z[j]);
                     let velocity = vec3(block.velocity_x[j], 0.0, block.velocity_z[j]);
-                    let matrix = rotate_dir(velocity, Vec3::Y) * half_turn_y_scale;
+                    let matrix =
+                        Mat3::from_quat(Quat::look_rotation(velocity, Vec3::Y)) * half_turn_y_scale;
                     self.transforms.push(Affine3::new(matrix, translation));
                 }
             }
@@ -470,8 +463,8 @@ impl<'gpu> DrawState<'gpu> {
                 gpu.destroy_image(frame, self.color_image);
                 gpu.destroy_image(frame, self.ui_image);
 
-                self.tile_resolution_x = width.div_ceil(DRAW_2D_TILE_SIZE);
-                self.tile_resolution_y = height.div_ceil(DRAW_2D_TILE_SIZE);
+                self.tile_resolution =
+                    uvec2(width, height).div_ceil(UVec2::splat(DRAW_2D_TILE_SIZE));
 
                 self.depth_image = gpu.create_image(&ImageDesc {
                     memory_location: MemoryLocation::Device,
@@ -756,10 +749,7 @@ impl<'gpu> DrawState<'gpu> {
                 frame,
                 thread_token,
                 BufferUsageFlags::STORAGE,
-                self.tile_resolution_x as usize
-                    * self.tile_resolution_y as usize
-                    * std::mem::size_of::<u32>()
-                    * 2,
+                self.tile_resolution.element_product() as usize * std::mem::size_of::<u32>() * 2,
             );
             let tile_buffer_address = gpu.get_buffer_address(tile_buffer.to_arg());
 
@@ -869,8 +859,8 @@ impl<'gpu> DrawState<'gpu> {
                     ShaderStageFlags::COMPUTE,
                     0,
                     &Draw2dScatterConstants {
-                        tile_resolution_x: self.tile_resolution_x,
-                        tile_resolution_y: self.tile_resolution_y,
+                        tile_resolution_x: self.tile_resolution.x,
+                        tile_resolution_y: self.tile_resolution.y,
                         draw_buffer_len,
                         coarse_buffer_len: COARSE_BUFFER_LEN as u32,
                         draw_buffer_address,
@@ -1006,7 +996,7 @@ impl<'gpu> DrawState<'gpu> {
                     ShaderStageFlags::COMPUTE,
                     0,
                     &Draw2dResolveConstants {
-                        tile_stride: self.tile_resolution_x,
+                        tile_stride: self.tile_resolution.x,
                         draw_buffer_len,
                         draw_buffer_address,
                         scissor_buffer_address,
@@ -1019,8 +1009,8 @@ impl<'gpu> DrawState<'gpu> {
                 gpu.cmd_dispatch(
                     cmd_encoder,
                     1,
-                    self.tile_resolution_x,
-                    self.tile_resolution_y,
+                    self.tile_resolution.x,
+                    self.tile_resolution.y,
                 );
 
                 gpu.cmd_barrier(
@@ -1039,7 +1029,7 @@ impl<'gpu> DrawState<'gpu> {
                     ShaderStageFlags::COMPUTE,
                     0,
                     &Draw2dRasterizeConstants {
-                        tile_stride: self.tile_resolution_x,
+                        tile_stride: self.tile_resolution.x,
                         _pad: 0,
                         draw_buffer_address,
                         scissor_buffer_address,
@@ -1095,8 +1085,8 @@ impl<'gpu> DrawState<'gpu> {
                     ShaderStageFlags::COMPUTE,
                     0,
                     &CompositeConstants {
-                        tile_resolution_x: self.tile_resolution_x,
-                        tile_resolution_y: self.tile_resolution_y,
+                        tile_resolution_x: self.tile_resolution.x,
+                        tile_resolution_y: self.tile_resolution.y,
                         tile_buffer_address,
                     },
                 );
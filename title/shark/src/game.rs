@@ -1,9 +1,10 @@
 use std::f32::consts::SQRT_2;
 
 use narcissus_core::{BitIter, box_assume_init, default, random::Pcg64, zeroed_box};
-use narcissus_maths::{Deg, HalfTurn, Mat4, Point3, Vec3, clamp, perlin_noise3, sin_pi_f32, vec3};
-
-use crate::spring::simple_spring_damper_exact;
+use narcissus_maths::{
+    Deg, HalfTurn, Mat4, Point3, Vec3, clamp, critical_spring_damper, damping_to_halflife, f32x8,
+    perlin_noise2, sin_pi_f32, vec2, vec3,
+};
 
 const ARCHTYPE_PROJECTILE_MAX: usize = 65536;
 
@@ -12,7 +13,7 @@ pub struct GameVariables {
 
     camera_distance: f32,
     camera_angle: Deg,
-    camera_damping: f32,
+    camera_halflife: f32,
     camera_deadzone: f32,
     camera_shake_decay: f32,
     camera_shake_max_offset: f32,
@@ -30,7 +31,7 @@ pub static GAME_VARIABLES: GameVariables = GameVariables {
 
     camera_distance: 45.0,
     camera_angle: Deg::new(60.0),
-    camera_damping: 35.0,
+    camera_halflife: damping_to_halflife(35.0),
     camera_deadzone: 0.1,
     camera_shake_decay: 2.0,
     camera_shake_max_offset: 2.0,
@@ -150,25 +151,18 @@ impl CameraState {
         if Point3::distance_sq(self.position, target)
             > (GAME_VARIABLES.camera_deadzone * GAME_VARIABLES.camera_deadzone)
         {
-            let (pos_x, vel_x) = simple_spring_damper_exact(
-                self.position.x,
-                self.velocity.x,
-                target.x,
-                GAME_VARIABLES.camera_damping,
-                delta_time,
-            );
-            let (pos_z, vel_z) = simple_spring_damper_exact(
-                self.position.z,
-                self.velocity.z,
-                target.z,
-                GAME_VARIABLES.camera_damping,
+            let (position, velocity) = critical_spring_damper(
+                vec2(self.position.x, self.position.z),
+                vec2(self.velocity.x, self.velocity.z),
+                vec2(target.x, target.z),
+                GAME_VARIABLES.camera_halflife,
                 delta_time,
             );
 
-            self.position.z = pos_z;
-            self.position.x = pos_x;
-            self.velocity.x = vel_x;
-            self.velocity.z = vel_z;
+            self.position.x = position.x;
+            self.position.z = position.y;
+            self.velocity.x = velocity.x;
+            self.velocity.z = velocity.y;
         }
 
         self.shake -= GAME_VARIABLES.camera_shake_decay * delta_time;
@@ -177,8 +171,8 @@ impl CameraState {
         let t = time * GAME_VARIABLES.camera_shake_frequency;
         let shake = GAME_VARIABLES.camera_shake_max_offset * self.shake * self.shake * self.shake;
 
-        self.shake_offset.x = shake * perlin_noise3(0.0, t, 0.0);
-        self.shake_offset.z = shake * perlin_noise3(1.0, t, 0.0);
+        self.shake_offset.x = shake * perlin_noise2(0.0, t);
+        self.shake_offset.z = shake * perlin_noise2(1.0, t);
     }
 
     pub fn camera_from_model(&self) -> Mat4 {
@@ -355,20 +349,19 @@ impl GameState {
                         continue;
                     }
 
-                    let old_bitmap = *bitmap;
-
-                    for j in 0..8 {
-                        if old_bitmap & (1 << j) == 0 {
-                            continue;
-                        }
+                    // Dead lanes are updated too, but their results are never read.
+                    let position_x = f32x8::load(&block.position_x);
+                    let position_z = f32x8::load(&block.position_z);
+                    let velocity_x = f32x8::load(&block.velocity_x);
+                    let velocity_z = f32x8::load(&block.velocity_z);
+                    (position_x + velocity_x * delta_time).store(&mut block.position_x);
+                    (position_z + velocity_z * delta_time).store(&mut block.position_z);
 
-                        block.position_x[j] += block.velocity_x[j] * delta_time;
-                        block.position_z[j] += block.velocity_z[j] * delta_time;
-                        block.lifetime[j] -= delta_time;
-                        let projectile_dead = block.lifetime[j] <= 0.0;
+                    let lifetime = f32x8::load(&block.lifetime) - delta_time;
+                    lifetime.store(&mut block.lifetime);
+                    let projectile_dead = lifetime.cmp_le(f32x8::ZERO);
 
-                        *bitmap &= !((projectile_dead as u8) << j);
-                    }
+                    *bitmap &= !projectile_dead.to_bitmask();
                 }
 
                 let non_empty = chunk.bitmap.iter().any(|&x| x != 0);
@@ -17,14 +17,13 @@ use narcissus_gpu::{
     ColorSpace, ImageFormat, ImageUsageFlags, PresentMode, SwapchainConfigurator, SwapchainImage,
     ThreadToken, create_device,
 };
-use narcissus_maths::{Vec2, sin_cos_pi_f32, vec2};
+use narcissus_maths::{Rect, Srgba, sin_cos_pi_f32, vec2};
 
 mod draw;
 mod fonts;
 mod game;
 mod helpers;
 pub mod microshades;
-mod spring;
 
 const GLYPH_CACHE_SIZE: usize = 1024;
 
@@ -79,23 +78,18 @@ impl<'a> UiState<'a> {
         });
     }
 
-    fn push_scissor(
-        &mut self,
-        mut offset_min: Vec2,
-        mut offset_max: Vec2,
-        intersect_with_current: bool,
-    ) {
+    fn push_scissor(&mut self, mut rect: Rect, intersect_with_current: bool) {
         if intersect_with_current {
             let current_scissor_index = self.scissor_stack.last().copied().unwrap_or(0);
             let current_scissor = &self.scissors[current_scissor_index.widen()];
-            offset_min = Vec2::max(offset_min, current_scissor.offset_min);
-            offset_max = Vec2::min(offset_max, current_scissor.offset_max);
+            let current_rect = Rect::new(current_scissor.offset_min, current_scissor.offset_max);
+            rect = Rect::intersection(rect, current_rect);
         }
 
         let scissor_index = self.scissors.len() as u32;
         self.scissors.push(Draw2dScissor {
-            offset_min,
-            offset_max,
+            offset_min: rect.min,
+            offset_max: rect.max,
         });
         self.scissor_stack.push(scissor_index);
     }
@@ -182,7 +176,7 @@ impl<'a> UiState<'a> {
             self.draw_cmds.push(Draw2dCmd::glyph(
                 scissor_index,
                 touched_glyph_index,
-                microshades::GRAY_RGBA8[4].rotate_right(8),
+                Srgba::from_hex(microshades::GRAY_RGBA8[4]).to_bgra8(),
                 vec2(x, y),
             ));
 
@@ -382,14 +376,15 @@ pub fn main() {
                 let h = height / 5.0;
                 let x = width / 2.0 + w * s;
                 let y = height / 2.0 + w * c;
+                let scissor = Rect::from_center_half_extents(vec2(x, y), vec2(w, h));
 
-                ui_state.push_scissor(vec2(x - w, y - h), vec2(x + w, y + h), true);
+                ui_state.push_scissor(scissor, true);
                 ui_state.rect(
                     0.0, 0.0, width, height, 0.0, [0.0; 4], 0xffffffff, 0xffffffff,
                 );
                 ui_state.pop_scissor();
 
-                ui_state.push_scissor(vec2(x - w, y - h), vec2(x + w, y + h), true);
+                ui_state.push_scissor(scissor, true);
 
                 let mut y = 8.0 * ui_state.scale;
                 for i in 0..224 {
@@ -426,7 +421,7 @@ pub fn main() {
                         100.0,
                         [100.0, 50.0, 25.0, 0.0],
                         0x33333333,
-                        microshades::BLUE_RGBA8[4].rotate_right(8),
+                        Srgba::from_hex(microshades::BLUE_RGBA8[4]).to_bgra8(),
                     );
                 }
 
@@ -1,4 +1,4 @@
-use narcissus_core::random::Pcg64;
+use narcissus_core::{random::Pcg64, slice::radix_sort_u32};
 use narcissus_gpu::{
     Access, BufferDesc, BufferUsageFlags, DeviceExt, GlobalBarrier, MemoryLocation,
     ShaderStageFlags, ThreadToken, create_device,
@@ -167,9 +167,12 @@ pub fn sort_random_input() {
 
     values.push((rng.next_u64() & 0xffff_ffff) as u32);
 
+    let mut expected = values.clone();
+    radix_sort_u32(&mut expected);
+
     gpu_sort(&mut values);
 
-    assert!(values.is_sorted());
+    assert!(values == expected);
 }
 
 #[ignore]